@@ -5,10 +5,11 @@ use crate::config::models::GlobalConfig;
 use crate::config::url_resolver::BaseUrlResolver;
 use crate::constants;
 use crate::error::Error;
-use crate::spec::{resolve_parameter_reference, resolve_schema_reference};
+use crate::spec::{resolve_parameter_reference, resolve_schema_reference, SpecDocument};
 use crate::utils::to_kebab_case;
 use openapiv3::{OpenAPI, Operation, Parameter as OpenApiParameter, ReferenceOr, SecurityScheme};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 
 /// Type alias for schema information extracted from a parameter
@@ -35,6 +36,33 @@ pub struct ApiCapabilityManifest {
     pub security_schemes: HashMap<String, SecuritySchemeInfo>,
     /// Batch processing capabilities
     pub batch: BatchCapabilityInfo,
+    /// Requests the API sends to subscribers (`OpenAPI` 3.1 `webhooks`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookInfo>,
+}
+
+/// A webhook the API sends, described so agents can handle its payload
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookInfo {
+    /// Webhook name (its key in the spec's `webhooks` section)
+    pub name: String,
+    /// HTTP method of the request
+    pub method: String,
+    /// Operation ID, if the spec declares one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// Brief summary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Detailed description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Content type of the payload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// JSON Schema of the payload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_schema: Option<Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -269,6 +297,11 @@ pub struct CommandInfo {
     /// Security requirements for this operation
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub security_requirements: Vec<String>,
+    /// Alternative security requirement sets: any one set authenticates the
+    /// operation, and the schemes within a set are sent together. An empty set
+    /// means the operation can also be called anonymously.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub security_alternatives: Vec<Vec<String>>,
     /// Tags associated with this operation (kebab-case)
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub tags: Vec<String>,
@@ -405,31 +438,16 @@ impl PaginationManifestInfo {
 /// This struct provides schema information extracted from `OpenAPI` response definitions,
 /// enabling AI agents to understand the expected response structure before execution.
 ///
-/// # Current Limitations
-///
-/// 1. **Response references not resolved**: If a response is defined as a reference
-///    (e.g., `$ref: '#/components/responses/UserResponse'`), the schema will not be
-///    extracted. Only inline response definitions are processed.
-///
-/// 2. **Nested schema references not resolved**: While top-level schema references
-///    (e.g., `$ref: '#/components/schemas/User'`) are resolved, nested references
-///    within object properties remain as `$ref` objects in the output. For example:
-///    ```json
-///    {
-///      "type": "object",
-///      "properties": {
-///        "user": { "$ref": "#/components/schemas/User" }  // Not resolved
-///      }
-///    }
-///    ```
+/// Component references are inlined before extraction, so the schema is
+/// self-contained. A schema that refers back to itself (e.g., a `User` with a
+/// `friends: User[]` property) keeps a `$ref` at the point where it recurses.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResponseSchemaInfo {
     /// Content type (e.g., "application/json")
     pub content_type: String,
     /// JSON Schema representation of the response body
     ///
-    /// Note: This schema may contain unresolved `$ref` objects for nested references.
-    /// Only the top-level schema reference is resolved.
+    /// Note: `$ref` objects only remain where a schema refers back to itself.
     pub schema: serde_json::Value,
     /// Example response if available from the spec
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -439,7 +457,7 @@ pub struct ResponseSchemaInfo {
 /// Detailed, parsable security scheme description
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SecuritySchemeInfo {
-    /// Type of security scheme (http, apiKey)
+    /// Type of security scheme (http, apiKey, oauth2, openIdConnect)
     #[serde(rename = "type")]
     pub scheme_type: String,
     /// Optional description of the security scheme
@@ -475,6 +493,19 @@ pub enum SecuritySchemeDetails {
         /// Name of the parameter/header
         name: String,
     },
+    /// `OAuth2` authentication (bearer token obtained from the token endpoint)
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        /// Token endpoint URL
+        token_url: String,
+        /// Scopes requested when fetching a token
+        scopes: Vec<String>,
+        /// Grant types Aperture uses to obtain tokens
+        grant_types: Vec<String>,
+        /// Authorization endpoint used by `aperture auth login`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        authorization_url: Option<String>,
+    },
 }
 
 fn build_openapi_temp_cached_spec(api_name: &str, spec: &OpenAPI) -> CachedSpec {
@@ -495,9 +526,10 @@ fn build_openapi_temp_cached_spec(api_name: &str, spec: &OpenAPI) -> CachedSpec
 }
 
 fn collect_openapi_command_groups(
-    spec: &OpenAPI,
+    document: &SpecDocument,
     cached_spec: &CachedSpec,
 ) -> HashMap<String, Vec<CommandInfo>> {
+    let spec = document.openapi();
     let skipped_set: std::collections::HashSet<(&str, &str)> = cached_spec
         .skipped_endpoints
         .iter()
@@ -520,8 +552,15 @@ fn collect_openapi_command_groups(
                 continue;
             }
 
-            let command_info =
-                convert_openapi_operation_to_info(method, path, op, spec, spec.security.as_ref());
+            let source = document.operation_source(path, method);
+            let command_info = convert_openapi_operation_to_info(
+                method,
+                path,
+                op,
+                source,
+                spec,
+                spec.security.as_ref(),
+            );
             let group_name = op.tags.first().map_or_else(
                 || constants::DEFAULT_GROUP.to_string(),
                 |tag| to_kebab_case(tag),
@@ -571,6 +610,8 @@ fn overlay_cached_command_metadata(
 /// This function creates a comprehensive JSON description of all available commands,
 /// parameters, and security requirements directly from the original `OpenAPI` spec,
 /// preserving all metadata that might be lost in the cached representation.
+/// Component references are inlined first, so schemas in the manifest are
+/// self-contained apart from self-referencing cycles.
 ///
 /// # Arguments
 /// * `api_name` - The name of the API context
@@ -589,6 +630,26 @@ pub fn generate_capability_manifest_from_openapi(
     cached_spec: &CachedSpec,
     global_config: Option<&GlobalConfig>,
 ) -> Result<String, Error> {
+    let document = SpecDocument::from_openapi(spec)?;
+    generate_capability_manifest_from_document(api_name, &document, cached_spec, global_config)
+}
+
+/// Generates a capability manifest from a parsed specification of any
+/// supported `OpenAPI` version.
+///
+/// Like [`generate_capability_manifest_from_openapi`], but response schemas
+/// are taken from the document as written, so `OpenAPI` 3.1 keywords are
+/// kept, and 3.1 webhooks are listed under `webhooks`.
+///
+/// # Errors
+/// Returns an error if JSON serialization fails
+pub fn generate_capability_manifest_from_document(
+    api_name: &str,
+    document: &SpecDocument,
+    cached_spec: &CachedSpec,
+    global_config: Option<&GlobalConfig>,
+) -> Result<String, Error> {
+    let spec = document.openapi();
     let temp_cached_spec = build_openapi_temp_cached_spec(api_name, spec);
     let resolver = BaseUrlResolver::new(&temp_cached_spec);
     let resolver = if let Some(config) = global_config {
@@ -598,7 +659,7 @@ pub fn generate_capability_manifest_from_openapi(
     };
     let resolved_base_url = resolver.resolve(None);
 
-    let command_groups = collect_openapi_command_groups(spec, cached_spec);
+    let command_groups = collect_openapi_command_groups(document, cached_spec);
     let regrouped = overlay_cached_command_metadata(command_groups, cached_spec);
     let security_schemes = extract_security_schemes_from_openapi(spec);
 
@@ -621,6 +682,7 @@ pub fn generate_capability_manifest_from_openapi(
         commands: regrouped,
         security_schemes,
         batch: build_batch_capability_info(),
+        webhooks: extract_webhooks(document),
     };
 
     serde_json::to_string_pretty(&manifest)
@@ -692,6 +754,7 @@ pub fn generate_capability_manifest(
         },
         commands: command_groups,
         security_schemes: extract_security_schemes(spec),
+        webhooks: Vec::new(),
         batch: build_batch_capability_info(),
     };
 
@@ -732,6 +795,7 @@ fn convert_cached_command_to_info(cached_command: &CachedCommand) -> CommandInfo
         parameters,
         request_body,
         security_requirements: cached_command.security_requirements.clone(),
+        security_alternatives: cached_command.security_alternatives(),
         tags: cached_command
             .tags
             .iter()
@@ -851,6 +915,14 @@ fn cached_security_scheme_details(
                 .clone()
                 .unwrap_or_else(|| constants::HEADER_AUTHORIZATION.to_string()),
         },
+        constants::AUTH_SCHEME_OAUTH2 | constants::AUTH_SCHEME_OPENID => {
+            scheme.oauth2.as_ref().map_or(
+                SecuritySchemeDetails::HttpBearer {
+                    bearer_format: None,
+                },
+                oauth2_security_details,
+            )
+        }
         // Default to bearer for unknown types.
         _ => SecuritySchemeDetails::HttpBearer {
             bearer_format: None,
@@ -858,6 +930,17 @@ fn cached_security_scheme_details(
     }
 }
 
+fn oauth2_security_details(
+    oauth2: &crate::cache::models::CachedOAuth2Config,
+) -> SecuritySchemeDetails {
+    SecuritySchemeDetails::OAuth2 {
+        token_url: oauth2.token_url.clone(),
+        scopes: oauth2.scopes.clone(),
+        grant_types: oauth2.grant_types.clone(),
+        authorization_url: oauth2.authorization_url.clone(),
+    }
+}
+
 fn cached_http_security_details(
     scheme: &crate::cache::models::CachedSecurityScheme,
 ) -> SecuritySchemeDetails {
@@ -917,33 +1000,49 @@ fn extract_request_body_info(operation: &Operation) -> Option<RequestBodyInfo> {
     })
 }
 
-fn extract_security_requirements(
+/// Returns the operation's security requirement sets, falling back to the
+/// global ones
+fn extract_security_alternatives(
     operation: &Operation,
     global_security: Option<&[openapiv3::SecurityRequirement]>,
-) -> Vec<String> {
+) -> Vec<Vec<String>> {
     operation
         .security
         .as_deref()
         .or(global_security)
         .map_or_else(Vec::new, |reqs| {
-            reqs.iter().flat_map(|req| req.keys().cloned()).collect()
+            reqs.iter()
+                .map(|req| req.keys().cloned().collect())
+                .collect()
         })
 }
 
+/// Lists each scheme named by any requirement set once, in order
+fn flatten_security_alternatives(alternatives: &[Vec<String>]) -> Vec<String> {
+    let mut requirements: Vec<String> = Vec::new();
+    for scheme_name in alternatives.iter().flatten() {
+        if !requirements.contains(scheme_name) {
+            requirements.push(scheme_name.clone());
+        }
+    }
+    requirements
+}
+
 /// Converts an `OpenAPI` operation to `CommandInfo` with full metadata
 fn convert_openapi_operation_to_info(
     method: &str,
     path: &str,
     operation: &Operation,
+    source: Option<&Value>,
     spec: &OpenAPI,
     global_security: Option<&Vec<openapiv3::SecurityRequirement>>,
 ) -> CommandInfo {
     let command_name = operation_command_name(operation, method);
     let parameters = extract_operation_parameters(operation, spec);
     let request_body = extract_request_body_info(operation);
-    let security_requirements =
-        extract_security_requirements(operation, global_security.map(std::vec::Vec::as_slice));
-    let response_schema = extract_response_schema_from_operation(operation, spec);
+    let security_alternatives =
+        extract_security_alternatives(operation, global_security.map(std::vec::Vec::as_slice));
+    let response_schema = extract_response_schema_from_operation(operation, source, spec);
 
     CommandInfo {
         name: command_name,
@@ -954,7 +1053,8 @@ fn convert_openapi_operation_to_info(
         operation_id: operation.operation_id.clone().unwrap_or_default(),
         parameters,
         request_body,
-        security_requirements,
+        security_requirements: flatten_security_alternatives(&security_alternatives),
+        security_alternatives,
         tags: operation.tags.iter().map(|t| to_kebab_case(t)).collect(),
         original_tags: operation.tags.clone(),
         deprecated: operation.deprecated,
@@ -977,47 +1077,49 @@ fn convert_openapi_operation_to_info(
 ///
 /// Looks for successful response codes (200, 201, 204) in priority order
 /// and extracts the schema for the first one found with application/json content.
+/// `source` is the operation as written, whose schemas are preferred when present.
 fn extract_response_schema_from_operation(
     operation: &Operation,
+    source: Option<&Value>,
     spec: &OpenAPI,
 ) -> Option<ResponseSchemaInfo> {
     constants::SUCCESS_STATUS_CODES.iter().find_map(|code| {
+        let source = source
+            .and_then(|operation| operation.get("responses"))
+            .and_then(|responses| responses.get(*code));
         operation
             .responses
             .responses
             .get(&openapiv3::StatusCode::Code(
                 code.parse().expect("valid status code"),
             ))
-            .and_then(|response_ref| extract_response_schema_from_response(response_ref, spec))
+            .and_then(|response_ref| {
+                extract_response_schema_from_response(response_ref, source, spec)
+            })
     })
 }
 
 /// Extracts response schema from a single response reference
 ///
-/// # Limitations
-///
-/// - **Response references are not resolved**: If `response_ref` is a `$ref` to
-///   `#/components/responses/...`, this function returns `None`. Only inline
-///   response definitions are processed. This is a known limitation that may
-///   be addressed in a future version.
-///
-/// - **Nested schema references**: While top-level schema references within the
-///   response content are resolved, any nested `$ref` within the schema's
-///   properties remain unresolved. See [`ResponseSchemaInfo`] for details.
+/// Response references are expected to have been inlined by
+/// [`crate::spec::inline_references`]; a remaining `$ref` yields `None`.
 fn extract_response_schema_from_response(
     response_ref: &ReferenceOr<openapiv3::Response>,
+    source: Option<&Value>,
     spec: &OpenAPI,
 ) -> Option<ResponseSchemaInfo> {
-    // Note: Response references ($ref to #/components/responses/...) are not
-    // currently resolved. This would require implementing resolve_response_reference()
-    // similar to resolve_schema_reference().
     let ReferenceOr::Item(response) = response_ref else {
         return None;
     };
 
     let content_type = select_response_content_type(response)?;
     let media_type = response.content.get(content_type)?;
-    let schema_value = extract_schema_value(media_type, spec)?;
+    let source_schema = source
+        .and_then(|response| response.get("content"))
+        .and_then(|content| content.get(content_type))
+        .and_then(|media_type| media_type.get("schema"))
+        .cloned();
+    let schema_value = source_schema.or_else(|| extract_schema_value(media_type, spec))?;
     let example = extract_response_example(media_type);
 
     Some(ResponseSchemaInfo {
@@ -1050,6 +1152,44 @@ fn extract_schema_value(
     }
 }
 
+/// Lists the webhooks of an `OpenAPI` 3.1 document with their payloads
+fn extract_webhooks(document: &SpecDocument) -> Vec<WebhookInfo> {
+    document
+        .webhooks()
+        .into_iter()
+        .map(|webhook| {
+            let text = |field: &str| {
+                webhook
+                    .operation
+                    .get(field)
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            };
+            let content = webhook
+                .operation
+                .get("requestBody")
+                .and_then(|body| body.get("content"))
+                .and_then(Value::as_object);
+            let payload = content.and_then(|content| {
+                content
+                    .get_key_value(constants::CONTENT_TYPE_JSON)
+                    .or_else(|| content.iter().next())
+            });
+
+            WebhookInfo {
+                name: webhook.name.to_string(),
+                method: webhook.method.to_uppercase(),
+                operation_id: text("operationId"),
+                summary: text("summary"),
+                description: text("description"),
+                content_type: payload.map(|(content_type, _)| content_type.clone()),
+                payload_schema: payload
+                    .and_then(|(_, media_type)| media_type.get("schema").cloned()),
+            }
+        })
+        .collect()
+}
+
 fn extract_response_example(media_type: &openapiv3::MediaType) -> Option<serde_json::Value> {
     media_type
         .example
@@ -1222,7 +1362,15 @@ fn convert_openapi_security_scheme(
             description.as_ref(),
             extract_aperture_secret_from_extensions(scheme),
         )),
-        SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. } => None,
+        SecurityScheme::OAuth2 {
+            flows, description, ..
+        } => crate::spec::oauth2_config_from_flows(flows).map(|oauth2| SecuritySchemeInfo {
+            scheme_type: constants::AUTH_SCHEME_OAUTH2.to_string(),
+            description: description.clone(),
+            details: oauth2_security_details(&oauth2),
+            aperture_secret: extract_aperture_secret_from_extensions(scheme),
+        }),
+        SecurityScheme::OpenIDConnect { .. } => None,
     }
 }
 
@@ -1286,10 +1434,10 @@ const fn security_scheme_extensions(
     scheme: &SecurityScheme,
 ) -> Option<&indexmap::IndexMap<String, serde_json::Value>> {
     match scheme {
-        SecurityScheme::APIKey { extensions, .. } | SecurityScheme::HTTP { extensions, .. } => {
-            Some(extensions)
-        }
-        SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. } => None,
+        SecurityScheme::APIKey { extensions, .. }
+        | SecurityScheme::HTTP { extensions, .. }
+        | SecurityScheme::OAuth2 { extensions, .. } => Some(extensions),
+        SecurityScheme::OpenIDConnect { .. } => None,
     }
 }
 
@@ -1298,9 +1446,8 @@ fn parse_aperture_secret_extension(value: &serde_json::Value) -> Option<CachedAp
     let source = obj.get(constants::EXT_KEY_SOURCE)?.as_str()?;
     let name = obj.get(constants::EXT_KEY_NAME)?.as_str()?;
 
-    if source != constants::SOURCE_ENV {
-        return None;
-    }
+    // Unknown sources are rejected during validation
+    source.parse::<crate::config::models::SecretSource>().ok()?;
 
     Some(CachedApertureSecret {
         source: source.to_string(),
@@ -1347,6 +1494,7 @@ mod tests {
                     source: constants::SOURCE_ENV.to_string(),
                     name: "API_TOKEN".to_string(),
                 }),
+                oauth2: None,
             },
         );
 
@@ -1372,6 +1520,7 @@ mod tests {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 }],
                 request_body: None,
                 responses: vec![],
@@ -1385,6 +1534,7 @@ mod tests {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             }],
             base_url: Some("https://test-api.example.com".to_string()),
             servers: vec!["https://test-api.example.com".to_string()],
@@ -69,6 +69,61 @@ pub fn atomic_write_sync(path: &Path, data: &[u8]) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Like [`atomic_write_sync`], but the file is readable and writable only by
+/// its owner from the moment it is created, so credentials are never exposed
+/// while they are written.
+///
+/// On platforms without Unix permissions this is [`atomic_write_sync`].
+///
+/// # Errors
+///
+/// Returns an error if any file operation fails.
+pub fn atomic_write_private_sync(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let temp_path = temp_sibling(path);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    if let Err(e) = options
+        .open(&temp_path)
+        .and_then(|mut file| file.write_all(data))
+    {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    if let Err(e) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Create `dir` and any missing parents, accessible only by their owner.
+///
+/// Directories that already exist keep their permissions.
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be created.
+pub fn create_private_dir_all(dir: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(dir)
+}
+
 /// Generate a unique temporary file path as a sibling of `path`.
 ///
 /// Uses `fastrand` for a random suffix to avoid collisions between
@@ -270,6 +325,26 @@ mod tests {
         assert_eq!(entries.len(), 1);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_private_writes_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let private_dir = dir.path().join("tokens");
+        let path = private_dir.join("secret.json");
+
+        create_private_dir_all(&private_dir).unwrap();
+        atomic_write_private_sync(&path, b"first").unwrap();
+        atomic_write_private_sync(&path, b"second").unwrap();
+
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&private_dir), 0o700);
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(std::fs::read_dir(&private_dir).unwrap().count(), 1);
+    }
+
     #[test]
     fn test_dir_lock_acquire_and_release() {
         let dir = TempDir::new().unwrap();
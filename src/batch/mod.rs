@@ -790,6 +790,17 @@ impl BatchProcessor {
         }))
     }
 
+    fn build_batch_token_store() -> Option<crate::oauth::TokenStore> {
+        let config_dir = std::env::var(crate::constants::ENV_APERTURE_CONFIG_DIR)
+            .map(std::path::PathBuf::from)
+            .ok()
+            .or_else(|| crate::config::manager::get_config_dir().ok())?;
+
+        Some(crate::oauth::TokenStore::new(
+            config_dir.join(crate::constants::DIR_TOKENS),
+        ))
+    }
+
     fn render_batch_execution_result(
         result: &crate::invocation::ExecutionResult,
         output_format: &crate::cli::OutputFormat,
@@ -852,6 +863,7 @@ impl BatchProcessor {
 
         let ctx = ExecutionContext {
             dry_run,
+            secret_references: false,
             idempotency_key: None,
             cache_config,
             retry_context,
@@ -860,6 +872,12 @@ impl BatchProcessor {
             global_config: global_config.cloned(),
             server_var_args: translate::extract_server_var_args(&matches),
             auto_paginate: false,
+            token_store: Self::build_batch_token_store(),
+            auth_scheme: None,
+            skip_validation: false,
+            response_validation: None,
+            cassette: None,
+            http_client: None,
         };
 
         let result = crate::engine::executor::execute(spec, call, ctx).await?;
@@ -104,7 +104,14 @@ pub struct SkippedEndpoint {
 /// Version 4: Added `example` field to `CachedResponse` for response schema examples
 /// Version 5: Added `display_group`, `display_name`, `aliases`, `hidden` fields for command mapping
 /// Version 6: Added `pagination` field to `CachedCommand` for auto-pagination support
-pub const CACHE_FORMAT_VERSION: u32 = 6;
+/// Version 7: Added `oauth2` field to `CachedSecurityScheme` for `OAuth2` token acquisition
+/// Version 8: Added `authorization_url` field to `CachedOAuth2Config` for interactive login
+/// Version 9: Added `encoding` field to `CachedRequestBody` for form-encoded bodies
+/// Version 10: Added `encoding` field to `CachedParameter` for parameter styles
+/// Version 11: Added `security_requirement_sets` field to `CachedCommand` for AND/OR security
+/// Version 12: Inlined nested component references in cached schemas
+/// Version 13: `OpenAPI` 3.1 schemas cached as written rather than lowered to 3.0
+pub const CACHE_FORMAT_VERSION: u32 = 13;
 
 /// Global cache metadata for all cached specifications
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -153,7 +160,8 @@ pub struct CachedCommand {
     pub parameters: Vec<CachedParameter>,
     pub request_body: Option<CachedRequestBody>,
     pub responses: Vec<CachedResponse>,
-    /// Security requirements for this operation (references to security scheme names)
+    /// Security requirements for this operation (references to security scheme names),
+    /// flattened across all alternatives
     pub security_requirements: Vec<String>,
     /// All tags associated with this operation
     pub tags: Vec<String>,
@@ -179,6 +187,25 @@ pub struct CachedCommand {
     /// Pagination configuration detected from the `OpenAPI` spec (added in v6)
     #[serde(default)]
     pub pagination: PaginationInfo,
+    /// Alternative security requirement sets; any one set satisfies the operation and
+    /// every scheme within a set must be sent together. An empty set allows anonymous
+    /// access (added in v11)
+    #[serde(default)]
+    pub security_requirement_sets: Vec<Vec<String>>,
+}
+
+impl CachedCommand {
+    /// Returns the alternative security requirement sets, in preference order.
+    ///
+    /// Commands without recorded sets treat all `security_requirements` as one set.
+    #[must_use]
+    pub fn security_alternatives(&self) -> Vec<Vec<String>> {
+        if self.security_requirement_sets.is_empty() && !self.security_requirements.is_empty() {
+            vec![self.security_requirements.clone()]
+        } else {
+            self.security_requirement_sets.clone()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -193,6 +220,9 @@ pub struct CachedParameter {
     pub default_value: Option<String>,
     pub enum_values: Vec<String>,
     pub example: Option<String>,
+    /// Declared `style`/`explode`; `None` means the location's default (added in v10)
+    #[serde(default)]
+    pub encoding: Option<CachedEncoding>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -202,6 +232,20 @@ pub struct CachedRequestBody {
     pub required: bool,
     pub description: Option<String>,
     pub example: Option<String>,
+    /// Per-property serialization for `application/x-www-form-urlencoded` bodies (added in v9)
+    #[serde(default)]
+    pub encoding: HashMap<String, CachedEncoding>,
+}
+
+/// How a parameter or form property is serialized, from its `style` and
+/// `explode` settings
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CachedEncoding {
+    /// `form`, `spaceDelimited`, `pipeDelimited`, `deepObject`, `simple`,
+    /// `label` or `matrix`
+    pub style: String,
+    /// Whether arrays and objects are sent as separate pairs
+    pub explode: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -234,6 +278,45 @@ pub struct CachedSecurityScheme {
     pub bearer_format: Option<String>,
     /// x-aperture-secret mapping for environment variable resolution
     pub aperture_secret: Option<CachedApertureSecret>,
+    /// Token endpoint configuration for `oauth2` schemes (added in v7)
+    #[serde(default)]
+    pub oauth2: Option<CachedOAuth2Config>,
+}
+
+impl CachedSecurityScheme {
+    /// Returns the query parameter that carries the secret of an `apiKey`
+    /// scheme with `in: query`
+    #[must_use]
+    pub fn query_parameter_name(&self) -> Option<&str> {
+        if self.scheme_type != crate::constants::AUTH_SCHEME_APIKEY
+            || self.location.as_deref() != Some(crate::constants::PARAM_LOCATION_QUERY)
+        {
+            return None;
+        }
+        self.parameter_name.as_deref()
+    }
+}
+
+/// Cached `OAuth2` configuration needed to obtain access tokens at execution time
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CachedOAuth2Config {
+    /// Token endpoint URL (`tokenUrl` in the `OpenAPI` flow object)
+    pub token_url: String,
+    /// Scope names declared by the flow, requested when fetching a token
+    pub scopes: Vec<String>,
+    /// Grant types Aperture can drive for this scheme (e.g. `client_credentials`)
+    pub grant_types: Vec<String>,
+    /// Authorization endpoint URL for the authorization-code flow (added in v8)
+    #[serde(default)]
+    pub authorization_url: Option<String>,
+}
+
+impl CachedOAuth2Config {
+    /// Returns true if Aperture can obtain tokens for this scheme with `grant_type`
+    #[must_use]
+    pub fn supports_grant(&self, grant_type: &str) -> bool {
+        self.grant_types.iter().any(|g| g == grant_type)
+    }
 }
 
 /// Cached representation of x-aperture-secret extension
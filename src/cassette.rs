@@ -0,0 +1,315 @@
+//! Recording and replaying HTTP exchanges.
+//!
+//! With `--record DIR` every response is saved to a cassette file in `DIR`
+//! next to the request that produced it; with `--replay DIR` requests are
+//! answered from those files without touching the network. Secrets are
+//! redacted before anything is written, using the same rules as request
+//! logging, so cassettes can be committed alongside tests.
+//!
+//! Requests are matched by a [`CacheKey`] of the method, the path and query,
+//! the body and the header parameters the operation declares. Credentials,
+//! the host and headers such as `User-Agent` are left out of the key, so a
+//! cassette recorded with real secrets against one server replays on a
+//! machine that has none, whatever its base URL. Streamed bodies, such as
+//! multipart uploads and files, cannot be matched and are refused.
+
+use crate::cache::models::CachedCommand;
+use crate::constants;
+use crate::error::Error;
+use crate::logging::{self, SecretContext};
+use crate::response_cache::CacheKey;
+use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+/// Whether a cassette directory is being written or read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests and save each exchange
+    Record,
+    /// Answer requests from saved exchanges
+    Replay,
+}
+
+/// A directory of recorded exchanges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cassette {
+    pub mode: CassetteMode,
+    pub dir: PathBuf,
+}
+
+impl Cassette {
+    #[must_use]
+    pub fn record(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            dir: dir.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: CassetteMode::Replay,
+            dir: dir.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_replay(&self) -> bool {
+        self.mode == CassetteMode::Replay
+    }
+
+    /// Binds the cassette to the requests of one operation
+    #[must_use]
+    pub const fn for_operation<'a>(
+        &'a self,
+        api_name: &'a str,
+        operation: &'a CachedCommand,
+    ) -> OperationCassette<'a> {
+        OperationCassette {
+            cassette: self,
+            api_name,
+            operation,
+        }
+    }
+}
+
+/// One recorded request and its response, as stored on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A request with its secrets redacted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// A response with its secrets redacted. Bodies that are not UTF-8 are
+/// stored base64-encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+}
+
+/// A cassette serving the requests of one operation
+#[derive(Debug, Clone, Copy)]
+pub struct OperationCassette<'a> {
+    cassette: &'a Cassette,
+    api_name: &'a str,
+    operation: &'a CachedCommand,
+}
+
+/// A request about to be recorded or replayed, with the file it maps to
+#[derive(Debug)]
+pub struct PendingInteraction {
+    request: RecordedRequest,
+    path: PathBuf,
+}
+
+impl OperationCassette<'_> {
+    #[must_use]
+    pub fn is_replay(&self) -> bool {
+        self.cassette.is_replay()
+    }
+
+    /// Redacts a request and locates its cassette file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request streams its body or the request key
+    /// cannot be computed.
+    pub fn prepare(
+        &self,
+        request: &reqwest::Request,
+        secret_ctx: Option<&SecretContext>,
+    ) -> Result<PendingInteraction, Error> {
+        if request.body().is_some_and(|body| body.as_bytes().is_none()) {
+            return Err(Error::validation_error(format!(
+                "Operation '{}' streams its request body (a multipart upload or a file), which cannot be recorded or replayed",
+                self.operation.operation_id
+            )));
+        }
+        let headers: BTreeMap<String, String> = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes());
+                (
+                    name.as_str().to_string(),
+                    logging::redact_header_value(name.as_str(), &value, secret_ctx),
+                )
+            })
+            .collect();
+        let body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .map(|bytes| redact_text(&String::from_utf8_lossy(bytes), secret_ctx));
+        let recorded = RecordedRequest {
+            method: request.method().to_string(),
+            url: logging::redact_url_query_params(request.url().as_str(), secret_ctx),
+            headers,
+            body,
+        };
+
+        let key = CacheKey::from_request(
+            self.api_name,
+            &self.operation.operation_id,
+            &recorded.method,
+            &key_target(request.url(), secret_ctx),
+            &self.key_headers(&recorded.headers),
+            recorded.body.as_deref(),
+        )?;
+        let hash_prefix = &key.request_hash[..key.request_hash.len().min(16)];
+        let path = self.cassette.dir.join(format!(
+            "{}_{}_{hash_prefix}.json",
+            key.api_name, key.operation_id
+        ));
+
+        Ok(PendingInteraction {
+            request: recorded,
+            path,
+        })
+    }
+
+    /// Answers a request from its recorded response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no response was recorded for the request or the
+    /// cassette file cannot be read.
+    pub fn replay(
+        &self,
+        pending: &PendingInteraction,
+    ) -> Result<(StatusCode, HashMap<String, String>, Bytes), Error> {
+        if !pending.path.exists() {
+            return Err(Error::cassette_miss(
+                &pending.request.method,
+                &pending.request.url,
+                &self.cassette.dir,
+            ));
+        }
+        let content = std::fs::read_to_string(&pending.path)?;
+        let interaction: Interaction = serde_json::from_str(&content).map_err(|e| {
+            Error::serialization_error(format!(
+                "Invalid cassette file '{}': {e}",
+                pending.path.display()
+            ))
+        })?;
+
+        let response = interaction.response;
+        let status = StatusCode::from_u16(response.status).map_err(|e| {
+            Error::serialization_error(format!(
+                "Invalid status in cassette file '{}': {e}",
+                pending.path.display()
+            ))
+        })?;
+        let body = match (response.body, response.body_base64) {
+            (_, Some(encoded)) => general_purpose::STANDARD.decode(encoded).map_err(|e| {
+                Error::serialization_error(format!(
+                    "Invalid body in cassette file '{}': {e}",
+                    pending.path.display()
+                ))
+            })?,
+            (Some(text), None) => text.into_bytes(),
+            (None, None) => Vec::new(),
+        };
+        Ok((status, response.headers.into_iter().collect(), body.into()))
+    }
+
+    /// The headers used for matching: those the operation declares as
+    /// parameters, except redacted ones, which carry credentials
+    fn key_headers(&self, headers: &BTreeMap<String, String>) -> HashMap<String, String> {
+        headers
+            .iter()
+            .filter(|(name, value)| {
+                value.as_str() != "[REDACTED]"
+                    && self.operation.parameters.iter().any(|param| {
+                        param.location == constants::PARAM_LOCATION_HEADER
+                            && param.name.eq_ignore_ascii_case(name)
+                    })
+            })
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Saves a response next to the request that produced it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cassette file cannot be written.
+    pub fn record(
+        &self,
+        pending: PendingInteraction,
+        status: StatusCode,
+        headers: &HashMap<String, String>,
+        body: &Bytes,
+        secret_ctx: Option<&SecretContext>,
+    ) -> Result<(), Error> {
+        let (text, encoded) = std::str::from_utf8(body).map_or_else(
+            |_| (None, Some(general_purpose::STANDARD.encode(body))),
+            |text| (Some(redact_text(text, secret_ctx)), None),
+        );
+        let interaction = Interaction {
+            request: pending.request,
+            response: RecordedResponse {
+                status: status.as_u16(),
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| {
+                        (
+                            name.clone(),
+                            logging::redact_header_value(name, value, secret_ctx),
+                        )
+                    })
+                    .collect(),
+                body: text.filter(|text| !text.is_empty()),
+                body_base64: encoded,
+            },
+        };
+
+        let json = serde_json::to_string_pretty(&interaction)
+            .map_err(|e| Error::serialization_error(e.to_string()))?;
+        std::fs::create_dir_all(&self.cassette.dir)?;
+        crate::atomic::atomic_write_sync(&pending.path, json.as_bytes())?;
+        Ok(())
+    }
+}
+
+fn redact_text(text: &str, secret_ctx: Option<&SecretContext>) -> String {
+    secret_ctx.map_or_else(|| text.to_string(), |ctx| ctx.redact_secrets_in_text(text))
+}
+
+/// The path and query used for matching. `apiKey` query parameters are
+/// dropped, since they are only present when a secret is configured.
+fn key_target(url: &reqwest::Url, secret_ctx: Option<&SecretContext>) -> String {
+    let pairs: Vec<&str> = url
+        .query()
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .filter(|pair| {
+            let name = pair.split_once('=').map_or(*pair, |(name, _)| name);
+            !secret_ctx.is_some_and(|ctx| ctx.is_secret_query_param(name))
+        })
+        .collect();
+    if pairs.is_empty() {
+        url.path().to_string()
+    } else {
+        format!("{}?{}", url.path(), pairs.join("&"))
+    }
+}
@@ -75,13 +75,13 @@ fn resolve_output_format(
     }
 }
 
-struct ApiCommandContext {
-    config_dir: PathBuf,
-    spec: CachedSpec,
-    global_config: Option<GlobalConfig>,
+pub(crate) struct ApiCommandContext {
+    pub(crate) config_dir: PathBuf,
+    pub(crate) spec: CachedSpec,
+    pub(crate) global_config: Option<GlobalConfig>,
 }
 
-fn load_api_command_context(context: &str) -> Result<ApiCommandContext, Error> {
+pub(crate) fn load_api_command_context(context: &str) -> Result<ApiCommandContext, Error> {
     let config_dir = if let Ok(dir) = std::env::var(constants::ENV_APERTURE_CONFIG_DIR) {
         PathBuf::from(dir)
     } else {
@@ -109,17 +109,34 @@ fn handle_describe_json_command(
     command_context: &ApiCommandContext,
     execution: &ExecutionFlags,
 ) -> Result<(), Error> {
+    if let Some(format) = &execution.describe_as {
+        let tools = crate::tool_export::generate_tools(
+            &command_context.spec,
+            format,
+            execution
+                .max_description_tokens
+                .unwrap_or(crate::tool_export::DEFAULT_DESCRIPTION_TOKENS),
+        )?;
+        let tools = serde_json::to_string_pretty(&tools)
+            .map_err(|e| Error::serialization_error(e.to_string()))?;
+        let output = match &execution.jq {
+            Some(jq_filter) => executor::apply_jq_filter(&tools, jq_filter)?,
+            None => tools,
+        };
+        return write_stdout_line(&output);
+    }
+
     let specs_dir = command_context.config_dir.join(constants::DIR_SPECS);
     let spec_path = specs_dir.join(format!("{context}.yaml"));
     if !spec_path.exists() {
         return Err(Error::spec_not_found(context));
     }
     let spec_content = std::fs::read_to_string(&spec_path)?;
-    let openapi_spec = crate::spec::parse_openapi(&spec_content)
+    let document = crate::spec::SpecDocument::parse(&spec_content)
         .map_err(|e| Error::invalid_config(format!("Failed to parse OpenAPI spec: {e}")))?;
-    let manifest = crate::agent::generate_capability_manifest_from_openapi(
+    let manifest = crate::agent::generate_capability_manifest_from_document(
         context,
-        &openapi_spec,
+        &document,
         &command_context.spec,
         command_context.global_config.as_ref(),
     )?;
@@ -210,11 +227,15 @@ fn render_api_context_landing(context: &str, spec: &CachedSpec) -> Result<(), Er
 const LANDING_INCOMPATIBLE_GLOBAL_FLAGS: &[&str] = &[
     "--json-errors",
     "--dry-run",
+    "--emit",
     "--idempotency-key",
     "--proxy",
     "--no-proxy",
+    "--record",
+    "--replay",
     "--format",
     "--jq",
+    "--output-file",
     "--batch-file",
     "--batch-concurrency",
     "--batch-rate-limit",
@@ -265,11 +286,41 @@ async fn execute_api_runtime(
     let mut ctx = crate::cli::translate::cli_to_execution_context(execution, global_config)?;
     ctx.server_var_args = crate::cli::translate::extract_server_var_args(matches);
 
+    if let Some(format) = &execution.emit {
+        return execute_emit_api_runtime(spec, call, ctx, format).await;
+    }
     if ctx.auto_paginate {
+        if execution.output_file.is_some() {
+            tracing::warn!(
+                "--output-file is ignored with --auto-paginate; output is always NDJSON"
+            );
+        }
         return execute_paginated_api_runtime(spec, call, ctx, cli, jq_filter, output_format).await;
     }
 
-    execute_standard_api_runtime(spec, call, ctx, output_format, jq_filter).await
+    execute_standard_api_runtime(
+        spec,
+        call,
+        ctx,
+        output_format,
+        jq_filter,
+        execution.output_file.as_deref(),
+    )
+    .await
+}
+
+/// Prints a runnable equivalent of the request instead of sending it
+async fn execute_emit_api_runtime(
+    spec: &CachedSpec,
+    call: crate::invocation::OperationCall,
+    ctx: crate::invocation::ExecutionContext,
+    format: &crate::cli::EmitFormat,
+) -> Result<(), Error> {
+    let result = executor::execute(spec, call, ctx).await?;
+    if let crate::invocation::ExecutionResult::DryRun { request_info } = &result {
+        write_stdout_line(&crate::snippet::render(format, request_info))?;
+    }
+    Ok(())
 }
 
 async fn execute_paginated_api_runtime(
@@ -311,11 +362,20 @@ async fn execute_standard_api_runtime(
     ctx: crate::invocation::ExecutionContext,
     output_format: crate::cli::OutputFormat,
     jq_filter: Option<&str>,
+    output_file: Option<&str>,
 ) -> Result<(), Error> {
     let result = executor::execute(spec, call, ctx)
         .await
         .map_err(enrich_network_error)?;
 
+    if let Some(path) = output_file {
+        return crate::cli::render::render_result_to_file(
+            &result,
+            std::path::Path::new(path),
+            &output_format,
+            jq_filter,
+        );
+    }
     crate::cli::render::render_result(&result, &output_format, jq_filter)?;
     Ok(())
 }
@@ -360,10 +420,15 @@ fn should_attempt_examples_fallback(args: &[String], parse_error: &clap::Error)
 const RESERVED_EXECUTION_FLAGS: &[(&str, bool)] = &[
     ("--api", true),
     ("--describe-json", false),
+    ("--as", true),
+    ("--max-description-tokens", true),
     ("--dry-run", false),
+    ("--emit", true),
     ("--idempotency-key", true),
     ("--proxy", true),
     ("--no-proxy", false),
+    ("--record", true),
+    ("--replay", true),
     ("--format", true),
     ("--jq", true),
     ("--batch-file", true),
@@ -0,0 +1,145 @@
+//! Handlers for `aperture auth` commands.
+
+use crate::cache::models::{CachedSecurityScheme, CachedSpec};
+use crate::cli::commands::api::load_api_command_context;
+use crate::cli::commands::config::validate_api_name;
+use crate::cli::AuthCommands;
+use crate::constants;
+use crate::engine::executor;
+use crate::error::Error;
+use crate::invocation::ExecutionContext;
+use crate::oauth::{ClientCredentials, PendingAuthorization, TokenStore};
+use crate::output::Output;
+use std::time::Duration;
+
+/// Dispatches an `aperture auth` subcommand.
+pub async fn execute_auth_command(command: AuthCommands, output: &Output) -> Result<(), Error> {
+    match command {
+        AuthCommands::Login { api, scheme, port } => {
+            handle_login(&api, scheme.as_deref(), port, output).await
+        }
+    }
+}
+
+async fn handle_login(
+    api: &str,
+    scheme_name: Option<&str>,
+    port: u16,
+    output: &Output,
+) -> Result<(), Error> {
+    let api = validate_api_name(api)?;
+    let context = load_api_command_context(api.as_str())?;
+    let scheme = select_login_scheme(&context.spec, scheme_name)?;
+
+    let secret = executor::resolve_authentication_secret(
+        scheme,
+        api.as_str(),
+        context.global_config.as_ref(),
+    )?
+    .ok_or_else(|| missing_client_id(api.as_str(), &scheme.name))?;
+    let credentials = ClientCredentials::for_scheme(scheme, &secret.description, &secret.value)?;
+
+    let pending = PendingAuthorization::start(scheme, &credentials, port).await?;
+
+    // The URL is required to proceed, so it is shown even in quiet mode
+    eprintln!(
+        "Open this URL in your browser to authorize '{}':",
+        scheme.name
+    );
+    eprintln!();
+    eprintln!("  {}", pending.authorization_url());
+    eprintln!();
+    eprintln!("Waiting for the redirect to {} ...", pending.redirect_uri());
+
+    let http = executor::build_http_client(&ExecutionContext {
+        global_config: context.global_config.clone(),
+        ..ExecutionContext::default()
+    })?;
+    let token = pending
+        .complete(
+            &http.client,
+            &credentials,
+            Duration::from_secs(constants::OAUTH2_LOGIN_TIMEOUT_SECS),
+        )
+        .await?;
+
+    let store = TokenStore::new(context.config_dir.join(constants::DIR_TOKENS));
+    store.save(api.as_str(), &scheme.name, &token)?;
+
+    output.success(format!("Authorized '{}' for API '{api}'.", scheme.name));
+    if token.refresh_token.is_none() {
+        output.tip(
+            "The provider issued no refresh token; run 'aperture auth login' again when the access token expires.",
+        );
+    }
+    Ok(())
+}
+
+/// Picks the authorization-code scheme to log in with.
+///
+/// An explicit `--scheme` must name such a scheme; otherwise the spec must
+/// declare exactly one.
+fn select_login_scheme<'a>(
+    spec: &'a CachedSpec,
+    scheme_name: Option<&str>,
+) -> Result<&'a CachedSecurityScheme, Error> {
+    let supports_login = |scheme: &CachedSecurityScheme| {
+        scheme
+            .oauth2
+            .as_ref()
+            .is_some_and(|config| config.supports_grant(constants::OAUTH2_GRANT_AUTHORIZATION_CODE))
+    };
+
+    if let Some(name) = scheme_name {
+        let scheme = spec.security_schemes.get(name).ok_or_else(|| {
+            Error::invalid_command(
+                "auth login",
+                format!("security scheme '{name}' is not defined in '{}'", spec.name),
+            )
+        })?;
+        if !supports_login(scheme) {
+            return Err(Error::invalid_command(
+                "auth login",
+                format!("security scheme '{name}' has no OAuth2 authorizationCode flow"),
+            ));
+        }
+        return Ok(scheme);
+    }
+
+    let mut candidates: Vec<&CachedSecurityScheme> = spec
+        .security_schemes
+        .values()
+        .filter(|scheme| supports_login(scheme))
+        .collect();
+    candidates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    match candidates.as_slice() {
+        [scheme] => Ok(scheme),
+        [] => Err(Error::invalid_command(
+            "auth login",
+            format!(
+                "'{}' has no OAuth2 security scheme with an authorizationCode flow",
+                spec.name
+            ),
+        )),
+        _ => {
+            let names: Vec<&str> = candidates.iter().map(|s| s.name.as_str()).collect();
+            Err(Error::invalid_command(
+                "auth login",
+                format!(
+                    "multiple OAuth2 schemes found ({}); choose one with --scheme",
+                    names.join(", ")
+                ),
+            ))
+        }
+    }
+}
+
+fn missing_client_id(api: &str, scheme_name: &str) -> Error {
+    Error::invalid_config(format!(
+        "No client id configured for OAuth2 scheme '{scheme_name}'"
+    ))
+    .with_suggestion(&format!(
+        "Map an environment variable holding 'client_id' or 'client_id:client_secret': aperture config secret set {api} {scheme_name} --env VAR"
+    ))
+}
@@ -13,6 +13,7 @@ use std::path::PathBuf;
 const TOP_LEVEL_COMMANDS: &[&str] = &[
     "completion",
     "config",
+    "auth",
     "commands",
     "list-commands",
     "api",
@@ -21,10 +22,18 @@ const TOP_LEVEL_COMMANDS: &[&str] = &[
     "exec",
     "docs",
     "overview",
+    "mock",
+    "serve",
+    "mcp",
+    "daemon",
 ];
 
 const GLOBAL_FLAGS: &[&str] = &["--help", "--json-errors", "--quiet", "-q", "-v"];
 
+const AUTH_COMMANDS: &[&str] = &["login"];
+
+const MCP_COMMANDS: &[&str] = &["serve"];
+
 const CONFIG_DOMAINS: &[&str] = &["api", "url", "secret", "cache", "setting", "mapping"];
 const CONFIG_API_COMMANDS: &[&str] = &["add", "list", "remove", "edit", "reinit"];
 const CONFIG_URL_COMMANDS: &[&str] = &["set", "get", "list"];
@@ -38,10 +47,15 @@ const SHELL_NAMES: &[&str] = &["bash", "zsh", "fish", "nu", "powershell"];
 const API_EXECUTION_FLAGS: &[&str] = &[
     "--help",
     "--describe-json",
+    "--as",
+    "--max-description-tokens",
     "--dry-run",
+    "--emit",
     "--idempotency-key",
     "--proxy",
     "--no-proxy",
+    "--record",
+    "--replay",
     "--format",
     "--jq",
     "--batch-file",
@@ -59,8 +73,13 @@ const API_EXECUTION_FLAGS: &[&str] = &[
 ];
 
 const API_PREFIX_FLAGS_WITH_VALUES: &[&str] = &[
+    "--as",
+    "--max-description-tokens",
+    "--emit",
     "--idempotency-key",
     "--proxy",
+    "--record",
+    "--replay",
     "--format",
     "--jq",
     "--batch-file",
@@ -201,7 +220,17 @@ fn complete_primary_command(
             &input.current,
         )),
         "config" => Some(complete_config(args_after_command, &input.current, catalog)),
-        "commands" | "list-commands" => Some(complete_single_context_argument(
+        "auth" => Some(complete_auth(
+            args_after_command,
+            &input.current,
+            &catalog.contexts,
+        )),
+        "commands" | "list-commands" | "mock" | "serve" => Some(complete_single_context_argument(
+            args_after_command,
+            &input.current,
+            &catalog.contexts,
+        )),
+        "mcp" => Some(complete_mcp(
             args_after_command,
             &input.current,
             &catalog.contexts,
@@ -297,6 +326,30 @@ fn complete_docs(args: &[String], current: &str, catalog: &CompletionCatalog) ->
     Vec::new()
 }
 
+fn complete_auth(args: &[String], current: &str, contexts: &[String]) -> Vec<String> {
+    let Some(command) = args.first().map(String::as_str) else {
+        return filter_candidates(AUTH_COMMANDS.iter().map(ToString::to_string), current);
+    };
+
+    match command {
+        "login" => complete_single_context_argument(&args[1..], current, contexts),
+        _ => Vec::new(),
+    }
+}
+
+fn complete_mcp(args: &[String], current: &str, contexts: &[String]) -> Vec<String> {
+    let Some(command) = args.first().map(String::as_str) else {
+        return filter_candidates(MCP_COMMANDS.iter().map(ToString::to_string), current);
+    };
+
+    match command {
+        "serve" if args.last().is_some_and(|arg| arg == "--api") => {
+            filter_candidates(contexts.iter().cloned(), current)
+        }
+        _ => Vec::new(),
+    }
+}
+
 fn complete_config(args: &[String], current: &str, catalog: &CompletionCatalog) -> Vec<String> {
     let args = strip_leading_option_tokens(args);
 
@@ -567,8 +620,10 @@ fn operation_flags(spec: &CachedSpec, group: &str, operation: &str) -> Vec<Strin
         .map(|parameter| format!("--{}", to_kebab_case(&parameter.name)))
         .collect::<BTreeSet<_>>();
 
-    if command.request_body.is_some() {
-        flags.insert("--body".to_string());
+    if let Some(request_body) = &command.request_body {
+        if !crate::spec::is_binary_content_type(&request_body.content_type) {
+            flags.insert("--body".to_string());
+        }
         flags.insert("--body-file".to_string());
     }
 
@@ -2,7 +2,8 @@
 
 use crate::config::context_name::ApiContextName;
 use crate::config::manager::{get_config_dir, ConfigManager};
-use crate::config::models::SecretSource;
+use crate::config::models::{ResponseValidation, SecretSource};
+use crate::config::vault::{self, Vault};
 use crate::constants;
 use crate::discovery_style::DiscoveryStyle;
 use crate::error::Error;
@@ -257,6 +258,18 @@ fn handle_set_url(
     Ok(())
 }
 
+fn handle_set_response_validation(
+    manager: &ConfigManager<OsFileSystem>,
+    name: &str,
+    mode: ResponseValidation,
+    output: &Output,
+) -> Result<(), Error> {
+    let name = validate_api_name(name)?;
+    manager.set_response_validation(&name, mode)?;
+    output.success(format!("Set response validation for '{name}' to {mode}"));
+    Ok(())
+}
+
 fn handle_list_urls(manager: &ConfigManager<OsFileSystem>, output: &Output) -> Result<(), Error> {
     let all_urls = manager.list_urls()?;
     if all_urls.is_empty() {
@@ -320,7 +333,7 @@ fn handle_set_secret(
     manager: &ConfigManager<OsFileSystem>,
     api_name: String,
     scheme_name: Option<String>,
-    env: Option<String>,
+    reference: Option<(SecretSource, String)>,
     interactive: bool,
     output: &Output,
 ) -> Result<(), Error> {
@@ -329,14 +342,15 @@ fn handle_set_secret(
         manager.set_secret_interactive(&api_name)?;
         return Ok(());
     }
-    let (Some(scheme), Some(env_var)) = (scheme_name, env) else {
+    let (Some(scheme), Some((source, name))) = (scheme_name, reference) else {
         return Err(Error::invalid_config(
-            "Either provide --scheme and --env, or use --interactive",
+            "Either provide --scheme and --env (or --source and --name), or use --interactive",
         ));
     };
-    manager.set_secret(&api_name, &scheme, &env_var)?;
+    manager.set_secret_from_source(&api_name, &scheme, source, &name)?;
     output.success(format!(
-        "Set secret for scheme '{scheme}' in API '{api_name}' to use environment variable '{env_var}'"
+        "Set secret for scheme '{scheme}' in API '{api_name}' to use {}",
+        source.describe(&name)
     ));
     Ok(())
 }
@@ -410,6 +424,88 @@ fn clear_all_api_secrets(
     Ok(())
 }
 
+fn handle_vault_command(
+    manager: &ConfigManager<OsFileSystem>,
+    command: crate::cli::ConfigVaultCommands,
+    output: &Output,
+) -> Result<(), Error> {
+    let path = Vault::path_in(manager.config_dir());
+    match command {
+        crate::cli::ConfigVaultCommands::Init { force } => handle_vault_init(path, force, output),
+        crate::cli::ConfigVaultCommands::Put { name } => {
+            let mut vault = Vault::open(path, &vault::passphrase()?)?;
+            let value = read_vault_value(&name)?;
+            vault.insert(name.as_str(), value);
+            vault.save()?;
+            output.success(format!("Stored '{name}' in the secret vault"));
+            Ok(())
+        }
+        crate::cli::ConfigVaultCommands::Get { name } => {
+            let vault = Vault::open(path, &vault::passphrase()?)?;
+            let value = vault
+                .get(&name)
+                .ok_or_else(|| Error::vault_entry_not_found(&name))?;
+            // ast-grep-ignore: no-println
+            crate::stdoutln!("{value}");
+            Ok(())
+        }
+        crate::cli::ConfigVaultCommands::Rm { name } => {
+            let mut vault = Vault::open(path, &vault::passphrase()?)?;
+            if !vault.remove(&name) {
+                return Err(Error::vault_entry_not_found(&name));
+            }
+            vault.save()?;
+            output.success(format!("Removed '{name}' from the secret vault"));
+            Ok(())
+        }
+        crate::cli::ConfigVaultCommands::List => {
+            let vault = Vault::open(path, &vault::passphrase()?)?;
+            let mut names = vault.names().peekable();
+            if names.peek().is_none() {
+                output.info("The secret vault is empty");
+            }
+            for name in names {
+                // ast-grep-ignore: no-println
+                crate::stdoutln!("{name}");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn handle_vault_init(path: PathBuf, force: bool, output: &Output) -> Result<(), Error> {
+    if path.exists() && !force {
+        return Err(Error::vault_already_exists(path.display().to_string()));
+    }
+
+    let passphrase = vault::new_passphrase()?;
+    let display = path.display().to_string();
+    Vault::create(path, &passphrase)?.save()?;
+    output.success(format!("Created secret vault at '{display}'"));
+    output.tip(
+        "Store secrets with 'aperture config secret vault put <name>' and map them with '--source vault --name <name>'.",
+    );
+    Ok(())
+}
+
+/// Reads a vault value from a prompt, or from stdin when it is piped
+fn read_vault_value(name: &str) -> Result<String, Error> {
+    use std::io::{IsTerminal, Read};
+
+    let value = if std::io::stdin().is_terminal() {
+        crate::interactive::prompt_for_secret(&format!("Value for '{name}': "))?
+    } else {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        input.trim_end_matches(['\r', '\n']).to_string()
+    };
+
+    if value.is_empty() {
+        return Err(Error::validation_error("The secret value cannot be empty"));
+    }
+    Ok(value)
+}
+
 #[allow(clippy::needless_pass_by_value)]
 fn handle_get_setting(
     manager: &ConfigManager<OsFileSystem>,
@@ -476,6 +572,12 @@ fn normalize_api_config_command(
         crate::cli::ConfigApiCommands::Reinit { context, all } => {
             crate::cli::ConfigCommands::Reinit { context, all }
         }
+        // Response validation has no legacy flat command; it stays nested
+        response_validation @ crate::cli::ConfigApiCommands::ResponseValidation { .. } => {
+            crate::cli::ConfigCommands::Api {
+                command: response_validation,
+            }
+        }
     }
 }
 
@@ -499,11 +601,15 @@ fn normalize_secret_config_command(
             api_name,
             scheme_name,
             env,
+            source,
+            name,
             interactive,
         } => crate::cli::ConfigCommands::SetSecret {
             api_name,
             scheme_name,
             env,
+            source,
+            name,
             interactive,
         },
         crate::cli::ConfigSecretCommands::List { api_name } => {
@@ -519,6 +625,10 @@ fn normalize_secret_config_command(
         crate::cli::ConfigSecretCommands::Clear { api_name, force } => {
             crate::cli::ConfigCommands::ClearSecrets { api_name, force }
         }
+        // The vault has no legacy flat command; it stays nested
+        vault @ crate::cli::ConfigSecretCommands::Vault { .. } => {
+            crate::cli::ConfigCommands::Secret { command: vault }
+        }
     }
 }
 
@@ -658,6 +768,9 @@ async fn execute_specs_config_command(
         crate::cli::ConfigCommands::Reinit { context, all } => {
             handle_reinit(manager, context, all, output)
         }
+        crate::cli::ConfigCommands::Api {
+            command: crate::cli::ConfigApiCommands::ResponseValidation { name, mode },
+        } => handle_set_response_validation(manager, &name, mode, output),
         _ => unreachable!("command family routing must be exhaustive"),
     }
 }
@@ -730,8 +843,23 @@ fn execute_secret_config_command(
             api_name,
             scheme_name,
             env,
+            source,
+            name,
             interactive,
-        } => handle_set_secret(manager, api_name, scheme_name, env, interactive, output),
+        } => {
+            // --env VAR is shorthand for --source env --name VAR
+            let reference = env
+                .map(|env| (SecretSource::Env, env))
+                .or_else(|| source.zip(name));
+            handle_set_secret(
+                manager,
+                api_name,
+                scheme_name,
+                reference,
+                interactive,
+                output,
+            )
+        }
         crate::cli::ConfigCommands::ListSecrets { api_name } => {
             handle_list_secrets(manager, api_name, output)
         }
@@ -742,6 +870,9 @@ fn execute_secret_config_command(
         crate::cli::ConfigCommands::ClearSecrets { api_name, force } => {
             handle_clear_secrets(manager, api_name, force, output)
         }
+        crate::cli::ConfigCommands::Secret {
+            command: crate::cli::ConfigSecretCommands::Vault { command },
+        } => handle_vault_command(manager, command, output),
         _ => unreachable!("command family routing must be exhaustive"),
     }
 }
@@ -827,12 +958,8 @@ pub fn print_secrets_list(
 ) {
     output.info(format!("Configured secrets for API '{api_name}':"));
     for (scheme_name, secret) in secrets {
-        match secret.source {
-            SecretSource::Env => {
-                // ast-grep-ignore: no-println
-                crate::stdoutln!("  {scheme_name}: environment variable '{}'", secret.name);
-            }
-        }
+        // ast-grep-ignore: no-println
+        crate::stdoutln!("  {scheme_name}: {}", secret.source.describe(&secret.name));
     }
 }
 
@@ -0,0 +1,45 @@
+//! Handler for `aperture daemon`.
+
+use crate::config::manager::ConfigManager;
+use crate::error::Error;
+use crate::fs::OsFileSystem;
+use crate::output::Output;
+use std::path::Path;
+
+/// Serves JSON-RPC requests on a Unix socket until the process is interrupted.
+#[cfg(unix)]
+pub async fn execute_daemon_command(
+    manager: &ConfigManager<OsFileSystem>,
+    socket: Option<&Path>,
+    output: &Output,
+) -> Result<(), Error> {
+    use crate::constants;
+    use crate::daemon::{self, Daemon};
+    use std::sync::Arc;
+
+    let socket = socket.map_or_else(
+        || manager.config_dir().join(constants::DAEMON_SOCKET_FILENAME),
+        Path::to_path_buf,
+    );
+    let daemon = Daemon::new(manager.config_dir().to_path_buf())?;
+    let listener = daemon::bind(&socket).await?;
+
+    output.success(format!("Daemon listening on {}", socket.display()));
+    output.tip("Send newline-delimited JSON-RPC requests: execute, describe, search.");
+    output.tip("Press Ctrl+C to stop. Run with -v to log each request.");
+
+    Arc::new(daemon).run(listener).await
+}
+
+/// Unix sockets are not available on this platform.
+#[cfg(not(unix))]
+pub async fn execute_daemon_command(
+    _manager: &ConfigManager<OsFileSystem>,
+    _socket: Option<&Path>,
+    _output: &Output,
+) -> Result<(), Error> {
+    Err(Error::invalid_command(
+        "daemon",
+        "aperture daemon requires Unix domain sockets, which this platform does not support",
+    ))
+}
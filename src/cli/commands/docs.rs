@@ -136,6 +136,7 @@ struct OperationDetailsJson {
     request_body: Option<RequestBodyJson>,
     responses: Vec<ResponseJson>,
     security_requirements: Vec<String>,
+    security_alternatives: Vec<Vec<String>>,
     examples: Vec<CommandExampleJson>,
 }
 
@@ -470,6 +471,7 @@ fn build_operation_details_json(api: &str, command: &CachedCommand) -> Operation
         request_body: serialize_request_body(command),
         responses: serialize_responses(command),
         security_requirements: command.security_requirements.clone(),
+        security_alternatives: command.security_alternatives(),
         examples: serialize_examples(api, command),
     }
 }
@@ -0,0 +1,109 @@
+//! Handlers for `aperture mcp` commands.
+
+use crate::cli::commands::config::validate_api_name;
+use crate::cli::McpCommands;
+use crate::config::manager::ConfigManager;
+use crate::constants;
+use crate::engine::executor::RetryContext;
+use crate::engine::loader;
+use crate::error::Error;
+use crate::fs::OsFileSystem;
+use crate::invocation::ExecutionContext;
+use crate::mcp::McpServer;
+use crate::oauth::TokenStore;
+use crate::response_cache::CacheConfig;
+use std::time::Duration;
+
+/// Dispatches an `aperture mcp` subcommand.
+pub async fn execute_mcp_command(
+    manager: &ConfigManager<OsFileSystem>,
+    command: McpCommands,
+) -> Result<(), Error> {
+    match command {
+        McpCommands::Serve {
+            apis,
+            cache,
+            cache_ttl,
+        } => handle_serve(manager, apis, cache, cache_ttl).await,
+    }
+}
+
+async fn handle_serve(
+    manager: &ConfigManager<OsFileSystem>,
+    apis: Vec<String>,
+    cache: bool,
+    cache_ttl: Option<u64>,
+) -> Result<(), Error> {
+    let apis = if apis.is_empty() {
+        manager.list_specs()?
+    } else {
+        apis
+    };
+
+    let cache_dir = manager.config_dir().join(constants::DIR_CACHE);
+    let specs = apis
+        .iter()
+        .map(|api| {
+            let api = validate_api_name(api)?;
+            loader::load_cached_spec(&cache_dir, api.as_str()).map_err(|e| match e {
+                Error::Io(_) => Error::spec_not_found(api.as_str()),
+                _ => e,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let context = server_execution_context(manager, cache, cache_ttl);
+
+    let server = McpServer::new(specs, context)?;
+    // stdout carries the protocol, so progress is only logged
+    tracing::info!(
+        target: "aperture::mcp",
+        apis = %apis.join(", "),
+        tools = server.tool_names().len(),
+        "MCP server ready on stdio"
+    );
+
+    let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+    server.serve(stdin, tokio::io::stdout()).await
+}
+
+/// Builds the execution context long-running servers share across requests:
+/// the global config's retry defaults, the persistent `OAuth2` token store, and
+/// the response cache when `cache` or `cache_ttl` enables it.
+pub(crate) fn server_execution_context(
+    manager: &ConfigManager<OsFileSystem>,
+    cache: bool,
+    cache_ttl: Option<u64>,
+) -> ExecutionContext {
+    let global_config = manager.load_global_config().ok();
+    let retry_context = global_config
+        .as_ref()
+        .map(|config| &config.retry_defaults)
+        .filter(|defaults| defaults.max_attempts > 0)
+        .map(|defaults| RetryContext {
+            max_attempts: defaults.max_attempts,
+            initial_delay_ms: defaults.initial_delay_ms,
+            max_delay_ms: defaults.max_delay_ms,
+            force_retry: false,
+            method: None, // Determined by executor at execution time
+            has_idempotency_key: false,
+        });
+    ExecutionContext {
+        cache_config: Some(CacheConfig {
+            cache_dir: manager
+                .config_dir()
+                .join(constants::DIR_CACHE)
+                .join(constants::DIR_RESPONSES),
+            default_ttl: Duration::from_secs(cache_ttl.unwrap_or(300)),
+            max_entries: 1000,
+            enabled: cache || cache_ttl.is_some(),
+            allow_authenticated: false,
+        }),
+        retry_context,
+        global_config,
+        token_store: Some(TokenStore::new(
+            manager.config_dir().join(constants::DIR_TOKENS),
+        )),
+        ..ExecutionContext::default()
+    }
+}
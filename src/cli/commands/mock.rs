@@ -0,0 +1,27 @@
+//! Handler for `aperture mock`.
+
+use crate::cli::commands::api::load_api_command_context;
+use crate::cli::commands::config::validate_api_name;
+use crate::error::Error;
+use crate::mock::MockServer;
+use crate::output::Output;
+
+/// Serves the operations of `api` until the process is interrupted.
+pub async fn execute_mock_command(api: &str, port: u16, output: &Output) -> Result<(), Error> {
+    let api = validate_api_name(api)?;
+    let context = load_api_command_context(api.as_str())?;
+    let operations = context.spec.commands.len();
+
+    let server = MockServer::bind(context.spec, port).await?;
+    let address = server.local_addr()?;
+
+    output.success(format!(
+        "Mock server for '{api}' listening on http://{address} ({operations} operations)"
+    ));
+    output.tip(format!(
+        "Send requests to it with APERTURE_BASE_URL=http://{address}, or with 'aperture config url set {api} http://{address}' if the API has a configured URL"
+    ));
+    output.tip("Press Ctrl+C to stop. Run with -v to log each request.");
+
+    server.run().await
+}
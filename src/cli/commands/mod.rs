@@ -11,6 +11,8 @@
 )]
 pub mod api;
 #[allow(clippy::missing_errors_doc)]
+pub mod auth;
+#[allow(clippy::missing_errors_doc)]
 pub mod completion;
 #[allow(
     clippy::missing_errors_doc,
@@ -18,7 +20,15 @@ pub mod completion;
     clippy::implicit_hasher
 )]
 pub mod config;
+#[allow(clippy::missing_errors_doc)]
+pub mod daemon;
 #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
 pub mod docs;
 #[allow(clippy::missing_errors_doc)]
+pub mod mcp;
+#[allow(clippy::missing_errors_doc)]
+pub mod mock;
+#[allow(clippy::missing_errors_doc)]
 pub mod search;
+#[allow(clippy::missing_errors_doc)]
+pub mod serve;
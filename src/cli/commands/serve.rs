@@ -0,0 +1,47 @@
+//! Handler for `aperture serve`.
+
+use crate::cli::commands::config::validate_api_name;
+use crate::cli::commands::mcp::server_execution_context;
+use crate::config::manager::ConfigManager;
+use crate::constants;
+use crate::engine::executor::SharedHttpClient;
+use crate::engine::loader;
+use crate::error::Error;
+use crate::fs::OsFileSystem;
+use crate::gateway::Gateway;
+use crate::output::Output;
+
+/// Forwards requests to `api` through a local gateway until the process is
+/// interrupted.
+pub async fn execute_serve_command(
+    manager: &ConfigManager<OsFileSystem>,
+    api: &str,
+    port: u16,
+    cache: bool,
+    cache_ttl: Option<u64>,
+    output: &Output,
+) -> Result<(), Error> {
+    let api = validate_api_name(api)?;
+    let cache_dir = manager.config_dir().join(constants::DIR_CACHE);
+    let spec = loader::load_cached_spec(&cache_dir, api.as_str()).map_err(|e| match e {
+        Error::Io(_) => Error::spec_not_found(api.as_str()),
+        _ => e,
+    })?;
+    let operations = spec.commands.len();
+
+    let mut context = server_execution_context(manager, cache, cache_ttl);
+    context.http_client = Some(SharedHttpClient::new(&context)?);
+
+    let gateway = Gateway::bind(spec, context, port).await?;
+    let address = gateway.local_addr()?;
+
+    output.success(format!(
+        "Gateway for '{api}' listening on http://{address} ({operations} operations)"
+    ));
+    output.tip(format!(
+        "Point clients at http://{address} instead of the API's base URL; requests are sent with the credentials configured for '{api}'"
+    ));
+    output.tip("Press Ctrl+C to stop. Run with -v to log each request.");
+
+    gateway.run().await
+}
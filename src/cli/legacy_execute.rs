@@ -46,6 +46,7 @@ pub async fn execute_request(
     // Build ExecutionContext from the individual parameters
     let ctx = ExecutionContext {
         dry_run,
+        secret_references: false,
         idempotency_key: idempotency_key.map(String::from),
         cache_config: cache_config.cloned(),
         retry_context: retry_context.cloned(),
@@ -54,6 +55,12 @@ pub async fn execute_request(
         global_config: global_config.cloned(),
         server_var_args: translate::extract_server_var_args(matches),
         auto_paginate: false,
+        token_store: None,
+        auth_scheme: None,
+        skip_validation: false,
+        response_validation: None,
+        cassette: None,
+        http_client: None,
     };
 
     // Execute using the new domain-type API
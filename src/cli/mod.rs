@@ -5,7 +5,9 @@ pub mod render;
 pub mod tracing_init;
 pub mod translate;
 
+use crate::config::models::{ResponseValidation, SecretSource};
 use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(ValueEnum, Clone, Debug)]
 pub enum OutputFormat {
@@ -25,6 +27,30 @@ pub enum DiscoveryFormat {
     Json,
 }
 
+#[derive(ValueEnum, Clone, Debug)]
+pub enum ToolFormat {
+    /// `OpenAI` function tools, with strict schemas where possible
+    OpenaiTools,
+    /// Anthropic tool definitions
+    AnthropicTools,
+    /// One standalone JSON Schema document per operation
+    JsonSchema,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum EmitFormat {
+    /// A curl command
+    Curl,
+    /// An `HTTPie` command
+    Httpie,
+    /// A Python script using `requests`
+    PythonRequests,
+    /// JavaScript using `fetch`
+    JsFetch,
+    /// Rust using `reqwest`
+    RustReqwest,
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum CompletionShell {
     /// GNU Bash
@@ -51,14 +77,69 @@ pub struct ExecutionFlags {
     )]
     pub describe_json: bool,
 
+    /// Describe operations as LLM tool definitions instead of a manifest
+    #[arg(
+        long = "as",
+        value_enum,
+        value_name = "FORMAT",
+        requires = "describe_json",
+        help = "With --describe-json, output one tool definition per operation in FORMAT"
+    )]
+    pub describe_as: Option<ToolFormat>,
+
+    /// Token budget for each tool and property description
+    #[arg(
+        long,
+        value_name = "TOKENS",
+        requires = "describe_as",
+        help = "Trim tool descriptions to about TOKENS tokens (default: 120, 0 for no limit)"
+    )]
+    pub max_description_tokens: Option<usize>,
+
     /// Show the HTTP request that would be made without executing it
     #[arg(long, help = "Show request details without executing")]
     pub dry_run: bool,
 
+    /// Print a runnable equivalent of the request instead of sending it
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        conflicts_with_all = ["describe_json", "auto_paginate", "batch_file", "output_file"],
+        help = "Print the request as a curl/HTTPie command or code snippet instead of sending it; secrets appear as environment variables"
+    )]
+    pub emit: Option<EmitFormat>,
+
     /// Set the Idempotency-Key header for safe retries
     #[arg(long, value_name = "KEY", help = "Set idempotency key header")]
     pub idempotency_key: Option<String>,
 
+    /// Authenticate with a specific security scheme
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Use the named security scheme instead of the first satisfiable one"
+    )]
+    pub auth_scheme: Option<String>,
+
+    /// Skip client-side validation of parameters and request body
+    #[arg(
+        long,
+        help = "Send the request without validating it against the OpenAPI schema"
+    )]
+    pub no_validate: bool,
+
+    /// Check the response against the spec's documented responses
+    #[arg(
+        long,
+        value_name = "MODE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "error",
+        help = "Check the status and body against the spec: error (default) or --validate-response=warn/off"
+    )]
+    pub validate_response: Option<ResponseValidation>,
+
     /// Use a specific HTTP/HTTPS proxy for this invocation
     #[arg(
         long,
@@ -72,6 +153,23 @@ pub struct ExecutionFlags {
     #[arg(long, help = "Bypass all proxy configuration for this request")]
     pub no_proxy: bool,
 
+    /// Save each request and response to a cassette directory
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with = "replay",
+        help = "Record each request and its response to DIR, with secrets redacted"
+    )]
+    pub record: Option<PathBuf>,
+
+    /// Answer requests from a cassette directory instead of the network
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Replay responses recorded with --record DIR; unmatched requests fail"
+    )]
+    pub replay: Option<PathBuf>,
+
     /// Output format for response data
     #[arg(
         long,
@@ -89,6 +187,14 @@ pub struct ExecutionFlags {
     )]
     pub jq: Option<String>,
 
+    /// Write the response body to a file instead of printing it
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write the response body to a file and print a summary (e.g., for images or PDFs)"
+    )]
+    pub output_file: Option<String>,
+
     /// Execute operations from a batch file
     #[arg(
         long,
@@ -276,6 +382,20 @@ pub enum Commands {
         #[command(subcommand)]
         command: ConfigCommands,
     },
+    /// Authorize Aperture against `OAuth2` providers
+    #[command(long_about = "Run interactive OAuth2 authorization for an API.\n\n\
+                      `auth login` performs the authorization-code flow with PKCE:\n\
+                      it prints the provider's authorization URL, waits for the\n\
+                      browser to redirect to a loopback address, and stores the\n\
+                      issued tokens. Later `aperture api` calls refresh the access\n\
+                      token automatically using the stored refresh token.\n\n\
+                      Examples:\n  \
+                      aperture auth login myapi\n  \
+                      aperture auth login myapi --scheme userAuth --port 8765")]
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommands,
+    },
     /// List available commands for an API specification
     #[command(
         name = "commands",
@@ -425,6 +545,106 @@ pub enum Commands {
         )]
         format: DiscoveryFormat,
     },
+    /// Serve an API's operations from a local mock server
+    #[command(
+        long_about = "Serve every operation of a registered API on 127.0.0.1.\n\n\
+                      Requests are matched by method and path template and checked\n\
+                      against the operation's parameters and request body; mismatches\n\
+                      get a 400 listing the violations. Matching requests receive the\n\
+                      documented success status with the response example, or a body\n\
+                      synthesized from the response schema.\n\n\
+                      Examples:\n  \
+                      aperture mock myapi\n  \
+                      aperture mock myapi --port 8080"
+    )]
+    Mock {
+        /// Name of the API specification.
+        /// Must start with a letter or digit; may contain letters, digits, dots, hyphens, or underscores (max 64 chars).
+        api: String,
+        /// Port to listen on (0 picks a free port)
+        #[arg(
+            long,
+            value_name = "PORT",
+            default_value = "4010",
+            help = "Local port for the mock server (0 = any free port)"
+        )]
+        port: u16,
+    },
+    /// Forward requests to an API through a local authenticated gateway
+    #[command(
+        long_about = "Expose a registered API on 127.0.0.1 as a reverse proxy.\n\n\
+                      Requests are matched by method and path template against the\n\
+                      spec's operations; anything else gets a 404, and requests that do\n\
+                      not match the operation's parameters or body a 400. Matching\n\
+                      requests are sent like `aperture api`: the configured secrets are\n\
+                      injected, the base URL is resolved, and retry defaults apply.\n\
+                      Only declared parameters are forwarded, so clients never need or\n\
+                      see credentials.\n\n\
+                      Examples:\n  \
+                      aperture serve myapi\n  \
+                      aperture serve myapi --port 9000 --cache"
+    )]
+    Serve {
+        /// Name of the API specification.
+        /// Must start with a letter or digit; may contain letters, digits, dots, hyphens, or underscores (max 64 chars).
+        api: String,
+        /// Port to listen on (0 picks a free port)
+        #[arg(
+            long,
+            value_name = "PORT",
+            default_value = "8080",
+            help = "Local port for the gateway (0 = any free port)"
+        )]
+        port: u16,
+        /// Enable response caching for forwarded requests
+        #[arg(long, help = "Cache responses of forwarded requests")]
+        cache: bool,
+        /// Cache TTL in seconds
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            help = "Cache TTL in seconds (implies --cache; default 300)"
+        )]
+        cache_ttl: Option<u64>,
+    },
+    /// Expose API operations to agents over the Model Context Protocol
+    #[command(
+        long_about = "Run a Model Context Protocol (MCP) server for registered APIs.\n\n\
+                      Each visible operation becomes a tool named <api>__<operationId>,\n\
+                      whose input schema has one property per parameter plus `body`.\n\
+                      Tool calls are executed like `aperture api`, with the configured\n\
+                      secrets and retry defaults.\n\n\
+                      Examples:\n  \
+                      aperture mcp serve\n  \
+                      aperture mcp serve --api github --api stripe --cache"
+    )]
+    Mcp {
+        #[command(subcommand)]
+        command: McpCommands,
+    },
+    /// Answer JSON-RPC requests on a Unix socket with specs kept in memory
+    #[command(
+        long_about = "Run a long-lived server that answers newline-delimited JSON-RPC 2.0\n\
+                      requests on a Unix socket, keeping specs, command trees and HTTP\n\
+                      connections warm between calls.\n\n\
+                      Methods:\n  \
+                      execute   {\"args\": [\"myapi\", \"users\", \"get-user\", \"--id\", \"42\"]}\n  \
+                      describe  {\"api\": \"myapi\"} or {\"api\": \"myapi\", \"as\": \"openai-tools\"}\n  \
+                      search    {\"query\": \"list users\", \"api\": \"myapi\"}\n\n\
+                      Specs are reloaded when their files change.\n\n\
+                      Examples:\n  \
+                      aperture daemon\n  \
+                      aperture daemon --socket /tmp/aperture.sock"
+    )]
+    Daemon {
+        /// Path of the Unix socket to listen on
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Socket path (default: daemon.sock in the config directory)"
+        )]
+        socket: Option<PathBuf>,
+    },
     /// Show API overview with statistics and quick start guide
     #[command(
         long_about = "Orient yourself to an API before diving into details.\n\n\
@@ -456,6 +676,55 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum AuthCommands {
+    /// Authorize an `OAuth2` scheme using the authorization-code flow
+    Login {
+        /// Name of the API specification.
+        /// Must start with a letter or digit; may contain letters, digits, dots, hyphens, or underscores (max 64 chars).
+        api: String,
+        /// Security scheme to authorize (required when the spec has several)
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "OAuth2 security scheme to authorize"
+        )]
+        scheme: Option<String>,
+        /// Loopback port for the redirect (0 picks a free port)
+        #[arg(
+            long,
+            value_name = "PORT",
+            default_value = "0",
+            help = "Local port for the OAuth2 redirect (0 = any free port)"
+        )]
+        port: u16,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum McpCommands {
+    /// Serve tools over stdio until stdin closes
+    Serve {
+        /// APIs to expose (default: every registered API)
+        #[arg(
+            long = "api",
+            value_name = "NAME",
+            help = "Expose only this API (repeatable; default: all registered APIs)"
+        )]
+        apis: Vec<String>,
+        /// Enable response caching for tool calls
+        #[arg(long, help = "Cache responses of tool calls")]
+        cache: bool,
+        /// Cache TTL in seconds
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            help = "Cache TTL in seconds (implies --cache; default 300)"
+        )]
+        cache_ttl: Option<u64>,
+    },
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum ConfigApiCommands {
     /// Add a new API specification from a file or URL
@@ -471,7 +740,7 @@ pub enum ConfigApiCommands {
         /// Reject specs with unsupported features instead of skipping endpoints
         #[arg(
             long,
-            help = "Reject entire spec if any endpoints have unsupported content types (e.g., multipart/form-data, XML). Default behavior skips unsupported endpoints with warnings."
+            help = "Reject entire spec if any endpoints have unsupported content types (e.g., XML, CSV). Default behavior skips unsupported endpoints with warnings."
         )]
         strict: bool,
     },
@@ -505,6 +774,14 @@ pub enum ConfigApiCommands {
         #[arg(long, conflicts_with = "context", help = "Reinitialize all specs")]
         all: bool,
     },
+    /// Set how responses are checked against the spec by default
+    ResponseValidation {
+        /// Name of the API specification.
+        /// Must start with a letter or digit; may contain letters, digits, dots, hyphens, or underscores (max 64 chars).
+        name: String,
+        /// off, warn (log violations) or error (fail the command)
+        mode: ResponseValidation,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -542,8 +819,25 @@ pub enum ConfigSecretCommands {
         /// Environment variable name containing the secret
         #[arg(long, value_name = "VAR", help = "Environment variable name")]
         env: Option<String>,
+        /// Secret source: env, file, command, dotenv, or vault (use with --name)
+        #[arg(
+            long,
+            value_name = "SOURCE",
+            conflicts_with = "env",
+            requires = "name",
+            help = "Secret source: env, file, command, dotenv, or vault"
+        )]
+        source: Option<SecretSource>,
+        /// Source-specific reference: variable name, file path, shell command, dotenv key, or vault entry
+        #[arg(
+            long,
+            value_name = "REF",
+            requires = "source",
+            help = "Variable name, file path, command, dotenv key, or vault entry for --source"
+        )]
+        name: Option<String>,
         /// Interactive mode to configure all undefined secrets
-        #[arg(long, conflicts_with_all = ["scheme_name", "env"], help = "Configure secrets interactively")]
+        #[arg(long, conflicts_with_all = ["scheme_name", "env", "source"], help = "Configure secrets interactively")]
         interactive: bool,
     },
     /// List configured secrets for an API specification
@@ -569,6 +863,47 @@ pub enum ConfigSecretCommands {
         #[arg(long, help = "Skip confirmation prompt")]
         force: bool,
     },
+    /// Manage the encrypted secret vault
+    #[command(long_about = "Manage the passphrase-encrypted secret vault.\n\n\
+                      Vault entries can be referenced from secret mappings with\n\
+                      '--source vault' or 'source: vault' in x-aperture-secret.\n\
+                      The passphrase is read from APERTURE_VAULT_PASSPHRASE or\n\
+                      prompted for when a terminal is attached.\n\n\
+                      Examples:\n  \
+                      aperture config secret vault init\n  \
+                      aperture config secret vault put github-token\n  \
+                      aperture config secret set myapi bearerAuth --source vault --name github-token")]
+    Vault {
+        #[command(subcommand)]
+        command: ConfigVaultCommands,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigVaultCommands {
+    /// Create an empty vault protected by a new passphrase
+    Init {
+        /// Replace an existing vault
+        #[arg(long, help = "Replace an existing vault, discarding its entries")]
+        force: bool,
+    },
+    /// Store a secret in the vault (the value is prompted for or read from stdin)
+    Put {
+        /// Name of the vault entry
+        name: String,
+    },
+    /// Print a secret stored in the vault
+    Get {
+        /// Name of the vault entry
+        name: String,
+    },
+    /// Remove a secret from the vault
+    Rm {
+        /// Name of the vault entry
+        name: String,
+    },
+    /// List the names of the secrets stored in the vault
+    List,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -722,7 +1057,7 @@ pub enum ConfigCommands {
         /// Reject specs with unsupported features instead of skipping endpoints
         #[arg(
             long,
-            help = "Reject entire spec if any endpoints have unsupported content types (e.g., multipart/form-data, XML). Default behavior skips unsupported endpoints with warnings."
+            help = "Reject entire spec if any endpoints have unsupported content types (e.g., XML, CSV). Default behavior skips unsupported endpoints with warnings."
         )]
         strict: bool,
     },
@@ -824,6 +1159,8 @@ pub enum ConfigCommands {
                       Examples:\n  \
                       aperture config set-secret myapi bearerAuth --env API_TOKEN\n  \
                       aperture config set-secret myapi apiKey --env API_KEY\n  \
+                      aperture config set-secret myapi apiKey --source file --name ~/.tokens/myapi\n  \
+                      aperture config set-secret myapi apiKey --source command --name 'pass show myapi'\n  \
                       aperture config set-secret myapi --interactive"
     )]
     SetSecret {
@@ -835,8 +1172,25 @@ pub enum ConfigCommands {
         /// Environment variable name containing the secret
         #[arg(long, value_name = "VAR", help = "Environment variable name")]
         env: Option<String>,
+        /// Secret source: env, file, command, dotenv, or vault (use with --name)
+        #[arg(
+            long,
+            value_name = "SOURCE",
+            conflicts_with = "env",
+            requires = "name",
+            help = "Secret source: env, file, command, dotenv, or vault"
+        )]
+        source: Option<SecretSource>,
+        /// Source-specific reference: variable name, file path, shell command, dotenv key, or vault entry
+        #[arg(
+            long,
+            value_name = "REF",
+            requires = "source",
+            help = "Variable name, file path, command, dotenv key, or vault entry for --source"
+        )]
+        name: Option<String>,
         /// Interactive mode to configure all undefined secrets
-        #[arg(long, conflicts_with_all = ["scheme_name", "env"], help = "Configure secrets interactively")]
+        #[arg(long, conflicts_with_all = ["scheme_name", "env", "source"], help = "Configure secrets interactively")]
         interactive: bool,
     },
     #[command(hide = true)]
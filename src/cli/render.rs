@@ -10,12 +10,14 @@ use crate::constants;
 use crate::engine::executor::apply_jq_filter;
 use crate::error::Error;
 use crate::invocation::ExecutionResult;
-use crate::output::write_stdout_line;
+use crate::output::{write_stdout_bytes, write_stdout_line};
 use crate::utils::to_kebab_case;
 use serde_json::Value;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::fmt::Write;
+use std::io::IsTerminal;
+use std::path::Path;
 use tabled::{Table, Tabled};
 
 /// Maximum number of rows to display in table format to prevent memory exhaustion.
@@ -58,9 +60,12 @@ impl Tabled for KeyValue {
 
 /// Renders an [`ExecutionResult`] to stdout in the given format.
 ///
+/// Binary bodies are written as-is, but only when stdout is not a terminal.
+///
 /// # Errors
 ///
-/// Returns an error if JQ filtering or serialization fails.
+/// Returns an error if JQ filtering or serialization fails, or if a binary
+/// body would be printed to a terminal.
 pub fn render_result(
     result: &ExecutionResult,
     format: &OutputFormat,
@@ -73,6 +78,18 @@ pub fn render_result(
             }
             format_and_print(body, format, jq_filter, false)?;
         }
+        ExecutionResult::Binary {
+            body, content_type, ..
+        } => {
+            if std::io::stdout().is_terminal() {
+                return Err(Error::validation_error(format!(
+                    "Not printing a binary response ({}) to the terminal. \
+                     Use --output-file PATH to save it, or redirect stdout",
+                    binary_description(content_type.as_deref(), body.len())
+                )));
+            }
+            write_stdout_bytes(body)?;
+        }
         ExecutionResult::DryRun { request_info } => {
             let output = serde_json::to_string_pretty(request_info).map_err(|e| {
                 Error::serialization_error(format!("Failed to serialize dry run info: {e}"))
@@ -84,9 +101,74 @@ pub fn render_result(
     Ok(())
 }
 
+/// Writes the response body of an [`ExecutionResult`] to `path` and prints a
+/// summary of what was saved in the given format.
+///
+/// Dry runs are rendered as usual and empty responses write nothing.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or the summary cannot be
+/// rendered.
+pub fn render_result_to_file(
+    result: &ExecutionResult,
+    path: &Path,
+    format: &OutputFormat,
+    jq_filter: Option<&str>,
+) -> Result<(), Error> {
+    let (body, content_type, status) = match result {
+        ExecutionResult::Success {
+            body,
+            status,
+            headers,
+        } => (
+            body.as_bytes(),
+            headers
+                .get(constants::HEADER_CONTENT_TYPE_LC)
+                .map(String::as_str),
+            Some(*status),
+        ),
+        ExecutionResult::Cached { body } => (body.as_bytes(), None, None),
+        ExecutionResult::Binary {
+            body,
+            content_type,
+            status,
+            ..
+        } => (body.as_slice(), content_type.as_deref(), Some(*status)),
+        ExecutionResult::DryRun { .. } | ExecutionResult::Empty => {
+            return render_result(result, format, jq_filter);
+        }
+    };
+
+    std::fs::write(path, body).map_err(|e| {
+        Error::io_error(format!(
+            "Failed to write response to '{}': {e}",
+            path.display()
+        ))
+    })?;
+
+    let summary = serde_json::json!({
+        "output_file": path.display().to_string(),
+        "content_type": content_type,
+        "size": body.len(),
+        "status": status,
+    });
+    format_and_print(&summary.to_string(), format, jq_filter, false)?;
+    Ok(())
+}
+
+/// Short description of a binary body, e.g. `image/png, 2048 bytes`
+fn binary_description(content_type: Option<&str>, size: usize) -> String {
+    format!(
+        "{}, {size} bytes",
+        content_type.unwrap_or(constants::CONTENT_TYPE_OCTET_STREAM)
+    )
+}
+
 /// Renders an [`ExecutionResult`] to a `String` instead of stdout.
 ///
-/// Used by the batch processor when capturing output.
+/// Used by the batch processor when capturing output. Binary bodies are
+/// replaced by a summary of their content type and size.
 ///
 /// # Errors
 ///
@@ -103,6 +185,15 @@ pub fn render_result_to_string(
             }
             format_and_print(body, format, jq_filter, true)
         }
+        ExecutionResult::Binary {
+            body, content_type, ..
+        } => {
+            let summary = serde_json::json!({
+                "content_type": content_type,
+                "size": body.len(),
+            });
+            format_and_print(&summary.to_string(), format, jq_filter, true)
+        }
         ExecutionResult::DryRun { request_info } => {
             let output = serde_json::to_string_pretty(request_info).map_err(|e| {
                 Error::serialization_error(format!("Failed to serialize dry run info: {e}"))
@@ -174,11 +265,25 @@ pub fn render_examples(api_name: &str, operation: &CachedCommand) -> Result<(),
     }
     writeln!(&mut output).expect("writing to String cannot fail");
 
-    if operation.request_body.is_some() {
-        writeln!(&mut output, "Request Body:").expect("writing to String cannot fail");
-        writeln!(&mut output, "  --body JSON (required)").expect("writing to String cannot fail");
-        writeln!(&mut output, "      JSON data to send in the request body")
+    match &operation.request_body {
+        Some(body) if crate::spec::is_binary_content_type(&body.content_type) => {
+            writeln!(&mut output, "Request Body:").expect("writing to String cannot fail");
+            writeln!(&mut output, "  --body-file PATH").expect("writing to String cannot fail");
+            writeln!(
+                &mut output,
+                "      File sent as the raw {} body",
+                body.content_type
+            )
             .expect("writing to String cannot fail");
+        }
+        Some(_) => {
+            writeln!(&mut output, "Request Body:").expect("writing to String cannot fail");
+            writeln!(&mut output, "  --body JSON (required)")
+                .expect("writing to String cannot fail");
+            writeln!(&mut output, "      JSON data to send in the request body")
+                .expect("writing to String cannot fail");
+        }
+        None => {}
     }
 
     write_stdout_line(output.trim_end())
@@ -4,14 +4,19 @@
 //! CLI-agnostic [`OperationCall`] and [`ExecutionContext`] types used
 //! by the execution engine.
 
-use crate::cache::models::{CachedCommand, CachedParameter, CachedSpec};
+use crate::cache::models::{CachedCommand, CachedParameter, CachedRequestBody, CachedSpec};
+use crate::cassette::Cassette;
 use crate::cli::ExecutionFlags;
 use crate::config::models::GlobalConfig;
 use crate::constants;
 use crate::duration::parse_duration;
 use crate::engine::executor::RetryContext;
+use crate::engine::{body, params};
 use crate::error::Error;
-use crate::invocation::{ExecutionContext, OperationCall, ProxyOverride};
+use crate::invocation::{
+    ExecutionContext, FilePart, OperationCall, ParamValues, ProxyOverride, RawBody,
+};
+use crate::oauth::TokenStore;
 use crate::response_cache::CacheConfig;
 use crate::utils::to_kebab_case;
 use clap::ArgMatches;
@@ -24,8 +29,8 @@ use std::time::Duration;
 /// into a CLI-agnostic [`OperationCall`].
 ///
 /// Walks the subcommand hierarchy to identify the operation, then extracts
-/// path, query, and header parameters, the request body, and any custom
-/// headers.
+/// path, query, header, and cookie parameters, the request body, and any
+/// custom headers.
 ///
 /// # Errors
 ///
@@ -40,6 +45,8 @@ pub fn matches_to_operation_call(
     let mut path_params = HashMap::new();
     let mut query_params = HashMap::new();
     let mut header_params = HashMap::new();
+    let mut cookie_params = HashMap::new();
+    let mut multi_params = HashMap::new();
 
     for param in &operation.parameters {
         extract_param(
@@ -48,11 +55,33 @@ pub fn matches_to_operation_call(
             &mut path_params,
             &mut query_params,
             &mut header_params,
-        );
+            &mut cookie_params,
+            &mut multi_params,
+        )?;
     }
 
-    // Extract request body
-    let body = extract_body(operation.request_body.is_some(), current_matches)?;
+    // Extract request body; form bodies also collect form-field flags,
+    // multipart bodies collect files and binary bodies are read raw
+    let (body, files, raw_body) = match &operation.request_body {
+        Some(request_body) if request_body.content_type == constants::CONTENT_TYPE_MULTIPART => (
+            extract_form_fields(request_body, current_matches)?,
+            extract_files(current_matches)?,
+            None,
+        ),
+        Some(request_body) if request_body.content_type == constants::CONTENT_TYPE_FORM => (
+            extract_form_fields(request_body, current_matches)?,
+            Vec::new(),
+            None,
+        ),
+        Some(request_body) if crate::spec::is_binary_content_type(&request_body.content_type) => {
+            (None, Vec::new(), extract_raw_body(current_matches)?)
+        }
+        request_body => (
+            extract_body(request_body.is_some(), current_matches)?,
+            Vec::new(),
+            None,
+        ),
+    };
 
     // Extract custom headers from --header/-H flags
     let custom_headers = current_matches
@@ -67,7 +96,11 @@ pub fn matches_to_operation_call(
         path_params,
         query_params,
         header_params,
+        cookie_params,
+        multi_params,
         body,
+        files,
+        raw_body,
         custom_headers,
     })
 }
@@ -203,32 +236,76 @@ fn extract_param(
     path_params: &mut HashMap<String, String>,
     query_params: &mut HashMap<String, String>,
     header_params: &mut HashMap<String, String>,
-) {
+    cookie_params: &mut HashMap<String, String>,
+    multi_params: &mut HashMap<String, ParamValues>,
+) -> Result<(), Error> {
     let target = match param.location.as_str() {
         "path" => path_params,
         "query" => query_params,
         "header" => header_params,
-        _ => return,
+        "cookie" => cookie_params,
+        _ => return Ok(()),
     };
 
+    if params::is_multi_valued(param) {
+        if let Some(values) = extract_multi_values(param, matches)? {
+            multi_params.insert(param.name.clone(), values);
+        }
+        return Ok(());
+    }
+
     let is_boolean = param.schema_type.as_ref().is_some_and(|t| t == "boolean");
 
     if !is_boolean {
         // Non-boolean: extract string value (must be checked first to avoid
         // get_flag panic on non-boolean args)
         let Some(value) = matches.try_get_one::<String>(&param.name).ok().flatten() else {
-            return;
+            return Ok(());
         };
         target.insert(param.name.clone(), value.clone());
-        return;
+        return Ok(());
     }
 
     // Boolean parameters are flags (SetTrue action in clap)
-    // Path booleans always need a value (true/false); query/header only when true
+    // Path booleans always need a value (true/false); others only when true
     let flag_set = matches.get_flag(&param.name);
     if flag_set || param.location == "path" {
         target.insert(param.name.clone(), flag_set.to_string());
     }
+    Ok(())
+}
+
+/// Collects the values of an array or object parameter.
+///
+/// Array items come from repeated flags or comma lists; object members are
+/// given as `KEY=VALUE`.
+fn extract_multi_values(
+    param: &CachedParameter,
+    matches: &ArgMatches,
+) -> Result<Option<ParamValues>, Error> {
+    let Some(values) = matches.try_get_many::<String>(&param.name).ok().flatten() else {
+        return Ok(None);
+    };
+
+    if param.schema_type.as_deref() != Some(constants::SCHEMA_TYPE_OBJECT) {
+        return Ok(Some(ParamValues::Array(values.cloned().collect())));
+    }
+
+    let members = values
+        .map(|value| {
+            value
+                .split_once('=')
+                .filter(|(key, _)| !key.is_empty())
+                .map(|(key, member)| (key.to_string(), member.to_string()))
+                .ok_or_else(|| {
+                    Error::validation_error(format!(
+                        "Invalid --{} value '{value}'. Expected KEY=VALUE",
+                        to_kebab_case(&param.name)
+                    ))
+                })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Some(ParamValues::Object(members)))
 }
 
 /// Extracts the request body from matches.
@@ -251,6 +328,92 @@ fn extract_body(has_request_body: bool, matches: &ArgMatches) -> Result<Option<S
         .transpose()
 }
 
+/// Builds the text fields of a form body as a JSON object.
+///
+/// Fields given as flags are merged over the object from `--body` or
+/// `--body-file`. Returns `None` when no field was given at all.
+fn extract_form_fields(
+    request_body: &CachedRequestBody,
+    matches: &ArgMatches,
+) -> Result<Option<String>, Error> {
+    let base = extract_body(true, matches)?;
+    let flag_values: Vec<(String, String)> = body::form_fields(request_body)
+        .into_iter()
+        .filter(|field| !field.binary)
+        .filter_map(|field| {
+            let value = matches
+                .try_get_one::<String>(&field.arg_id())
+                .ok()
+                .flatten()?;
+            Some((field.name, value.clone()))
+        })
+        .collect();
+
+    if flag_values.is_empty() {
+        return Ok(base);
+    }
+
+    let mut fields = match base
+        .as_deref()
+        .map(serde_json::from_str::<serde_json::Value>)
+    {
+        None => serde_json::Map::new(),
+        Some(Ok(serde_json::Value::Object(fields))) => fields,
+        Some(_) => {
+            return Err(Error::validation_error(
+                "Form field flags can only be combined with a JSON object body",
+            ))
+        }
+    };
+    for (name, value) in flag_values {
+        fields.insert(name, serde_json::Value::String(value));
+    }
+    Ok(Some(serde_json::Value::Object(fields).to_string()))
+}
+
+/// Parses `--file FIELD=@PATH` values into file parts.
+fn extract_files(matches: &ArgMatches) -> Result<Vec<FilePart>, Error> {
+    let Some(values) = matches.try_get_many::<String>("file").ok().flatten() else {
+        return Ok(Vec::new());
+    };
+
+    values
+        .map(|value| {
+            let (field, path) = value
+                .split_once('=')
+                .filter(|(field, path)| !field.is_empty() && !path.is_empty())
+                .ok_or_else(|| {
+                    Error::validation_error(format!(
+                        "Invalid --file value '{value}'. Expected FIELD=@PATH"
+                    ))
+                })?;
+            let path = path.strip_prefix('@').unwrap_or(path);
+            Ok(FilePart {
+                field: field.to_string(),
+                path: PathBuf::from(shellexpand::tilde(path).as_ref()),
+            })
+        })
+        .collect()
+}
+
+/// Reads a binary body from `--body-file` without interpreting it.
+///
+/// Files are streamed when the request is sent; stdin (`-`) is read here.
+fn extract_raw_body(matches: &ArgMatches) -> Result<Option<RawBody>, Error> {
+    let Some(path) = matches.try_get_one::<String>("body-file").ok().flatten() else {
+        return Ok(None);
+    };
+    if path != "-" {
+        return Ok(Some(RawBody::File(PathBuf::from(path))));
+    }
+
+    let mut buf = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut buf)
+        .map_err(|e| Error::io_error(format!("Failed to read body from stdin: {e}")))?;
+    Ok(Some(RawBody::Bytes(buf)))
+}
+
 fn read_body_file(path: &str) -> Result<String, Error> {
     let raw = if path == "-" {
         let mut buf = String::new();
@@ -339,7 +502,8 @@ pub fn cli_to_execution_context(
     let proxy_override = proxy_override_from_execution_flags(execution);
 
     Ok(ExecutionContext {
-        dry_run: execution.dry_run,
+        dry_run: execution.dry_run || execution.emit.is_some(),
+        secret_references: execution.emit.is_some(),
         idempotency_key: execution.idempotency_key.clone(),
         cache_config,
         retry_context,
@@ -348,6 +512,18 @@ pub fn cli_to_execution_context(
         global_config,
         server_var_args: Vec::new(), // Populated from dynamic matches in the caller
         auto_paginate: execution.auto_paginate,
+        token_store: Some(TokenStore::new(
+            config_dir.join(crate::constants::DIR_TOKENS),
+        )),
+        auth_scheme: execution.auth_scheme.clone(),
+        skip_validation: execution.no_validate,
+        response_validation: execution.validate_response,
+        cassette: execution
+            .record
+            .clone()
+            .map(Cassette::record)
+            .or_else(|| execution.replay.clone().map(Cassette::replay)),
+        http_client: None,
     })
 }
 
@@ -2,14 +2,18 @@ use crate::cache::fingerprint::{compute_content_hash, get_file_mtime_secs};
 use crate::cache::metadata::CacheMetadataManager;
 use crate::cache::models::CachedSecurityScheme;
 use crate::config::context_name::ApiContextName;
-use crate::config::models::{ApertureSecret, ApiConfig, GlobalConfig, SecretSource};
+use crate::config::models::{
+    ApertureSecret, ApiConfig, GlobalConfig, ResponseValidation, SecretSource,
+};
 use crate::config::url_resolver::BaseUrlResolver;
 use crate::constants;
 use crate::engine::loader;
 use crate::error::Error;
 use crate::fs::{FileSystem, OsFileSystem};
 use crate::interactive::{confirm, prompt_for_input, select_from_options};
-use crate::spec::{SpecTransformer, SpecValidator};
+use crate::oauth::discovery::{self, OpenIdConnectDiscovery};
+use crate::spec::transformer::TransformOptions;
+use crate::spec::{SpecDocument, SpecLocation, SpecTransformer, SpecValidator};
 use openapiv3::{OpenAPI, ReferenceOr};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -20,6 +24,7 @@ struct CategorizedWarnings<'a> {
     content_type: Vec<&'a crate::spec::validator::ValidationWarning>,
     auth: Vec<&'a crate::spec::validator::ValidationWarning>,
     mixed_content: Vec<&'a crate::spec::validator::ValidationWarning>,
+    conversion: Vec<&'a crate::spec::validator::ValidationWarning>,
 }
 
 pub struct ConfigManager<F: FileSystem> {
@@ -82,6 +87,7 @@ impl<F: FileSystem> ConfigManager<F> {
                 strict_mode: false,
                 secrets: HashMap::new(),
                 command_mapping: None,
+                response_validation: None,
             });
         api_config.strict_mode = strict;
         self.save_global_config(&config)?;
@@ -168,6 +174,13 @@ impl<F: FileSystem> ConfigManager<F> {
                 !categorized_warnings.content_type.is_empty()
                     || !categorized_warnings.auth.is_empty(),
             );
+            let add_blank_line = !lines.is_empty();
+            Self::format_conversion_warnings(
+                &mut lines,
+                &categorized_warnings.conversion,
+                indent,
+                add_blank_line,
+            );
         }
 
         lines
@@ -187,7 +200,10 @@ impl<F: FileSystem> ConfigManager<F> {
                         // ast-grep-ignore: no-println
                         eprintln!();
                     }
-                    s if s.starts_with("Skipping") || s.starts_with("Endpoints") => {
+                    s if s.starts_with("Skipping")
+                        || s.starts_with("Endpoints")
+                        || s.starts_with("Swagger") =>
+                    {
                         // ast-grep-ignore: no-println
                         eprintln!("{} {line}", crate::constants::MSG_WARNING_PREFIX);
                     }
@@ -225,11 +241,21 @@ impl<F: FileSystem> ConfigManager<F> {
         self.check_spec_exists(name.as_str(), force)?;
 
         let content = self.fs.read_to_string(file_path)?;
-        let openapi_spec = crate::spec::parse_openapi(&content)?;
+        let content = self.bundle_spec_content(
+            content,
+            &SpecLocation::File(file_path.to_path_buf()),
+            spec_fetch_timeout(),
+        )?;
+        let (content, conversion_warnings) = crate::spec::convert_swagger2(content)?;
+        // Inline component references so cached schemas are self-contained
+        let document = SpecDocument::parse(&content)?;
+        let openapi_spec = document.openapi();
+        let openid_connect = discovery::discover_blocking(openapi_spec, discovery_timeout());
 
         // Validate against Aperture's supported feature set using SpecValidator
         let validator = SpecValidator::new();
-        let validation_result = validator.validate_with_mode(&openapi_spec, strict);
+        let validation_result =
+            validator.validate_with_discovery(openapi_spec, strict, &openid_connect);
 
         // Check for errors first
         if !validation_result.is_valid() {
@@ -237,16 +263,18 @@ impl<F: FileSystem> ConfigManager<F> {
         }
 
         // Count total operations for better UX
-        let total_operations = Self::count_total_operations(&openapi_spec);
+        let total_operations = Self::count_total_operations(openapi_spec);
 
-        // Display warnings if any
-        Self::display_validation_warnings(&validation_result.warnings, Some(total_operations));
+        // Display warnings if any, starting with notes from a Swagger 2.0 conversion
+        let warnings = [conversion_warnings, validation_result.warnings.clone()].concat();
+        Self::display_validation_warnings(&warnings, Some(total_operations));
 
         self.add_spec_from_validated_openapi(
             name.as_str(),
-            &openapi_spec,
+            &document,
             &content,
             &validation_result,
+            openid_connect,
             strict,
         )
     }
@@ -278,11 +306,18 @@ impl<F: FileSystem> ConfigManager<F> {
 
         // Fetch content from URL
         let content = fetch_spec_from_url(url).await?;
-        let openapi_spec = crate::spec::parse_openapi(&content)?;
+        let content =
+            self.bundle_spec_content(content, &SpecLocation::parse(url)?, spec_fetch_timeout())?;
+        let (content, conversion_warnings) = crate::spec::convert_swagger2(content)?;
+        // Inline component references so cached schemas are self-contained
+        let document = SpecDocument::parse(&content)?;
+        let openapi_spec = document.openapi();
+        let openid_connect = discovery::discover(openapi_spec, discovery_timeout()).await;
 
         // Validate against Aperture's supported feature set using SpecValidator
         let validator = SpecValidator::new();
-        let validation_result = validator.validate_with_mode(&openapi_spec, strict);
+        let validation_result =
+            validator.validate_with_discovery(openapi_spec, strict, &openid_connect);
 
         // Check for errors first
         if !validation_result.is_valid() {
@@ -290,16 +325,18 @@ impl<F: FileSystem> ConfigManager<F> {
         }
 
         // Count total operations for better UX
-        let total_operations = Self::count_total_operations(&openapi_spec);
+        let total_operations = Self::count_total_operations(openapi_spec);
 
-        // Display warnings if any
-        Self::display_validation_warnings(&validation_result.warnings, Some(total_operations));
+        // Display warnings if any, starting with notes from a Swagger 2.0 conversion
+        let warnings = [conversion_warnings, validation_result.warnings.clone()].concat();
+        Self::display_validation_warnings(&warnings, Some(total_operations));
 
         self.add_spec_from_validated_openapi(
             name.as_str(),
-            &openapi_spec,
+            &document,
             &content,
             &validation_result,
+            openid_connect,
             strict,
         )
     }
@@ -728,6 +765,7 @@ impl<F: FileSystem> ConfigManager<F> {
                 strict_mode: false,
                 secrets: HashMap::new(),
                 command_mapping: None,
+                response_validation: None,
             });
 
         // Set the URL
@@ -744,6 +782,45 @@ impl<F: FileSystem> ConfigManager<F> {
         Ok(())
     }
 
+    /// Sets how responses from an API are checked against its spec.
+    ///
+    /// `off` removes the setting, so responses are not checked unless
+    /// `--validate-response` is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spec doesn't exist or config cannot be saved.
+    pub fn set_response_validation(
+        &self,
+        api_name: &ApiContextName,
+        mode: ResponseValidation,
+    ) -> Result<(), Error> {
+        let api_name = api_name.as_str();
+        let spec_path = self
+            .config_dir
+            .join(crate::constants::DIR_SPECS)
+            .join(format!("{api_name}{}", crate::constants::FILE_EXT_YAML));
+        if !self.fs.exists(&spec_path) {
+            return Err(Error::spec_not_found(api_name));
+        }
+
+        let mut config = self.load_global_config()?;
+        let api_config = config
+            .api_configs
+            .entry(api_name.to_string())
+            .or_insert_with(|| ApiConfig {
+                base_url_override: None,
+                environment_urls: HashMap::new(),
+                strict_mode: false,
+                secrets: HashMap::new(),
+                command_mapping: None,
+                response_validation: None,
+            });
+        api_config.response_validation = (mode != ResponseValidation::Off).then_some(mode);
+
+        self.save_global_config(&config)
+    }
+
     /// Gets the base URL configuration for an API specification.
     ///
     /// # Arguments
@@ -857,11 +934,17 @@ impl<F: FileSystem> ConfigManager<F> {
 
         // Fetch content from URL with custom timeout
         let content = fetch_spec_from_url_with_timeout(url, timeout).await?;
-        let openapi_spec = crate::spec::parse_openapi(&content)?;
+        let content = self.bundle_spec_content(content, &SpecLocation::parse(url)?, timeout)?;
+        let (content, _conversion_warnings) = crate::spec::convert_swagger2(content)?;
+        // Inline component references so cached schemas are self-contained
+        let document = SpecDocument::parse(&content)?;
+        let openapi_spec = document.openapi();
+        let openid_connect = discovery::discover(openapi_spec, timeout).await;
 
         // Validate against Aperture's supported feature set using SpecValidator
         let validator = SpecValidator::new();
-        let validation_result = validator.validate_with_mode(&openapi_spec, strict);
+        let validation_result =
+            validator.validate_with_discovery(openapi_spec, strict, &openid_connect);
 
         // Check for errors first
         if !validation_result.is_valid() {
@@ -872,9 +955,10 @@ impl<F: FileSystem> ConfigManager<F> {
 
         self.add_spec_from_validated_openapi(
             name,
-            &openapi_spec,
+            &document,
             &content,
             &validation_result,
+            openid_connect,
             strict,
         )
     }
@@ -895,6 +979,33 @@ impl<F: FileSystem> ConfigManager<F> {
         scheme_name: &str,
         env_var_name: &str,
     ) -> Result<(), Error> {
+        self.set_secret_from_source(api_name, scheme_name, SecretSource::Env, env_var_name)
+    }
+
+    /// Sets a secret configuration that reads from any [`SecretSource`]
+    ///
+    /// # Arguments
+    /// * `api_name` - The name of the API specification
+    /// * `scheme_name` - The name of the security scheme
+    /// * `source` - Where the secret is read from
+    /// * `name` - Source-specific reference (variable, path, command, dotenv key, or vault entry)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spec doesn't exist, the reference is empty, or
+    /// config cannot be saved.
+    pub fn set_secret_from_source(
+        &self,
+        api_name: &ApiContextName,
+        scheme_name: &str,
+        source: SecretSource,
+        name: &str,
+    ) -> Result<(), Error> {
+        if name.trim().is_empty() {
+            return Err(Error::invalid_config(format!(
+                "The {source} secret reference for scheme '{scheme_name}' must not be empty"
+            )));
+        }
         let api_name = api_name.as_str();
         // Verify the spec exists
         let spec_path = self
@@ -918,14 +1029,15 @@ impl<F: FileSystem> ConfigManager<F> {
                 strict_mode: false,
                 secrets: HashMap::new(),
                 command_mapping: None,
+                response_validation: None,
             });
 
         // Set the secret
         api_config.secrets.insert(
             scheme_name.to_string(),
             ApertureSecret {
-                source: SecretSource::Env,
-                name: env_var_name.to_string(),
+                source,
+                name: name.to_string(),
             },
         );
 
@@ -1129,6 +1241,7 @@ impl<F: FileSystem> ConfigManager<F> {
                 strict_mode: false,
                 secrets: HashMap::new(),
                 command_mapping: None,
+                response_validation: None,
             });
         if api_config.command_mapping.is_none() {
             api_config.command_mapping = Some(crate::config::models::CommandMapping::default());
@@ -1429,8 +1542,9 @@ impl<F: FileSystem> ConfigManager<F> {
     /// Returns an error if transformation fails
     fn transform_spec_to_cached(
         name: &str,
-        openapi_spec: &OpenAPI,
+        document: &SpecDocument,
         validation_result: &crate::spec::validator::ValidationResult,
+        openid_connect: OpenIdConnectDiscovery,
     ) -> Result<crate::cache::models::CachedSpec, Error> {
         let transformer = SpecTransformer::new();
 
@@ -1441,12 +1555,38 @@ impl<F: FileSystem> ConfigManager<F> {
             .filter_map(super::super::spec::validator::ValidationWarning::to_skip_endpoint)
             .collect();
 
-        transformer.transform_with_warnings(
-            name,
-            openapi_spec,
-            &skip_endpoints,
-            &validation_result.warnings,
-        )
+        let options = TransformOptions::new(name)
+            .with_skip_endpoints(skip_endpoints)
+            .with_warnings(validation_result.warnings.clone())
+            .with_openid_connect(openid_connect);
+        transformer.transform_document(document, &options)
+    }
+
+    /// Inlines the external references of a spec read from `location`
+    ///
+    /// Returns the bundled document, or `content` unchanged when it references no
+    /// other documents. The result is what gets stored in `specs/`, so `reinit`
+    /// does not need the referenced files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a referenced file or URL cannot be read or resolved
+    fn bundle_spec_content(
+        &self,
+        content: String,
+        location: &SpecLocation,
+        timeout: std::time::Duration,
+    ) -> Result<String, Error> {
+        let bundled =
+            crate::spec::bundle_external_references(
+                &content,
+                location,
+                |document| match document {
+                    SpecLocation::File(path) => Ok(self.fs.read_to_string(path)?),
+                    SpecLocation::Url(url) => fetch_spec_blocking(url.as_str(), timeout),
+                },
+            )?;
+        Ok(bundled.unwrap_or(content))
     }
 
     /// Creates necessary directories for spec and cache files
@@ -1532,14 +1672,15 @@ impl<F: FileSystem> ConfigManager<F> {
     fn add_spec_from_validated_openapi(
         &self,
         name: &str,
-        openapi_spec: &OpenAPI,
+        document: &SpecDocument,
         content: &str,
         validation_result: &crate::spec::validator::ValidationResult,
+        openid_connect: OpenIdConnectDiscovery,
         strict: bool,
     ) -> Result<(), Error> {
         // Transform to cached representation
         let mut cached_spec =
-            Self::transform_spec_to_cached(name, openapi_spec, validation_result)?;
+            Self::transform_spec_to_cached(name, document, validation_result, openid_connect)?;
 
         // Apply command mappings from config (if any)
         self.apply_command_mapping_if_configured(name, &mut cached_spec)?;
@@ -1841,6 +1982,7 @@ impl<F: FileSystem> ConfigManager<F> {
             content_type: Vec::new(),
             auth: Vec::new(),
             mixed_content: Vec::new(),
+            conversion: Vec::new(),
         };
 
         for warning in warnings {
@@ -1857,9 +1999,17 @@ impl<F: FileSystem> ConfigManager<F> {
 
             if warning
                 .reason
-                .contains("unsupported content types alongside JSON")
+                .starts_with(crate::spec::validator::PARTIAL_SUPPORT_REASON_PREFIX)
             {
                 categorized.mixed_content.push(warning);
+                continue;
+            }
+
+            if warning
+                .reason
+                .starts_with(crate::spec::swagger2::CONVERSION_REASON_PREFIX)
+            {
+                categorized.conversion.push(warning);
             }
         }
 
@@ -1976,15 +2126,52 @@ impl<F: FileSystem> ConfigManager<F> {
             "{indent}Endpoints with partial content type support:"
         ));
         for warning in mixed_content_warnings {
+            let supported = warning
+                .reason
+                .strip_prefix(crate::spec::validator::PARTIAL_SUPPORT_REASON_PREFIX)
+                .unwrap_or("JSON");
             lines.push(format!(
-                "{}  - {} {} supports JSON but not: {}",
+                "{}  - {} {} supports {} but not: {}",
                 indent,
                 warning.endpoint.method,
                 warning.endpoint.path,
+                supported,
                 warning.endpoint.content_type
             ));
         }
     }
+
+    /// Formats notes raised while converting a Swagger 2.0 document
+    fn format_conversion_warnings(
+        lines: &mut Vec<String>,
+        conversion_warnings: &[&crate::spec::validator::ValidationWarning],
+        indent: &str,
+        add_blank_line: bool,
+    ) {
+        if conversion_warnings.is_empty() {
+            return;
+        }
+
+        if add_blank_line {
+            lines.push(String::new()); // Add blank line between sections
+        }
+
+        lines.push(format!("{indent}Swagger 2.0 conversion notes:"));
+        for warning in conversion_warnings {
+            let note = warning
+                .reason
+                .strip_prefix(crate::spec::swagger2::CONVERSION_REASON_PREFIX)
+                .unwrap_or(&warning.reason);
+            if warning.endpoint.path.is_empty() {
+                lines.push(format!("{indent}  - {note}"));
+            } else {
+                lines.push(format!(
+                    "{}  - {} {}: {}",
+                    indent, warning.endpoint.method, warning.endpoint.path, note
+                ));
+            }
+        }
+    }
 }
 
 /// Gets the default configuration directory path.
@@ -1998,6 +2185,16 @@ pub fn get_config_dir() -> Result<PathBuf, Error> {
     Ok(config_dir)
 }
 
+/// Timeout for fetching `OpenID` Connect discovery documents during `config api add`
+const fn discovery_timeout() -> std::time::Duration {
+    std::time::Duration::from_secs(constants::OPENID_DISCOVERY_TIMEOUT_SECS)
+}
+
+/// Timeout for fetching specifications and the documents they reference
+const fn spec_fetch_timeout() -> std::time::Duration {
+    std::time::Duration::from_secs(constants::SPEC_FETCH_TIMEOUT_SECS)
+}
+
 /// Determines if the input string is a URL (starts with http:// or https://)
 #[must_use]
 pub fn is_url(input: &str) -> bool {
@@ -2017,7 +2214,32 @@ const MAX_RESPONSE_SIZE: u64 = 10 * 1024 * 1024; // 10MB
 
 #[allow(clippy::future_not_send)]
 async fn fetch_spec_from_url(url: &str) -> Result<String, Error> {
-    fetch_spec_from_url_with_timeout(url, std::time::Duration::from_secs(30)).await
+    fetch_spec_from_url_with_timeout(url, spec_fetch_timeout()).await
+}
+
+/// Fetches a document referenced by a spec from synchronous code
+///
+/// The request runs on its own runtime in a scoped thread, so this is safe to
+/// call from within an async context.
+fn fetch_spec_blocking(url: &str, timeout: std::time::Duration) -> Result<String, Error> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| {
+                        Error::network_request_failed(format!("Failed to start fetch runtime: {e}"))
+                    })?;
+                runtime.block_on(fetch_spec_from_url_with_timeout(url, timeout))
+            })
+            .join()
+            .unwrap_or_else(|_| {
+                Err(Error::network_request_failed(format!(
+                    "Fetching {url} was interrupted"
+                )))
+            })
+    })
 }
 
 #[allow(clippy::future_not_send)]
@@ -217,6 +217,7 @@ mod tests {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }
     }
 
@@ -2,6 +2,8 @@ pub mod context_name;
 pub mod manager;
 pub mod mapping;
 pub mod models;
+pub mod secrets;
 pub mod server_variable_resolver;
 pub mod settings;
 pub mod url_resolver;
+pub mod vault;
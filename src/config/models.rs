@@ -114,6 +114,9 @@ pub struct ApiConfig {
     /// Custom command tree mapping (rename groups, operations, add aliases, hide commands)
     #[serde(default)]
     pub command_mapping: Option<CommandMapping>,
+    /// How responses are checked against the spec when `--validate-response` is not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_validation: Option<ResponseValidation>,
 }
 
 impl ApiConfig {
@@ -128,6 +131,57 @@ impl ApiConfig {
             && self.environment_urls.is_empty()
             && self.secrets.is_empty()
             && self.command_mapping.is_none()
+            && self.response_validation.is_none()
+    }
+}
+
+/// How a response is checked against the operation's documented responses
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseValidation {
+    /// Responses are not checked
+    #[default]
+    Off,
+    /// Contract violations are logged as warnings
+    Warn,
+    /// Contract violations fail the command
+    Error,
+}
+
+impl ResponseValidation {
+    /// All modes, in the order they are documented
+    pub const ALL: [Self; 3] = [Self::Off, Self::Warn, Self::Error];
+
+    /// Identifier used in the config file and on the command line
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for ResponseValidation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ResponseValidation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown response validation mode '{s}' (expected one of: {})",
+                    Self::ALL.map(Self::as_str).join(", ")
+                )
+            })
     }
 }
 
@@ -174,9 +228,97 @@ pub struct ApertureSecret {
     pub name: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone)]
+/// Where the value of a secret mapping comes from.
+///
+/// The meaning of [`ApertureSecret::name`] depends on the source.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum SecretSource {
+    /// `name` is an environment variable
     Env,
+    /// `name` is a file path; the contents are trimmed
+    File,
+    /// `name` is a shell command; its trimmed stdout is the secret
+    Command,
+    /// `name` is a key in the API's `.env` file (`<config>/env/<api>.env`)
+    Dotenv,
+    /// `name` is an entry in the encrypted secret vault
+    Vault,
     // Keychain, // Future option
 }
+
+impl SecretSource {
+    /// All sources, in the order they are documented
+    pub const ALL: [Self; 5] = [
+        Self::Env,
+        Self::File,
+        Self::Command,
+        Self::Dotenv,
+        Self::Vault,
+    ];
+
+    /// Identifier used in `x-aperture-secret` and on the command line
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Env => "env",
+            Self::File => "file",
+            Self::Command => "command",
+            Self::Dotenv => "dotenv",
+            Self::Vault => "vault",
+        }
+    }
+
+    /// Human-readable description of a secret reference, for messages
+    #[must_use]
+    pub fn describe(self, name: &str) -> String {
+        match self {
+            Self::Env => format!("environment variable '{name}'"),
+            Self::File => format!("file '{name}'"),
+            Self::Command => format!("command '{name}'"),
+            Self::Dotenv => format!("dotenv entry '{name}'"),
+            Self::Vault => format!("vault entry '{name}'"),
+        }
+    }
+
+    /// Whether a specification's own `x-aperture-secret` may use this source.
+    ///
+    /// Specs can be added from untrusted URLs, so sources that run commands
+    /// or read arbitrary files are reserved for mappings the user sets.
+    #[must_use]
+    pub const fn allowed_in_spec(self) -> bool {
+        matches!(self, Self::Env | Self::Dotenv | Self::Vault)
+    }
+
+    /// Sources a specification's `x-aperture-secret` may use
+    #[must_use]
+    pub fn spec_sources() -> Vec<&'static str> {
+        Self::ALL
+            .into_iter()
+            .filter(|source| source.allowed_in_spec())
+            .map(Self::as_str)
+            .collect()
+    }
+}
+
+impl std::fmt::Display for SecretSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for SecretSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|source| source.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown secret source '{s}' (expected one of: {})",
+                    Self::ALL.map(Self::as_str).join(", ")
+                )
+            })
+    }
+}
@@ -0,0 +1,265 @@
+//! Resolution of secret values from their configured [`SecretSource`].
+//!
+//! Secret mappings come from `x-aperture-secret` extensions or from
+//! `aperture config secret set`. Both name a source and a source-specific
+//! reference; `file` and `command` are only available to the latter:
+//!
+//! - `env`: an environment variable
+//! - `file`: a file whose trimmed contents are the secret; on Unix the file
+//!   must not be readable by group or others
+//! - `command`: a shell command whose trimmed stdout is the secret, e.g.
+//!   `pass show api/token`; each command runs at most once per process
+//! - `dotenv`: a key in the API's `.env` file at `<config>/env/<api>.env`
+//! - `vault`: an entry in the encrypted vault (see [`crate::config::vault`])
+
+use crate::config::models::SecretSource;
+use crate::config::vault;
+use crate::constants;
+use crate::error::Error;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+
+/// Resolves the secret referenced by `name` from `source`.
+///
+/// # Errors
+///
+/// Returns an error if the environment variable is unset, the file or dotenv
+/// entry cannot be read, the command fails or prints nothing, or the vault
+/// cannot be unlocked or lacks the entry.
+pub fn resolve(
+    source: SecretSource,
+    name: &str,
+    api_name: &str,
+    scheme_name: &str,
+) -> Result<String, Error> {
+    let unavailable =
+        |reason: String| Error::secret_unavailable(scheme_name, source.describe(name), reason);
+
+    match source {
+        SecretSource::Env => {
+            std::env::var(name).map_err(|_| Error::secret_not_set(scheme_name, name))
+        }
+        SecretSource::File => {
+            read_secret_file(Path::new(shellexpand::tilde(name).as_ref())).map_err(unavailable)
+        }
+        SecretSource::Command => run_secret_command(name).map_err(unavailable),
+        SecretSource::Dotenv => {
+            let path = dotenv_path(&config_dir()?, api_name);
+            read_dotenv_entry(&path, name).map_err(unavailable)
+        }
+        SecretSource::Vault => vault::lookup(&config_dir()?, name)?
+            .ok_or_else(|| unavailable("no such entry in the vault".to_string())),
+    }
+}
+
+/// Resolves a secret whose source is given by its `x-aperture-secret` identifier.
+///
+/// Only sources a specification may use are resolved (see
+/// [`SecretSource::allowed_in_spec`]), including for specs cached before the
+/// validator rejected the others.
+///
+/// # Errors
+///
+/// Returns an error if the source is unknown or not allowed in a spec, or
+/// resolution fails.
+pub fn resolve_named_source(
+    source: &str,
+    name: &str,
+    api_name: &str,
+    scheme_name: &str,
+) -> Result<String, Error> {
+    let source = source
+        .parse::<SecretSource>()
+        .map_err(|reason| Error::secret_unavailable(scheme_name, format!("'{name}'"), reason))?;
+    if !source.allowed_in_spec() {
+        return Err(Error::secret_unavailable(
+            scheme_name,
+            source.describe(name),
+            format!(
+                "the '{source}' source is only allowed in mappings set with 'aperture config secret set'"
+            ),
+        ));
+    }
+    resolve(source, name, api_name, scheme_name)
+}
+
+/// Path of the per-API dotenv file consulted by the `dotenv` source
+#[must_use]
+pub fn dotenv_path(config_dir: &Path, api_name: &str) -> PathBuf {
+    config_dir
+        .join(constants::DIR_DOTENV)
+        .join(format!("{api_name}{}", constants::FILE_EXT_ENV))
+}
+
+fn config_dir() -> Result<PathBuf, Error> {
+    std::env::var(constants::ENV_APERTURE_CONFIG_DIR).map_or_else(
+        |_| crate::config::manager::get_config_dir(),
+        |dir| Ok(PathBuf::from(dir)),
+    )
+}
+
+/// Reads a secret file after checking that only its owner can access it
+fn read_secret_file(path: &Path) -> Result<String, String> {
+    check_private_permissions(path)?;
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let value = content.trim();
+    if value.is_empty() {
+        return Err("the file is empty".to_string());
+    }
+    Ok(value.to_string())
+}
+
+#[cfg(unix)]
+fn check_private_permissions(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path)
+        .map_err(|e| e.to_string())?
+        .permissions()
+        .mode();
+    if mode & 0o077 != 0 {
+        return Err(format!(
+            "permissions {:o} allow access by other users; run 'chmod 600 {}'",
+            mode & 0o777,
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_private_permissions(path: &Path) -> Result<(), String> {
+    std::fs::metadata(path)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Runs a secret helper command, reusing its output within this process
+fn run_secret_command(command: &str) -> Result<String, String> {
+    static OUTPUTS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    let outputs = OUTPUTS.get_or_init(Mutex::default);
+
+    if let Some(value) = outputs.lock().ok().and_then(|o| o.get(command).cloned()) {
+        return Ok(value);
+    }
+
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let output = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.lines().next().unwrap_or_default().trim();
+        return Err(if detail.is_empty() {
+            format!("exited with {}", output.status)
+        } else {
+            format!("exited with {}: {detail}", output.status)
+        });
+    }
+
+    let value = String::from_utf8(output.stdout)
+        .map_err(|_| "output is not valid UTF-8".to_string())?
+        .trim()
+        .to_string();
+    if value.is_empty() {
+        return Err("the command printed nothing".to_string());
+    }
+
+    if let Ok(mut outputs) = outputs.lock() {
+        outputs.insert(command.to_string(), value.clone());
+    }
+    Ok(value)
+}
+
+fn read_dotenv_entry(path: &Path, key: &str) -> Result<String, String> {
+    check_private_permissions(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let content = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    parse_dotenv(&content)
+        .remove(key)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("'{key}' is not set in {}", path.display()))
+}
+
+/// Parses `KEY=value` lines, ignoring blank lines, comments and `export` prefixes
+fn parse_dotenv(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            Some((key.trim().to_string(), unquote_dotenv_value(value.trim())))
+        })
+        .collect()
+}
+
+fn unquote_dotenv_value(value: &str) -> String {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner.to_string();
+        }
+    }
+    // Unquoted values may carry a trailing comment
+    value
+        .split_once(" #")
+        .map_or(value, |(value, _)| value)
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let entries = parse_dotenv(
+            "# tokens\nexport API_TOKEN=abc123 # primary\nQUOTED=\"with spaces\"\nSINGLE='x=y'\n\nbroken line\n",
+        );
+        assert_eq!(entries["API_TOKEN"], "abc123");
+        assert_eq!(entries["QUOTED"], "with spaces");
+        assert_eq!(entries["SINGLE"], "x=y");
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secret_file_requires_private_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token");
+        std::fs::write(&path, "  s3cret\n").unwrap();
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let err = read_secret_file(&path).unwrap_err();
+        assert!(err.contains("chmod 600"), "{err}");
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(read_secret_file(&path).unwrap(), "s3cret");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secret_command_output_is_trimmed_and_failures_reported() {
+        assert_eq!(
+            run_secret_command("printf '  from-helper\\n'").unwrap(),
+            "from-helper"
+        );
+        let err = run_secret_command("echo nope >&2; exit 3").unwrap_err();
+        assert!(err.contains("nope"), "{err}");
+    }
+}
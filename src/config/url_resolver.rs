@@ -324,6 +324,7 @@ mod tests {
                     strict_mode: false,
                     secrets: HashMap::new(),
                     command_mapping: None,
+                    response_validation: None,
                 },
             );
 
@@ -359,6 +360,7 @@ mod tests {
                     strict_mode: false,
                     secrets: HashMap::new(),
                     command_mapping: None,
+                    response_validation: None,
                 },
             );
 
@@ -396,6 +398,7 @@ mod tests {
                     strict_mode: false,
                     secrets: HashMap::new(),
                     command_mapping: None,
+                    response_validation: None,
                 },
             );
 
@@ -588,6 +591,7 @@ mod tests {
                     strict_mode: false,
                     secrets: HashMap::new(),
                     command_mapping: None,
+                    response_validation: None,
                 },
             );
 
@@ -0,0 +1,392 @@
+//! Passphrase-encrypted secret vault.
+//!
+//! The vault is a single JSON file (`<config>/vault.json`) holding a map of
+//! entry names to secret values, encrypted with AES-256-GCM. The key is
+//! derived from a passphrase with PBKDF2-HMAC-SHA256 and a random salt, and
+//! every write uses a fresh nonce. Entries are referenced from secret mappings
+//! with the `vault` source.
+//!
+//! The passphrase is taken from `APERTURE_VAULT_PASSPHRASE` or, when a
+//! terminal is attached, prompted for without echo.
+
+use crate::constants;
+use crate::error::Error;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::IsTerminal;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+#[cfg(windows)]
+use aws_lc_rs::{aead, pbkdf2};
+#[cfg(not(windows))]
+use ring::{aead, pbkdf2};
+
+/// Version of the on-disk vault layout
+const VAULT_FORMAT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+/// On-disk representation; binary fields are base64-encoded
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// An unlocked vault. Changes are only persisted by [`Vault::save`].
+pub struct Vault {
+    path: PathBuf,
+    iterations: u32,
+    salt: [u8; SALT_LEN],
+    key: [u8; KEY_LEN],
+    entries: BTreeMap<String, String>,
+}
+
+impl Vault {
+    /// Location of the vault inside a configuration directory
+    #[must_use]
+    pub fn path_in(config_dir: &Path) -> PathBuf {
+        config_dir.join(constants::VAULT_FILENAME)
+    }
+
+    /// Creates an empty vault protected by `passphrase`. Nothing is written
+    /// until [`Vault::save`] is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if random bytes cannot be generated.
+    pub fn create(path: PathBuf, passphrase: &str) -> Result<Self, Error> {
+        let mut salt = [0u8; SALT_LEN];
+        fill_random(&mut salt)?;
+        let iterations = constants::VAULT_KDF_ITERATIONS;
+        let key = derive_key(passphrase, &salt, iterations);
+        Ok(Self {
+            path,
+            iterations,
+            salt,
+            key,
+            entries: BTreeMap::new(),
+        })
+    }
+
+    /// Opens and decrypts the vault at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vault does not exist, cannot be parsed, or the
+    /// passphrase does not decrypt it.
+    pub fn open(path: PathBuf, passphrase: &str) -> Result<Self, Error> {
+        let display = path.display().to_string();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::vault_not_found(display));
+            }
+            Err(e) => {
+                return Err(Error::io_error(format!(
+                    "Failed to read secret vault '{display}': {e}"
+                )));
+            }
+        };
+
+        let file: VaultFile =
+            serde_json::from_str(&content).map_err(|_| Error::vault_decryption_failed(&display))?;
+        if file.version != VAULT_FORMAT_VERSION {
+            return Err(Error::invalid_config(format!(
+                "Unsupported secret vault version {} in '{display}'",
+                file.version
+            )));
+        }
+
+        if !(constants::VAULT_KDF_MIN_ITERATIONS..=constants::VAULT_KDF_MAX_ITERATIONS)
+            .contains(&file.iterations)
+        {
+            return Err(Error::invalid_config(format!(
+                "Secret vault '{display}' declares {} key derivation iterations; expected between {} and {}",
+                file.iterations,
+                constants::VAULT_KDF_MIN_ITERATIONS,
+                constants::VAULT_KDF_MAX_ITERATIONS
+            )));
+        }
+
+        let salt: [u8; SALT_LEN] =
+            decode_fixed(&file.salt).ok_or_else(|| Error::vault_decryption_failed(&display))?;
+        let nonce: [u8; aead::NONCE_LEN] =
+            decode_fixed(&file.nonce).ok_or_else(|| Error::vault_decryption_failed(&display))?;
+        let mut ciphertext = STANDARD
+            .decode(&file.ciphertext)
+            .map_err(|_| Error::vault_decryption_failed(&display))?;
+
+        let key = derive_key(passphrase, &salt, file.iterations);
+        let plaintext = sealing_key(&key)
+            .open_in_place(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::empty(),
+                &mut ciphertext,
+            )
+            .map_err(|_| Error::vault_decryption_failed(&display))?;
+        let entries = serde_json::from_slice(plaintext)
+            .map_err(|_| Error::vault_decryption_failed(&display))?;
+
+        Ok(Self {
+            path,
+            iterations: file.iterations,
+            salt,
+            key,
+            entries,
+        })
+    }
+
+    /// Encrypts the entries and writes the vault, readable only by its owner.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encryption or writing fails.
+    pub fn save(&self) -> Result<(), Error> {
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        fill_random(&mut nonce)?;
+
+        let mut in_out = serde_json::to_vec(&self.entries).map_err(|e| {
+            Error::serialization_error(format!("Failed to serialize vault entries: {e}"))
+        })?;
+        sealing_key(&self.key)
+            .seal_in_place_append_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| Error::io_error("Failed to encrypt the secret vault"))?;
+
+        let file = VaultFile {
+            version: VAULT_FORMAT_VERSION,
+            iterations: self.iterations,
+            salt: STANDARD.encode(self.salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(in_out),
+        };
+        let content = serde_json::to_vec_pretty(&file)
+            .map_err(|e| Error::serialization_error(format!("Failed to serialize vault: {e}")))?;
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                Error::io_error(format!(
+                    "Failed to create directory '{}': {e}",
+                    parent.display()
+                ))
+            })?;
+        }
+        crate::atomic::atomic_write_private_sync(&self.path, &content).map_err(|e| {
+            Error::io_error(format!(
+                "Failed to write secret vault '{}': {e}",
+                self.path.display()
+            ))
+        })?;
+
+        forget_unlocked(&self.path);
+        Ok(())
+    }
+
+    /// Returns the value stored under `name`
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries.get(name).map(String::as_str)
+    }
+
+    /// Stores `value` under `name`, replacing any previous value
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.entries.insert(name.into(), value.into());
+    }
+
+    /// Removes the entry, returning whether it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    /// Entry names in sorted order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+}
+
+/// Returns the vault passphrase from the environment or a terminal prompt.
+///
+/// # Errors
+///
+/// Returns an error if the passphrase is not set and stdin is not a terminal,
+/// or if the prompt fails or yields an empty passphrase.
+pub fn passphrase() -> Result<String, Error> {
+    read_passphrase(false)
+}
+
+/// Like [`passphrase`], but asks twice when prompting so typos are caught
+/// before a new vault is encrypted with them.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as [`passphrase`], or if the
+/// two entries differ.
+pub fn new_passphrase() -> Result<String, Error> {
+    read_passphrase(true)
+}
+
+fn read_passphrase(confirm: bool) -> Result<String, Error> {
+    if let Ok(value) = std::env::var(constants::ENV_APERTURE_VAULT_PASSPHRASE) {
+        if !value.is_empty() {
+            return Ok(value);
+        }
+    }
+
+    if !std::io::stdin().is_terminal() {
+        return Err(Error::vault_passphrase_required());
+    }
+
+    let value = crate::interactive::prompt_for_secret("Vault passphrase: ")?;
+    if value.is_empty() {
+        return Err(Error::validation_error(
+            "The vault passphrase cannot be empty",
+        ));
+    }
+    if confirm && crate::interactive::prompt_for_secret("Confirm passphrase: ")? != value {
+        return Err(Error::validation_error("The passphrases do not match"));
+    }
+    Ok(value)
+}
+
+/// Looks up a vault entry for secret resolution.
+///
+/// The vault is unlocked at most once per process, so resolving several
+/// secrets (and collecting them for redaction) prompts only once.
+///
+/// # Errors
+///
+/// Returns an error if the vault cannot be unlocked.
+pub fn lookup(config_dir: &Path, name: &str) -> Result<Option<String>, Error> {
+    let path = Vault::path_in(config_dir);
+    let mut unlocked = unlocked_vaults()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    if !unlocked.contains_key(&path) {
+        let vault = Vault::open(path.clone(), &passphrase()?)?;
+        unlocked.insert(path.clone(), vault.entries);
+    }
+    Ok(unlocked
+        .get(&path)
+        .and_then(|entries| entries.get(name))
+        .cloned())
+}
+
+fn unlocked_vaults() -> &'static Mutex<HashMap<PathBuf, BTreeMap<String, String>>> {
+    static UNLOCKED: OnceLock<Mutex<HashMap<PathBuf, BTreeMap<String, String>>>> = OnceLock::new();
+    UNLOCKED.get_or_init(Mutex::default)
+}
+
+/// Drops the memoized contents of a vault after it has been rewritten
+fn forget_unlocked(path: &Path) {
+    if let Ok(mut unlocked) = unlocked_vaults().lock() {
+        unlocked.remove(path);
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; KEY_LEN] {
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(iterations).unwrap_or(NonZeroU32::MIN),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+fn sealing_key(key: &[u8; KEY_LEN]) -> aead::LessSafeKey {
+    // A 32-byte key is always valid for AES-256-GCM
+    let unbound = aead::UnboundKey::new(&aead::AES_256_GCM, key)
+        .unwrap_or_else(|_| unreachable!("AES-256-GCM key length is fixed"));
+    aead::LessSafeKey::new(unbound)
+}
+
+fn decode_fixed<const N: usize>(encoded: &str) -> Option<[u8; N]> {
+    STANDARD.decode(encoded).ok()?.try_into().ok()
+}
+
+fn fill_random(bytes: &mut [u8]) -> Result<(), Error> {
+    getrandom::fill(bytes)
+        .map_err(|e| Error::io_error(format!("Failed to generate random bytes: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_round_trip_and_wrong_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Vault::path_in(dir.path());
+
+        let mut vault = Vault::create(path.clone(), "correct horse").unwrap();
+        vault.insert("github", "ghp_secret");
+        vault.insert("stripe", "sk_test");
+        vault.save().unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("ghp_secret"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let mut reopened = Vault::open(path.clone(), "correct horse").unwrap();
+        assert_eq!(reopened.get("github"), Some("ghp_secret"));
+        assert_eq!(reopened.names().collect::<Vec<_>>(), ["github", "stripe"]);
+        assert!(reopened.remove("stripe"));
+        assert!(!reopened.remove("stripe"));
+        reopened.save().unwrap();
+
+        // The nonce changes on every write
+        assert_ne!(std::fs::read_to_string(&path).unwrap(), raw);
+
+        let err = Vault::open(path, "wrong").err().unwrap();
+        assert!(err.to_string().contains("wrong passphrase"), "{err}");
+    }
+
+    #[test]
+    fn test_open_rejects_out_of_range_iterations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Vault::path_in(dir.path());
+        Vault::create(path.clone(), "correct horse")
+            .unwrap()
+            .save()
+            .unwrap();
+
+        for iterations in [0, 1_000, u32::MAX] {
+            let mut file: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            file["iterations"] = iterations.into();
+            std::fs::write(&path, file.to_string()).unwrap();
+
+            let err = Vault::open(path.clone(), "correct horse").err().unwrap();
+            assert!(
+                err.to_string().contains("key derivation iterations"),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_open_missing_vault() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = Vault::open(Vault::path_in(dir.path()), "x").err().unwrap();
+        assert!(err.to_string().contains("No secret vault"), "{err}");
+    }
+}
@@ -17,6 +17,9 @@ pub const HEADER_API_KEY: &str = "Api-Key";
 pub const HEADER_TOKEN: &str = "Token";
 pub const HEADER_BEARER: &str = "Bearer";
 pub const HEADER_COOKIE: &str = "Cookie";
+pub const HEADER_HOST: &str = "Host";
+pub const HEADER_ORIGIN: &str = "Origin";
+pub const HEADER_SEC_FETCH_SITE: &str = "Sec-Fetch-Site";
 
 // Lowercase header names (for HTTP/2 compatibility and internal use)
 pub const HEADER_AUTHORIZATION_LC: &str = "authorization";
@@ -84,12 +87,40 @@ pub const AUTH_SCHEME_BEARER: &str = "bearer";
 pub const AUTH_SCHEME_BASIC: &str = "basic";
 pub const AUTH_SCHEME_APIKEY: &str = "apiKey";
 pub const AUTH_SCHEME_OAUTH2: &str = "oauth2";
-pub const AUTH_SCHEME_OPENID: &str = "openidconnect";
+pub const AUTH_SCHEME_OPENID: &str = "openIdConnect";
+
+// OAuth2 grant types (RFC 6749)
+pub const OAUTH2_GRANT_CLIENT_CREDENTIALS: &str = "client_credentials";
+pub const OAUTH2_GRANT_AUTHORIZATION_CODE: &str = "authorization_code";
+pub const OAUTH2_GRANT_REFRESH_TOKEN: &str = "refresh_token";
+
+/// Seconds before expiry at which a stored `OAuth2` access token is refreshed.
+pub const OAUTH2_TOKEN_REFRESH_SKEW_SECS: u64 = 60;
+
+/// Seconds `aperture auth login` waits for the browser redirect.
+pub const OAUTH2_LOGIN_TIMEOUT_SECS: u64 = 300;
+
+/// Seconds allowed for fetching an `OpenID` Connect discovery document.
+pub const OPENID_DISCOVERY_TIMEOUT_SECS: u64 = 10;
+
+/// Seconds allowed for fetching a specification or a document it references.
+pub const SPEC_FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// PBKDF2-HMAC-SHA256 iterations used to derive the vault key from its passphrase.
+pub const VAULT_KDF_ITERATIONS: u32 = 600_000;
+
+/// Fewest KDF iterations a vault file may declare; fewer would make the
+/// passphrase cheap to brute-force.
+pub const VAULT_KDF_MIN_ITERATIONS: u32 = 100_000;
+
+/// Most KDF iterations a vault file may declare; more would stall unlocking.
+pub const VAULT_KDF_MAX_ITERATIONS: u32 = 10_000_000;
 
 // Environment Variables
 pub const ENV_APERTURE_CONFIG_DIR: &str = "APERTURE_CONFIG_DIR";
 pub const ENV_APERTURE_BASE_URL: &str = "APERTURE_BASE_URL";
 pub const ENV_APERTURE_ENV: &str = "APERTURE_ENV";
+pub const ENV_APERTURE_VAULT_PASSPHRASE: &str = "APERTURE_VAULT_PASSPHRASE";
 
 // Common Response Messages
 pub const EMPTY_RESPONSE: &str = "(empty response)";
@@ -144,14 +175,19 @@ pub const CACHE_SUFFIX: &str = "cache";
 pub const FILE_EXT_JSON: &str = ".json";
 pub const FILE_EXT_YAML: &str = ".yaml";
 pub const FILE_EXT_BIN: &str = ".bin";
+pub const FILE_EXT_ENV: &str = ".env";
 pub const CACHE_FILE_SUFFIX: &str = "_cache.json";
 pub const CACHE_METADATA_FILENAME: &str = "cache_metadata.json";
 pub const CONFIG_FILENAME: &str = "config.toml";
+pub const VAULT_FILENAME: &str = "vault.json";
+pub const DAEMON_SOCKET_FILENAME: &str = "daemon.sock";
 
 // Directory names
 pub const DIR_CACHE: &str = ".cache";
 pub const DIR_RESPONSES: &str = "responses";
 pub const DIR_SPECS: &str = "specs";
+pub const DIR_TOKENS: &str = "tokens";
+pub const DIR_DOTENV: &str = "env";
 
 // Schema Types
 pub const SCHEMA_TYPE_STRING: &str = "string";
@@ -207,6 +243,7 @@ pub const COMPONENT_HEADERS: &str = "headers";
 pub const COMPONENT_SECURITY_SCHEMES: &str = "securitySchemes";
 pub const COMPONENT_LINKS: &str = "links";
 pub const COMPONENT_CALLBACKS: &str = "callbacks";
+pub const COMPONENT_PATH_ITEMS: &str = "pathItems";
 pub const COMPONENT_COMPONENTS: &str = "components";
 
 /// Check if a header name is authentication-related
@@ -0,0 +1,458 @@
+//! Long-lived JSON-RPC server for low-latency agent calls.
+//!
+//! `aperture daemon` listens on a Unix socket and answers newline-delimited
+//! JSON-RPC 2.0 requests, so that an agent making many calls pays process
+//! startup, spec deserialization, command tree generation and the TLS
+//! handshake once rather than on every call:
+//!
+//! - `execute` runs `{"args": [...]}` exactly like `aperture api ARGS...`
+//! - `describe` returns the capability manifest of `{"api": NAME}`, or its
+//!   tool definitions with `"as": FORMAT`
+//! - `search` finds operations matching `{"query": TEXT}` like
+//!   `aperture search`
+//!
+//! Loaded specs keep their command trees, and all requests share one HTTP
+//! client and its connection pool. Before a spec is used, the modification
+//! times and sizes of its cache and source files are compared with those it
+//! was loaded from, and the spec is reloaded when they differ.
+
+use crate::cache::models::CachedSpec;
+use crate::cli::translate;
+use crate::cli::{Cli, Commands, ExecutionFlags, ToolFormat};
+use crate::config::context_name::ApiContextName;
+use crate::config::manager::ConfigManager;
+use crate::config::models::GlobalConfig;
+use crate::constants;
+use crate::engine::executor::{self, SharedHttpClient};
+use crate::engine::{generator, loader};
+use crate::error::Error;
+use crate::fs::OsFileSystem;
+use crate::invocation::{ExecutionContext, ExecutionResult, ProxyOverride};
+use crate::jsonrpc::{self, invalid_params, RpcError};
+use crate::search::CommandSearcher;
+use base64::{engine::general_purpose, Engine as _};
+use clap::{Parser, ValueEnum};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+/// An `aperture` error; its structured form is in the error's `data`
+const EXECUTION_ERROR: i64 = -32000;
+
+/// Modification time and size of a file, if it exists
+type FileStamp = Option<(SystemTime, u64)>;
+
+/// What a loaded spec was read from, to detect when it must be reloaded
+#[derive(Debug, PartialEq, Eq)]
+struct SpecFingerprint {
+    cache: FileStamp,
+    source: FileStamp,
+}
+
+/// A spec kept in memory with its command tree
+struct LoadedApi {
+    spec: CachedSpec,
+    command: clap::Command,
+    fingerprint: SpecFingerprint,
+}
+
+/// Answers JSON-RPC requests for the APIs registered in a config directory
+pub struct Daemon {
+    manager: ConfigManager<OsFileSystem>,
+    apis: Mutex<HashMap<String, Arc<LoadedApi>>>,
+    http_client: SharedHttpClient,
+}
+
+impl Daemon {
+    /// Creates a daemon for the APIs registered in `config_dir`. Specs are
+    /// loaded when first used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built from the proxy
+    /// configuration.
+    pub fn new(config_dir: PathBuf) -> Result<Self, Error> {
+        let manager = ConfigManager::with_fs(OsFileSystem, config_dir);
+        let http_client = SharedHttpClient::new(&ExecutionContext {
+            global_config: manager.load_global_config().ok(),
+            ..ExecutionContext::default()
+        })?;
+        Ok(Self {
+            manager,
+            apis: Mutex::new(HashMap::new()),
+            http_client,
+        })
+    }
+
+    /// Accepts connections on `listener` until the process exits. Each
+    /// connection is served concurrently.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if accepting a connection fails.
+    pub async fn run(self: Arc<Self>, listener: UnixListener) -> Result<(), Error> {
+        loop {
+            let (stream, _) = listener
+                .accept()
+                .await
+                .map_err(|e| Error::io_error(format!("Failed to accept daemon connection: {e}")))?;
+            let daemon = Arc::clone(&self);
+            tokio::spawn(async move { daemon.serve_connection(stream).await });
+        }
+    }
+
+    async fn serve_connection(&self, stream: UnixStream) {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some(response) = self.handle_message(&line).await else {
+                continue;
+            };
+            let written = async {
+                writer.write_all(response.as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+                writer.flush().await
+            };
+            if written.await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Handles one JSON-RPC message and returns the response to send, if any.
+    /// Notifications get no response.
+    pub async fn handle_message(&self, message: &str) -> Option<String> {
+        let message = match serde_json::from_str::<Value>(message) {
+            Ok(message) => message,
+            Err(e) => return Some(jsonrpc::parse_error_response(&e).to_string()),
+        };
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return Some(jsonrpc::invalid_request_response(&message).to_string());
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        tracing::debug!(target: "aperture::daemon", method, "Handling request");
+        let result = match method {
+            "execute" => self.execute(&params).await,
+            "describe" => self.describe(&params),
+            "search" => self.search(&params),
+            "ping" => Ok(json!({})),
+            _ => Err(jsonrpc::method_not_found(method)),
+        };
+
+        let id = message.get("id")?;
+        Some(jsonrpc::response(id, result).to_string())
+    }
+
+    async fn execute(&self, params: &Value) -> Result<Value, RpcError> {
+        let args: Vec<String> = params
+            .get("args")
+            .and_then(Value::as_array)
+            .and_then(|args| {
+                args.iter()
+                    .map(|arg| arg.as_str().map(str::to_string))
+                    .collect()
+            })
+            .ok_or_else(|| invalid_params("'args' must be an array of strings"))?;
+        let cli = Cli::try_parse_from(
+            ["aperture", constants::CLI_ROOT_COMMAND]
+                .into_iter()
+                .map(str::to_string)
+                .chain(args),
+        )
+        .map_err(|e| invalid_params(e.to_string().trim_end()))?;
+        let Commands::Api {
+            context,
+            execution,
+            args,
+        } = cli.command
+        else {
+            return Err(invalid_params("'args' must start with an API name"));
+        };
+        if let Some((flag, instead)) = unsupported_flag(&execution) {
+            return Err(invalid_params(&format!(
+                "{flag} is not supported by the daemon; use {instead}"
+            )));
+        }
+
+        let api = self.load_api(&context).map_err(execution_error)?;
+        let command = if execution.positional_args {
+            generator::generate_command_tree_for_api_with_flags(&api.spec, &context, true)
+        } else {
+            api.command.clone()
+        };
+        let matches = command
+            .try_get_matches_from(
+                std::iter::once(constants::CLI_ROOT_COMMAND.to_string()).chain(args),
+            )
+            .map_err(|e| invalid_params(e.to_string().trim_end()))?;
+
+        let call =
+            translate::matches_to_operation_call(&api.spec, &matches).map_err(execution_error)?;
+        let mut ctx = translate::cli_to_execution_context(&execution, self.global_config())
+            .map_err(execution_error)?;
+        ctx.server_var_args = translate::extract_server_var_args(&matches);
+        if ctx.proxy_override == ProxyOverride::Default {
+            ctx.http_client = Some(self.http_client.clone());
+        }
+
+        let result = executor::execute(&api.spec, call, ctx)
+            .await
+            .map_err(execution_error)?;
+        let jq_filter = matches
+            .get_one::<String>("jq")
+            .map(String::as_str)
+            .or(execution.jq.as_deref());
+        result_json(result, jq_filter).map_err(execution_error)
+    }
+
+    fn describe(&self, params: &Value) -> Result<Value, RpcError> {
+        let api = params
+            .get("api")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_params("'api' must be a string"))?;
+        let api = self.load_api(api).map_err(execution_error)?;
+
+        let Some(format) = params.get("as").filter(|format| !format.is_null()) else {
+            let manifest = crate::agent::generate_capability_manifest(
+                &api.spec,
+                self.global_config().as_ref(),
+            )
+            .map_err(execution_error)?;
+            return serde_json::from_str(&manifest)
+                .map_err(|e| execution_error(Error::serialization_error(e.to_string())));
+        };
+        let format = format
+            .as_str()
+            .and_then(|format| ToolFormat::from_str(format, false).ok())
+            .ok_or_else(|| {
+                invalid_params("'as' must be one of openai-tools, anthropic-tools, json-schema")
+            })?;
+        let tokens = params
+            .get("max_description_tokens")
+            .and_then(Value::as_u64)
+            .map_or(crate::tool_export::DEFAULT_DESCRIPTION_TOKENS, |tokens| {
+                usize::try_from(tokens).unwrap_or(usize::MAX)
+            });
+        crate::tool_export::generate_tools(&api.spec, &format, tokens).map_err(execution_error)
+    }
+
+    fn search(&self, params: &Value) -> Result<Value, RpcError> {
+        let query = params
+            .get("query")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_params("'query' must be a string"))?;
+        let api_filter = params.get("api").and_then(Value::as_str);
+
+        let names = match api_filter {
+            Some(api) => vec![api.to_string()],
+            None => self.manager.list_specs().map_err(execution_error)?,
+        };
+        let mut specs = BTreeMap::new();
+        for name in names {
+            match self.load_api(&name) {
+                Ok(api) => {
+                    specs.insert(name, api.spec.clone());
+                }
+                Err(e) if api_filter.is_some() => return Err(execution_error(e)),
+                Err(e) => tracing::warn!(spec = name, error = %e, "could not load spec"),
+            }
+        }
+
+        let results = CommandSearcher::new()
+            .search(&specs, query, api_filter)
+            .map_err(execution_error)?;
+        Ok(Value::Array(
+            results
+                .into_iter()
+                .map(|result| {
+                    json!({
+                        "api": result.api_context,
+                        "command": result.command_path,
+                        "operation_id": result.command.operation_id,
+                        "method": result.command.method,
+                        "path": result.command.path,
+                        "summary": result.command.summary,
+                        "score": result.score,
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    /// Returns a loaded spec, loading or reloading it when its files changed
+    fn load_api(&self, name: &str) -> Result<Arc<LoadedApi>, Error> {
+        let name = ApiContextName::new(name)?;
+        let name = name.as_str();
+        let fingerprint = self.fingerprint(name);
+
+        let loaded = self.lock_apis().get(name).map(Arc::clone);
+        if let Some(api) = loaded {
+            if api.fingerprint == fingerprint {
+                return Ok(api);
+            }
+            tracing::info!(target: "aperture::daemon", api = name, "Spec changed, reloading");
+        }
+
+        let cache_dir = self.manager.config_dir().join(constants::DIR_CACHE);
+        let spec = loader::load_cached_spec(&cache_dir, name).map_err(|e| match e {
+            Error::Io(_) => Error::spec_not_found(name),
+            _ => e,
+        })?;
+        let command = generator::generate_command_tree_for_api_with_flags(&spec, name, false);
+        let api = Arc::new(LoadedApi {
+            spec,
+            command,
+            fingerprint,
+        });
+        self.lock_apis().insert(name.to_string(), Arc::clone(&api));
+        Ok(api)
+    }
+
+    fn lock_apis(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<LoadedApi>>> {
+        self.apis
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn fingerprint(&self, name: &str) -> SpecFingerprint {
+        let config_dir = self.manager.config_dir();
+        SpecFingerprint {
+            cache: file_stamp(
+                &config_dir
+                    .join(constants::DIR_CACHE)
+                    .join(format!("{name}{}", constants::FILE_EXT_BIN)),
+            ),
+            source: file_stamp(
+                &config_dir
+                    .join(constants::DIR_SPECS)
+                    .join(format!("{name}{}", constants::FILE_EXT_YAML)),
+            ),
+        }
+    }
+
+    /// Read for every request, so that URL and secret changes apply at once
+    fn global_config(&self) -> Option<GlobalConfig> {
+        self.manager.load_global_config().ok()
+    }
+}
+
+/// Binds the daemon's socket at `path`.
+///
+/// The socket is readable and writable only by the current user, since
+/// requests run with the user's credentials. A socket left behind by a
+/// daemon that is no longer running is replaced; anything else at `path` is
+/// left alone.
+///
+/// # Errors
+///
+/// Returns an error if `path` exists and is not a socket, another daemon is
+/// listening on it, or the socket cannot be bound.
+pub async fn bind(path: &Path) -> Result<UnixListener, Error> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.file_type().is_socket() => {
+            return Err(Error::invalid_config(format!(
+                "'{}' exists and is not a socket, so the daemon will not replace it",
+                path.display()
+            )));
+        }
+        Ok(_) => {
+            if UnixStream::connect(path).await.is_ok() {
+                return Err(Error::invalid_config(format!(
+                    "A daemon is already listening on '{}'",
+                    path.display()
+                )));
+            }
+            std::fs::remove_file(path)?;
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let listener = UnixListener::bind(path).map_err(|e| {
+        Error::io_error(format!(
+            "Failed to listen on '{}' for the daemon: {e}",
+            path.display()
+        ))
+    })?;
+    crate::oauth::store::restrict_to_owner(path)?;
+    Ok(listener)
+}
+
+/// An execution flag the daemon does not support, with what to use instead
+const fn unsupported_flag(execution: &ExecutionFlags) -> Option<(&'static str, &'static str)> {
+    if execution.describe_json {
+        Some(("--describe-json", "the 'describe' method"))
+    } else if execution.batch_file.is_some() {
+        Some(("--batch-file", "one 'execute' request per operation"))
+    } else if execution.auto_paginate {
+        Some(("--auto-paginate", "one 'execute' request per page"))
+    } else if execution.output_file.is_some() {
+        Some(("--output-file", "the 'body_base64' of the result"))
+    } else {
+        None
+    }
+}
+
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Describes an execution result. JSON bodies are embedded as JSON, other
+/// text as a string and binary bodies base64-encoded.
+fn result_json(result: ExecutionResult, jq_filter: Option<&str>) -> Result<Value, Error> {
+    Ok(match result {
+        ExecutionResult::Success {
+            body,
+            status,
+            headers,
+        } => json!({
+            "status": status,
+            "headers": headers,
+            "body": body_json(&body, jq_filter)?,
+        }),
+        ExecutionResult::Cached { body } => json!({
+            "cached": true,
+            "body": body_json(&body, jq_filter)?,
+        }),
+        ExecutionResult::Binary {
+            body,
+            content_type,
+            status,
+            headers,
+        } => json!({
+            "status": status,
+            "headers": headers,
+            "content_type": content_type,
+            "body_base64": general_purpose::STANDARD.encode(&body),
+        }),
+        ExecutionResult::DryRun { request_info } => json!({ "dry_run": request_info }),
+        ExecutionResult::Empty => json!({ "body": null }),
+    })
+}
+
+fn body_json(body: &str, jq_filter: Option<&str>) -> Result<Value, Error> {
+    let body = match jq_filter {
+        Some(filter) => executor::apply_jq_filter(body, filter)?,
+        None => body.to_string(),
+    };
+    Ok(serde_json::from_str(&body).unwrap_or(Value::String(body)))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn execution_error(error: Error) -> RpcError {
+    let data = serde_json::to_value(error.to_json()).ok();
+    (EXECUTION_ERROR, error.to_string(), data)
+}
@@ -202,12 +202,14 @@ impl DocumentationGenerator {
             usage.push_str(&Self::required_parameter_usage_fragment(param));
         }
 
-        if command
-            .request_body
-            .as_ref()
-            .is_some_and(|body| body.required)
-        {
-            usage.push_str(" --body '{\"key\": \"value\"}'");
+        match &command.request_body {
+            Some(body)
+                if body.required && crate::spec::is_binary_content_type(&body.content_type) =>
+            {
+                usage.push_str(" --body-file ./upload.bin");
+            }
+            Some(body) if body.required => usage.push_str(" --body '{\"key\": \"value\"}'"),
+            _ => {}
         }
 
         usage
@@ -340,20 +342,26 @@ impl DocumentationGenerator {
         command: &CachedCommand,
         required_params: &[&CachedParameter],
     ) {
-        if command.request_body.is_none() {
+        let Some(request_body) = &command.request_body else {
             return;
-        }
+        };
 
         let mut command_line = base_cmd.to_string();
         for param in required_params {
             Self::append_example_parameter(&mut command_line, param, "example");
         }
-        command_line.push_str(" --body '{\"name\": \"example\", \"value\": 42}'");
+        let explanation = if crate::spec::is_binary_content_type(&request_body.content_type) {
+            command_line.push_str(" --body-file ./upload.bin");
+            "Sends a file as the raw request body"
+        } else {
+            command_line.push_str(" --body '{\"name\": \"example\", \"value\": 42}'");
+            "Sends JSON data in the request body"
+        };
 
         examples.push(CommandExample {
             description: "With request body".to_string(),
             command_line,
-            explanation: Some("Sends JSON data in the request body".to_string()),
+            explanation: Some(explanation.to_string()),
         });
     }
 
@@ -462,11 +470,19 @@ impl DocumentationGenerator {
         command: &CachedCommand,
         style: DiscoveryStyle,
     ) {
-        if !command.security_requirements.is_empty() {
+        let alternatives = command.security_alternatives();
+        if alternatives.iter().any(|set| !set.is_empty()) {
             writeln!(help, "## {}\n", style.heading("Authentication")).ok();
-            help.push_str("This operation requires authentication. Available schemes:\n\n");
-            for scheme_name in &command.security_requirements {
-                writeln!(help, "- {scheme_name}").ok();
+            help.push_str(
+                "This operation accepts any one of these credential sets \
+                 (choose with --auth-scheme):\n\n",
+            );
+            for set in &alternatives {
+                if set.is_empty() {
+                    writeln!(help, "- none (anonymous access)").ok();
+                } else {
+                    writeln!(help, "- {}", set.join(" + ")).ok();
+                }
             }
             help.push('\n');
         }
@@ -0,0 +1,832 @@
+//! Request body encoding.
+//!
+//! JSON bodies are sent as given. `application/x-www-form-urlencoded` bodies
+//! are flattened from a JSON object following the spec's `encoding` object.
+//! `multipart/form-data` bodies are assembled from a JSON object of text
+//! fields plus a list of files, and binary bodies send a file or bytes as-is.
+//! Files are streamed from disk when the request is sent, so uploads are never
+//! buffered in memory, and each attempt reopens them so retries resend them.
+
+use crate::cache::models::{CachedEncoding, CachedRequestBody};
+use crate::constants;
+use crate::engine::params;
+use crate::error::Error;
+use crate::invocation::{FilePart, ParamValues, RawBody};
+use bytes::Bytes;
+use http_body::{Frame, SizeHint};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Size of the chunks file parts are read in
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Media types guessed from file extensions; anything else is sent as
+/// `application/octet-stream`
+const EXTENSION_CONTENT_TYPES: &[(&str, &str)] = &[
+    ("csv", constants::CONTENT_TYPE_CSV),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("json", constants::CONTENT_TYPE_JSON),
+    ("md", "text/markdown"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("pdf", constants::CONTENT_TYPE_PDF),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("txt", constants::CONTENT_TYPE_TEXT),
+    ("wav", "audio/wav"),
+    ("webp", "image/webp"),
+    ("xml", constants::CONTENT_TYPE_XML),
+    ("yaml", constants::CONTENT_TYPE_YAML),
+    ("yml", constants::CONTENT_TYPE_YAML),
+    ("zip", "application/zip"),
+];
+
+/// A top-level property of a form request schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+    /// Property name, used as the part name
+    pub name: String,
+    /// Whether the property holds file contents (`format: binary`)
+    pub binary: bool,
+    /// Whether the schema lists the property as required
+    pub required: bool,
+    /// Property description from the schema
+    pub description: Option<String>,
+}
+
+/// Lists the form fields declared by a cached form body.
+///
+/// In `multipart/form-data` bodies, properties typed `string` with
+/// `format: binary`, or arrays of them, are file fields. Returns an empty list
+/// for schemas without properties.
+#[must_use]
+pub fn form_fields(request_body: &CachedRequestBody) -> Vec<FormField> {
+    let accepts_files = request_body.content_type == constants::CONTENT_TYPE_MULTIPART;
+    let Ok(schema) = serde_json::from_str::<Value>(&request_body.schema) else {
+        return Vec::new();
+    };
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    properties
+        .iter()
+        .map(|(name, property)| FormField {
+            name: name.clone(),
+            binary: accepts_files
+                && (is_binary_schema(property)
+                    || property.get("items").is_some_and(is_binary_schema)),
+            required: required.contains(&name.as_str()),
+            description: property
+                .get("description")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        })
+        .collect()
+}
+
+impl FormField {
+    /// Id of the clap arg generated for this field. It is namespaced so it
+    /// cannot clash with parameter ids.
+    #[must_use]
+    pub fn arg_id(&self) -> String {
+        format!("form-field:{}", self.name)
+    }
+}
+
+fn is_binary_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("string")
+        && schema.get("format").and_then(Value::as_str) == Some("binary")
+}
+
+/// A request body ready to be attached to a request
+#[derive(Debug)]
+pub enum RequestBody {
+    /// A JSON document
+    Json(String),
+    /// An encoded `application/x-www-form-urlencoded` body
+    Form(String),
+    /// A `multipart/form-data` form
+    Multipart(MultipartForm),
+    /// Raw binary content such as `application/octet-stream`
+    Binary(BinaryBody),
+}
+
+impl RequestBody {
+    /// Textual form of the body used for request logging and cache keys
+    #[must_use]
+    pub fn as_text(&self) -> &str {
+        match self {
+            Self::Json(text) | Self::Form(text) => text,
+            Self::Multipart(form) => &form.summary,
+            Self::Binary(body) => &body.summary,
+        }
+    }
+
+    /// Value reported by `--dry-run`. Multipart and binary bodies are
+    /// described without their contents.
+    #[must_use]
+    pub fn dry_run_value(&self) -> Value {
+        match self {
+            Self::Json(text) | Self::Form(text) => Value::String(text.clone()),
+            Self::Multipart(form) => form.describe(false),
+            Self::Binary(body) => body.describe(false),
+        }
+    }
+
+    /// Value used to render a runnable equivalent of the request: like
+    /// [`Self::dry_run_value`], but multipart values and file paths are kept.
+    #[must_use]
+    pub fn snippet_value(&self) -> Value {
+        match self {
+            Self::Json(text) | Self::Form(text) => Value::String(text.clone()),
+            Self::Multipart(form) => form.describe(true),
+            Self::Binary(body) => body.describe(true),
+        }
+    }
+
+    /// Attaches the body to a request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an uploaded file cannot be opened.
+    pub fn apply(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, Error> {
+        match self {
+            Self::Json(text) => Ok(match serde_json::from_str::<Value>(text) {
+                Ok(json_body) => request.json(&json_body),
+                Err(_) => request,
+            }),
+            Self::Form(text) => Ok(request.body(text.clone())),
+            Self::Multipart(form) => Ok(request.body(reqwest::Body::wrap(form.open()?))),
+            Self::Binary(body) => Ok(request.body(reqwest::Body::wrap(body.open()?))),
+        }
+    }
+}
+
+/// Encodes a JSON object of form fields as `application/x-www-form-urlencoded`.
+///
+/// Each member is serialized with its `encoding` entry, defaulting to the
+/// exploded `form` style: arrays repeat the key and objects send their own
+/// members as pairs. `deepObject` objects use `field[key]=value`. Values
+/// nested inside arrays or objects are sent as JSON, and `null` members are
+/// omitted.
+///
+/// # Errors
+///
+/// Returns an error if the body is not a JSON object.
+pub fn encode_form<S: std::hash::BuildHasher>(
+    body: &str,
+    encoding: &HashMap<String, CachedEncoding, S>,
+) -> Result<String, Error> {
+    let fields = match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(fields)) => fields,
+        Ok(_) => {
+            return Err(Error::validation_error(
+                "Form-encoded request bodies must be a JSON object of form fields",
+            ))
+        }
+        Err(e) => return Err(Error::invalid_json_body(e.to_string())),
+    };
+
+    let mut pairs = Vec::new();
+    for (name, value) in &fields {
+        let (style, explode) = encoding.get(name).map_or(("form", true), |encoding| {
+            (encoding.style.as_str(), encoding.explode)
+        });
+        push_form_pairs(&mut pairs, name, value, style, explode);
+    }
+
+    Ok(pairs
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "{}={}",
+                urlencoding::encode(name),
+                urlencoding::encode(value)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&"))
+}
+
+fn push_form_pairs(
+    pairs: &mut Vec<(String, String)>,
+    name: &str,
+    value: &Value,
+    style: &str,
+    explode: bool,
+) {
+    let values = match value {
+        Value::Null => return,
+        Value::Array(items) => ParamValues::Array(
+            items
+                .iter()
+                .filter(|item| !item.is_null())
+                .map(form_scalar)
+                .collect(),
+        ),
+        Value::Object(members) => ParamValues::Object(
+            members
+                .iter()
+                .filter(|(_, member)| !member.is_null())
+                .map(|(key, member)| (key.clone(), form_scalar(member)))
+                .collect(),
+        ),
+        value => {
+            pairs.push((name.to_string(), form_scalar(value)));
+            return;
+        }
+    };
+    pairs.extend(params::form_pairs(name, &values, style, explode));
+}
+
+/// Text of a single form value; strings are sent without quotes
+fn form_scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// A `multipart/form-data` body whose file parts are read lazily
+#[derive(Debug)]
+pub struct MultipartForm {
+    boundary: String,
+    text_parts: Vec<TextPart>,
+    file_parts: Vec<FileAttachment>,
+    summary: String,
+}
+
+#[derive(Debug)]
+struct TextPart {
+    name: String,
+    value: String,
+    content_type: Option<&'static str>,
+}
+
+#[derive(Debug)]
+struct FileAttachment {
+    name: String,
+    path: PathBuf,
+    filename: String,
+    content_type: &'static str,
+    size: u64,
+}
+
+impl MultipartForm {
+    /// Builds a form from a JSON object of text fields and a list of files.
+    ///
+    /// String members are sent verbatim, arrays as one part per element and
+    /// other values as JSON. `null` members are omitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the body is not a JSON object or a file cannot be
+    /// read.
+    pub fn new(body: Option<&str>, files: &[FilePart]) -> Result<Self, Error> {
+        let fields = match body.map(serde_json::from_str::<Value>).transpose() {
+            Ok(None) => serde_json::Map::new(),
+            Ok(Some(Value::Object(fields))) => fields,
+            Ok(Some(_)) => {
+                return Err(Error::validation_error(
+                    "Multipart request bodies must be a JSON object of form fields",
+                ))
+            }
+            Err(e) => return Err(Error::invalid_json_body(e.to_string())),
+        };
+
+        let mut text_parts = Vec::new();
+        for (name, value) in fields {
+            match value {
+                Value::Null => {}
+                Value::Array(items) => {
+                    text_parts.extend(items.into_iter().map(|item| TextPart::new(&name, item)));
+                }
+                value => text_parts.push(TextPart::new(&name, value)),
+            }
+        }
+
+        let file_parts = files
+            .iter()
+            .map(FileAttachment::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut form = Self {
+            boundary: random_boundary()?,
+            text_parts,
+            file_parts,
+            summary: String::new(),
+        };
+        form.summary = form.describe(true).to_string();
+        Ok(form)
+    }
+
+    /// Value of the `Content-Type` header for this form
+    #[must_use]
+    pub fn content_type(&self) -> String {
+        format!(
+            "{}; boundary={}",
+            constants::CONTENT_TYPE_MULTIPART,
+            self.boundary
+        )
+    }
+
+    /// Describes the parts, including text values only when asked to
+    fn describe(&self, include_values: bool) -> Value {
+        let text_parts = self.text_parts.iter().map(|part| {
+            let mut description = json!({
+                "name": part.name,
+                "content_type": part.content_type.unwrap_or(constants::CONTENT_TYPE_TEXT),
+                "size": part.value.len(),
+            });
+            if include_values {
+                description["value"] = Value::String(part.value.clone());
+            }
+            description
+        });
+        let file_parts = self.file_parts.iter().map(|part| {
+            let mut description = json!({
+                "name": part.name,
+                "filename": part.filename,
+                "content_type": part.content_type,
+                "size": part.size,
+            });
+            if include_values {
+                description["path"] = Value::String(part.path.display().to_string());
+            }
+            description
+        });
+
+        json!({
+            "content_type": constants::CONTENT_TYPE_MULTIPART,
+            "parts": text_parts.chain(file_parts).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Opens the files and returns a body streaming the encoded form
+    fn open(&self) -> Result<BodyStream, Error> {
+        let mut segments = VecDeque::new();
+
+        for part in &self.text_parts {
+            let mut head = self.part_head(&part.name, None, part.content_type);
+            head.push_str(&part.value);
+            head.push_str("\r\n");
+            segments.push_back(Segment::from(head));
+        }
+
+        for part in &self.file_parts {
+            let head = self.part_head(&part.name, Some(&part.filename), Some(part.content_type));
+            segments.push_back(Segment::from(head));
+
+            segments.push_back(Segment::open_file(&part.path, part.size)?);
+            segments.push_back(Segment::from("\r\n".to_string()));
+        }
+
+        segments.push_back(Segment::from(format!("--{}--\r\n", self.boundary)));
+        Ok(BodyStream::new(segments))
+    }
+
+    fn part_head(&self, name: &str, filename: Option<&str>, content_type: Option<&str>) -> String {
+        let mut head = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+            self.boundary,
+            escape_quoted(name)
+        );
+        if let Some(filename) = filename {
+            let _ = write!(head, "; filename=\"{}\"", escape_quoted(filename));
+        }
+        if let Some(content_type) = content_type {
+            let _ = write!(head, "\r\nContent-Type: {content_type}");
+        }
+        head.push_str("\r\n\r\n");
+        head
+    }
+}
+
+/// A raw binary body read from a file or from memory
+#[derive(Debug)]
+pub struct BinaryBody {
+    source: RawBody,
+    content_type: String,
+    size: u64,
+    summary: String,
+}
+
+impl BinaryBody {
+    /// Prepares a binary body for an operation declaring `declared_content_type`.
+    ///
+    /// Wildcard types such as `image/*` are narrowed from the file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn new(source: RawBody, declared_content_type: &str) -> Result<Self, Error> {
+        let size = match &source {
+            RawBody::File(path) => {
+                let metadata = std::fs::metadata(path).map_err(|e| upload_error(path, &e))?;
+                if !metadata.is_file() {
+                    return Err(Error::validation_error(format!(
+                        "Body file '{}' is not a file",
+                        path.display()
+                    )));
+                }
+                metadata.len()
+            }
+            RawBody::Bytes(bytes) => bytes.len() as u64,
+        };
+        let content_type = match &source {
+            RawBody::File(path) if declared_content_type.contains('*') => {
+                content_type_for(path).to_string()
+            }
+            RawBody::Bytes(_) if declared_content_type.contains('*') => {
+                constants::CONTENT_TYPE_OCTET_STREAM.to_string()
+            }
+            _ => declared_content_type.to_string(),
+        };
+
+        let mut body = Self {
+            source,
+            content_type,
+            size,
+            summary: String::new(),
+        };
+        body.summary = body.describe(true).to_string();
+        Ok(body)
+    }
+
+    /// Value of the `Content-Type` header for this body
+    #[must_use]
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// Describes the body, including the file path only when asked to
+    fn describe(&self, include_path: bool) -> Value {
+        let mut description = json!({
+            "content_type": self.content_type,
+            "size": self.size,
+        });
+        if let RawBody::File(path) = &self.source {
+            if let Some(filename) = path.file_name() {
+                description["filename"] = Value::String(filename.to_string_lossy().into_owned());
+            }
+            if include_path {
+                description["path"] = Value::String(path.display().to_string());
+            }
+        }
+        description
+    }
+
+    fn open(&self) -> Result<BodyStream, Error> {
+        let segment = match &self.source {
+            RawBody::File(path) => Segment::open_file(path, self.size)?,
+            RawBody::Bytes(bytes) => Segment::Bytes(Bytes::from(bytes.clone())),
+        };
+        Ok(BodyStream::new(VecDeque::from([segment])))
+    }
+}
+
+impl TextPart {
+    fn new(name: &str, value: Value) -> Self {
+        match value {
+            Value::String(value) => Self {
+                name: name.to_string(),
+                value,
+                content_type: None,
+            },
+            Value::Object(_) => Self {
+                name: name.to_string(),
+                value: value.to_string(),
+                content_type: Some(constants::CONTENT_TYPE_JSON),
+            },
+            value => Self {
+                name: name.to_string(),
+                value: value.to_string(),
+                content_type: None,
+            },
+        }
+    }
+}
+
+impl FileAttachment {
+    fn new(part: &FilePart) -> Result<Self, Error> {
+        let metadata = std::fs::metadata(&part.path).map_err(|e| upload_error(&part.path, &e))?;
+        if !metadata.is_file() {
+            return Err(Error::validation_error(format!(
+                "Upload path '{}' for field '{}' is not a file",
+                part.path.display(),
+                part.field
+            )));
+        }
+
+        Ok(Self {
+            name: part.field.clone(),
+            path: part.path.clone(),
+            filename: part.path.file_name().map_or_else(
+                || part.field.clone(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            content_type: content_type_for(&part.path),
+            size: metadata.len(),
+        })
+    }
+}
+
+/// Guesses a file's media type from its extension
+fn content_type_for(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    extension
+        .and_then(|ext| {
+            EXTENSION_CONTENT_TYPES
+                .iter()
+                .find(|(known, _)| *known == ext)
+                .map(|(_, content_type)| *content_type)
+        })
+        .unwrap_or(constants::CONTENT_TYPE_OCTET_STREAM)
+}
+
+fn escape_quoted(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(['\r', '\n'], " ")
+}
+
+fn random_boundary() -> Result<String, Error> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| Error::io_error(format!("Failed to generate random bytes: {e}")))?;
+    let mut boundary = String::from("aperture-");
+    for byte in bytes {
+        let _ = write!(boundary, "{byte:02x}");
+    }
+    Ok(boundary)
+}
+
+fn upload_error(path: &Path, error: &std::io::Error) -> Error {
+    Error::io_error(format!(
+        "Failed to read upload file '{}': {error}",
+        path.display()
+    ))
+}
+
+enum Segment {
+    Bytes(Bytes),
+    File {
+        file: tokio::fs::File,
+        remaining: u64,
+    },
+}
+
+impl Segment {
+    fn open_file(path: &Path, size: u64) -> Result<Self, Error> {
+        let file = std::fs::File::open(path).map_err(|e| upload_error(path, &e))?;
+        Ok(Self::File {
+            file: tokio::fs::File::from_std(file),
+            remaining: size,
+        })
+    }
+
+    const fn len(&self) -> u64 {
+        match self {
+            Self::Bytes(bytes) => bytes.len() as u64,
+            Self::File { remaining, .. } => *remaining,
+        }
+    }
+}
+
+impl From<String> for Segment {
+    fn from(text: String) -> Self {
+        Self::Bytes(Bytes::from(text))
+    }
+}
+
+/// Streams a body with an exact length, so `Content-Length` is set
+struct BodyStream {
+    segments: VecDeque<Segment>,
+    remaining: u64,
+    buffer: Box<[u8]>,
+}
+
+impl BodyStream {
+    fn new(segments: VecDeque<Segment>) -> Self {
+        let remaining = segments.iter().map(Segment::len).sum();
+        Self {
+            segments,
+            remaining,
+            buffer: vec![0; FILE_CHUNK_SIZE].into_boxed_slice(),
+        }
+    }
+}
+
+impl http_body::Body for BodyStream {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        loop {
+            let Some(segment) = this.segments.front_mut() else {
+                return Poll::Ready(None);
+            };
+
+            let chunk = match segment {
+                Segment::Bytes(bytes) => {
+                    let chunk = std::mem::take(bytes);
+                    this.segments.pop_front();
+                    chunk
+                }
+                Segment::File { remaining: 0, .. } => {
+                    this.segments.pop_front();
+                    continue;
+                }
+                Segment::File { file, remaining } => {
+                    let limit = usize::try_from(*remaining)
+                        .map_or(this.buffer.len(), |left| left.min(this.buffer.len()));
+                    let mut read_buf = ReadBuf::new(&mut this.buffer[..limit]);
+                    ready!(Pin::new(file).poll_read(cx, &mut read_buf))?;
+                    let filled = read_buf.filled();
+                    if filled.is_empty() {
+                        return Poll::Ready(Some(Err(std::io::Error::new(
+                            std::io::ErrorKind::UnexpectedEof,
+                            "upload file shrank while it was being sent",
+                        ))));
+                    }
+                    *remaining -= filled.len() as u64;
+                    Bytes::copy_from_slice(filled)
+                }
+            };
+
+            this.remaining -= chunk.len() as u64;
+            return Poll::Ready(Some(Ok(Frame::data(chunk))));
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.remaining == 0
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_fields_marks_binary_properties() {
+        let body = CachedRequestBody {
+            content_type: constants::CONTENT_TYPE_MULTIPART.to_string(),
+            schema: json!({
+                "type": "object",
+                "required": ["file"],
+                "properties": {
+                    "file": {"type": "string", "format": "binary"},
+                    "attachments": {"type": "array", "items": {"type": "string", "format": "binary"}},
+                    "title": {"type": "string", "description": "Document title"}
+                }
+            })
+            .to_string(),
+            required: true,
+            description: None,
+            example: None,
+            encoding: HashMap::new(),
+        };
+
+        let fields = form_fields(&body);
+        let by_name = |name: &str| fields.iter().find(|f| f.name == name).unwrap();
+        assert!(by_name("file").binary && by_name("file").required);
+        assert!(by_name("attachments").binary);
+        assert!(!by_name("title").binary && !by_name("title").required);
+        assert_eq!(
+            by_name("title").description.as_deref(),
+            Some("Document title")
+        );
+    }
+
+    #[test]
+    fn test_multipart_form_rejects_non_object_body() {
+        let err = MultipartForm::new(Some("[1, 2]"), &[]).unwrap_err();
+        assert!(err.to_string().contains("JSON object"), "{err}");
+    }
+
+    #[test]
+    fn test_encode_form_honors_encoding() {
+        let encoding = HashMap::from([
+            (
+                "ids".to_string(),
+                CachedEncoding {
+                    style: "form".to_string(),
+                    explode: false,
+                },
+            ),
+            (
+                "scopes".to_string(),
+                CachedEncoding {
+                    style: "spaceDelimited".to_string(),
+                    explode: false,
+                },
+            ),
+            (
+                "filter".to_string(),
+                CachedEncoding {
+                    style: "deepObject".to_string(),
+                    explode: true,
+                },
+            ),
+        ]);
+        let body = json!({
+            "name": "Jane Doe",
+            "tags": ["a", "b"],
+            "ids": [1, 2],
+            "scopes": ["read", "write"],
+            "filter": {"status": "open"},
+            "limit": 10,
+            "skipped": null
+        })
+        .to_string();
+
+        let encoded = encode_form(&body, &encoding).unwrap();
+        let pairs: Vec<&str> = encoded.split('&').collect();
+        assert_eq!(
+            pairs,
+            [
+                "filter%5Bstatus%5D=open",
+                "ids=1%2C2",
+                "limit=10",
+                "name=Jane%20Doe",
+                "scopes=read%20write",
+                "tags=a",
+                "tags=b",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_form_fields_of_urlencoded_body_are_never_files() {
+        let body = CachedRequestBody {
+            content_type: constants::CONTENT_TYPE_FORM.to_string(),
+            schema: json!({
+                "type": "object",
+                "properties": {"data": {"type": "string", "format": "binary"}}
+            })
+            .to_string(),
+            required: true,
+            description: None,
+            example: None,
+            encoding: HashMap::new(),
+        };
+
+        assert!(!form_fields(&body)[0].binary);
+    }
+
+    #[test]
+    fn test_binary_body_narrows_wildcard_content_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("photo.png");
+        std::fs::write(&path, [0x89, 0x50, 0x4e, 0x47]).unwrap();
+
+        let body = BinaryBody::new(RawBody::File(path), "image/*").unwrap();
+        assert_eq!(body.content_type(), "image/png");
+        assert_eq!(body.describe(false)["size"], 4);
+
+        let body = BinaryBody::new(RawBody::Bytes(vec![1, 2]), "image/*").unwrap();
+        assert_eq!(body.content_type(), constants::CONTENT_TYPE_OCTET_STREAM);
+    }
+
+    #[test]
+    fn test_content_type_for_extension() {
+        assert_eq!(content_type_for(Path::new("photo.JPG")), "image/jpeg");
+        assert_eq!(content_type_for(Path::new("report.pdf")), "application/pdf");
+        assert_eq!(
+            content_type_for(Path::new("archive.bin")),
+            constants::CONTENT_TYPE_OCTET_STREAM
+        );
+    }
+}
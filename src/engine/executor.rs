@@ -1,10 +1,18 @@
-use crate::cache::models::{CachedCommand, CachedSecurityScheme, CachedSpec};
-use crate::config::models::{GlobalConfig, ProxyConfig};
+use crate::cache::models::{CachedCommand, CachedParameter, CachedSecurityScheme, CachedSpec};
+use crate::cassette::{Cassette, OperationCassette};
+use crate::config::models::{GlobalConfig, ProxyConfig, ResponseValidation, SecretSource};
+use crate::config::secrets;
 use crate::config::url_resolver::BaseUrlResolver;
 use crate::constants;
-use crate::error::Error;
-use crate::invocation::{ExecutionResult, ProxyOverride};
+use crate::engine::body::{self, BinaryBody, MultipartForm, RequestBody};
+use crate::engine::params;
+use crate::engine::validation;
+use crate::error::{Error, ErrorKind};
+use crate::invocation::{
+    ExecutionResult, FilePart, ParamValues, ProxyOverride, RawBody, ValueSegment,
+};
 use crate::logging;
+use crate::oauth::{ClientCredentials, TokenStore};
 use crate::resilience::{
     calculate_retry_delay_with_header, is_retryable_status, parse_retry_after_value, RetryConfig,
 };
@@ -14,16 +22,22 @@ use crate::response_cache::{
 };
 use crate::utils::to_kebab_case;
 use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::Method;
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::fmt::Write;
 use std::str::FromStr;
 use tokio::time::sleep;
 
 const DEFAULT_USER_AGENT: &str = concat!("aperture/", env!("CARGO_PKG_VERSION"));
 
+/// Placeholder bearer token used for `OAuth2` schemes in dry-run mode, where no
+/// token request is made.
+const OAUTH2_DRY_RUN_TOKEN: &str = "<oauth2-access-token>";
+
 #[cfg(feature = "jq")]
 use jaq_core::{Ctx, RcIter};
 #[cfg(feature = "jq")]
@@ -132,11 +146,29 @@ impl ProxyDiagnostics {
     }
 }
 
-struct ProxyBuildResult {
-    client: reqwest::Client,
+#[derive(Debug, Clone)]
+pub(crate) struct ProxyBuildResult {
+    pub(crate) client: reqwest::Client,
     diagnostics: ProxyDiagnostics,
 }
 
+/// An HTTP client built once and reused by several invocations, so that
+/// they share its connection pool and skip repeated TLS handshakes
+#[derive(Debug, Clone)]
+pub struct SharedHttpClient(ProxyBuildResult);
+
+impl SharedHttpClient {
+    /// Builds a client with the proxy settings of `ctx`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proxy configuration is invalid or the client
+    /// cannot be built.
+    pub fn new(ctx: &crate::invocation::ExecutionContext) -> Result<Self, Error> {
+        build_http_client(ctx).map(Self)
+    }
+}
+
 fn configure_proxy(
     builder: reqwest::ClientBuilder,
     ctx: &crate::invocation::ExecutionContext,
@@ -359,7 +391,9 @@ fn log_proxy_diagnostics(diagnostics: &ProxyDiagnostics) {
 }
 
 /// Build HTTP client with default timeout and resolved proxy behavior.
-fn build_http_client(ctx: &crate::invocation::ExecutionContext) -> Result<ProxyBuildResult, Error> {
+pub(crate) fn build_http_client(
+    ctx: &crate::invocation::ExecutionContext,
+) -> Result<ProxyBuildResult, Error> {
     let (builder, diagnostics) = configure_proxy(reqwest::Client::builder(), ctx)?;
     let client = builder
         .timeout(std::time::Duration::from_secs(30))
@@ -378,17 +412,41 @@ fn build_http_client(ctx: &crate::invocation::ExecutionContext) -> Result<ProxyB
     })
 }
 
-/// Send HTTP request and get response
+/// Send HTTP request and get response, recording or replaying it when a
+/// cassette is in use
 async fn send_request(
     request: reqwest::RequestBuilder,
     secret_ctx: Option<&logging::SecretContext>,
-) -> Result<(reqwest::StatusCode, HashMap<String, String>, String), Error> {
+    cassette: Option<OperationCassette<'_>>,
+) -> Result<(reqwest::StatusCode, HashMap<String, String>, Bytes), Error> {
+    let Some(cassette) = cassette else {
+        return send_request_over_network(request, secret_ctx).await;
+    };
+
+    let (client, request) = request.build_split();
+    let request = request
+        .map_err(|e| Error::network_request_failed(redacted_request_error(&e, secret_ctx)))?;
+    let pending = cassette.prepare(&request, secret_ctx)?;
+    if cassette.is_replay() {
+        return cassette.replay(&pending);
+    }
+
+    let request = reqwest::RequestBuilder::from_parts(client, request);
+    let (status, headers, body) = send_request_over_network(request, secret_ctx).await?;
+    cassette.record(pending, status, &headers, &body, secret_ctx)?;
+    Ok((status, headers, body))
+}
+
+async fn send_request_over_network(
+    request: reqwest::RequestBuilder,
+    secret_ctx: Option<&logging::SecretContext>,
+) -> Result<(reqwest::StatusCode, HashMap<String, String>, Bytes), Error> {
     let start_time = std::time::Instant::now();
 
     let response = request
         .send()
         .await
-        .map_err(|e| Error::network_request_failed(e.to_string()))?;
+        .map_err(|e| Error::network_request_failed(redacted_request_error(&e, secret_ctx)))?;
 
     let status = response.status();
     let duration_ms = start_time.elapsed().as_millis();
@@ -405,8 +463,8 @@ async fn send_request(
         .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap_or("").to_string()))
         .collect();
 
-    let response_text = response
-        .text()
+    let response_body = response
+        .bytes()
         .await
         .map_err(|e| Error::response_read_error(e.to_string()))?;
 
@@ -415,12 +473,27 @@ async fn send_request(
         status.as_u16(),
         duration_ms,
         Some(&response_headers_map),
-        Some(&response_text),
+        Some(std::str::from_utf8(&response_body).unwrap_or("<binary response body>")),
         logging::get_max_body_len(),
         secret_ctx,
     );
 
-    Ok((status, response_headers, response_text))
+    Ok((status, response_headers, response_body))
+}
+
+/// Formats a request error with secrets scrubbed from the URL it mentions
+fn redacted_request_error(
+    error: &reqwest::Error,
+    secret_ctx: Option<&logging::SecretContext>,
+) -> String {
+    let message = error.to_string();
+    error.url().map_or_else(
+        || message.clone(),
+        |url| {
+            let url = url.to_string();
+            message.replace(&url, &logging::redact_url_query_params(&url, secret_ctx))
+        },
+    )
 }
 
 /// Send HTTP request with retry logic
@@ -431,23 +504,24 @@ async fn send_request_with_retry(
     method: Method,
     url: &str,
     headers: HeaderMap,
-    body: Option<String>,
+    body: Option<&RequestBody>,
     retry_context: Option<&RetryContext>,
     operation: &CachedCommand,
     secret_ctx: Option<&logging::SecretContext>,
-) -> Result<(reqwest::StatusCode, HashMap<String, String>, String), Error> {
+    cassette: Option<OperationCassette<'_>>,
+) -> Result<(reqwest::StatusCode, HashMap<String, String>, Bytes), Error> {
     use crate::resilience::RetryConfig;
 
     logging::log_request(
         method.as_str(),
         url,
         Some(&headers),
-        body.as_deref(),
+        body.map(RequestBody::as_text),
         secret_ctx,
     );
 
     let Some(ctx) = retry_context.filter(|ctx| ctx.is_enabled()) else {
-        return send_request_once(client, method, url, headers, body, secret_ctx).await;
+        return send_request_once(client, method, url, headers, body, secret_ctx, cassette).await;
     };
 
     if !ctx.is_safe_to_retry() {
@@ -457,7 +531,16 @@ async fn send_request_with_retry(
             "Retries disabled - method is not idempotent and no idempotency key provided. \
              Use --force-retry or provide --idempotency-key"
         );
-        return send_request_once(client, method.clone(), url, headers, body, secret_ctx).await;
+        return send_request_once(
+            client,
+            method.clone(),
+            url,
+            headers,
+            body,
+            secret_ctx,
+            cassette,
+        )
+        .await;
     }
 
     let retry_config = RetryConfig {
@@ -478,6 +561,7 @@ async fn send_request_with_retry(
         &retry_config,
         operation,
         secret_ctx,
+        cassette,
     )
     .await
 }
@@ -488,25 +572,26 @@ async fn retry_request_with_backoff(
     method: Method,
     url: &str,
     headers: HeaderMap,
-    body: Option<String>,
+    body: Option<&RequestBody>,
     ctx: &RetryContext,
     retry_config: &crate::resilience::RetryConfig,
     operation: &CachedCommand,
     secret_ctx: Option<&logging::SecretContext>,
-) -> Result<(reqwest::StatusCode, HashMap<String, String>, String), Error> {
+    cassette: Option<OperationCassette<'_>>,
+) -> Result<(reqwest::StatusCode, HashMap<String, String>, Bytes), Error> {
     let max_attempts = ctx.max_attempts;
     let mut attempt: u32 = 0;
     let mut last_error: Option<Error> = None;
     let mut last_status: Option<reqwest::StatusCode> = None;
     let mut last_response_headers: Option<HashMap<String, String>> = None;
-    let mut last_response_text: Option<String> = None;
+    let mut last_response_body: Option<Bytes> = None;
 
     while attempt < max_attempts {
         attempt += 1;
 
-        let request = build_request(client, method.clone(), url, headers.clone(), body.clone());
-        match send_request(request, secret_ctx).await {
-            Ok((status, response_headers, response_text)) => {
+        let request = build_request(client, method.clone(), url, headers.clone(), body)?;
+        match send_request(request, secret_ctx, cassette).await {
+            Ok((status, response_headers, response_body)) => {
                 match handle_retryable_http_response(
                     retry_config,
                     attempt,
@@ -515,7 +600,7 @@ async fn retry_request_with_backoff(
                     operation,
                     status,
                     response_headers,
-                    response_text,
+                    response_body,
                 )
                 .await
                 {
@@ -523,11 +608,11 @@ async fn retry_request_with_backoff(
                     RetryableHttpResponse::Retry {
                         status,
                         response_headers,
-                        response_text,
+                        response_body,
                     } => {
                         last_status = Some(status);
                         last_response_headers = Some(response_headers);
-                        last_response_text = Some(response_text);
+                        last_response_body = Some(response_body);
                     }
                 }
             }
@@ -554,7 +639,7 @@ async fn retry_request_with_backoff(
         attempt,
         last_status,
         last_response_headers,
-        last_response_text,
+        last_response_body,
         last_error,
         ctx,
         &method,
@@ -568,14 +653,14 @@ fn finish_retry_result(
     attempt: u32,
     last_status: Option<reqwest::StatusCode>,
     last_response_headers: Option<HashMap<String, String>>,
-    last_response_text: Option<String>,
+    last_response_body: Option<Bytes>,
     last_error: Option<Error>,
     ctx: &RetryContext,
     method: &Method,
     operation: &CachedCommand,
-) -> Result<(reqwest::StatusCode, HashMap<String, String>, String), Error> {
+) -> Result<(reqwest::StatusCode, HashMap<String, String>, Bytes), Error> {
     if let (Some(status), Some(headers), Some(text)) =
-        (last_status, last_response_headers, last_response_text)
+        (last_status, last_response_headers, last_response_body)
     {
         tracing::warn!(
             method = %method,
@@ -616,11 +701,11 @@ fn finish_retry_result(
 }
 
 enum RetryableHttpResponse {
-    Return((reqwest::StatusCode, HashMap<String, String>, String)),
+    Return((reqwest::StatusCode, HashMap<String, String>, Bytes)),
     Retry {
         status: reqwest::StatusCode,
         response_headers: HashMap<String, String>,
-        response_text: String,
+        response_body: Bytes,
     },
 }
 
@@ -638,14 +723,14 @@ async fn handle_retryable_http_response(
     operation: &CachedCommand,
     status: reqwest::StatusCode,
     response_headers: HashMap<String, String>,
-    response_text: String,
+    response_body: Bytes,
 ) -> RetryableHttpResponse {
     if status.is_success() {
-        return RetryableHttpResponse::Return((status, response_headers, response_text));
+        return RetryableHttpResponse::Return((status, response_headers, response_body));
     }
 
     if !is_retryable_status(status.as_u16()) {
-        return RetryableHttpResponse::Return((status, response_headers, response_text));
+        return RetryableHttpResponse::Return((status, response_headers, response_body));
     }
 
     let retry_after = response_headers
@@ -670,7 +755,7 @@ async fn handle_retryable_http_response(
     RetryableHttpResponse::Retry {
         status,
         response_headers,
-        response_text,
+        response_body,
     }
 }
 
@@ -710,13 +795,13 @@ fn build_request(
     method: Method,
     url: &str,
     headers: HeaderMap,
-    body: Option<String>,
-) -> reqwest::RequestBuilder {
-    let mut request = client.request(method, url).headers(headers);
-    if let Some(json_body) = body.and_then(|s| serde_json::from_str::<Value>(&s).ok()) {
-        request = request.json(&json_body);
+    body: Option<&RequestBody>,
+) -> Result<reqwest::RequestBuilder, Error> {
+    let request = client.request(method, url).headers(headers);
+    match body {
+        Some(body) => body.apply(request),
+        None => Ok(request),
     }
-    request
 }
 
 async fn send_request_once(
@@ -724,11 +809,12 @@ async fn send_request_once(
     method: Method,
     url: &str,
     headers: HeaderMap,
-    body: Option<String>,
+    body: Option<&RequestBody>,
     secret_ctx: Option<&logging::SecretContext>,
-) -> Result<(reqwest::StatusCode, HashMap<String, String>, String), Error> {
-    let request = build_request(client, method, url, headers, body);
-    send_request(request, secret_ctx).await
+    cassette: Option<OperationCassette<'_>>,
+) -> Result<(reqwest::StatusCode, HashMap<String, String>, Bytes), Error> {
+    let request = build_request(client, method, url, headers, body)?;
+    send_request(request, secret_ctx, cassette).await
 }
 
 /// Handle HTTP error responses
@@ -892,8 +978,14 @@ fn validate_header_value(name: &str, value: &str) -> Result<(), Error> {
     Ok(())
 }
 
-/// Parses a custom header string in the format "Name: Value" or "Name:Value"
-fn parse_custom_header(header_str: &str) -> Result<(String, String), Error> {
+/// Parses a custom header string in the format "Name: Value" or "Name:Value".
+///
+/// A `${VAR}` value is read from the environment, or kept as a reference to
+/// the variable when describing a request with `references`.
+fn parse_custom_header(
+    header_str: &str,
+    references: Option<&ReferenceTag>,
+) -> Result<(String, String), Error> {
     // Find the colon separator
     let colon_pos = header_str
         .find(':')
@@ -907,12 +999,11 @@ fn parse_custom_header(header_str: &str) -> Result<(String, String), Error> {
     }
 
     // Support environment variable expansion in header values
-    let expanded_value = if value.starts_with("${") && value.ends_with('}') {
-        // Extract environment variable name
-        let var_name = &value[2..value.len() - 1];
-        std::env::var(var_name).unwrap_or_else(|_| value.to_string())
-    } else {
-        value.to_string()
+    let variable = value.strip_prefix("${").and_then(|v| v.strip_suffix('}'));
+    let expanded_value = match (variable, references) {
+        (Some(var_name), Some(tag)) if ReferenceTag::is_variable(var_name) => tag.mark(var_name),
+        (Some(var_name), None) => std::env::var(var_name).unwrap_or_else(|_| value.to_string()),
+        _ => value.to_string(),
     };
 
     // Validate the header value
@@ -921,13 +1012,17 @@ fn parse_custom_header(header_str: &str) -> Result<(String, String), Error> {
     Ok((name.to_string(), expanded_value))
 }
 
-struct ResolvedAuthenticationSecret {
-    value: String,
-    env_var_name: String,
+#[derive(Clone)]
+pub(crate) struct ResolvedAuthenticationSecret {
+    pub(crate) value: String,
+    /// Human-readable origin of the value, used in error messages
+    pub(crate) description: String,
     source: &'static str,
+    /// Whether `value` is a marked reference rather than the secret itself
+    reference: bool,
 }
 
-fn resolve_authentication_secret(
+pub(crate) fn resolve_authentication_secret(
     security_scheme: &CachedSecurityScheme,
     api_name: &str,
     global_config: Option<&GlobalConfig>,
@@ -937,36 +1032,163 @@ fn resolve_authentication_secret(
         .and_then(|api_config| api_config.secrets.get(&security_scheme.name));
 
     if let Some(secret) = configured_secret {
-        return resolve_secret_from_env(&security_scheme.name, &secret.name, "config").map(Some);
+        let value = secrets::resolve(secret.source, &secret.name, api_name, &security_scheme.name)?;
+        return Ok(Some(ResolvedAuthenticationSecret {
+            value,
+            description: secret.source.describe(&secret.name),
+            source: "config",
+            reference: false,
+        }));
     }
 
     let Some(aperture_secret) = &security_scheme.aperture_secret else {
         return Ok(None);
     };
 
-    resolve_secret_from_env(
-        &security_scheme.name,
+    let value = secrets::resolve_named_source(
+        &aperture_secret.source,
         &aperture_secret.name,
-        "x-aperture-secret",
-    )
-    .map(Some)
+        api_name,
+        &security_scheme.name,
+    )?;
+    let description = aperture_secret.source.parse::<SecretSource>().map_or_else(
+        |_| format!("'{}'", aperture_secret.name),
+        |source| source.describe(&aperture_secret.name),
+    );
+    Ok(Some(ResolvedAuthenticationSecret {
+        value,
+        description,
+        source: "x-aperture-secret",
+        reference: false,
+    }))
 }
 
-fn resolve_secret_from_env(
-    scheme_name: &str,
-    env_var_name: &str,
-    source: &'static str,
-) -> Result<ResolvedAuthenticationSecret, Error> {
-    let value = std::env::var(env_var_name)
-        .map_err(|_| Error::secret_not_set(scheme_name, env_var_name))?;
+/// Marks references to environment variables in a request built with
+/// `secret_references`.
+///
+/// A reference is written as `<tag>-NAME-<tag>` with a random tag, so a value
+/// the user supplied, such as a literal `${NAME}` in a query parameter, is
+/// never taken for one. The marker only uses characters that percent-encoding
+/// leaves alone, so it survives in query strings and cookies.
+struct ReferenceTag(String);
+
+impl ReferenceTag {
+    fn new() -> Result<Self, Error> {
+        let mut bytes = [0u8; 16];
+        getrandom::fill(&mut bytes)
+            .map_err(|e| Error::io_error(format!("Failed to generate random bytes: {e}")))?;
+        let mut tag = String::from("aperture");
+        for byte in bytes {
+            let _ = write!(tag, "{byte:02x}");
+        }
+        Ok(Self(tag))
+    }
 
-    Ok(ResolvedAuthenticationSecret {
-        value,
-        env_var_name: env_var_name.to_string(),
-        source,
+    fn is_variable(name: &str) -> bool {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// The marker standing for the value of `variable`
+    fn mark(&self, variable: &str) -> String {
+        format!("{tag}-{variable}-{tag}", tag = self.0)
+    }
+
+    /// Splits `text` into literal runs and the variables marked in it
+    fn segments(&self, text: &str) -> Vec<ValueSegment> {
+        let open = format!("{}-", self.0);
+        let close = format!("-{}", self.0);
+        let mut segments = Vec::new();
+        let mut rest = text;
+        while let Some(start) = rest.find(&open) {
+            let after = &rest[start + open.len()..];
+            let Some(end) = after.find(&close) else {
+                break;
+            };
+            if start > 0 {
+                segments.push(ValueSegment::Text(rest[..start].to_string()));
+            }
+            segments.push(ValueSegment::Env(after[..end].to_string()));
+            rest = &after[end + close.len()..];
+        }
+        if !rest.is_empty() {
+            segments.push(ValueSegment::Text(rest.to_string()));
+        }
+        segments
+    }
+}
+
+/// Builds a reference to the environment variable expected to hold a
+/// scheme's secret, without reading the secret.
+///
+/// Secrets read from the environment or a dotenv file keep their variable
+/// name; others are named after the API and scheme, e.g. `GITHUB_BEARERAUTH`.
+/// `OAuth2` schemes refer to an access token, e.g. `GITHUB_OAUTH_ACCESS_TOKEN`.
+fn reference_authentication_secret(
+    security_scheme: &CachedSecurityScheme,
+    api_name: &str,
+    global_config: Option<&GlobalConfig>,
+    tag: &ReferenceTag,
+) -> Option<ResolvedAuthenticationSecret> {
+    let configured_secret = global_config
+        .and_then(|config| config.api_configs.get(api_name))
+        .and_then(|api_config| api_config.secrets.get(&security_scheme.name))
+        .map(|secret| (Some(secret.source), secret.name.as_str()));
+    let (source, name) = configured_secret.or_else(|| {
+        security_scheme.aperture_secret.as_ref().map(|secret| {
+            (
+                secret.source.parse::<SecretSource>().ok(),
+                secret.name.as_str(),
+            )
+        })
+    })?;
+
+    let derived_name = || {
+        format!("{api_name}_{}", security_scheme.name)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>()
+    };
+    let variable = match (security_scheme.scheme_type.as_str(), source) {
+        (constants::AUTH_SCHEME_OAUTH2 | constants::AUTH_SCHEME_OPENID, _) => {
+            format!("{}_ACCESS_TOKEN", derived_name())
+        }
+        (_, Some(SecretSource::Env | SecretSource::Dotenv)) => name.to_string(),
+        _ => derived_name(),
+    };
+
+    Some(ResolvedAuthenticationSecret {
+        value: tag.mark(&variable),
+        description: SecretSource::Env.describe(&variable),
+        source: "reference",
+        reference: true,
     })
 }
 
+/// Resolves a scheme's secret, or only a reference to it when `references`
+/// is set
+fn scheme_secret(
+    security_scheme: &CachedSecurityScheme,
+    api_name: &str,
+    global_config: Option<&GlobalConfig>,
+    references: Option<&ReferenceTag>,
+) -> Result<Option<ResolvedAuthenticationSecret>, Error> {
+    if let Some(tag) = references {
+        return Ok(reference_authentication_secret(
+            security_scheme,
+            api_name,
+            global_config,
+            tag,
+        ));
+    }
+    resolve_authentication_secret(security_scheme, api_name, global_config)
+}
+
 fn insert_api_key_header(
     headers: &mut HeaderMap,
     security_scheme: &CachedSecurityScheme,
@@ -978,17 +1200,50 @@ fn insert_api_key_header(
         return Ok(());
     };
 
-    if location == "header" {
-        let header_name = HeaderName::from_str(param_name)
-            .map_err(|e| Error::invalid_header_name(param_name, e.to_string()))?;
-        let header_value = HeaderValue::from_str(secret_value)
-            .map_err(|e| Error::invalid_header_value(param_name, e.to_string()))?;
-        headers.insert(header_name, header_value);
+    match location.as_str() {
+        constants::PARAM_LOCATION_HEADER => {
+            let header_name = HeaderName::from_str(param_name)
+                .map_err(|e| Error::invalid_header_name(param_name, e.to_string()))?;
+            let header_value = HeaderValue::from_str(secret_value)
+                .map_err(|e| Error::invalid_header_value(param_name, e.to_string()))?;
+            headers.insert(header_name, header_value);
+        }
+        constants::PARAM_LOCATION_COOKIE => append_cookie(headers, param_name, secret_value)?,
+        _ => {}
     }
 
     Ok(())
 }
 
+/// Appends a `name=value` pair to the `Cookie` header.
+///
+/// The value is percent-encoded so characters such as `;`, `,` and spaces
+/// cannot split or corrupt the header.
+fn append_cookie(headers: &mut HeaderMap, name: &str, value: &str) -> Result<(), Error> {
+    let is_token = !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&b));
+    if !is_token {
+        return Err(Error::validation_error(format!(
+            "Invalid cookie name '{name}': names must be HTTP tokens"
+        )));
+    }
+
+    let pair = format!("{name}={}", urlencoding::encode(value));
+    let cookie = match headers
+        .get(reqwest::header::COOKIE)
+        .and_then(|existing| existing.to_str().ok())
+    {
+        Some(existing) => format!("{existing}; {pair}"),
+        None => pair,
+    };
+    let cookie = HeaderValue::from_str(&cookie)
+        .map_err(|e| Error::invalid_header_value(constants::HEADER_COOKIE, e.to_string()))?;
+    headers.insert(reqwest::header::COOKIE, cookie);
+    Ok(())
+}
+
 fn build_http_authorization_value(scheme_str: &str, secret_value: &str) -> String {
     let auth_scheme: AuthScheme = AuthScheme::from(scheme_str);
     match &auth_scheme {
@@ -1006,13 +1261,20 @@ fn build_http_authorization_value(scheme_str: &str, secret_value: &str) -> Strin
 fn insert_http_authorization_header(
     headers: &mut HeaderMap,
     security_scheme: &CachedSecurityScheme,
-    secret_value: &str,
+    secret: &ResolvedAuthenticationSecret,
 ) -> Result<(), Error> {
     let Some(scheme_str) = &security_scheme.scheme else {
         return Ok(());
     };
 
-    let auth_value = build_http_authorization_value(scheme_str, secret_value);
+    // A reference to `user:password` cannot be encoded; it is left for the
+    // snippet to encode when it runs
+    let auth_value =
+        if secret.reference && AuthScheme::from(scheme_str.as_str()) == AuthScheme::Basic {
+            format!("Basic {}", secret.value)
+        } else {
+            build_http_authorization_value(scheme_str, &secret.value)
+        };
     let header_value = HeaderValue::from_str(&auth_value)
         .map_err(|e| Error::invalid_header_value(constants::HEADER_AUTHORIZATION, e.to_string()))?;
     headers.insert(constants::HEADER_AUTHORIZATION, header_value);
@@ -1021,29 +1283,75 @@ fn insert_http_authorization_header(
     Ok(())
 }
 
-/// Adds an authentication header based on a security scheme
-fn add_authentication_header(
+/// Inputs needed to obtain `OAuth2` access tokens while building headers
+struct OAuth2Runtime<'a> {
+    client: &'a reqwest::Client,
+    token_store: Option<&'a TokenStore>,
+    dry_run: bool,
+}
+
+async fn insert_oauth2_authorization_header(
     headers: &mut HeaderMap,
     security_scheme: &CachedSecurityScheme,
     api_name: &str,
-    global_config: Option<&GlobalConfig>,
+    resolved_secret: &ResolvedAuthenticationSecret,
+    oauth: &OAuth2Runtime<'_>,
+) -> Result<(), Error> {
+    if resolved_secret.reference {
+        let header_value = HeaderValue::from_str(&format!("Bearer {}", resolved_secret.value))
+            .map_err(|e| {
+                Error::invalid_header_value(constants::HEADER_AUTHORIZATION, e.to_string())
+            })?;
+        headers.insert(constants::HEADER_AUTHORIZATION, header_value);
+        return Ok(());
+    }
+
+    let credentials = ClientCredentials::for_scheme(
+        security_scheme,
+        &resolved_secret.description,
+        &resolved_secret.value,
+    )?;
+
+    let access_token = if oauth.dry_run {
+        OAUTH2_DRY_RUN_TOKEN.to_string()
+    } else {
+        crate::oauth::access_token(
+            oauth.client,
+            oauth.token_store,
+            api_name,
+            security_scheme,
+            &credentials,
+        )
+        .await?
+    };
+
+    let header_value = HeaderValue::from_str(&format!("Bearer {access_token}"))
+        .map_err(|e| Error::invalid_header_value(constants::HEADER_AUTHORIZATION, e.to_string()))?;
+    headers.insert(constants::HEADER_AUTHORIZATION, header_value);
+
+    tracing::debug!(scheme_name = %security_scheme.name, "Added OAuth2 authentication header");
+    Ok(())
+}
+
+/// Adds an authentication header based on a security scheme
+async fn add_authentication_header(
+    headers: &mut HeaderMap,
+    credential: &SelectedCredential<'_>,
+    api_name: &str,
+    oauth: &OAuth2Runtime<'_>,
 ) -> Result<(), Error> {
+    let security_scheme = credential.scheme;
+    let resolved_secret = &credential.secret;
     tracing::debug!(
         scheme_name = %security_scheme.name,
         scheme_type = %security_scheme.scheme_type,
         "Adding authentication header"
     );
 
-    let Some(resolved_secret) =
-        resolve_authentication_secret(security_scheme, api_name, global_config)?
-    else {
-        return Ok(());
-    };
-
     tracing::debug!(
         source = resolved_secret.source,
         scheme_name = %security_scheme.name,
-        env_var = %resolved_secret.env_var_name,
+        secret = %resolved_secret.description,
         "Resolved secret"
     );
 
@@ -1054,7 +1362,18 @@ fn add_authentication_header(
             insert_api_key_header(headers, security_scheme, &resolved_secret.value)?;
         }
         "http" => {
-            insert_http_authorization_header(headers, security_scheme, &resolved_secret.value)?;
+            insert_http_authorization_header(headers, security_scheme, resolved_secret)?;
+        }
+        // OpenID Connect schemes carry the OAuth2 endpoints found during discovery
+        constants::AUTH_SCHEME_OAUTH2 | constants::AUTH_SCHEME_OPENID => {
+            insert_oauth2_authorization_header(
+                headers,
+                security_scheme,
+                api_name,
+                resolved_secret,
+                oauth,
+            )
+            .await?;
         }
         _ => {
             return Err(Error::unsupported_security_scheme(
@@ -1110,7 +1429,7 @@ fn build_dry_run_result(
     method: &Method,
     url: &str,
     headers: &HeaderMap,
-    body: Option<&str>,
+    body: Option<&RequestBody>,
     operation_id: &str,
     proxy: &ProxyDiagnostics,
 ) -> Option<ExecutionResult> {
@@ -1135,7 +1454,7 @@ fn build_dry_run_result(
         "method": method.to_string(),
         "url": url,
         "headers": headers_map,
-        "body": body,
+        "body": body.map(RequestBody::dry_run_value),
         "operation_id": operation_id,
         "proxy": proxy.to_json()
     });
@@ -1147,7 +1466,7 @@ fn build_dry_run_result(
 async fn finalize_execution_result(
     status: reqwest::StatusCode,
     response_headers: HashMap<String, String>,
-    response_text: String,
+    response_body: Bytes,
     spec: &CachedSpec,
     operation: &CachedCommand,
     method: Method,
@@ -1156,11 +1475,48 @@ async fn finalize_execution_result(
     body: Option<&str>,
     cache_context: Option<(CacheKey, ResponseCache)>,
     cache_config: Option<&CacheConfig>,
+    response_validation: ResponseValidation,
 ) -> Result<ExecutionResult, Error> {
+    let content_type = response_headers
+        .get(constants::HEADER_CONTENT_TYPE_LC)
+        .cloned();
+    let violations = check_response_contract(
+        response_validation,
+        operation,
+        status,
+        content_type.as_deref(),
+        &response_body,
+    );
+
     if !status.is_success() {
-        return Err(handle_http_error(status, response_text, spec, operation));
+        let response_text = String::from_utf8_lossy(&response_body).into_owned();
+        return Err(handle_http_error(status, response_text, spec, operation)
+            .with_contract_violations(&violations));
+    }
+    if !violations.is_empty() {
+        return Err(Error::response_contract_violation(
+            &operation.operation_id,
+            status.as_u16(),
+            &violations,
+        ));
     }
 
+    // Binary responses are returned as bytes and never cached, since the
+    // response cache stores text
+    let is_binary = content_type
+        .as_deref()
+        .is_some_and(crate::spec::is_binary_content_type)
+        || std::str::from_utf8(&response_body).is_err();
+    if is_binary {
+        return Ok(ExecutionResult::Binary {
+            body: response_body.to_vec(),
+            content_type,
+            status: status.as_u16(),
+            headers: response_headers,
+        });
+    }
+    let response_text = String::from_utf8_lossy(&response_body).into_owned();
+
     store_in_cache(
         cache_context,
         &response_text,
@@ -1185,6 +1541,46 @@ async fn finalize_execution_result(
     }
 }
 
+/// Checks a response, successful or not, against the operation's documented
+/// responses. Violations are logged in warn mode and returned in error mode,
+/// for the caller to fail with.
+fn check_response_contract(
+    mode: ResponseValidation,
+    operation: &CachedCommand,
+    status: reqwest::StatusCode,
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Vec<validation::SchemaViolation> {
+    if mode == ResponseValidation::Off {
+        return Vec::new();
+    }
+    let violations = validation::validate_response(operation, status.as_u16(), content_type, body);
+    if mode == ResponseValidation::Error {
+        return violations;
+    }
+    for violation in &violations {
+        tracing::warn!(
+            operation_id = %operation.operation_id,
+            status = status.as_u16(),
+            path = %violation.path,
+            "Response does not match the API spec: {}",
+            violation.message
+        );
+    }
+    Vec::new()
+}
+
+/// Returns the response validation mode configured for the API
+fn configured_response_validation(
+    spec: &CachedSpec,
+    global_config: Option<&GlobalConfig>,
+) -> ResponseValidation {
+    global_config
+        .and_then(|config| config.api_configs.get(&spec.name))
+        .and_then(|api_config| api_config.response_validation)
+        .unwrap_or_default()
+}
+
 /// Executes an API operation using CLI-agnostic domain types.
 ///
 /// This is the primary entry point for the execution engine. It accepts
@@ -1202,7 +1598,7 @@ struct PreExecutionInput<'a> {
     method: &'a Method,
     url: &'a str,
     headers: &'a HeaderMap,
-    body: Option<&'a str>,
+    body: Option<&'a RequestBody>,
     operation_id: &'a str,
     proxy: &'a ProxyDiagnostics,
 }
@@ -1237,15 +1633,23 @@ pub async fn execute(
     call: crate::invocation::OperationCall,
     ctx: crate::invocation::ExecutionContext,
 ) -> Result<crate::invocation::ExecutionResult, Error> {
-    let prepared = prepare_execution(spec, call, &ctx)?;
+    let references = ctx.secret_references.then(ReferenceTag::new).transpose()?;
+    let prepared = prepare_execution(spec, call, &ctx, references.as_ref()).await?;
+    if let Some(tag) = &references {
+        return Ok(ExecutionResult::DryRun {
+            request_info: describe_request_with_references(&prepared, tag),
+        });
+    }
+    // Dry-run output and cached request info never show query secrets
+    let redacted_url = logging::redact_url_query_params(&prepared.url, Some(&prepared.secret_ctx));
 
     if let Some(result) = resolve_pre_execution_result(PreExecutionInput {
         cache_context: prepared.cache_context.as_ref(),
         dry_run: ctx.dry_run,
         method: &prepared.method,
-        url: &prepared.url,
+        url: &redacted_url,
         headers: &prepared.headers_clone,
-        body: prepared.body.as_deref(),
+        body: prepared.body.as_ref(),
         operation_id: &prepared.operation.operation_id,
         proxy: &prepared.proxy_diagnostics,
     })
@@ -1254,34 +1658,60 @@ pub async fn execute(
         return Ok(result);
     }
 
-    let (status, response_headers, response_text) = send_request_with_retry(
+    let (status, response_headers, response_body) = send_request_with_retry(
         &prepared.client,
         prepared.method.clone(),
         &prepared.url,
         prepared.headers,
-        prepared.body.clone(),
+        prepared.body.as_ref(),
         prepared.retry_ctx.as_ref(),
         prepared.operation,
         Some(&prepared.secret_ctx),
+        ctx.cassette
+            .as_ref()
+            .map(|cassette| cassette.for_operation(&spec.name, prepared.operation)),
     )
     .await?;
 
     finalize_execution_result(
         status,
         response_headers,
-        response_text,
+        response_body,
         spec,
         prepared.operation,
         prepared.method,
-        prepared.url,
+        redacted_url,
         &prepared.headers_clone,
-        prepared.body.as_deref(),
+        prepared.body.as_ref().map(RequestBody::as_text),
         prepared.cache_context,
         prepared.cache_config,
+        ctx.response_validation
+            .unwrap_or_else(|| configured_response_validation(spec, ctx.global_config.as_ref())),
     )
     .await
 }
 
+/// Describes a request whose secrets are references. Nothing needs
+/// redacting, so headers, the URL and form values are shown in full.
+fn describe_request_with_references(prepared: &PreparedExecution<'_>, tag: &ReferenceTag) -> Value {
+    let headers: serde_json::Map<String, Value> = prepared
+        .headers_clone
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>");
+            (name.to_string(), serde_json::json!(tag.segments(value)))
+        })
+        .collect();
+
+    serde_json::json!({
+        "method": prepared.method.to_string(),
+        "url": tag.segments(&prepared.url),
+        "headers": headers,
+        "body": prepared.body.as_ref().map(RequestBody::snippet_value),
+        "operation_id": prepared.operation.operation_id,
+    })
+}
+
 struct PreparedExecution<'a> {
     operation: &'a CachedCommand,
     method: Method,
@@ -1293,7 +1723,7 @@ struct PreparedExecution<'a> {
     cache_context: Option<(CacheKey, ResponseCache)>,
     retry_ctx: Option<RetryContext>,
     secret_ctx: logging::SecretContext,
-    body: Option<String>,
+    body: Option<RequestBody>,
     cache_config: Option<&'a CacheConfig>,
 }
 
@@ -1305,7 +1735,9 @@ struct PreparedRequest<'a> {
     proxy_diagnostics: ProxyDiagnostics,
     headers: HeaderMap,
     headers_clone: HeaderMap,
-    body: Option<String>,
+    body: Option<RequestBody>,
+    /// Whether the selected credentials include an `apiKey` in the query
+    query_authenticated: bool,
 }
 
 struct PreparedRuntimeContext<'a> {
@@ -1315,21 +1747,14 @@ struct PreparedRuntimeContext<'a> {
     cache_config: Option<&'a CacheConfig>,
 }
 
-fn prepare_execution<'a>(
+async fn prepare_execution<'a>(
     spec: &'a CachedSpec,
     call: crate::invocation::OperationCall,
     ctx: &'a crate::invocation::ExecutionContext,
+    references: Option<&ReferenceTag>,
 ) -> Result<PreparedExecution<'a>, Error> {
-    let request = prepare_request(spec, call, ctx)?;
-    let runtime = prepare_runtime_context(
-        spec,
-        request.operation,
-        &request.method,
-        &request.url,
-        &request.headers_clone,
-        request.body.as_deref(),
-        ctx,
-    )?;
+    let request = prepare_request(spec, call, ctx, references).await?;
+    let runtime = prepare_runtime_context(spec, &request, ctx)?;
 
     Ok(PreparedExecution {
         operation: request.operation,
@@ -1347,33 +1772,67 @@ fn prepare_execution<'a>(
     })
 }
 
-fn prepare_request<'a>(
+async fn prepare_request<'a>(
     spec: &'a CachedSpec,
     call: crate::invocation::OperationCall,
     ctx: &'a crate::invocation::ExecutionContext,
+    references: Option<&ReferenceTag>,
 ) -> Result<PreparedRequest<'a>, Error> {
     let operation = find_operation_by_id(spec, &call.operation_id)?;
+    if !ctx.skip_validation {
+        validation::validate_request(operation, &call)?;
+    }
     let resolver = resolve_base_url_resolver(spec, ctx.global_config.as_ref());
     let base_url =
         resolver.resolve_with_variables(ctx.base_url.as_deref(), &ctx.server_var_args)?;
+    let replaying = ctx.cassette.as_ref().is_some_and(Cassette::is_replay);
+    let credentials = select_credentials(
+        spec,
+        operation,
+        ctx.global_config.as_ref(),
+        ctx.auth_scheme.as_deref(),
+        references,
+    )
+    .or_else(|error| match error {
+        // Replayed requests are never sent, so they do not need secrets
+        Error::Internal {
+            kind: ErrorKind::Authentication,
+            ..
+        } if replaying => Ok(Vec::new()),
+        error => Err(error),
+    })?;
+    let api_key_params = query_api_keys(&credentials);
     let url = build_url_from_params(
         &base_url,
         &operation.path,
+        &operation.parameters,
         &call.path_params,
         &call.query_params,
+        &call.multi_params,
+        &api_key_params,
     )?;
-    let proxy_build_result = build_http_client(ctx)?;
-    let mut headers = build_headers_from_params(
-        spec,
-        operation,
-        &call.header_params,
-        &call.custom_headers,
-        &spec.name,
-        ctx.global_config.as_ref(),
-    )?;
+    let proxy_build_result = match &ctx.http_client {
+        Some(shared) => shared.0.clone(),
+        None => build_http_client(ctx)?,
+    };
+    let oauth = OAuth2Runtime {
+        client: &proxy_build_result.client,
+        token_store: ctx.token_store.as_ref(),
+        // A replayed request is never sent, so no token is fetched for it
+        dry_run: ctx.dry_run || replaying,
+    };
+    let mut headers =
+        build_headers_from_params(spec, operation, &call, &credentials, &oauth, references).await?;
     add_idempotency_key(&mut headers, ctx.idempotency_key.as_ref())?;
     let method = Method::from_str(&operation.method)
         .map_err(|_| Error::invalid_http_method(&operation.method))?;
+    let body = build_request_body(
+        operation,
+        call.body,
+        &call.files,
+        call.raw_body,
+        &mut headers,
+    )?;
     let headers_clone = headers.clone();
 
     Ok(PreparedRequest {
@@ -1384,30 +1843,94 @@ fn prepare_request<'a>(
         proxy_diagnostics: proxy_build_result.diagnostics,
         headers,
         headers_clone,
-        body: call.body,
+        body,
+        query_authenticated: !api_key_params.is_empty(),
     })
 }
 
+/// Builds the body for the operation's request content type.
+///
+/// Form bodies set their own `Content-Type` (a multipart form includes its
+/// boundary), replacing any value given with `--header`. Binary bodies use
+/// the operation's content type unless `--header` sets one.
+fn build_request_body(
+    operation: &CachedCommand,
+    body: Option<String>,
+    files: &[FilePart],
+    raw_body: Option<RawBody>,
+    headers: &mut HeaderMap,
+) -> Result<Option<RequestBody>, Error> {
+    let content_type = operation
+        .request_body
+        .as_ref()
+        .map_or("", |request_body| request_body.content_type.as_str());
+    if content_type != constants::CONTENT_TYPE_MULTIPART {
+        if let Some(file) = files.first() {
+            return Err(Error::validation_error(format!(
+                "Operation '{}' does not take multipart/form-data, so file '{}' cannot be attached",
+                operation.operation_id,
+                file.path.display()
+            )));
+        }
+    }
+
+    let request_body = if crate::spec::is_binary_content_type(content_type) {
+        let Some(source) = raw_body.or_else(|| body.map(|text| RawBody::Bytes(text.into_bytes())))
+        else {
+            return Ok(None);
+        };
+        RequestBody::Binary(BinaryBody::new(source, content_type)?)
+    } else if content_type == constants::CONTENT_TYPE_MULTIPART {
+        if body.is_none() && files.is_empty() {
+            return Ok(None);
+        }
+        RequestBody::Multipart(MultipartForm::new(body.as_deref(), files)?)
+    } else if let Some(body) = body {
+        match &operation.request_body {
+            Some(request_body) if content_type == constants::CONTENT_TYPE_FORM => {
+                RequestBody::Form(body::encode_form(&body, &request_body.encoding)?)
+            }
+            _ => return Ok(Some(RequestBody::Json(body))),
+        }
+    } else {
+        return Ok(None);
+    };
+
+    let content_type = match &request_body {
+        RequestBody::Binary(_) if headers.contains_key(reqwest::header::CONTENT_TYPE) => {
+            return Ok(Some(request_body));
+        }
+        RequestBody::Binary(binary) => binary.content_type().to_string(),
+        RequestBody::Multipart(form) => form.content_type(),
+        RequestBody::Json(_) | RequestBody::Form(_) => constants::CONTENT_TYPE_FORM.to_string(),
+    };
+    let content_type = HeaderValue::from_str(&content_type)
+        .map_err(|e| Error::invalid_header_value(constants::HEADER_CONTENT_TYPE, e.to_string()))?;
+    headers.insert(reqwest::header::CONTENT_TYPE, content_type);
+    Ok(Some(request_body))
+}
+
 fn prepare_runtime_context<'a>(
-    spec: &'a CachedSpec,
-    operation: &'a CachedCommand,
-    method: &Method,
-    url: &str,
-    headers: &HeaderMap,
-    body: Option<&str>,
+    spec: &CachedSpec,
+    request: &PreparedRequest<'_>,
     ctx: &'a crate::invocation::ExecutionContext,
 ) -> Result<PreparedRuntimeContext<'a>, Error> {
+    // A query API key authenticates the request just like an auth header
+    let cache_config = ctx
+        .cache_config
+        .as_ref()
+        .filter(|config| config.allow_authenticated || !request.query_authenticated);
     let cache_context = prepare_cache_context(
-        ctx.cache_config.as_ref(),
+        cache_config,
         &spec.name,
-        &operation.operation_id,
-        method,
-        url,
-        headers,
-        body,
+        &request.operation.operation_id,
+        &request.method,
+        &request.url,
+        &request.headers_clone,
+        request.body.as_ref().map(RequestBody::as_text),
     )?;
     let retry_ctx = ctx.retry_context.clone().map(|mut rc| {
-        rc.method = Some(method.to_string());
+        rc.method = Some(request.method.to_string());
         rc
     });
     let secret_ctx =
@@ -1437,15 +1960,22 @@ fn find_operation_by_id<'a>(
 }
 
 /// Builds the full URL from pre-extracted path and query parameter maps.
+///
+/// Parameters are serialized with their declared `style` and `explode`;
+/// query pairs are sorted by name, keeping the order of repeated items.
+/// `api_key_params` carries the secrets of `apiKey` schemes sent in the query.
 fn build_url_from_params(
     base_url: &str,
     path_template: &str,
+    parameters: &[CachedParameter],
     path_params: &HashMap<String, String>,
     query_params: &HashMap<String, String>,
+    multi_params: &HashMap<String, ParamValues>,
+    api_key_params: &[(String, String)],
 ) -> Result<String, Error> {
     let mut url = format!("{}{}", base_url.trim_end_matches('/'), path_template);
 
-    // Substitute path parameters: replace {param} with values from the map
+    // Substitute path parameters: replace {param} with values from the maps
     let mut start = 0;
     while let Some(open) = url[start..].find('{') {
         let open_pos = start + open;
@@ -1455,22 +1985,31 @@ fn build_url_from_params(
         let close_pos = open_pos + close;
         let param_name = url[open_pos + 1..close_pos].to_string();
 
-        let value = path_params
-            .get(&param_name)
-            .ok_or_else(|| Error::missing_path_parameter(&param_name))?;
+        let value = path_segment(parameters, &param_name, path_params, multi_params)?;
 
-        url.replace_range(open_pos..=close_pos, value);
+        url.replace_range(open_pos..=close_pos, &value);
         start = open_pos + value.len();
     }
 
     // Append query parameters
-    if !query_params.is_empty() {
-        let mut qs_pairs: Vec<(&String, &String)> = query_params.iter().collect();
-        qs_pairs.sort_by_key(|(k1, _)| *k1);
+    let mut qs_pairs: Vec<(String, String)> = query_params
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    for param in parameters_in(parameters, constants::PARAM_LOCATION_QUERY) {
+        if let Some(values) = multi_params.get(&param.name) {
+            let (style, explode) = params::param_style(param);
+            qs_pairs.extend(params::form_pairs(&param.name, values, style, explode));
+        }
+    }
+    qs_pairs.extend_from_slice(api_key_params);
+
+    if !qs_pairs.is_empty() {
+        qs_pairs.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
 
         let qs: Vec<String> = qs_pairs
             .into_iter()
-            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(&v)))
             .collect();
 
         url.push('?');
@@ -1480,20 +2019,50 @@ fn build_url_from_params(
     Ok(url)
 }
 
-/// Builds HTTP headers from pre-extracted header parameter maps.
-#[allow(clippy::too_many_arguments)]
-fn build_headers_from_params(
+/// Serializes one path parameter with its declared style. A plain value in
+/// the default `simple` style is substituted as-is.
+fn path_segment(
+    parameters: &[CachedParameter],
+    name: &str,
+    path_params: &HashMap<String, String>,
+    multi_params: &HashMap<String, ParamValues>,
+) -> Result<String, Error> {
+    let values = match (multi_params.get(name), path_params.get(name)) {
+        (Some(values), _) => values.clone(),
+        (None, Some(value)) => ParamValues::Array(vec![value.clone()]),
+        (None, None) => return Err(Error::missing_path_parameter(name)),
+    };
+    let (style, explode) = parameters_in(parameters, constants::PARAM_LOCATION_PATH)
+        .find(|param| param.name == name)
+        .map_or(("simple", false), params::param_style);
+    Ok(params::path_value(name, &values, style, explode))
+}
+
+fn parameters_in<'a>(
+    parameters: &'a [CachedParameter],
+    location: &'a str,
+) -> impl Iterator<Item = &'a CachedParameter> {
+    parameters
+        .iter()
+        .filter(move |param| param.location == location)
+}
+
+/// Builds HTTP headers from the call's pre-extracted header and cookie
+/// parameter maps and custom headers.
+async fn build_headers_from_params(
     spec: &CachedSpec,
     operation: &CachedCommand,
-    header_params: &HashMap<String, String>,
-    custom_headers: &[String],
-    api_name: &str,
-    global_config: Option<&GlobalConfig>,
+    call: &crate::invocation::OperationCall,
+    credentials: &[SelectedCredential<'_>],
+    oauth: &OAuth2Runtime<'_>,
+    references: Option<&ReferenceTag>,
 ) -> Result<HeaderMap, Error> {
     let mut headers = default_request_headers();
-    apply_header_parameters(&mut headers, header_params)?;
-    apply_security_headers(&mut headers, spec, operation, api_name, global_config)?;
-    apply_custom_headers(&mut headers, custom_headers)?;
+    apply_header_parameters(&mut headers, &call.header_params)?;
+    apply_cookie_parameters(&mut headers, &call.cookie_params)?;
+    apply_multi_valued_parameters(&mut headers, operation, &call.multi_params)?;
+    apply_security_headers(&mut headers, credentials, &spec.name, oauth).await?;
+    apply_custom_headers(&mut headers, &call.custom_headers, references)?;
     Ok(headers)
 }
 
@@ -1521,25 +2090,235 @@ fn apply_header_parameters(
     Ok(())
 }
 
-fn apply_security_headers(
+/// Sends cookie parameters in one `Cookie` header, ordered by name so the
+/// header is the same on every run.
+fn apply_cookie_parameters(
+    headers: &mut HeaderMap,
+    cookie_params: &HashMap<String, String>,
+) -> Result<(), Error> {
+    let mut cookies: Vec<(&String, &String)> = cookie_params.iter().collect();
+    cookies.sort_by_key(|(name, _)| *name);
+    for (name, value) in cookies {
+        append_cookie(headers, name, value)?;
+    }
+    Ok(())
+}
+
+/// Adds array and object header and cookie parameters. Headers use the
+/// `simple` style; cookies use `form`.
+fn apply_multi_valued_parameters(
     headers: &mut HeaderMap,
-    spec: &CachedSpec,
     operation: &CachedCommand,
-    api_name: &str,
-    global_config: Option<&GlobalConfig>,
+    multi_params: &HashMap<String, ParamValues>,
 ) -> Result<(), Error> {
-    for security_scheme_name in &operation.security_requirements {
-        let Some(security_scheme) = spec.security_schemes.get(security_scheme_name) else {
+    for param in &operation.parameters {
+        let Some(values) = multi_params.get(&param.name) else {
             continue;
         };
-        add_authentication_header(headers, security_scheme, api_name, global_config)?;
+        let (style, explode) = params::param_style(param);
+        match param.location.as_str() {
+            constants::PARAM_LOCATION_HEADER => {
+                let value = params::path_value(&param.name, values, "simple", explode);
+                let header_name = HeaderName::from_str(&param.name)
+                    .map_err(|e| Error::invalid_header_name(&param.name, e.to_string()))?;
+                let header_value = HeaderValue::from_str(&value)
+                    .map_err(|e| Error::invalid_header_value(&param.name, e.to_string()))?;
+                headers.insert(header_name, header_value);
+            }
+            constants::PARAM_LOCATION_COOKIE => {
+                for (name, value) in params::form_pairs(&param.name, values, style, explode) {
+                    append_cookie(headers, &name, &value)?;
+                }
+            }
+            _ => {}
+        }
     }
     Ok(())
 }
 
-fn apply_custom_headers(headers: &mut HeaderMap, custom_headers: &[String]) -> Result<(), Error> {
+async fn apply_security_headers(
+    headers: &mut HeaderMap,
+    credentials: &[SelectedCredential<'_>],
+    api_name: &str,
+    oauth: &OAuth2Runtime<'_>,
+) -> Result<(), Error> {
+    for credential in credentials {
+        // Query API keys are added to the URL instead
+        if credential.scheme.query_parameter_name().is_some() {
+            continue;
+        }
+        add_authentication_header(headers, credential, api_name, oauth).await?;
+    }
+    Ok(())
+}
+
+/// Returns `(parameter, secret)` pairs for the selected `apiKey` schemes
+/// that are sent as query parameters.
+fn query_api_keys(credentials: &[SelectedCredential<'_>]) -> Vec<(String, String)> {
+    credentials
+        .iter()
+        .filter_map(|credential| {
+            credential
+                .scheme
+                .query_parameter_name()
+                .map(|param_name| (param_name.to_string(), credential.secret.value.clone()))
+        })
+        .collect()
+}
+
+/// A security scheme chosen for the request, with its resolved secret
+struct SelectedCredential<'a> {
+    scheme: &'a CachedSecurityScheme,
+    secret: ResolvedAuthenticationSecret,
+}
+
+/// Chooses the security requirement set to authenticate with.
+///
+/// `--auth-scheme` forces the set containing that scheme, and every scheme in
+/// it must have a secret. Otherwise the first set whose secrets all resolve
+/// wins; an empty set means no authentication is needed. When no set is
+/// satisfiable, the failure of the last set whose secret source failed is
+/// reported. If none failed, the first set is used as before, without the
+/// schemes that have no secret mapping.
+///
+/// Each scheme's secret is resolved at most once, however many sets share it.
+/// With `references`, secrets are not read and every mapped scheme counts as
+/// satisfiable.
+fn select_credentials<'a>(
+    spec: &'a CachedSpec,
+    operation: &CachedCommand,
+    global_config: Option<&GlobalConfig>,
+    auth_scheme: Option<&str>,
+    references: Option<&ReferenceTag>,
+) -> Result<Vec<SelectedCredential<'a>>, Error> {
+    let alternatives = operation.security_alternatives();
+    let mut secrets = SchemeSecrets {
+        spec,
+        global_config,
+        references,
+        resolved: HashMap::new(),
+    };
+
+    if let Some(forced) = auth_scheme {
+        let set = alternatives
+            .iter()
+            .find(|set| set.iter().any(|name| name == forced))
+            .ok_or_else(|| {
+                let accepted = alternatives
+                    .iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                Error::validation_error(format!(
+                    "Operation '{}' does not accept security scheme '{forced}'. Accepted schemes: {}",
+                    operation.operation_id,
+                    if accepted.is_empty() {
+                        "none".to_string()
+                    } else {
+                        accepted.join(", ")
+                    }
+                ))
+            })?;
+        return match secrets.requirement_set(set) {
+            Ok(credentials) => Ok(credentials),
+            Err(SetFailure::Failed(name)) => Err(secrets.take_error(name)),
+            Err(SetFailure::Unmapped(name)) => Err(Error::validation_error(format!(
+                "No secret is configured for security scheme '{name}' in '{}' required by --auth-scheme {forced}",
+                set.join(" + ")
+            ))),
+        };
+    }
+
+    let mut last_failed = None;
+    for set in &alternatives {
+        match secrets.requirement_set(set) {
+            Ok(credentials) => return Ok(credentials),
+            Err(SetFailure::Failed(name)) => last_failed = Some(name),
+            Err(SetFailure::Unmapped(_)) => {}
+        }
+    }
+    if let Some(name) = last_failed {
+        return Err(secrets.take_error(name));
+    }
+
+    let mut credentials = Vec::new();
+    for name in alternatives.first().into_iter().flatten() {
+        if let Some(Ok(Some(secret))) = secrets.resolve(name) {
+            let scheme = &spec.security_schemes[name.as_str()];
+            credentials.push(SelectedCredential {
+                scheme,
+                secret: secret.clone(),
+            });
+        }
+    }
+    Ok(credentials)
+}
+
+/// Why a requirement set cannot be used, naming the first scheme at fault
+enum SetFailure<'s> {
+    /// The scheme is unknown or has no secret mapping
+    Unmapped(&'s str),
+    /// Resolving the scheme's secret failed
+    Failed(&'s str),
+}
+
+/// Secrets of an operation's security schemes, resolved on first use
+struct SchemeSecrets<'a, 'c> {
+    spec: &'a CachedSpec,
+    global_config: Option<&'c GlobalConfig>,
+    references: Option<&'c ReferenceTag>,
+    resolved: HashMap<String, Result<Option<ResolvedAuthenticationSecret>, Error>>,
+}
+
+impl<'a> SchemeSecrets<'a, '_> {
+    /// The scheme's secret, or `None` if the spec does not define the scheme
+    fn resolve(
+        &mut self,
+        name: &str,
+    ) -> Option<&Result<Option<ResolvedAuthenticationSecret>, Error>> {
+        let scheme = self.spec.security_schemes.get(name)?;
+        if !self.resolved.contains_key(name) {
+            let secret =
+                scheme_secret(scheme, &self.spec.name, self.global_config, self.references);
+            self.resolved.insert(name.to_string(), secret);
+        }
+        self.resolved.get(name)
+    }
+
+    /// Resolves the secret of every scheme in a requirement set
+    fn requirement_set<'s>(
+        &mut self,
+        set: &'s [String],
+    ) -> Result<Vec<SelectedCredential<'a>>, SetFailure<'s>> {
+        let mut credentials = Vec::with_capacity(set.len());
+        for name in set {
+            let secret = match self.resolve(name) {
+                Some(Ok(Some(secret))) => secret.clone(),
+                Some(Err(_)) => return Err(SetFailure::Failed(name)),
+                Some(Ok(None)) | None => return Err(SetFailure::Unmapped(name)),
+            };
+            let scheme = &self.spec.security_schemes[name.as_str()];
+            credentials.push(SelectedCredential { scheme, secret });
+        }
+        Ok(credentials)
+    }
+
+    /// Takes the error a scheme's secret failed to resolve with
+    fn take_error(&mut self, name: &str) -> Error {
+        match self.resolved.remove(name) {
+            Some(Err(error)) => error,
+            _ => Error::secret_unavailable(name, format!("'{name}'"), "resolution failed"),
+        }
+    }
+}
+
+fn apply_custom_headers(
+    headers: &mut HeaderMap,
+    custom_headers: &[String],
+    references: Option<&ReferenceTag>,
+) -> Result<(), Error> {
     for header_str in custom_headers {
-        let (name, value) = parse_custom_header(header_str)?;
+        let (name, value) = parse_custom_header(header_str, references)?;
         let header_name = HeaderName::from_str(&name)
             .map_err(|e| Error::invalid_header_name(&name, e.to_string()))?;
         let header_value = HeaderValue::from_str(&value)
@@ -1762,8 +2541,11 @@ mod tests {
         let url = build_url_from_params(
             "https://example.com",
             "/items",
+            &[],
             &std::collections::HashMap::new(),
             &query,
+            &std::collections::HashMap::new(),
+            &[],
         )
         .expect("url build should succeed");
 
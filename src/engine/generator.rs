@@ -12,12 +12,13 @@
 //! - Total leaked memory is bounded by the spec size (typically <100KB).
 //! - No long-running process or repeated allocation occurs.
 
-use crate::cache::models::{CachedCommand, CachedParameter, CachedSpec};
+use crate::cache::models::{CachedCommand, CachedParameter, CachedRequestBody, CachedSpec};
 use crate::constants;
 use crate::docs::DocumentationGenerator;
+use crate::engine::{body, params};
 use crate::utils::to_kebab_case;
-use clap::{Arg, ArgAction, Command};
-use std::collections::HashMap;
+use clap::{Arg, ArgAction, ArgGroup, Command};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 
 /// Converts a String to a 'static str by leaking it
@@ -162,7 +163,13 @@ pub fn generate_command_tree_for_api_with_flags(
 
             // Add request body argument if present
             if let Some(request_body) = &cached_command.request_body {
-                operation_command = add_body_args(operation_command, request_body.required);
+                operation_command = if is_form_body(request_body) {
+                    add_form_args(operation_command, cached_command, request_body)
+                } else if crate::spec::is_binary_content_type(&request_body.content_type) {
+                    add_raw_body_args(operation_command, request_body)
+                } else {
+                    add_body_args(operation_command, request_body.required)
+                };
             }
 
             // Add custom header support
@@ -239,8 +246,136 @@ fn add_body_args(cmd: Command, required: bool) -> Command {
     )
 }
 
+/// Attaches `--body-file` to a command whose body is raw binary content.
+///
+/// The file is sent as-is rather than parsed as JSON, so there is no `--body`.
+fn add_raw_body_args(cmd: Command, request_body: &CachedRequestBody) -> Command {
+    cmd.arg(
+        Arg::new("body-file")
+            .long("body-file")
+            .help(format!(
+                "Send a file as the raw {} request body, or - for stdin",
+                request_body.content_type
+            ))
+            .value_name("PATH")
+            .required(request_body.required)
+            .action(ArgAction::Set),
+    )
+}
+
+/// Long flags an operation command defines besides its parameters, plus the
+/// long forms of the root's global flags (`-v` has none); form fields with
+/// these names get no flag of their own
+const RESERVED_OPERATION_FLAGS: &[&str] = &[
+    "body",
+    "body-file",
+    "file",
+    "header",
+    "show-examples",
+    "help",
+    "jq",
+    "format",
+    "server-var",
+    "json-errors",
+    "quiet",
+];
+
+/// Whether a cached body is a form whose properties get their own flags
+fn is_form_body(request_body: &CachedRequestBody) -> bool {
+    matches!(
+        request_body.content_type.as_str(),
+        constants::CONTENT_TYPE_MULTIPART | constants::CONTENT_TYPE_FORM
+    )
+}
+
+/// Attaches the args for a `multipart/form-data` or
+/// `application/x-www-form-urlencoded` body.
+///
+/// Each non-binary top-level property gets its own flag (`--title`), and
+/// multipart file properties are attached with the repeatable
+/// `--file field=@path`. `--body` and `--body-file` still accept the text
+/// fields as a JSON object, which is also the way to set fields whose flag
+/// would collide with a parameter or a built-in flag. A required body needs
+/// at least one of these args.
+fn add_form_args(
+    cmd: Command,
+    command: &CachedCommand,
+    request_body: &CachedRequestBody,
+) -> Command {
+    let accepts_files = request_body.content_type == constants::CONTENT_TYPE_MULTIPART;
+    let mut cmd = add_body_args(cmd, false)
+        .mut_arg("body", |arg| arg.help("Form fields as a JSON object"))
+        .mut_arg("body-file", |arg| {
+            arg.help("Read form fields as a JSON object from a file path, or - for stdin")
+        });
+
+    let mut taken: HashSet<String> = command
+        .parameters
+        .iter()
+        .map(|param| to_kebab_case(&param.name))
+        .chain(RESERVED_OPERATION_FLAGS.iter().map(ToString::to_string))
+        .collect();
+    let mut body_args = vec!["body", "body-file"];
+    let mut file_fields = Vec::new();
+
+    for field in body::form_fields(request_body) {
+        if field.binary {
+            file_fields.push(field.name);
+            continue;
+        }
+
+        let long_name = to_kebab_case(&field.name);
+        if long_name.is_empty() || !taken.insert(long_name.clone()) {
+            continue;
+        }
+
+        let arg_id = to_static_str(field.arg_id());
+        body_args.push(arg_id);
+        cmd = cmd.arg(
+            Arg::new(arg_id)
+                .long(to_static_str(long_name))
+                .help(
+                    field
+                        .description
+                        .unwrap_or_else(|| format!("Form field: {}", field.name)),
+                )
+                .value_name("VALUE")
+                .action(ArgAction::Set),
+        );
+    }
+
+    if accepts_files {
+        let file_help = if file_fields.is_empty() {
+            "Attach a file as a form part (FIELD=@PATH). Can be used multiple times.".to_string()
+        } else {
+            format!(
+                "Attach a file as a form part (FIELD=@PATH; file fields: {}). Can be used multiple times.",
+                file_fields.join(", ")
+            )
+        };
+        body_args.push("file");
+        cmd = cmd.arg(
+            Arg::new("file")
+                .long("file")
+                .help(file_help)
+                .value_name("FIELD=@PATH")
+                .action(ArgAction::Append),
+        );
+    }
+
+    if request_body.required {
+        cmd = cmd.group(
+            ArgGroup::new("form-body")
+                .args(body_args)
+                .multiple(true)
+                .required(true),
+        );
+    }
+    cmd
+}
+
 /// Returns the effective group name for a command, using `display_group` override if present.
-fn effective_group_name(command: &CachedCommand) -> String {
+pub(crate) fn effective_group_name(command: &CachedCommand) -> String {
     command.display_group.as_ref().map_or_else(
         || {
             if command.name.is_empty() {
@@ -254,7 +389,7 @@ fn effective_group_name(command: &CachedCommand) -> String {
 }
 
 /// Returns the effective subcommand name for a command, using `display_name` override if present.
-fn effective_subcommand_name(command: &CachedCommand) -> String {
+pub(crate) fn effective_subcommand_name(command: &CachedCommand) -> String {
     command.display_name.as_ref().map_or_else(
         || {
             if command.operation_id.is_empty() {
@@ -278,7 +413,7 @@ fn effective_subcommand_name(command: &CachedCommand) -> String {
 /// - Flag presence = true (substitutes "true" in path), absence = false (substitutes "false")
 /// - Example: `/items/{active}` with `--active` → `/items/true`, without → `/items/false`
 ///
-/// **Query/Header Parameters:**
+/// **Query/Header/Cookie Parameters:**
 /// - **Optional booleans** (`required: false`): Flag presence = true, absence = false
 /// - **Required booleans** (`required: true`): Flag MUST be provided, presence = true
 /// - Example: `--verbose` (optional) omitted means `verbose=false`
@@ -287,10 +422,33 @@ fn effective_subcommand_name(command: &CachedCommand) -> String {
 fn create_arg_from_parameter(param: &CachedParameter, use_positional_args: bool) -> Arg {
     let is_boolean = param.schema_type.as_ref().is_some_and(|t| t == "boolean");
 
-    match param.location.as_str() {
+    let arg = match param.location.as_str() {
         "path" => create_path_parameter_arg(param, use_positional_args, is_boolean),
-        "query" | "header" => create_scoped_parameter_arg(param, is_boolean),
+        "query" | "header" | "cookie" => create_scoped_parameter_arg(param, is_boolean),
         _ => create_generic_parameter_arg(param, is_boolean),
+    };
+
+    if params::is_multi_valued(param) {
+        allow_multiple_values(arg, param)
+    } else {
+        arg
+    }
+}
+
+/// Lets an array or object parameter take several values: flags can be
+/// repeated and each value may be a comma-separated list. Object members
+/// are given as `KEY=VALUE`.
+fn allow_multiple_values(arg: Arg, param: &CachedParameter) -> Arg {
+    let arg = if arg.is_positional() {
+        arg
+    } else {
+        arg.action(ArgAction::Append)
+    };
+    let arg = arg.value_delimiter(',');
+    if param.schema_type.as_deref() == Some(constants::SCHEMA_TYPE_OBJECT) {
+        arg.value_name("KEY=VALUE")
+    } else {
+        arg
     }
 }
 
@@ -1,3 +1,6 @@
+pub mod body;
 pub mod executor;
 pub mod generator;
 pub mod loader;
+pub mod params;
+pub mod validation;
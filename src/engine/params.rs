@@ -0,0 +1,210 @@
+//! Serialization of parameter values by their `OpenAPI` `style` and `explode`.
+//!
+//! Scalar values are treated as single-item arrays, which every style
+//! serializes the same way as a plain value (e.g., `.5` in `label` style).
+
+use crate::cache::models::CachedParameter;
+use crate::constants;
+use crate::invocation::ParamValues;
+
+/// Returns true for parameters whose values are arrays or objects
+#[must_use]
+pub fn is_multi_valued(param: &CachedParameter) -> bool {
+    matches!(
+        param.schema_type.as_deref(),
+        Some(constants::SCHEMA_TYPE_ARRAY | constants::SCHEMA_TYPE_OBJECT)
+    )
+}
+
+/// Returns the parameter's style and explode setting, falling back to the
+/// defaults for its location when the cache has none
+#[must_use]
+pub fn param_style(param: &CachedParameter) -> (&str, bool) {
+    if let Some(encoding) = &param.encoding {
+        return (encoding.style.as_str(), encoding.explode);
+    }
+    match param.location.as_str() {
+        constants::PARAM_LOCATION_PATH | constants::PARAM_LOCATION_HEADER => ("simple", false),
+        _ => ("form", true),
+    }
+}
+
+/// Serializes a value into name/value pairs for a query string, cookie or
+/// form-encoded body (`form`, `spaceDelimited`, `pipeDelimited` and
+/// `deepObject` styles)
+#[must_use]
+pub fn form_pairs(
+    name: &str,
+    values: &ParamValues,
+    style: &str,
+    explode: bool,
+) -> Vec<(String, String)> {
+    match values {
+        ParamValues::Array(items) => {
+            let separator = match style {
+                "spaceDelimited" => " ",
+                "pipeDelimited" => "|",
+                _ if explode => {
+                    return items
+                        .iter()
+                        .map(|item| (name.to_string(), item.clone()))
+                        .collect();
+                }
+                _ => ",",
+            };
+            vec![(name.to_string(), items.join(separator))]
+        }
+        ParamValues::Object(members) => {
+            if style == "deepObject" {
+                members
+                    .iter()
+                    .map(|(key, value)| (format!("{name}[{key}]"), value.clone()))
+                    .collect()
+            } else if explode {
+                members.clone()
+            } else {
+                vec![(name.to_string(), flatten_members(members))]
+            }
+        }
+    }
+}
+
+/// Serializes a value for a path segment (`simple`, `label` and `matrix`
+/// styles) or a header (`simple` style)
+#[must_use]
+pub fn path_value(name: &str, values: &ParamValues, style: &str, explode: bool) -> String {
+    let (prefix, separator) = match style {
+        "label" if explode => (".", "."),
+        "label" => (".", ","),
+        "matrix" => return matrix_value(name, values, explode),
+        _ => ("", ","),
+    };
+
+    let joined = match values {
+        ParamValues::Array(items) => items.join(separator),
+        ParamValues::Object(members) if explode => members
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(separator),
+        ParamValues::Object(members) => flatten_members(members),
+    };
+    format!("{prefix}{joined}")
+}
+
+fn matrix_value(name: &str, values: &ParamValues, explode: bool) -> String {
+    let pairs: Vec<(&str, String)> = match values {
+        ParamValues::Array(items) if explode => {
+            items.iter().map(|item| (name, item.clone())).collect()
+        }
+        ParamValues::Array(items) => vec![(name, items.join(","))],
+        ParamValues::Object(members) if explode => members
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect(),
+        ParamValues::Object(members) => vec![(name, flatten_members(members))],
+    };
+    pairs
+        .iter()
+        .map(|(key, value)| format!(";{key}={value}"))
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Joins object members as `key,value,key,value`
+fn flatten_members(members: &[(String, String)]) -> String {
+    members
+        .iter()
+        .flat_map(|(key, value)| [key.as_str(), value.as_str()])
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn array() -> ParamValues {
+        ParamValues::Array(vec!["a".to_string(), "b".to_string()])
+    }
+
+    fn object() -> ParamValues {
+        ParamValues::Object(vec![
+            ("role".to_string(), "admin".to_string()),
+            ("name".to_string(), "x".to_string()),
+        ])
+    }
+
+    fn pairs(list: &[(&str, &str)]) -> Vec<(String, String)> {
+        list.iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_form_pairs_by_style() {
+        assert_eq!(
+            form_pairs("tag", &array(), "form", true),
+            pairs(&[("tag", "a"), ("tag", "b")])
+        );
+        assert_eq!(
+            form_pairs("tag", &array(), "form", false),
+            pairs(&[("tag", "a,b")])
+        );
+        assert_eq!(
+            form_pairs("tag", &array(), "spaceDelimited", false),
+            pairs(&[("tag", "a b")])
+        );
+        assert_eq!(
+            form_pairs("tag", &array(), "pipeDelimited", false),
+            pairs(&[("tag", "a|b")])
+        );
+        assert_eq!(
+            form_pairs("filter", &object(), "deepObject", true),
+            pairs(&[("filter[role]", "admin"), ("filter[name]", "x")])
+        );
+        assert_eq!(
+            form_pairs("filter", &object(), "form", true),
+            pairs(&[("role", "admin"), ("name", "x")])
+        );
+        assert_eq!(
+            form_pairs("filter", &object(), "form", false),
+            pairs(&[("filter", "role,admin,name,x")])
+        );
+    }
+
+    #[test]
+    fn test_path_value_by_style() {
+        let single = ParamValues::Array(vec!["5".to_string()]);
+        assert_eq!(path_value("id", &single, "simple", false), "5");
+        assert_eq!(path_value("id", &single, "label", false), ".5");
+        assert_eq!(path_value("id", &single, "matrix", false), ";id=5");
+
+        assert_eq!(path_value("id", &array(), "simple", false), "a,b");
+        assert_eq!(path_value("id", &array(), "label", false), ".a,b");
+        assert_eq!(path_value("id", &array(), "label", true), ".a.b");
+        assert_eq!(path_value("id", &array(), "matrix", false), ";id=a,b");
+        assert_eq!(path_value("id", &array(), "matrix", true), ";id=a;id=b");
+
+        assert_eq!(
+            path_value("p", &object(), "simple", false),
+            "role,admin,name,x"
+        );
+        assert_eq!(
+            path_value("p", &object(), "simple", true),
+            "role=admin,name=x"
+        );
+        assert_eq!(
+            path_value("p", &object(), "label", true),
+            ".role=admin.name=x"
+        );
+        assert_eq!(
+            path_value("p", &object(), "matrix", true),
+            ";role=admin;name=x"
+        );
+        assert_eq!(
+            path_value("p", &object(), "matrix", false),
+            ";p=role,admin,name,x"
+        );
+    }
+}
@@ -0,0 +1,656 @@
+//! Validation of request values and responses against the operation's schemas.
+//!
+//! Schemas are the JSON-serialized `OpenAPI` schema objects stored in the
+//! cache. Supported keywords are `type` (including `OpenAPI` 3.1 type arrays),
+//! `nullable`, `enum`, `const`, `pattern`, `format`, string length, numeric
+//! bounds, array and object constraints, and `allOf`/`anyOf`/`oneOf`/`not`. Unresolved `$ref`s and unknown formats
+//! are accepted, so validation never rejects a value the spec allows.
+
+use crate::cache::models::{CachedCommand, CachedParameter, CachedResponse};
+use crate::constants;
+use crate::error::Error;
+use crate::invocation::{OperationCall, ParamValues};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// A value that does not satisfy its schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value (e.g., `/body/items/0/id`)
+    pub path: String,
+    /// What the value fails to satisfy
+    pub message: String,
+}
+
+/// Validates the parameters and JSON body of a call against the operation's
+/// schemas.
+///
+/// Parameters are reported under `/{location}/{name}` and the body under
+/// `/body`. Form, multipart and binary bodies are not validated.
+///
+/// # Errors
+///
+/// Returns a validation error listing every violation found.
+pub fn validate_request(operation: &CachedCommand, call: &OperationCall) -> Result<(), Error> {
+    let violations = request_violations(operation, call);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::request_validation_failed(
+            &operation.operation_id,
+            &violations,
+        ))
+    }
+}
+
+/// Lists the violations [`validate_request`] reports, without building an error
+#[must_use]
+pub fn request_violations(operation: &CachedCommand, call: &OperationCall) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+
+    for param in &operation.parameters {
+        let Some(schema) = parse_schema(param.schema.as_deref()) else {
+            continue;
+        };
+        let Some(value) = parameter_value(param, call, &schema) else {
+            continue;
+        };
+        let path = format!("/{}/{}", param.location, escape_pointer(&param.name));
+        validate_value(&schema, &value, &path, Direction::Request, &mut violations);
+    }
+
+    if let (Some(request_body), Some(body)) = (&operation.request_body, &call.body) {
+        let schema = parse_schema(Some(&request_body.schema));
+        let instance = serde_json::from_str::<Value>(body).ok();
+        if let (true, Some(schema), Some(instance)) = (
+            constants::is_json_content_type(&request_body.content_type),
+            schema,
+            instance,
+        ) {
+            validate_value(
+                &schema,
+                &instance,
+                "/body",
+                Direction::Request,
+                &mut violations,
+            );
+        }
+    }
+
+    violations
+}
+
+/// Checks a response against the operation's documented responses.
+///
+/// The status code must match a documented code, a range such as `2XX`, or
+/// `default`. A JSON body is validated against that response's schema and
+/// reported under `/body`.
+#[must_use]
+pub fn validate_response(
+    operation: &CachedCommand,
+    status: u16,
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    let Some(response) = documented_response(operation, status) else {
+        let documented = operation
+            .responses
+            .iter()
+            .map(|response| response.status_code.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        push(
+            &mut violations,
+            "/status",
+            format!("status {status} is not documented (documented: {documented})"),
+        );
+        return violations;
+    };
+
+    let is_json = content_type
+        .or(response.content_type.as_deref())
+        .is_some_and(constants::is_json_content_type);
+    let Some(schema) = parse_schema(response.schema.as_deref()) else {
+        return violations;
+    };
+    if !is_json || body.is_empty() {
+        return violations;
+    }
+    match serde_json::from_slice::<Value>(body) {
+        Ok(instance) => validate_value(
+            &schema,
+            &instance,
+            "/body",
+            Direction::Response,
+            &mut violations,
+        ),
+        Err(e) => push(&mut violations, "/body", format!("is not valid JSON: {e}")),
+    }
+    violations
+}
+
+/// Finds the response documented for a status code, preferring an exact
+/// code over a range and a range over `default`
+fn documented_response(operation: &CachedCommand, status: u16) -> Option<&CachedResponse> {
+    let exact = status.to_string();
+    let range = format!("{}XX", status / 100);
+    let find = |code: &str| {
+        operation
+            .responses
+            .iter()
+            .find(|response| response.status_code.eq_ignore_ascii_case(code))
+    };
+    find(&exact)
+        .or_else(|| find(&range))
+        .or_else(|| find("default"))
+}
+
+/// Which way a value travels, deciding whether `readOnly` or `writeOnly`
+/// properties may be left out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent by the client; `readOnly` properties are set by the server
+    Request,
+    /// Returned by the server; `writeOnly` properties are never returned
+    Response,
+}
+
+impl Direction {
+    /// The keyword marking properties absent in this direction
+    const fn omitted_keyword(self) -> &'static str {
+        match self {
+            Self::Request => "readOnly",
+            Self::Response => "writeOnly",
+        }
+    }
+}
+
+/// Validates `instance` against `schema`, appending a violation for each
+/// failed constraint. `path` is the JSON pointer reported for `instance`.
+pub fn validate_value(
+    schema: &Value,
+    instance: &Value,
+    path: &str,
+    direction: Direction,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        Value::Bool(false) => {
+            push(violations, path, "no value is allowed here".to_string());
+            return;
+        }
+        _ => return,
+    };
+    if schema.contains_key("$ref") {
+        return;
+    }
+    if instance.is_null() && schema.get("nullable") == Some(&Value::Bool(true)) {
+        return;
+    }
+    if !check_type(schema, instance, path, violations) {
+        return;
+    }
+
+    check_enum(schema, instance, path, violations);
+    if let Some(expected) = schema.get("const") {
+        if expected != instance {
+            push(violations, path, format!("{instance} must be {expected}"));
+        }
+    }
+    match instance {
+        Value::String(value) => check_string(schema, value, path, violations),
+        Value::Number(number) => {
+            if let Some(value) = number.as_f64() {
+                check_number(schema, value, path, violations);
+            }
+        }
+        Value::Array(items) => check_array(schema, items, path, direction, violations),
+        Value::Object(members) => check_object(schema, members, path, direction, violations),
+        Value::Null | Value::Bool(_) => {}
+    }
+    check_composition(schema, instance, path, direction, violations);
+}
+
+/// Returns true if `instance` satisfies `schema`
+#[must_use]
+pub fn is_valid(schema: &Value, instance: &Value, direction: Direction) -> bool {
+    let mut violations = Vec::new();
+    validate_value(schema, instance, "", direction, &mut violations);
+    violations.is_empty()
+}
+
+fn parse_schema(schema: Option<&str>) -> Option<Value> {
+    serde_json::from_str(schema?).ok()
+}
+
+/// Builds the typed value of a parameter from its string form
+fn parameter_value(param: &CachedParameter, call: &OperationCall, schema: &Value) -> Option<Value> {
+    if let Some(values) = call.multi_params.get(&param.name) {
+        return Some(multi_value(values, schema));
+    }
+    let raw = match param.location.as_str() {
+        constants::PARAM_LOCATION_PATH => call.path_params.get(&param.name),
+        constants::PARAM_LOCATION_QUERY => call.query_params.get(&param.name),
+        constants::PARAM_LOCATION_HEADER => call.header_params.get(&param.name),
+        constants::PARAM_LOCATION_COOKIE => call.cookie_params.get(&param.name),
+        _ => None,
+    }?;
+    Some(coerce(raw, schema))
+}
+
+fn multi_value(values: &ParamValues, schema: &Value) -> Value {
+    match values {
+        ParamValues::Array(items) => {
+            let item_schema = schema.get("items").unwrap_or(&Value::Null);
+            Value::Array(items.iter().map(|item| coerce(item, item_schema)).collect())
+        }
+        ParamValues::Object(members) => Value::Object(
+            members
+                .iter()
+                .map(|(key, member)| {
+                    let member_schema = schema
+                        .get("properties")
+                        .and_then(|properties| properties.get(key))
+                        .or_else(|| schema.get("additionalProperties"))
+                        .unwrap_or(&Value::Null);
+                    (key.clone(), coerce(member, member_schema))
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// Converts a string to the JSON type its schema declares, leaving it a
+/// string when it does not parse so the type check reports it
+fn coerce(raw: &str, schema: &Value) -> Value {
+    // A 3.1 type array such as `[integer, "null"]` coerces to its first type
+    let schema_type = match schema.get("type") {
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|schema_type| *schema_type != "null"),
+        schema_type => schema_type.and_then(Value::as_str),
+    };
+    let parsed = match schema_type {
+        Some(constants::SCHEMA_TYPE_INTEGER) => raw
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| raw.parse::<u64>().map(Value::from))
+            .ok(),
+        Some(constants::SCHEMA_TYPE_NUMBER) => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        Some(constants::SCHEMA_TYPE_BOOLEAN) => match raw {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+pub(crate) fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn push(violations: &mut Vec<SchemaViolation>, path: &str, message: String) {
+    violations.push(SchemaViolation {
+        path: path.to_string(),
+        message,
+    });
+}
+
+const fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => constants::SCHEMA_TYPE_BOOLEAN,
+        Value::Number(_) => constants::SCHEMA_TYPE_NUMBER,
+        Value::String(_) => constants::SCHEMA_TYPE_STRING,
+        Value::Array(_) => constants::SCHEMA_TYPE_ARRAY,
+        Value::Object(_) => constants::SCHEMA_TYPE_OBJECT,
+    }
+}
+
+fn matches_type(schema_type: &str, instance: &Value) -> bool {
+    match schema_type {
+        constants::SCHEMA_TYPE_INTEGER => {
+            instance.as_f64().is_some_and(|value| value.fract() == 0.0)
+        }
+        constants::SCHEMA_TYPE_NUMBER => instance.is_number(),
+        other => type_name(instance) == other,
+    }
+}
+
+/// Checks `type`, returning false if the instance has the wrong type
+fn check_type(
+    schema: &Map<String, Value>,
+    instance: &Value,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) -> bool {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(schema_type)) => vec![schema_type.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => return true,
+    };
+    if types.is_empty() || types.iter().any(|t| matches_type(t, instance)) {
+        return true;
+    }
+    push(
+        violations,
+        path,
+        format!(
+            "expected {}, found {}",
+            types.join(" or "),
+            type_name(instance)
+        ),
+    );
+    false
+}
+
+fn check_enum(
+    schema: &Map<String, Value>,
+    instance: &Value,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let Some(Value::Array(allowed)) = schema.get("enum") else {
+        return;
+    };
+    // `openapiv3` serializes a nullable enum with a `null` member
+    if allowed.is_empty() || allowed.contains(instance) {
+        return;
+    }
+    let allowed = allowed
+        .iter()
+        .filter(|value| !value.is_null())
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    push(
+        violations,
+        path,
+        format!("{instance} is not one of the allowed values: {allowed}"),
+    );
+}
+
+fn check_string(
+    schema: &Map<String, Value>,
+    value: &str,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let length = value.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if length < min {
+            push(
+                violations,
+                path,
+                format!("must be at least {min} characters long"),
+            );
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if length > max {
+            push(
+                violations,
+                path,
+                format!("must be at most {max} characters long"),
+            );
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        // Patterns the regex crate cannot compile are not enforced
+        if regex::Regex::new(pattern).is_ok_and(|regex| !regex.is_match(value)) {
+            push(
+                violations,
+                path,
+                format!("\"{value}\" does not match the pattern {pattern}"),
+            );
+        }
+    }
+    if let Some(format) = schema.get("format").and_then(Value::as_str) {
+        if !matches_format(format, value) {
+            push(
+                violations,
+                path,
+                format!("\"{value}\" is not a valid {format}"),
+            );
+        }
+    }
+}
+
+/// Returns false only for values that violate a known string format
+fn matches_format(format: &str, value: &str) -> bool {
+    match format {
+        "uuid" => is_uuid(value),
+        "date-time" => chrono::DateTime::parse_from_rfc3339(value).is_ok(),
+        "date" => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        "email" => value.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty() && domain.contains('.') && !domain.contains('@')
+        }),
+        "ipv4" => value.parse::<std::net::Ipv4Addr>().is_ok(),
+        "ipv6" => value.parse::<std::net::Ipv6Addr>().is_ok(),
+        "uri" => reqwest::Url::parse(value).is_ok(),
+        _ => true,
+    }
+}
+
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn check_number(
+    schema: &Map<String, Value>,
+    value: f64,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    // `OpenAPI` 3.0 uses boolean `exclusiveMinimum`/`exclusiveMaximum`
+    // modifiers; JSON Schema uses the bound itself
+    let exclusive_flag = |key: &str| schema.get(key) == Some(&Value::Bool(true));
+
+    if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+        if exclusive_flag("exclusiveMinimum") && value <= min {
+            push(violations, path, format!("must be greater than {min}"));
+        } else if value < min {
+            push(violations, path, format!("must be at least {min}"));
+        }
+    }
+    if let Some(min) = schema.get("exclusiveMinimum").and_then(Value::as_f64) {
+        if value <= min {
+            push(violations, path, format!("must be greater than {min}"));
+        }
+    }
+    if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+        if exclusive_flag("exclusiveMaximum") && value >= max {
+            push(violations, path, format!("must be less than {max}"));
+        } else if value > max {
+            push(violations, path, format!("must be at most {max}"));
+        }
+    }
+    if let Some(max) = schema.get("exclusiveMaximum").and_then(Value::as_f64) {
+        if value >= max {
+            push(violations, path, format!("must be less than {max}"));
+        }
+    }
+    if let Some(divisor) = schema.get("multipleOf").and_then(Value::as_f64) {
+        let quotient = value / divisor;
+        if divisor > 0.0 && (quotient - quotient.round()).abs() > 1e-9 {
+            push(violations, path, format!("must be a multiple of {divisor}"));
+        }
+    }
+    if schema.get("format").and_then(Value::as_str) == Some("int32")
+        && (value < f64::from(i32::MIN) || value > f64::from(i32::MAX))
+    {
+        push(violations, path, "is out of range for int32".to_string());
+    }
+}
+
+fn check_array(
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &str,
+    direction: Direction,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let count = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if count < min {
+            push(violations, path, format!("must have at least {min} items"));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if count > max {
+            push(violations, path, format!("must have at most {max} items"));
+        }
+    }
+    if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+        let has_duplicates = items
+            .iter()
+            .enumerate()
+            .any(|(index, item)| items[..index].contains(item));
+        if has_duplicates {
+            push(violations, path, "items must be unique".to_string());
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (index, item) in items.iter().enumerate() {
+            validate_value(
+                item_schema,
+                item,
+                &format!("{path}/{index}"),
+                direction,
+                violations,
+            );
+        }
+    }
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    members: &Map<String, Value>,
+    path: &str,
+    direction: Direction,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let property = |name: &str| properties.and_then(|properties| properties.get(name));
+
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            let omitted = property(name)
+                .and_then(|property| property.get(direction.omitted_keyword()))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            if !omitted && !members.contains_key(name) {
+                push(
+                    violations,
+                    path,
+                    format!("missing required property \"{name}\""),
+                );
+            }
+        }
+    }
+
+    let count = members.len() as u64;
+    if let Some(min) = schema.get("minProperties").and_then(Value::as_u64) {
+        if count < min {
+            push(
+                violations,
+                path,
+                format!("must have at least {min} properties"),
+            );
+        }
+    }
+    if let Some(max) = schema.get("maxProperties").and_then(Value::as_u64) {
+        if count > max {
+            push(
+                violations,
+                path,
+                format!("must have at most {max} properties"),
+            );
+        }
+    }
+
+    for (name, member) in members {
+        let member_path = format!("{path}/{}", escape_pointer(name));
+        match (property(name), schema.get("additionalProperties")) {
+            (Some(member_schema), _) => {
+                validate_value(member_schema, member, &member_path, direction, violations);
+            }
+            (None, Some(Value::Bool(false))) => push(
+                violations,
+                &member_path,
+                format!("unknown property \"{name}\""),
+            ),
+            (None, Some(extra_schema)) => {
+                validate_value(extra_schema, member, &member_path, direction, violations);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn check_composition(
+    schema: &Map<String, Value>,
+    instance: &Value,
+    path: &str,
+    direction: Direction,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    if let Some(Value::Array(schemas)) = schema.get("allOf") {
+        for sub_schema in schemas {
+            validate_value(sub_schema, instance, path, direction, violations);
+        }
+    }
+    if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+        if !schemas.is_empty() && !schemas.iter().any(|s| is_valid(s, instance, direction)) {
+            push(
+                violations,
+                path,
+                "does not match any of the allowed schemas (anyOf)".to_string(),
+            );
+        }
+    }
+    // Unresolved references accept anything, so matching several `oneOf`
+    // alternatives or a `not` schema with references proves nothing
+    if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+        if !schemas.is_empty() && !schemas.iter().any(|s| is_valid(s, instance, direction)) {
+            push(
+                violations,
+                path,
+                "does not match any of the allowed schemas (oneOf)".to_string(),
+            );
+        }
+    }
+    if let Some(not_schema) = schema.get("not") {
+        if !has_reference(not_schema) && is_valid(not_schema, instance, direction) {
+            push(
+                violations,
+                path,
+                "matches a schema it must not match (not)".to_string(),
+            );
+        }
+    }
+}
+
+fn has_reference(schema: &Value) -> bool {
+    match schema {
+        Value::Object(members) => {
+            members.contains_key("$ref") || members.values().any(has_reference)
+        }
+        Value::Array(items) => items.iter().any(has_reference),
+        _ => false,
+    }
+}
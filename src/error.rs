@@ -460,6 +460,110 @@ impl Error {
         }
     }
 
+    /// Create an error for a secret that could not be read from its source
+    pub fn secret_unavailable(
+        scheme_name: impl Into<String>,
+        secret: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        let scheme_name = scheme_name.into();
+        let secret = secret.into();
+        let reason = reason.into();
+        Self::Internal {
+            kind: ErrorKind::Authentication,
+            message: Cow::Owned(format!(
+                "Failed to read the secret for authentication '{scheme_name}' from {secret}: {reason}"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({ "scheme_name": scheme_name, "secret": secret, "reason": reason })),
+                Some(Cow::Borrowed(
+                    "Check the secret mapping with 'aperture config secret list <api>'.",
+                )),
+            )),
+        }
+    }
+
+    /// Create an error for a missing secret vault
+    pub fn vault_not_found(path: impl Into<String>) -> Self {
+        let path = path.into();
+        Self::Internal {
+            kind: ErrorKind::Validation,
+            message: Cow::Owned(format!("No secret vault found at '{path}'")),
+            context: Some(ErrorContext::new(
+                Some(json!({ "path": path })),
+                Some(Cow::Borrowed(
+                    "Create one with 'aperture config secret vault init'.",
+                )),
+            )),
+        }
+    }
+
+    /// Create an error for initializing a vault that already exists
+    pub fn vault_already_exists(path: impl Into<String>) -> Self {
+        let path = path.into();
+        Self::Internal {
+            kind: ErrorKind::Validation,
+            message: Cow::Owned(format!("A secret vault already exists at '{path}'")),
+            context: Some(ErrorContext::new(
+                Some(json!({ "path": path })),
+                Some(Cow::Borrowed(
+                    "Use --force to replace it. Existing entries will be lost.",
+                )),
+            )),
+        }
+    }
+
+    /// Create an error for a missing vault entry
+    #[must_use]
+    pub fn vault_entry_not_found(name: &str) -> Self {
+        Self::Internal {
+            kind: ErrorKind::Validation,
+            message: Cow::Owned(format!("No entry named '{name}' in the secret vault")),
+            context: Some(ErrorContext::new(
+                Some(json!({ "name": name })),
+                Some(Cow::Borrowed(
+                    "List entries with 'aperture config secret vault list'.",
+                )),
+            )),
+        }
+    }
+
+    /// Create an error for a vault that cannot be decrypted
+    #[must_use]
+    pub fn vault_decryption_failed(path: &str) -> Self {
+        Self::Internal {
+            kind: ErrorKind::Authentication,
+            message: Cow::Owned(format!(
+                "Failed to decrypt the secret vault at '{path}': wrong passphrase or corrupted file"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({ "path": path })),
+                Some(Cow::Owned(format!(
+                    "Check the passphrase in {}.",
+                    constants::ENV_APERTURE_VAULT_PASSPHRASE
+                ))),
+            )),
+        }
+    }
+
+    /// Create an error for a vault passphrase that is neither set nor promptable
+    #[must_use]
+    pub fn vault_passphrase_required() -> Self {
+        Self::Internal {
+            kind: ErrorKind::Authentication,
+            message: Cow::Borrowed(
+                "The secret vault passphrase is required but no terminal is available to prompt for it",
+            ),
+            context: Some(ErrorContext::new(
+                Some(json!({ "env_var": constants::ENV_APERTURE_VAULT_PASSPHRASE })),
+                Some(Cow::Owned(format!(
+                    "Set {} to the vault passphrase.",
+                    constants::ENV_APERTURE_VAULT_PASSPHRASE
+                ))),
+            )),
+        }
+    }
+
     /// Create an unsupported auth scheme error
     pub fn unsupported_auth_scheme(scheme: impl Into<String>) -> Self {
         let scheme = scheme.into();
@@ -484,7 +588,91 @@ impl Error {
             context: Some(ErrorContext::new(
                 Some(json!({ "scheme_type": scheme_type })),
                 Some(Cow::Borrowed(
-                    "Only 'apiKey' and 'http' security schemes are supported.",
+                    "Only 'apiKey', 'http', and 'oauth2' security schemes are supported.",
+                )),
+            )),
+        }
+    }
+
+    /// Create an invalid `OAuth2` client credentials error
+    pub fn invalid_oauth2_credentials(
+        scheme_name: impl Into<String>,
+        secret: impl Into<String>,
+    ) -> Self {
+        let scheme_name = scheme_name.into();
+        let secret = secret.into();
+        Self::Internal {
+            kind: ErrorKind::Authentication,
+            message: Cow::Owned(format!(
+                "The secret from {secret} for OAuth2 scheme '{scheme_name}' must contain 'client_id:client_secret'"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({ "scheme_name": scheme_name, "secret": secret })),
+                Some(Cow::Borrowed(
+                    "Provide your client credentials in the form \"my-client:my-secret\".",
+                )),
+            )),
+        }
+    }
+
+    /// Create an `OAuth2` token request failure error
+    pub fn oauth2_token_request_failed(
+        scheme_name: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        let scheme_name = scheme_name.into();
+        let reason = reason.into();
+        Self::Internal {
+            kind: ErrorKind::Authentication,
+            message: Cow::Owned(format!(
+                "Failed to obtain OAuth2 access token for '{scheme_name}': {reason}"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({ "scheme_name": scheme_name, "reason": reason })),
+                Some(Cow::Borrowed(
+                    "Check the client credentials and that the token endpoint is reachable.",
+                )),
+            )),
+        }
+    }
+
+    /// Create an error for an `OAuth2` scheme that needs interactive authorization
+    pub fn oauth2_login_required(
+        api_name: impl Into<String>,
+        scheme_name: impl Into<String>,
+    ) -> Self {
+        let api_name = api_name.into();
+        let scheme_name = scheme_name.into();
+        Self::Internal {
+            kind: ErrorKind::Authentication,
+            message: Cow::Owned(format!(
+                "No usable OAuth2 token for '{scheme_name}'; authorization is required"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({ "api_name": api_name, "scheme_name": scheme_name })),
+                Some(Cow::Owned(format!(
+                    "Run 'aperture auth login {api_name} --scheme {scheme_name}' to authorize."
+                ))),
+            )),
+        }
+    }
+
+    /// Create an `OAuth2` authorization (login) failure error
+    pub fn oauth2_authorization_failed(
+        scheme_name: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        let scheme_name = scheme_name.into();
+        let reason = reason.into();
+        Self::Internal {
+            kind: ErrorKind::Authentication,
+            message: Cow::Owned(format!(
+                "OAuth2 authorization for '{scheme_name}' failed: {reason}"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({ "scheme_name": scheme_name, "reason": reason })),
+                Some(Cow::Borrowed(
+                    "Retry the login and complete the authorization in your browser.",
                 )),
             )),
         }
@@ -528,6 +716,94 @@ impl Error {
         }
     }
 
+    /// Create an error for request values that violate the operation's schema
+    #[must_use]
+    pub fn request_validation_failed(
+        operation_id: &str,
+        violations: &[crate::engine::validation::SchemaViolation],
+    ) -> Self {
+        let summary = violations
+            .iter()
+            .map(|violation| format!("{}: {}", violation.path, violation.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Self::Internal {
+            kind: ErrorKind::Validation,
+            message: Cow::Owned(format!(
+                "Request for '{operation_id}' does not match the API schema: {summary}"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({ "operation_id": operation_id, "violations": violations })),
+                Some(Cow::Borrowed(
+                    "Fix the listed values, or pass --no-validate to send the request anyway.",
+                )),
+            )),
+        }
+    }
+
+    /// Create an error for a response that does not match the operation's
+    /// documented responses
+    #[must_use]
+    pub fn response_contract_violation(
+        operation_id: &str,
+        status: u16,
+        violations: &[crate::engine::validation::SchemaViolation],
+    ) -> Self {
+        let summary = violations
+            .iter()
+            .map(|violation| format!("{}: {}", violation.path, violation.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Self::Internal {
+            kind: ErrorKind::Validation,
+            message: Cow::Owned(format!(
+                "Response from '{operation_id}' (status {status}) does not match the API spec: {summary}"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({
+                    "operation_id": operation_id,
+                    "status": status,
+                    "violations": violations
+                })),
+                Some(Cow::Borrowed(
+                    "The API differs from its registered spec. Update the spec or report the difference to the API owner.",
+                )),
+            )),
+        }
+    }
+
+    /// Adds the contract violations of an error response to an HTTP error
+    #[must_use]
+    pub fn with_contract_violations(
+        mut self,
+        violations: &[crate::engine::validation::SchemaViolation],
+    ) -> Self {
+        if violations.is_empty() {
+            return self;
+        }
+        if let Self::Internal {
+            message,
+            context:
+                Some(ErrorContext {
+                    details: Some(serde_json::Value::Object(details)),
+                    ..
+                }),
+            ..
+        } = &mut self
+        {
+            let summary = violations
+                .iter()
+                .map(|violation| format!("{}: {}", violation.path, violation.message))
+                .collect::<Vec<_>>()
+                .join("; ");
+            *message = Cow::Owned(format!(
+                "{message} (the response does not match the API spec: {summary})"
+            ));
+            details.insert("contract_violations".to_string(), json!(violations));
+        }
+        self
+    }
+
     /// Create an invalid path error
     pub fn invalid_path(path: impl Into<String>, reason: impl Into<String>) -> Self {
         let path = path.into();
@@ -881,6 +1157,24 @@ impl Error {
         }
     }
 
+    /// Create an error for a replayed request with no recorded response
+    #[must_use]
+    pub fn cassette_miss(method: &str, url: &str, dir: &std::path::Path) -> Self {
+        let dir = dir.display().to_string();
+        Self::Internal {
+            kind: ErrorKind::Runtime,
+            message: Cow::Owned(format!(
+                "No recorded response in '{dir}' matches {method} {url}"
+            )),
+            context: Some(ErrorContext::new(
+                Some(json!({ "method": method, "url": url, "cassette_dir": dir })),
+                Some(Cow::Owned(format!(
+                    "Record the request first with --record {dir}"
+                ))),
+            )),
+        }
+    }
+
     /// Create a serialization error
     pub fn serialization_error(reason: impl Into<String>) -> Self {
         let reason = reason.into();
@@ -0,0 +1,344 @@
+//! Local authenticated HTTP gateway for a cached specification.
+//!
+//! [`Gateway`] exposes a registered API on a loopback port as a reverse
+//! proxy. Requests are routed by method and path template as the mock server
+//! routes them, so only the operations the spec documents are reachable, and
+//! each one becomes an [`OperationCall`] run through [`executor::execute`]:
+//! the configured secrets are injected, the base URL is resolved as for
+//! `aperture api`, and retries and the response cache apply.
+//!
+//! Only the parameters an operation declares are forwarded. Credentials a
+//! client sends along, such as its own `Authorization` header, never reach
+//! the upstream API. Requests must name the gateway itself in `Host`, so a
+//! web page cannot reach it through a DNS name rebound to `127.0.0.1`, and
+//! requests a browser marks as sent by a web page (`Origin`, or a cross-site
+//! `Sec-Fetch-Site`) are refused. A body is only forwarded under the content
+//! type the operation takes, so a plain-text form post is never read as JSON.
+
+use crate::cache::models::{CachedCommand, CachedSpec};
+use crate::constants;
+use crate::engine::{executor, validation};
+use crate::error::{Error, ErrorKind};
+use crate::invocation::{ExecutionContext, ExecutionResult, OperationCall, RawBody};
+use crate::mock::{self, MockRequest, MockResponse};
+use serde_json::{json, Map, Value};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+
+/// A gateway bound to a loopback port
+#[derive(Debug)]
+pub struct Gateway {
+    spec: Arc<CachedSpec>,
+    context: Arc<ExecutionContext>,
+    listener: TcpListener,
+}
+
+impl Gateway {
+    /// Binds the gateway to `127.0.0.1:port`. Every forwarded request runs
+    /// with a copy of `context`.
+    ///
+    /// Pass `port = 0` to let the operating system choose a free port.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listener cannot be bound.
+    pub async fn bind(
+        spec: CachedSpec,
+        context: ExecutionContext,
+        port: u16,
+    ) -> Result<Self, Error> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .await
+            .map_err(|e| {
+                Error::io_error(format!(
+                    "Failed to listen on 127.0.0.1:{port} for the gateway: {e}"
+                ))
+            })?;
+        Ok(Self {
+            spec: Arc::new(spec),
+            context: Arc::new(context),
+            listener,
+        })
+    }
+
+    /// Address the gateway is listening on
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listener address cannot be read.
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        self.listener
+            .local_addr()
+            .map_err(|e| Error::io_error(format!("Failed to read listener address: {e}")))
+    }
+
+    /// Serves requests until the process exits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if accepting a connection fails.
+    pub async fn run(self) -> Result<(), Error> {
+        let port = self.local_addr()?.port();
+        loop {
+            let (stream, _) = self.listener.accept().await.map_err(|e| {
+                Error::io_error(format!("Failed to accept gateway connection: {e}"))
+            })?;
+            let spec = Arc::clone(&self.spec);
+            let context = Arc::clone(&self.context);
+            tokio::spawn(async move { serve_connection(stream, &spec, &context, port).await });
+        }
+    }
+}
+
+async fn serve_connection(
+    mut stream: TcpStream,
+    spec: &CachedSpec,
+    context: &ExecutionContext,
+    port: u16,
+) {
+    let request = mock::read_request(&mut stream)
+        .await
+        .and_then(|request| check_local_request(&request, port).map(|()| request));
+    let response = match request {
+        Ok(request) => {
+            let response = handle_request(spec, context, &request).await;
+            tracing::info!(
+                target: "aperture::gateway",
+                "{} {} -> {}",
+                request.method,
+                request.target,
+                response.status
+            );
+            response
+        }
+        Err(response) => response,
+    };
+    mock::write_response(&mut stream, &response).await;
+}
+
+/// Refuses with `403` a request that does not name the gateway in `Host` or
+/// that a browser sent on behalf of a web page
+fn check_local_request(request: &MockRequest, port: u16) -> Result<(), MockResponse> {
+    let message = if !is_local_host(request.header(constants::HEADER_HOST), port) {
+        format!("The Host header must be 127.0.0.1:{port} or localhost:{port}")
+    } else if request.header(constants::HEADER_ORIGIN).is_some()
+        || request
+            .header(constants::HEADER_SEC_FETCH_SITE)
+            .is_some_and(|site| site != "none" && site != "same-origin")
+    {
+        "Requests sent by web pages are not forwarded".to_string()
+    } else {
+        return Ok(());
+    };
+    Err(MockResponse::json(403, &json!({ "message": message })))
+}
+
+/// Whether `host` names the gateway's own loopback address
+fn is_local_host(host: Option<&str>, port: u16) -> bool {
+    host.and_then(|host| host.rsplit_once(':'))
+        .is_some_and(|(name, host_port)| {
+            (name == "127.0.0.1" || name.eq_ignore_ascii_case("localhost"))
+                && host_port.parse() == Ok(port)
+        })
+}
+
+/// Forwards a request to the operation of `spec` it matches.
+///
+/// Unknown paths get `404`, known paths with another method `405`, bodies
+/// sent under another content type than the operation's `415`, and requests
+/// that do not match the operation's parameters or body `400` without
+/// reaching the upstream API. Upstream responses are relayed with
+/// their status, content type and body; failures to reach the API get `502`.
+pub async fn handle_request(
+    spec: &CachedSpec,
+    context: &ExecutionContext,
+    request: &MockRequest,
+) -> MockResponse {
+    let (command, mut call, mut violations) = match mock::resolve_request(spec, request) {
+        Ok(resolved) => resolved,
+        Err(response) => return response,
+    };
+    if let Err(response) = check_content_type(command, request) {
+        return response;
+    }
+    violations.extend(validation::request_violations(command, &call));
+    if !violations.is_empty() {
+        return mock::violations_response(command, &violations);
+    }
+    if let Err(response) = attach_body(command, request, &mut call) {
+        return response;
+    }
+
+    // The request was validated above, against what the client sent
+    let context = ExecutionContext {
+        skip_validation: true,
+        ..context.clone()
+    };
+    match executor::execute(spec, call, context).await {
+        Ok(result) => result_response(result),
+        Err(error) => error_response(&error),
+    }
+}
+
+/// Refuses a body whose `Content-Type` is not the one the operation takes
+fn check_content_type(command: &CachedCommand, request: &MockRequest) -> Result<(), MockResponse> {
+    let Some(request_body) = &command.request_body else {
+        return Ok(());
+    };
+    if request.body.is_empty() {
+        return Ok(());
+    }
+
+    let expected = request_body.content_type.as_str();
+    let matches = request
+        .header(constants::HEADER_CONTENT_TYPE)
+        .and_then(|value| value.split(';').next())
+        .map(str::trim)
+        .is_some_and(|media_type| {
+            expected.strip_suffix("/*").map_or_else(
+                || media_type.eq_ignore_ascii_case(expected),
+                |main_type| {
+                    media_type
+                        .split_once('/')
+                        .is_some_and(|(sent, _)| sent.eq_ignore_ascii_case(main_type))
+                },
+            )
+        });
+    if matches {
+        return Ok(());
+    }
+    Err(MockResponse::json(
+        415,
+        &json!({
+            "message": format!(
+                "Operation '{}' takes a '{expected}' body",
+                command.operation_id
+            )
+        }),
+    ))
+}
+
+/// Passes on a body the mock routing leaves out: only JSON bodies are read
+/// into the call there
+fn attach_body(
+    command: &CachedCommand,
+    request: &MockRequest,
+    call: &mut OperationCall,
+) -> Result<(), MockResponse> {
+    let Some(request_body) = &command.request_body else {
+        return Ok(());
+    };
+    let content_type = request_body.content_type.as_str();
+    if request.body.is_empty() || constants::is_json_content_type(content_type) {
+        return Ok(());
+    }
+
+    if crate::spec::is_binary_content_type(content_type) {
+        call.raw_body = Some(RawBody::Bytes(request.body.clone()));
+    } else if content_type == constants::CONTENT_TYPE_FORM {
+        let fields: Map<String, Value> = reqwest::Url::parse(&format!(
+            "http://127.0.0.1/?{}",
+            String::from_utf8_lossy(&request.body)
+        ))
+        .map(|url| {
+            url.query_pairs()
+                .map(|(key, value)| (key.into_owned(), Value::String(value.into_owned())))
+                .collect()
+        })
+        .unwrap_or_default();
+        call.body = Some(Value::Object(fields).to_string());
+    } else if content_type == constants::CONTENT_TYPE_MULTIPART {
+        return Err(MockResponse::json(
+            415,
+            &json!({"message": "multipart/form-data bodies cannot be forwarded by the gateway"}),
+        ));
+    } else {
+        call.body = Some(String::from_utf8_lossy(&request.body).into_owned());
+    }
+    Ok(())
+}
+
+fn result_response(result: ExecutionResult) -> MockResponse {
+    match result {
+        ExecutionResult::Success {
+            body,
+            status,
+            headers,
+        } => MockResponse {
+            status,
+            content_type: headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(constants::HEADER_CONTENT_TYPE))
+                .map(|(_, value)| value.clone()),
+            body: body.into_bytes(),
+        },
+        ExecutionResult::Binary {
+            body,
+            content_type,
+            status,
+            ..
+        } => MockResponse {
+            status,
+            content_type,
+            body,
+        },
+        ExecutionResult::Cached { body } => MockResponse {
+            status: 200,
+            content_type: json_content_type(&body),
+            body: body.into_bytes(),
+        },
+        ExecutionResult::DryRun { request_info } => MockResponse::json(200, &request_info),
+        ExecutionResult::Empty => MockResponse {
+            status: 204,
+            content_type: None,
+            body: Vec::new(),
+        },
+    }
+}
+
+/// Relays an upstream error response as it was received; other failures get
+/// `400` for invalid input, `502` when the API could not be reached, and
+/// `500` otherwise, with the error as JSON
+fn error_response(error: &Error) -> MockResponse {
+    let error_json = error.to_json();
+    let upstream = error_json.details.as_ref().and_then(|details| {
+        let status = u16::try_from(details.get("status")?.as_u64()?).ok()?;
+        let body = details.get("response_body")?.as_str()?;
+        Some((status, body))
+    });
+    if let Some((status, body)) = upstream {
+        // The executor substitutes a placeholder for an empty body
+        let body = if body == constants::EMPTY_RESPONSE {
+            ""
+        } else {
+            body
+        };
+        return MockResponse {
+            status,
+            content_type: json_content_type(body),
+            body: body.as_bytes().to_vec(),
+        };
+    }
+
+    let status = match error {
+        Error::Internal {
+            kind: ErrorKind::Validation,
+            ..
+        } => 400,
+        Error::Network(_)
+        | Error::Internal {
+            kind: ErrorKind::Network,
+            ..
+        } => 502,
+        _ => 500,
+    };
+    MockResponse::json(status, &json!(error_json))
+}
+
+/// A relayed body whose content type is unknown is labelled JSON when it is
+fn json_content_type(body: &str) -> Option<String> {
+    serde_json::from_str::<Value>(body)
+        .is_ok()
+        .then(|| constants::CONTENT_TYPE_JSON.to_string())
+}
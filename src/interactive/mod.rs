@@ -78,6 +78,15 @@ pub fn prompt_for_input(prompt: &str) -> Result<String, Error> {
     prompt_for_input_with_io(prompt, &io)
 }
 
+/// Prompt the user for a secret on the terminal without echoing it
+///
+/// # Errors
+/// Returns an error if the terminal cannot be read
+pub fn prompt_for_secret(prompt: &str) -> Result<String, Error> {
+    rpassword::prompt_password(prompt)
+        .map_err(|e| Error::io_error(format!("Failed to read from the terminal: {e}")))
+}
+
 /// Prompt the user for input with a custom timeout
 ///
 /// # Errors
@@ -5,17 +5,21 @@
 //! and returns [`ExecutionResult`], enabling library/SDK usage, alternative
 //! frontends, and unit testing without CLI parsing dependencies.
 
-use crate::config::models::GlobalConfig;
-use crate::engine::executor::RetryContext;
+use crate::cassette::Cassette;
+use crate::config::models::{GlobalConfig, ResponseValidation};
+use crate::engine::executor::{RetryContext, SharedHttpClient};
+use crate::oauth::TokenStore;
 use crate::response_cache::CacheConfig;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Describes a single API operation to invoke, fully resolved from user input.
 ///
 /// All parameter values are pre-extracted and categorized by their `OpenAPI`
-/// location (path, query, header). This struct is framework-agnostic — it
-/// can be constructed from clap `ArgMatches`, a GUI form, or programmatically.
+/// location (path, query, header, cookie). This struct is framework-agnostic —
+/// it can be constructed from clap `ArgMatches`, a GUI form, or programmatically.
 #[derive(Debug, Clone)]
 pub struct OperationCall {
     /// The `operationId` from the `OpenAPI` spec (e.g., `"getUserById"`).
@@ -30,13 +34,68 @@ pub struct OperationCall {
     /// Header parameters keyed by name (e.g., `{"X-Request-Id": "abc"}`).
     pub header_params: HashMap<String, String>,
 
+    /// Cookie parameters keyed by name (e.g., `{"session_id": "abc"}`).
+    ///
+    /// Sent together in a single `Cookie` header.
+    pub cookie_params: HashMap<String, String>,
+
+    /// Array and object parameters keyed by name, for any location.
+    ///
+    /// Serialized according to the parameter's `style` and `explode`
+    /// settings (e.g., `tag=a&tag=b` or `filter[status]=open`).
+    pub multi_params: HashMap<String, ParamValues>,
+
     /// Optional JSON request body.
+    ///
+    /// For `multipart/form-data` and `application/x-www-form-urlencoded`
+    /// operations this is a JSON object whose top-level members become the
+    /// form fields.
     pub body: Option<String>,
 
+    /// Files to send as parts of a `multipart/form-data` body.
+    pub files: Vec<FilePart>,
+
+    /// Raw request body for operations taking binary content such as
+    /// `application/octet-stream`. Takes precedence over `body`.
+    pub raw_body: Option<RawBody>,
+
     /// Custom headers in raw `"Name: Value"` format, as provided by the user.
     pub custom_headers: Vec<String>,
 }
 
+/// A file attached to a `multipart/form-data` request body.
+///
+/// The file is read when the request is sent, so its contents are never held
+/// in memory as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePart {
+    /// Name of the form field the file is sent as.
+    pub field: String,
+
+    /// Path of the file to upload.
+    pub path: PathBuf,
+}
+
+/// The items of an array or object parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamValues {
+    /// Array items, in order.
+    Array(Vec<String>),
+
+    /// Object members as `(key, value)` pairs, in order.
+    Object(Vec<(String, String)>),
+}
+
+/// Source of a raw binary request body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawBody {
+    /// A file streamed from disk when the request is sent.
+    File(PathBuf),
+
+    /// Bytes already in memory, e.g. read from stdin.
+    Bytes(Vec<u8>),
+}
+
 /// Execution-time configuration that is orthogonal to the operation itself.
 ///
 /// Controls retry behavior, caching, dry-run mode, and authentication
@@ -54,10 +113,17 @@ pub enum ProxyOverride {
 }
 
 #[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ExecutionContext {
     /// If true, show the request that would be made without executing it.
     pub dry_run: bool,
 
+    /// If true, describe the request instead of sending it, with every secret
+    /// replaced by a reference to the environment variable expected to hold
+    /// it, so the description can be shared or turned into a snippet. The URL
+    /// and header values are described as lists of [`ValueSegment`]s.
+    pub secret_references: bool,
+
     /// Optional idempotency key for safe retries.
     pub idempotency_key: Option<String>,
 
@@ -81,6 +147,41 @@ pub struct ExecutionContext {
 
     /// If true, loop through all pages and stream results as NDJSON.
     pub auto_paginate: bool,
+
+    /// Persistent store for `OAuth2` access tokens. `None` fetches a fresh
+    /// token for every invocation.
+    pub token_store: Option<TokenStore>,
+
+    /// Security scheme to authenticate with. `None` uses the first
+    /// requirement set whose secrets are all available.
+    pub auth_scheme: Option<String>,
+
+    /// If true, send the request without checking parameters and body
+    /// against the operation's schema.
+    pub skip_validation: bool,
+
+    /// How the response is checked against the spec. `None` uses the
+    /// API's configured mode.
+    pub response_validation: Option<ResponseValidation>,
+
+    /// Directory of recorded exchanges to write or answer requests from.
+    /// `None` sends requests without recording them.
+    pub cassette: Option<Cassette>,
+
+    /// HTTP client to send with, shared across invocations. `None` builds a
+    /// client for this invocation from the proxy settings above.
+    pub http_client: Option<SharedHttpClient>,
+}
+
+/// A run of a URL or header value described with
+/// [`ExecutionContext::secret_references`] set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueSegment {
+    /// Text sent as written
+    Text(String),
+    /// The value of an environment variable holding a secret
+    Env(String),
 }
 
 /// Structured result returned by the executor. The CLI layer decides how
@@ -97,6 +198,19 @@ pub enum ExecutionResult {
         headers: HashMap<String, String>,
     },
 
+    /// Successful HTTP response whose body is binary, such as an image or a
+    /// PDF, or is not valid UTF-8.
+    Binary {
+        /// Raw response body.
+        body: Vec<u8>,
+        /// The response `Content-Type`, if the server sent one.
+        content_type: Option<String>,
+        /// HTTP status code.
+        status: u16,
+        /// Response headers.
+        headers: HashMap<String, String>,
+    },
+
     /// Dry-run mode: the request that *would* have been sent.
     DryRun {
         /// Structured JSON representation of the request.
@@ -0,0 +1,59 @@
+//! JSON-RPC 2.0 messages shared by the MCP server and the daemon.
+
+use serde_json::{json, Value};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// A JSON-RPC error code with its message and optional data
+pub type RpcError = (i64, String, Option<Value>);
+
+#[must_use]
+pub fn invalid_params(message: &str) -> RpcError {
+    (INVALID_PARAMS, message.to_string(), None)
+}
+
+#[must_use]
+pub fn method_not_found(method: &str) -> RpcError {
+    (
+        METHOD_NOT_FOUND,
+        format!("Method not found: {method}"),
+        None,
+    )
+}
+
+/// The response to a message that is not valid JSON
+#[must_use]
+pub fn parse_error_response(error: &serde_json::Error) -> Value {
+    error_response(
+        &Value::Null,
+        (PARSE_ERROR, format!("Parse error: {error}"), None),
+    )
+}
+
+/// The response to a message without a method
+#[must_use]
+pub fn invalid_request_response(message: &Value) -> Value {
+    let id = message.get("id").cloned().unwrap_or(Value::Null);
+    error_response(&id, (INVALID_REQUEST, "Invalid request".to_string(), None))
+}
+
+/// The response carrying a method's result or error
+#[must_use]
+pub fn response(id: &Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => error_response(id, error),
+    }
+}
+
+#[must_use]
+pub fn error_response(id: &Value, (code, message, data): RpcError) -> Value {
+    let mut error = json!({ "code": code, "message": message });
+    if let Some(data) = data {
+        error["data"] = data;
+    }
+    json!({ "jsonrpc": "2.0", "id": id, "error": error })
+}
@@ -2,27 +2,37 @@ pub mod agent;
 pub mod atomic;
 pub mod batch;
 pub mod cache;
+pub mod cassette;
 pub mod cli;
 pub mod command_guidance;
 pub mod config;
 pub mod constants;
+#[cfg(unix)]
+pub mod daemon;
 pub mod discovery_style;
 pub mod docs;
 pub mod duration;
 pub mod engine;
 pub mod error;
 pub mod fs;
+pub mod gateway;
 pub mod interactive;
 pub mod invocation;
+pub mod jsonrpc;
 pub mod logging;
+pub mod mcp;
+pub mod mock;
+pub mod oauth;
 pub mod output;
 pub mod pagination;
 pub mod resilience;
 pub mod response_cache;
 pub mod search;
 pub mod shortcuts;
+pub mod snippet;
 pub mod spec;
 pub mod suggestions;
+pub mod tool_export;
 pub mod utils;
 
 // Unit tests in src/ call reqwest::Client directly and have no access to
@@ -4,7 +4,7 @@
 //! with built-in automatic redaction of sensitive information including:
 //! - Authorization headers
 //! - API keys in query parameters
-//! - Values matching configured `x-aperture-secret` environment variables
+//! - Values resolved from configured `x-aperture-secret` and config-based secrets
 
 use crate::cache::models::CachedSpec;
 use crate::config::models::GlobalConfig;
@@ -16,22 +16,32 @@ const MIN_SECRET_LENGTH_FOR_BODY_REDACTION: usize = 8;
 
 /// Context containing resolved secret values for dynamic redaction.
 ///
-/// This struct collects actual secret values from environment variables
-/// referenced by `x-aperture-secret` extensions and config-based secrets,
-/// allowing them to be redacted from logs wherever they appear.
+/// This struct collects actual secret values from the sources referenced by
+/// `x-aperture-secret` extensions and config-based secrets (environment
+/// variables, files, helper commands, or dotenv entries), allowing them to be
+/// redacted from logs wherever they appear.
 #[derive(Debug, Default, Clone)]
 pub struct SecretContext {
     /// Resolved secret values that should be redacted
     secrets: Vec<String>,
+    /// Query parameters that carry `apiKey` secrets
+    query_params: Vec<String>,
+    /// Headers that carry `apiKey` secrets
+    header_params: Vec<String>,
 }
 
 /// Collects non-empty secret values from spec's security schemes.
-fn collect_secrets_from_spec(spec: &CachedSpec, secrets: &mut Vec<String>) {
+fn collect_secrets_from_spec(spec: &CachedSpec, api_name: &str, secrets: &mut Vec<String>) {
     for scheme in spec.security_schemes.values() {
         let Some(ref aperture_secret) = scheme.aperture_secret else {
             continue;
         };
-        let Ok(value) = std::env::var(&aperture_secret.name) else {
+        let Ok(value) = crate::config::secrets::resolve_named_source(
+            &aperture_secret.source,
+            &aperture_secret.name,
+            api_name,
+            &scheme.name,
+        ) else {
             continue;
         };
         if !value.is_empty() {
@@ -52,8 +62,10 @@ fn collect_secrets_from_config(
     let Some(api_config) = config.api_configs.get(api_name) else {
         return;
     };
-    for secret in api_config.secrets.values() {
-        let Ok(value) = std::env::var(&secret.name) else {
+    for (scheme_name, secret) in &api_config.secrets {
+        let Ok(value) =
+            crate::config::secrets::resolve(secret.source, &secret.name, api_name, scheme_name)
+        else {
             continue;
         };
         if !value.is_empty() {
@@ -71,7 +83,7 @@ impl SecretContext {
 
     /// Creates a `SecretContext` by collecting secrets from the spec and config.
     ///
-    /// This resolves environment variables referenced by:
+    /// This resolves the secrets referenced by:
     /// 1. `x-aperture-secret` extensions in the `OpenAPI` spec's security schemes
     /// 2. Config-based secrets in the global configuration
     ///
@@ -88,7 +100,7 @@ impl SecretContext {
         let mut secrets = Vec::new();
 
         // Collect secrets from x-aperture-secret extensions in security schemes
-        collect_secrets_from_spec(spec, &mut secrets);
+        collect_secrets_from_spec(spec, api_name, &mut secrets);
 
         // Collect secrets from config-based secrets
         collect_secrets_from_config(global_config, api_name, &mut secrets);
@@ -97,7 +109,26 @@ impl SecretContext {
         secrets.sort();
         secrets.dedup();
 
-        Self { secrets }
+        let query_params = spec
+            .security_schemes
+            .values()
+            .filter_map(|scheme| scheme.query_parameter_name().map(str::to_string))
+            .collect();
+        let header_params = spec
+            .security_schemes
+            .values()
+            .filter(|scheme| {
+                scheme.scheme_type == crate::constants::AUTH_SCHEME_APIKEY
+                    && scheme.location.as_deref() == Some(crate::constants::PARAM_LOCATION_HEADER)
+            })
+            .filter_map(|scheme| scheme.parameter_name.clone())
+            .collect();
+
+        Self {
+            secrets,
+            query_params,
+            header_params,
+        }
     }
 
     /// Checks if a value exactly matches any of the secrets.
@@ -126,6 +157,20 @@ impl SecretContext {
     pub const fn has_secrets(&self) -> bool {
         !self.secrets.is_empty()
     }
+
+    /// Checks if a query parameter carries an `apiKey` secret.
+    #[must_use]
+    pub fn is_secret_query_param(&self, name: &str) -> bool {
+        self.query_params.iter().any(|param| param == name)
+    }
+
+    /// Checks if a header carries an `apiKey` secret.
+    #[must_use]
+    pub fn is_secret_header(&self, name: &str) -> bool {
+        self.header_params
+            .iter()
+            .any(|param| param.eq_ignore_ascii_case(name))
+    }
 }
 
 /// Returns the canonical status text for an HTTP status code
@@ -230,8 +275,10 @@ fn should_redact_query_param(param_name: &str) -> bool {
 /// Redacts sensitive query parameters from a URL
 ///
 /// Returns the URL with sensitive parameter values replaced with `[REDACTED]`.
+/// Besides well-known names, this covers the query parameters of the spec's
+/// `apiKey` schemes when a secret context is given.
 #[must_use]
-pub fn redact_url_query_params(url: &str) -> String {
+pub fn redact_url_query_params(url: &str, secret_ctx: Option<&SecretContext>) -> String {
     // Find the query string start
     let Some(query_start) = url.find('?') else {
         return url.to_string();
@@ -259,7 +306,9 @@ pub fn redact_url_query_params(url: &str) -> String {
                 || param.to_string(),
                 |eq_pos| {
                     let name = &param[..eq_pos];
-                    if should_redact_query_param(name) {
+                    if should_redact_query_param(name)
+                        || secret_ctx.is_some_and(|ctx| ctx.is_secret_query_param(name))
+                    {
                         format!("{name}=[REDACTED]")
                     } else {
                         param.to_string()
@@ -292,7 +341,7 @@ pub fn log_request(
     secret_ctx: Option<&SecretContext>,
 ) {
     // Redact sensitive query parameters from URL before logging
-    let redacted_url = redact_url_query_params(url);
+    let redacted_url = redact_url_query_params(url, secret_ctx);
 
     // Log at info level: method, URL, and duration (duration added by caller)
     info!(
@@ -349,7 +398,8 @@ pub fn log_request(
 }
 
 /// Redacts a header value based on static rules and dynamic secret context.
-fn redact_header_value(
+#[must_use]
+pub fn redact_header_value(
     header_name: &str,
     value: &str,
     secret_ctx: Option<&SecretContext>,
@@ -359,8 +409,9 @@ fn redact_header_value(
         return "[REDACTED]".to_string();
     }
 
-    // Check if the value matches a dynamic secret
-    let is_dynamic_secret = secret_ctx.is_some_and(|ctx| ctx.is_secret(value));
+    // Check if the header or its value carries a dynamic secret
+    let is_dynamic_secret =
+        secret_ctx.is_some_and(|ctx| ctx.is_secret_header(header_name) || ctx.is_secret(value));
     if is_dynamic_secret {
         return "[REDACTED]".to_string();
     }
@@ -592,7 +643,7 @@ mod tests {
     #[test]
     fn test_redact_url_query_params_with_api_key() {
         let url = "https://api.example.com/users?api_key=secret123&page=1";
-        let redacted = redact_url_query_params(url);
+        let redacted = redact_url_query_params(url, None);
         assert_eq!(
             redacted,
             "https://api.example.com/users?api_key=[REDACTED]&page=1"
@@ -602,7 +653,7 @@ mod tests {
     #[test]
     fn test_redact_url_query_params_multiple_sensitive() {
         let url = "https://api.example.com/auth?token=abc123&secret=xyz789&user=john";
-        let redacted = redact_url_query_params(url);
+        let redacted = redact_url_query_params(url, None);
         assert_eq!(
             redacted,
             "https://api.example.com/auth?token=[REDACTED]&secret=[REDACTED]&user=john"
@@ -612,14 +663,14 @@ mod tests {
     #[test]
     fn test_redact_url_query_params_no_query_string() {
         let url = "https://api.example.com/users";
-        let redacted = redact_url_query_params(url);
+        let redacted = redact_url_query_params(url, None);
         assert_eq!(redacted, "https://api.example.com/users");
     }
 
     #[test]
     fn test_redact_url_query_params_with_fragment() {
         let url = "https://api.example.com/users?api_key=secret123#section";
-        let redacted = redact_url_query_params(url);
+        let redacted = redact_url_query_params(url, None);
         assert_eq!(
             redacted,
             "https://api.example.com/users?api_key=[REDACTED]#section"
@@ -629,7 +680,7 @@ mod tests {
     #[test]
     fn test_redact_url_query_params_empty_value() {
         let url = "https://api.example.com/users?api_key=&page=1";
-        let redacted = redact_url_query_params(url);
+        let redacted = redact_url_query_params(url, None);
         assert_eq!(
             redacted,
             "https://api.example.com/users?api_key=[REDACTED]&page=1"
@@ -639,10 +690,24 @@ mod tests {
     #[test]
     fn test_redact_url_query_params_no_sensitive() {
         let url = "https://api.example.com/users?page=1&limit=10";
-        let redacted = redact_url_query_params(url);
+        let redacted = redact_url_query_params(url, None);
         assert_eq!(redacted, "https://api.example.com/users?page=1&limit=10");
     }
 
+    #[test]
+    fn test_redact_url_query_params_with_api_key_scheme_param() {
+        let mut ctx = SecretContext::empty();
+        ctx.query_params = vec!["appid".to_string()];
+
+        let url = "https://api.example.com/weather?appid=abc123&q=London";
+        assert_eq!(
+            redact_url_query_params(url, Some(&ctx)),
+            "https://api.example.com/weather?appid=[REDACTED]&q=London"
+        );
+        // Without the context the custom name is not known to be sensitive
+        assert_eq!(redact_url_query_params(url, None), url);
+    }
+
     // SecretContext tests
 
     #[test]
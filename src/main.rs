@@ -174,6 +174,34 @@ async fn run_user_command(
             run_overview_command(manager, api.as_deref(), *all, format, output)
         }
         Commands::Completion { .. } | Commands::Complete { .. } => unreachable!(),
+        Commands::Auth { command } => {
+            aperture_cli::cli::commands::auth::execute_auth_command(command.clone(), output).await
+        }
+        Commands::Mcp { command } => {
+            aperture_cli::cli::commands::mcp::execute_mcp_command(manager, command.clone()).await
+        }
+        Commands::Daemon { socket } => {
+            aperture_cli::cli::commands::daemon::execute_daemon_command(
+                manager,
+                socket.as_deref(),
+                output,
+            )
+            .await
+        }
+        Commands::Mock { api, port } => {
+            aperture_cli::cli::commands::mock::execute_mock_command(api, *port, output).await
+        }
+        Commands::Serve {
+            api,
+            port,
+            cache,
+            cache_ttl,
+        } => {
+            aperture_cli::cli::commands::serve::execute_serve_command(
+                manager, api, *port, *cache, *cache_ttl, output,
+            )
+            .await
+        }
         Commands::Config { .. } => unreachable!("config commands are handled separately"),
     }
 }
@@ -0,0 +1,510 @@
+//! Model Context Protocol server.
+//!
+//! [`McpServer`] publishes every visible operation of one or more cached
+//! specifications as an MCP tool. Messages are newline-delimited JSON-RPC 2.0
+//! on stdin and stdout, as the protocol's stdio transport specifies. A tool's
+//! input schema has one property per parameter, keyed by the parameter's name
+//! in the spec, plus `body` for the request body; `tools/call` turns the
+//! arguments into an [`OperationCall`] and runs it through
+//! [`executor::execute`], so secrets, retries and caching behave as they do
+//! for `aperture api`.
+
+use crate::cache::models::{CachedCommand, CachedParameter, CachedRequestBody, CachedSpec};
+use crate::constants;
+use crate::engine::{executor, params};
+use crate::error::Error;
+use crate::invocation::{ExecutionContext, ExecutionResult, OperationCall, ParamValues, RawBody};
+use crate::jsonrpc::{self, invalid_params, RpcError};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Protocol revisions the server understands, newest first
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Name of the tool argument that carries the request body
+pub const BODY_ARGUMENT: &str = "body";
+
+/// Separates the two parts of a tool name
+const TOOL_NAME_SEPARATOR: &str = "__";
+
+/// Longest tool name that model providers accept
+const MAX_TOOL_NAME_LENGTH: usize = 64;
+
+/// Hex digits of the hash that tells shortened or clashing tool names apart
+const TOOL_NAME_HASH_LENGTH: usize = 8;
+
+/// An operation published as a tool
+#[derive(Debug, Clone)]
+struct Tool {
+    name: String,
+    spec_index: usize,
+    operation_id: String,
+    input_schema: Value,
+}
+
+/// Serves the operations of cached specifications as MCP tools
+#[derive(Debug)]
+pub struct McpServer {
+    specs: Vec<CachedSpec>,
+    tools: Vec<Tool>,
+    context: ExecutionContext,
+}
+
+impl McpServer {
+    /// Creates a server for `specs`. Every tool call runs with a copy of
+    /// `context`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an operation has two parameters with the same
+    /// name, which cannot both be tool arguments, or if two operations cannot
+    /// be given distinct tool names.
+    pub fn new(specs: Vec<CachedSpec>, context: ExecutionContext) -> Result<Self, Error> {
+        let mut names = ToolNames::default();
+        let mut tools = Vec::new();
+        for (spec_index, spec) in specs.iter().enumerate() {
+            for command in spec.commands.iter().filter(|command| !command.hidden) {
+                tools.push(Tool {
+                    name: names.assign(&spec.name, &command.operation_id)?,
+                    spec_index,
+                    operation_id: command.operation_id.clone(),
+                    input_schema: input_schema(command)?,
+                });
+            }
+        }
+
+        Ok(Self {
+            specs,
+            tools,
+            context,
+        })
+    }
+
+    /// Names of the published tools, in the order `tools/list` returns them
+    #[must_use]
+    pub fn tool_names(&self) -> Vec<&str> {
+        self.tools.iter().map(|tool| tool.name.as_str()).collect()
+    }
+
+    /// Reads messages from `reader` until it closes, writing each response
+    /// to `writer` on its own line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading or writing fails.
+    pub async fn serve<R, W>(&self, reader: R, mut writer: W) -> Result<(), Error>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_message(&line).await {
+                writer.write_all(response.as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+                writer.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles one JSON-RPC message and returns the response to send, if any.
+    /// Notifications get no response.
+    pub async fn handle_message(&self, message: &str) -> Option<String> {
+        let response = match serde_json::from_str::<Value>(message) {
+            Ok(Value::Array(batch)) => {
+                let mut responses = Vec::new();
+                for message in batch {
+                    responses.extend(self.handle_value(message).await);
+                }
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            Ok(message) => self.handle_value(message).await,
+            Err(e) => Some(jsonrpc::parse_error_response(&e)),
+        };
+        response.map(|response| response.to_string())
+    }
+
+    async fn handle_value(&self, message: Value) -> Option<Value> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // The server sends no requests, so a stray response is ignored
+            if message.get("result").is_some() || message.get("error").is_some() {
+                return None;
+            }
+            return Some(jsonrpc::invalid_request_response(&message));
+        };
+        let id = message.get("id")?.clone();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        tracing::debug!(target: "aperture::mcp", method, "Handling request");
+        let result = match method {
+            "initialize" => Ok(initialize_result(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": self.list_tools() })),
+            "tools/call" => self.call_tool(&params).await,
+            _ => Err(jsonrpc::method_not_found(method)),
+        };
+
+        Some(jsonrpc::response(&id, result))
+    }
+
+    fn list_tools(&self) -> Vec<Value> {
+        self.tools
+            .iter()
+            .filter_map(|tool| {
+                let command = self.command(tool)?;
+                Some(json!({
+                    "name": tool.name,
+                    "description": tool_description(command),
+                    "inputSchema": tool.input_schema,
+                    "annotations": {
+                        "readOnlyHint": matches!(command.method.as_str(), "GET" | "HEAD"),
+                    },
+                }))
+            })
+            .collect()
+    }
+
+    async fn call_tool(&self, params: &Value) -> Result<Value, RpcError> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_params("Missing tool name"))?;
+        let (tool, command) = self
+            .tools
+            .iter()
+            .find(|tool| tool.name == name)
+            .and_then(|tool| Some((tool, self.command(tool)?)))
+            .ok_or_else(|| invalid_params(&format!("Unknown tool: {name}")))?;
+        let arguments = match params.get("arguments") {
+            None | Some(Value::Null) => Map::new(),
+            Some(Value::Object(arguments)) => arguments.clone(),
+            Some(_) => return Err(invalid_params("Tool arguments must be an object")),
+        };
+
+        let spec = &self.specs[tool.spec_index];
+        let call = operation_call(command, &arguments);
+        let outcome = executor::execute(spec, call, self.context.clone()).await;
+        tracing::info!(
+            target: "aperture::mcp",
+            tool = %tool.name,
+            success = outcome.is_ok(),
+            "Tool call"
+        );
+
+        Ok(match outcome {
+            Ok(result) => tool_result(result),
+            Err(error) => json!({
+                "content": [{ "type": "text", "text": error.to_string() }],
+                "isError": true,
+            }),
+        })
+    }
+
+    fn command(&self, tool: &Tool) -> Option<&CachedCommand> {
+        self.specs[tool.spec_index]
+            .commands
+            .iter()
+            .find(|command| command.operation_id == tool.operation_id)
+    }
+}
+
+/// Gives each tool of a listing its own name
+#[derive(Debug, Default)]
+pub(crate) struct ToolNames {
+    taken: HashSet<String>,
+}
+
+impl ToolNames {
+    /// Builds a tool name from two parts: an API and operation id for MCP,
+    /// or a command's group and subcommand for exported tools.
+    ///
+    /// Characters outside `[A-Za-z0-9_-]`, which some clients reject, become
+    /// underscores. A name longer than 64 characters, or already given to
+    /// another tool, is cut short and ends with a hash of both parts instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hashed name is taken as well.
+    pub(crate) fn assign(&mut self, prefix: &str, name: &str) -> Result<String, Error> {
+        let full_name = format!("{prefix}{TOOL_NAME_SEPARATOR}{name}");
+        let mut assigned: String = full_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        if assigned.len() > MAX_TOOL_NAME_LENGTH || self.taken.contains(&assigned) {
+            let mut hash = format!("{:x}", Sha256::digest(full_name.as_bytes()));
+            hash.truncate(TOOL_NAME_HASH_LENGTH);
+            assigned.truncate(MAX_TOOL_NAME_LENGTH - TOOL_NAME_HASH_LENGTH - 1);
+            assigned = format!("{assigned}_{hash}");
+        }
+        if !self.taken.insert(assigned.clone()) {
+            return Err(Error::validation_error(format!(
+                "'{full_name}' cannot be given a tool name of its own: '{assigned}' is already taken"
+            )));
+        }
+        Ok(assigned)
+    }
+}
+
+/// The operation's summary and description, joined
+pub(crate) fn tool_description(command: &CachedCommand) -> String {
+    let mut parts: Vec<&str> = [command.summary.as_deref(), command.description.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .collect();
+    parts.dedup();
+
+    let mut description = if parts.is_empty() {
+        format!("{} {}", command.method, command.path)
+    } else {
+        parts.join("\n\n")
+    };
+    if command.deprecated {
+        description.insert_str(0, "[Deprecated] ");
+    }
+    description
+}
+
+/// Builds the JSON Schema of an operation's arguments: one property per
+/// parameter and `body` for the request body
+///
+/// # Errors
+///
+/// Returns an error if two parameters have the same name.
+pub fn input_schema(command: &CachedCommand) -> Result<Value, Error> {
+    let (mut properties, mut required) = parameter_properties(command, str::to_string)?;
+
+    if let Some(request_body) = &command.request_body {
+        properties.insert(BODY_ARGUMENT.to_string(), body_schema(request_body));
+        if request_body.required {
+            required.push(Value::String(BODY_ARGUMENT.to_string()));
+        }
+    }
+
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    Ok(schema)
+}
+
+/// The argument properties of an operation's parameters, each named by
+/// `property_name`, and the names of the required ones
+///
+/// # Errors
+///
+/// Returns an error if two parameters get the same property name.
+pub(crate) fn parameter_properties(
+    command: &CachedCommand,
+    property_name: impl Fn(&str) -> String,
+) -> Result<(Map<String, Value>, Vec<Value>), Error> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for param in &command.parameters {
+        let name = property_name(&param.name);
+        if properties.contains_key(&name) {
+            return Err(Error::validation_error(format!(
+                "Operation '{}' has more than one parameter named '{name}', so it cannot be described as a tool",
+                command.operation_id
+            )));
+        }
+        if param.required {
+            required.push(Value::String(name.clone()));
+        }
+        properties.insert(name, parameter_schema(param));
+    }
+    Ok((properties, required))
+}
+
+/// The JSON Schema of a request body argument. A binary body is passed as a
+/// string.
+pub(crate) fn body_schema(request_body: &CachedRequestBody) -> Value {
+    let mut schema = if crate::spec::is_binary_content_type(&request_body.content_type) {
+        json!({ "type": "string" })
+    } else {
+        serde_json::from_str::<Value>(&request_body.schema)
+            .ok()
+            .filter(Value::is_object)
+            .unwrap_or_else(|| json!({}))
+    };
+    let description = request_body
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("Request body ({})", request_body.content_type));
+    if let Some(schema) = schema.as_object_mut() {
+        schema
+            .entry("description")
+            .or_insert(Value::String(description));
+    }
+    schema
+}
+
+/// The JSON Schema of a parameter argument, described with its location
+pub(crate) fn parameter_schema(param: &CachedParameter) -> Value {
+    let mut schema = param
+        .schema
+        .as_deref()
+        .and_then(|schema| serde_json::from_str::<Value>(schema).ok())
+        .filter(Value::is_object)
+        .unwrap_or_else(|| {
+            json!({
+                "type": param.schema_type.as_deref().unwrap_or(constants::SCHEMA_TYPE_STRING)
+            })
+        });
+
+    if let Some(schema) = schema.as_object_mut() {
+        if !param.enum_values.is_empty() && !schema.contains_key("enum") {
+            schema.insert("enum".to_string(), json!(param.enum_values));
+        }
+        let description = param.description.as_deref().map_or_else(
+            || format!("{} parameter", param.location),
+            |description| format!("{description} ({} parameter)", param.location),
+        );
+        schema.insert("description".to_string(), Value::String(description));
+    }
+    schema
+}
+
+/// Builds the call for a tool's arguments. Unknown arguments are ignored;
+/// values are checked by the executor's request validation.
+fn operation_call(command: &CachedCommand, arguments: &Map<String, Value>) -> OperationCall {
+    let mut path_params = HashMap::new();
+    let mut query_params = HashMap::new();
+    let mut header_params = HashMap::new();
+    let mut cookie_params = HashMap::new();
+    let mut multi_params = HashMap::new();
+
+    for param in &command.parameters {
+        let Some(value) = arguments.get(&param.name).filter(|value| !value.is_null()) else {
+            continue;
+        };
+        let target = match param.location.as_str() {
+            constants::PARAM_LOCATION_PATH => &mut path_params,
+            constants::PARAM_LOCATION_QUERY => &mut query_params,
+            constants::PARAM_LOCATION_HEADER => &mut header_params,
+            constants::PARAM_LOCATION_COOKIE => &mut cookie_params,
+            _ => continue,
+        };
+        if params::is_multi_valued(param) {
+            multi_params.insert(param.name.clone(), param_values(value));
+        } else {
+            target.insert(param.name.clone(), argument_text(value));
+        }
+    }
+
+    let body_argument = arguments
+        .get(BODY_ARGUMENT)
+        .filter(|value| !value.is_null());
+    let is_binary = command
+        .request_body
+        .as_ref()
+        .is_some_and(|body| crate::spec::is_binary_content_type(&body.content_type));
+    let (body, raw_body) = match body_argument {
+        Some(value) if is_binary => (
+            None,
+            Some(RawBody::Bytes(argument_text(value).into_bytes())),
+        ),
+        Some(value) => (Some(value.to_string()), None),
+        None => (None, None),
+    };
+
+    OperationCall {
+        operation_id: command.operation_id.clone(),
+        path_params,
+        query_params,
+        header_params,
+        cookie_params,
+        multi_params,
+        body,
+        files: Vec::new(),
+        raw_body,
+        custom_headers: Vec::new(),
+    }
+}
+
+/// A parameter value as the CLI would receive it: strings unquoted, other
+/// values as JSON
+fn argument_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn param_values(value: &Value) -> ParamValues {
+    match value {
+        Value::Array(items) => ParamValues::Array(items.iter().map(argument_text).collect()),
+        Value::Object(members) => ParamValues::Object(
+            members
+                .iter()
+                .map(|(key, member)| (key.clone(), argument_text(member)))
+                .collect(),
+        ),
+        other => ParamValues::Array(vec![argument_text(other)]),
+    }
+}
+
+fn tool_result(result: ExecutionResult) -> Value {
+    let text = match result {
+        ExecutionResult::Success { body, .. } | ExecutionResult::Cached { body } => body,
+        ExecutionResult::Binary {
+            body, content_type, ..
+        } => {
+            let content_type =
+                content_type.unwrap_or_else(|| constants::CONTENT_TYPE_OCTET_STREAM.to_string());
+            if content_type.starts_with("image/") {
+                return json!({
+                    "content": [{
+                        "type": "image",
+                        "data": general_purpose::STANDARD.encode(&body),
+                        "mimeType": content_type,
+                    }],
+                    "isError": false,
+                });
+            }
+            format!(
+                "Binary response ({content_type}, {} bytes): {}",
+                body.len(),
+                general_purpose::STANDARD.encode(&body)
+            )
+        }
+        ExecutionResult::DryRun { request_info } => request_info.to_string(),
+        ExecutionResult::Empty => String::new(),
+    };
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": false,
+    })
+}
+
+fn initialize_result(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|version| SUPPORTED_PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0]);
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": { "listChanged": false } },
+        "serverInfo": {
+            "name": "aperture",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
@@ -0,0 +1,811 @@
+//! Local mock server for a cached specification.
+//!
+//! [`MockServer`] serves every operation of a [`CachedSpec`] on a loopback
+//! port. Incoming requests are routed by method and path template, validated
+//! against the operation's parameters and request body, and answered with the
+//! documented success response: its example when the spec has one, otherwise
+//! a body synthesized from the response schema.
+
+use crate::cache::models::{CachedCommand, CachedParameter, CachedResponse, CachedSpec};
+use crate::constants;
+use crate::engine::validation::{self, SchemaViolation};
+use crate::error::Error;
+use crate::invocation::{OperationCall, ParamValues};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Upper bound on the size of a request head
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// Upper bound on the size of a request body
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// How long a client has to send a whole request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How deep schema synthesis descends before giving up on a branch
+const MAX_SYNTHESIS_DEPTH: usize = 8;
+
+/// An HTTP request received by the mock server
+#[derive(Debug, Clone, Default)]
+pub struct MockRequest {
+    /// Uppercase HTTP method
+    pub method: String,
+    /// Request target: path and optional query string
+    pub target: String,
+    /// Header names and values in the order received
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The answer to a [`MockRequest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub(crate) fn json(status: u16, body: &Value) -> Self {
+        Self {
+            status,
+            content_type: Some(constants::CONTENT_TYPE_JSON.to_string()),
+            body: body.to_string().into_bytes(),
+        }
+    }
+}
+
+/// A mock server bound to a loopback port
+#[derive(Debug)]
+pub struct MockServer {
+    spec: Arc<CachedSpec>,
+    listener: TcpListener,
+}
+
+impl MockServer {
+    /// Binds the server to `127.0.0.1:port`.
+    ///
+    /// Pass `port = 0` to let the operating system choose a free port.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listener cannot be bound.
+    pub async fn bind(spec: CachedSpec, port: u16) -> Result<Self, Error> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .await
+            .map_err(|e| {
+                Error::io_error(format!(
+                    "Failed to listen on 127.0.0.1:{port} for the mock server: {e}"
+                ))
+            })?;
+        Ok(Self {
+            spec: Arc::new(spec),
+            listener,
+        })
+    }
+
+    /// Address the server is listening on
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listener address cannot be read.
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        self.listener
+            .local_addr()
+            .map_err(|e| Error::io_error(format!("Failed to read listener address: {e}")))
+    }
+
+    /// Serves requests until the process exits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if accepting a connection fails.
+    pub async fn run(self) -> Result<(), Error> {
+        loop {
+            let (stream, _) = self.listener.accept().await.map_err(|e| {
+                Error::io_error(format!("Failed to accept mock server connection: {e}"))
+            })?;
+            let spec = Arc::clone(&self.spec);
+            tokio::spawn(async move { serve_connection(stream, &spec).await });
+        }
+    }
+}
+
+async fn serve_connection(mut stream: TcpStream, spec: &CachedSpec) {
+    let response = match read_request(&mut stream).await {
+        Ok(request) => {
+            let response = handle_request(spec, &request);
+            tracing::info!(
+                target: "aperture::mock",
+                "{} {} -> {}",
+                request.method,
+                request.target,
+                response.status
+            );
+            response
+        }
+        Err(response) => response,
+    };
+    write_response(&mut stream, &response).await;
+}
+
+/// Answers a request from the operations of `spec`.
+///
+/// Unknown paths get `404`, known paths with another method `405`, and
+/// requests that do not match the operation's parameters or body `400` with
+/// the list of violations.
+#[must_use]
+pub fn handle_request(spec: &CachedSpec, request: &MockRequest) -> MockResponse {
+    let (command, call, mut violations) = match resolve_request(spec, request) {
+        Ok(resolved) => resolved,
+        Err(response) => return response,
+    };
+    violations.extend(validation::request_violations(command, &call));
+    if !violations.is_empty() {
+        return violations_response(command, &violations);
+    }
+
+    mock_response(command)
+}
+
+/// Routes a request to its operation and reads the parameters and JSON body
+/// it carries into an [`OperationCall`], together with any violations found
+/// while reading them.
+///
+/// Requests that match no operation are answered with `404`, or `405` when
+/// the path is known but the method is not.
+pub(crate) fn resolve_request<'a>(
+    spec: &'a CachedSpec,
+    request: &MockRequest,
+) -> Result<(&'a CachedCommand, OperationCall, Vec<SchemaViolation>), MockResponse> {
+    let (path, query) = request
+        .target
+        .split_once('?')
+        .unwrap_or((request.target.as_str(), ""));
+
+    let Some((command, path_params)) = route(spec, &request.method, path) else {
+        let known_path = spec
+            .commands
+            .iter()
+            .any(|command| match_path(&command.path, path).is_some());
+        return Err(if known_path {
+            MockResponse::json(
+                405,
+                &json!({"message": format!("{} is not allowed on {path}", request.method)}),
+            )
+        } else {
+            MockResponse::json(
+                404,
+                &json!({"message": format!("No operation matches {} {path}", request.method)}),
+            )
+        });
+    };
+
+    let (call, violations) = operation_call(command, request, path_params, query);
+    Ok((command, call, violations))
+}
+
+/// Answers a request that does not match its operation with `400`
+pub(crate) fn violations_response(
+    command: &CachedCommand,
+    violations: &[SchemaViolation],
+) -> MockResponse {
+    MockResponse::json(
+        400,
+        &json!({
+            "message": format!("Request does not match operation '{}'", command.operation_id),
+            "violations": violations,
+        }),
+    )
+}
+
+/// Finds the operation for a method and path, preferring the template with
+/// the most literal segments
+fn route<'a>(
+    spec: &'a CachedSpec,
+    method: &str,
+    path: &str,
+) -> Option<(&'a CachedCommand, HashMap<String, String>)> {
+    let base_path = spec
+        .base_url
+        .as_deref()
+        .and_then(|url| reqwest::Url::parse(url).ok())
+        .map(|url| url.path().trim_end_matches('/').to_string())
+        .unwrap_or_default();
+    let stripped = path
+        .strip_prefix(base_path.as_str())
+        .filter(|rest| !base_path.is_empty() && rest.starts_with('/'));
+
+    [Some(path), stripped]
+        .into_iter()
+        .flatten()
+        .find_map(|path| {
+            spec.commands
+                .iter()
+                .filter(|command| command.method.eq_ignore_ascii_case(method))
+                .filter_map(|command| {
+                    match_path(&command.path, path).map(|params| (command, params))
+                })
+                .max_by_key(|(command, params)| {
+                    command.path.split('/').count().saturating_sub(params.len())
+                })
+        })
+}
+
+/// Matches a request path against a path template such as `/pets/{petId}`,
+/// returning the decoded path parameters
+fn match_path(template: &str, path: &str) -> Option<HashMap<String, String>> {
+    let template_segments: Vec<&str> = template.trim_end_matches('/').split('/').collect();
+    let path_segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    if template_segments.len() != path_segments.len() {
+        return None;
+    }
+
+    let mut params = HashMap::new();
+    for (pattern, segment) in template_segments.iter().zip(&path_segments) {
+        let (Some(open), Some(close)) = (pattern.find('{'), pattern.rfind('}')) else {
+            if pattern != segment {
+                return None;
+            }
+            continue;
+        };
+        // A template may sit between literal text, as in `{id}.json`
+        let (prefix, suffix) = (&pattern[..open], &pattern[close + 1..]);
+        let value = segment
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix))
+            .filter(|value| !value.is_empty())?;
+        let value = urlencoding::decode(value).map_or_else(|_| value.to_string(), Into::into);
+        params.insert(pattern[open + 1..close].to_string(), value);
+    }
+    Some(params)
+}
+
+/// Reads the operation's parameters and body from a request, reporting the
+/// ones that are missing or malformed
+fn operation_call(
+    command: &CachedCommand,
+    request: &MockRequest,
+    path_params: HashMap<String, String>,
+    query: &str,
+) -> (OperationCall, Vec<SchemaViolation>) {
+    let query_pairs: Vec<(String, String)> =
+        reqwest::Url::parse(&format!("http://127.0.0.1/?{query}"))
+            .map(|url| {
+                url.query_pairs()
+                    .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
+    let cookies: Vec<(String, String)> = request
+        .headers
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("cookie"))
+        .flat_map(|(_, value)| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+    let mut call = OperationCall {
+        operation_id: command.operation_id.clone(),
+        path_params,
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    };
+    let mut violations = Vec::new();
+
+    for param in &command.parameters {
+        let values: Vec<&str> = match param.location.as_str() {
+            constants::PARAM_LOCATION_PATH => call
+                .path_params
+                .get(&param.name)
+                .into_iter()
+                .map(String::as_str)
+                .collect(),
+            constants::PARAM_LOCATION_QUERY => pair_values(&query_pairs, &param.name, false),
+            constants::PARAM_LOCATION_HEADER => pair_values(&request.headers, &param.name, true),
+            constants::PARAM_LOCATION_COOKIE => pair_values(&cookies, &param.name, false),
+            _ => continue,
+        };
+        let values: Vec<String> = values.into_iter().map(ToString::to_string).collect();
+
+        match param.schema_type.as_deref() {
+            Some(constants::SCHEMA_TYPE_ARRAY) if !values.is_empty() => {
+                call.multi_params.insert(
+                    param.name.clone(),
+                    ParamValues::Array(array_items(param, values)),
+                );
+            }
+            // Exploded object members arrive under their own names, so only
+            // `deepObject` members can be attributed to the parameter
+            Some(constants::SCHEMA_TYPE_OBJECT) => {
+                let prefix = format!("{}[", param.name);
+                let members: Vec<(String, String)> = query_pairs
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let member = key.strip_prefix(&prefix)?.strip_suffix(']')?;
+                        Some((member.to_string(), value.clone()))
+                    })
+                    .collect();
+                if !members.is_empty() {
+                    call.multi_params
+                        .insert(param.name.clone(), ParamValues::Object(members));
+                }
+                continue;
+            }
+            _ => {
+                if let Some(value) = values.into_iter().next() {
+                    location_params(&mut call, &param.location).insert(param.name.clone(), value);
+                    continue;
+                }
+            }
+        }
+
+        if param.required && !call.multi_params.contains_key(&param.name) {
+            violations.push(SchemaViolation {
+                path: format!(
+                    "/{}/{}",
+                    param.location,
+                    validation::escape_pointer(&param.name)
+                ),
+                message: "is required".to_string(),
+            });
+        }
+    }
+
+    check_body(command, request, &mut call, &mut violations);
+    (call, violations)
+}
+
+/// Checks that a required body is present and parses a JSON body into `call`
+fn check_body(
+    command: &CachedCommand,
+    request: &MockRequest,
+    call: &mut OperationCall,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let Some(request_body) = &command.request_body else {
+        return;
+    };
+    if request.body.is_empty() {
+        if request_body.required {
+            violations.push(SchemaViolation {
+                path: "/body".to_string(),
+                message: "is required".to_string(),
+            });
+        }
+    } else if constants::is_json_content_type(&request_body.content_type) {
+        match serde_json::from_slice::<Value>(&request.body) {
+            Ok(body) => call.body = Some(body.to_string()),
+            Err(e) => violations.push(SchemaViolation {
+                path: "/body".to_string(),
+                message: format!("is not valid JSON: {e}"),
+            }),
+        }
+    }
+}
+
+fn pair_values<'a>(pairs: &'a [(String, String)], name: &str, ignore_case: bool) -> Vec<&'a str> {
+    pairs
+        .iter()
+        .filter(|(key, _)| {
+            if ignore_case {
+                key.eq_ignore_ascii_case(name)
+            } else {
+                key == name
+            }
+        })
+        .map(|(_, value)| value.as_str())
+        .collect()
+}
+
+/// Splits a delimited array value; repeated keys are already separate items
+fn array_items(param: &CachedParameter, values: Vec<String>) -> Vec<String> {
+    if values.len() > 1 {
+        return values;
+    }
+    let delimiter = match param
+        .encoding
+        .as_ref()
+        .map(|encoding| encoding.style.as_str())
+    {
+        Some("spaceDelimited") => ' ',
+        Some("pipeDelimited") => '|',
+        _ => ',',
+    };
+    values
+        .iter()
+        .flat_map(|value| value.split(delimiter))
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn location_params<'a>(
+    call: &'a mut OperationCall,
+    location: &str,
+) -> &'a mut HashMap<String, String> {
+    match location {
+        constants::PARAM_LOCATION_PATH => &mut call.path_params,
+        constants::PARAM_LOCATION_HEADER => &mut call.header_params,
+        constants::PARAM_LOCATION_COOKIE => &mut call.cookie_params,
+        _ => &mut call.query_params,
+    }
+}
+
+/// Builds the documented success response of an operation
+fn mock_response(command: &CachedCommand) -> MockResponse {
+    let Some(response) = success_response(command) else {
+        return MockResponse {
+            status: 204,
+            content_type: None,
+            body: Vec::new(),
+        };
+    };
+    let status = response.status_code.parse::<u16>().unwrap_or(200);
+
+    let body = response
+        .example
+        .as_deref()
+        .and_then(|example| serde_json::from_str::<Value>(example).ok())
+        .or_else(|| {
+            response
+                .schema
+                .as_deref()
+                .and_then(|schema| serde_json::from_str::<Value>(schema).ok())
+                .map(|schema| example_from_schema(&schema))
+        });
+    let Some(body) = body.filter(|_| status != 204) else {
+        return MockResponse {
+            status,
+            content_type: None,
+            body: Vec::new(),
+        };
+    };
+
+    let content_type = response
+        .content_type
+        .clone()
+        .unwrap_or_else(|| constants::CONTENT_TYPE_JSON.to_string());
+    let body = match body {
+        Value::String(text) if !constants::is_json_content_type(&content_type) => text,
+        body => body.to_string(),
+    };
+    MockResponse {
+        status,
+        content_type: Some(content_type),
+        body: body.into_bytes(),
+    }
+}
+
+/// Picks the lowest documented 2xx response, then `2XX`, then `default`,
+/// then whatever is documented first
+fn success_response(command: &CachedCommand) -> Option<&CachedResponse> {
+    let responses = &command.responses;
+    responses
+        .iter()
+        .filter(|response| {
+            response
+                .status_code
+                .parse::<u16>()
+                .is_ok_and(|status| (200..300).contains(&status))
+        })
+        .min_by_key(|response| response.status_code.clone())
+        .or_else(|| {
+            responses
+                .iter()
+                .find(|response| response.status_code.eq_ignore_ascii_case("2XX"))
+        })
+        .or_else(|| {
+            responses
+                .iter()
+                .find(|response| response.status_code == "default")
+        })
+        .or_else(|| responses.first())
+}
+
+/// Synthesizes a value that satisfies `schema`.
+///
+/// Declared `example`, `examples`, `default`, `const` and `enum` values are
+/// used as they are; otherwise objects get every property, arrays one item
+/// and scalars a placeholder honoring `format` and numeric bounds.
+#[must_use]
+pub fn example_from_schema(schema: &Value) -> Value {
+    synthesize(schema, 0)
+}
+
+fn synthesize(schema: &Value, depth: usize) -> Value {
+    let Some(schema) = schema.as_object() else {
+        return Value::Null;
+    };
+    if depth > MAX_SYNTHESIS_DEPTH {
+        return Value::Null;
+    }
+
+    let declared = schema
+        .get("example")
+        .or_else(|| schema.get("examples").and_then(|examples| examples.get(0)))
+        .or_else(|| schema.get("default"))
+        .or_else(|| schema.get("const"))
+        .or_else(|| schema.get("enum").and_then(|values| values.get(0)));
+    if let Some(value) = declared {
+        return value.clone();
+    }
+
+    if let Some(Value::Array(subschemas)) = schema.get("allOf") {
+        let mut merged = Map::new();
+        for subschema in subschemas {
+            match synthesize(subschema, depth + 1) {
+                Value::Object(members) => merged.extend(members),
+                other if subschemas.len() == 1 => return other,
+                _ => {}
+            }
+        }
+        return Value::Object(merged);
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema.get(key).and_then(|subschemas| subschemas.get(0)) {
+            return synthesize(first, depth + 1);
+        }
+    }
+
+    let schema_type = match schema.get("type") {
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|schema_type| *schema_type != "null"),
+        schema_type => schema_type.and_then(Value::as_str),
+    };
+    let schema_type = schema_type.or_else(|| {
+        if schema.contains_key("properties") {
+            Some(constants::SCHEMA_TYPE_OBJECT)
+        } else if schema.contains_key("items") {
+            Some(constants::SCHEMA_TYPE_ARRAY)
+        } else {
+            None
+        }
+    });
+
+    match schema_type {
+        Some(constants::SCHEMA_TYPE_OBJECT) => {
+            let properties = schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .map(|(name, property)| (name.clone(), synthesize(property, depth + 1)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Value::Object(properties)
+        }
+        Some(constants::SCHEMA_TYPE_ARRAY) => schema.get("items").map_or_else(
+            || json!([]),
+            |items| Value::Array(vec![synthesize(items, depth + 1)]),
+        ),
+        Some(constants::SCHEMA_TYPE_STRING) => Value::String(
+            string_placeholder(schema.get("format").and_then(Value::as_str)).to_string(),
+        ),
+        // Placeholders are small whole numbers taken from the schema's bounds
+        #[allow(clippy::cast_possible_truncation)]
+        Some(constants::SCHEMA_TYPE_INTEGER) => json!(numeric_placeholder(schema).round() as i64),
+        Some(constants::SCHEMA_TYPE_NUMBER) => json!(numeric_placeholder(schema)),
+        Some(constants::SCHEMA_TYPE_BOOLEAN) => Value::Bool(true),
+        _ => Value::Null,
+    }
+}
+
+fn string_placeholder(format: Option<&str>) -> &'static str {
+    match format {
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("time") => "00:00:00",
+        Some("uuid") => "00000000-0000-0000-0000-000000000000",
+        Some("email") => "user@example.com",
+        Some("uri" | "url") => "https://example.com",
+        Some("hostname") => "example.com",
+        Some("ipv4") => "127.0.0.1",
+        Some("ipv6") => "::1",
+        Some("byte") => "c3RyaW5n",
+        _ => "string",
+    }
+}
+
+/// Zero, or the nearest value the bounds allow
+fn numeric_placeholder(schema: &Map<String, Value>) -> f64 {
+    let number = |key: &str| schema.get(key).and_then(Value::as_f64);
+    // 3.0 marks an exclusive bound with a boolean next to `minimum`/`maximum`,
+    // 3.1 gives the bound itself
+    let step = |key: &str| match schema.get(key) {
+        Some(Value::Bool(true)) => 1.0,
+        _ => 0.0,
+    };
+    let minimum = number(constants::FIELD_EXCLUSIVE_MINIMUM)
+        .map(|bound| bound + 1.0)
+        .or_else(|| {
+            number("minimum").map(|bound| bound + step(constants::FIELD_EXCLUSIVE_MINIMUM))
+        });
+    let maximum = number(constants::FIELD_EXCLUSIVE_MAXIMUM)
+        .map(|bound| bound - 1.0)
+        .or_else(|| {
+            number("maximum").map(|bound| bound - step(constants::FIELD_EXCLUSIVE_MAXIMUM))
+        });
+    match (minimum, maximum) {
+        (Some(minimum), _) if minimum > 0.0 => minimum,
+        (_, Some(maximum)) if maximum < 0.0 => maximum,
+        _ => 0.0,
+    }
+}
+
+/// Reads a request head and its `Content-Length` body.
+///
+/// Requests that cannot be read are answered with the returned response:
+/// `400` when malformed or too large, `411` for chunked bodies, which are
+/// not supported, and `408` when the client takes longer than
+/// [`READ_TIMEOUT`].
+pub(crate) async fn read_request(stream: &mut TcpStream) -> Result<MockRequest, MockResponse> {
+    tokio::time::timeout(READ_TIMEOUT, receive_request(stream))
+        .await
+        .unwrap_or_else(|_| {
+            Err(MockResponse::json(
+                408,
+                &json!({"message": "Timed out reading the request"}),
+            ))
+        })
+}
+
+async fn receive_request(stream: &mut TcpStream) -> Result<MockRequest, MockResponse> {
+    let malformed = || MockResponse::json(400, &json!({"message": "Malformed HTTP request"}));
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(position) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break position + 4;
+        }
+        let read = stream.read(&mut chunk).await.map_err(|_| malformed())?;
+        if read == 0 || buffer.len() + read > MAX_HEAD_BYTES {
+            return Err(malformed());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().ok_or_else(malformed)?.split_whitespace();
+    let method = request_line.next().ok_or_else(malformed)?.to_uppercase();
+    let target = request_line.next().ok_or_else(malformed)?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let mut request = MockRequest {
+        method,
+        target,
+        headers,
+        body: buffer[head_end..].to_vec(),
+    };
+    // A chunked body would otherwise be read as empty
+    if request.header("transfer-encoding").is_some() {
+        return Err(MockResponse::json(
+            411,
+            &json!({"message": "Chunked request bodies are not supported; send a Content-Length"}),
+        ));
+    }
+    let length = match request.header("content-length") {
+        Some(length) => length.parse::<usize>().map_err(|_| malformed())?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(malformed());
+    }
+    while request.body.len() < length {
+        let read = stream.read(&mut chunk).await.map_err(|_| malformed())?;
+        if read == 0 {
+            return Err(malformed());
+        }
+        request.body.extend_from_slice(&chunk[..read]);
+    }
+    request.body.truncate(length);
+    Ok(request)
+}
+
+pub(crate) async fn write_response(stream: &mut TcpStream, response: &MockResponse) {
+    let reason = reqwest::StatusCode::from_u16(response.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+    let content_type = response
+        .content_type
+        .as_ref()
+        .map(|content_type| format!("Content-Type: {content_type}\r\n"))
+        .unwrap_or_default();
+    let head = format!(
+        "HTTP/1.1 {} {reason}\r\n{content_type}Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.body.len()
+    );
+    // The client may already be gone; nothing depends on the write succeeding
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(&response.body).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_path_templates() {
+        assert_eq!(
+            match_path("/pets/{petId}", "/pets/42"),
+            Some(HashMap::from([("petId".to_string(), "42".to_string())]))
+        );
+        assert_eq!(
+            match_path("/files/{name}.json", "/files/a%20b.json"),
+            Some(HashMap::from([("name".to_string(), "a b".to_string())]))
+        );
+        assert_eq!(match_path("/pets/{petId}", "/pets"), None);
+        assert_eq!(match_path("/pets/{petId}", "/pets/"), None);
+        assert_eq!(match_path("/pets", "/users"), None);
+    }
+
+    #[test]
+    fn test_example_from_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string", "format": "uuid"},
+                "age": {"type": "integer", "minimum": 1},
+                "score": {"type": ["number", "null"], "exclusiveMinimum": 0},
+                "status": {"type": "string", "enum": ["open", "closed"]},
+                "tags": {"type": "array", "items": {"type": "string", "example": "cat"}},
+                "owner": {"allOf": [{"properties": {"name": {"type": "string"}}}]}
+            }
+        });
+        assert_eq!(
+            example_from_schema(&schema),
+            json!({
+                "id": "00000000-0000-0000-0000-000000000000",
+                "age": 1,
+                "score": 1.0,
+                "status": "open",
+                "tags": ["cat"],
+                "owner": {"name": "string"}
+            })
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_read_request_times_out() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        // Connected, but never sends anything
+        let _client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        let response = read_request(&mut stream).await.unwrap_err();
+        assert_eq!(response.status, 408);
+    }
+}
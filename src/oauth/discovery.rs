@@ -0,0 +1,258 @@
+//! `OpenID` Connect discovery (`OpenID` Connect Discovery 1.0 §4).
+//!
+//! `openIdConnect` security schemes only carry the URL of a discovery
+//! document. When a spec is added, that document is fetched once and the
+//! advertised token and authorization endpoints are turned into a
+//! [`CachedOAuth2Config`], so execution can reuse the regular `OAuth2` token
+//! handling without contacting the discovery URL again.
+
+use crate::cache::models::CachedOAuth2Config;
+use crate::constants;
+use crate::error::Error;
+use openapiv3::{OpenAPI, ReferenceOr, SecurityScheme};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+/// Outcome of discovery per `openIdConnect` scheme name.
+///
+/// `Err` carries a human-readable reason used in validation messages.
+pub type OpenIdConnectDiscovery = HashMap<String, Result<CachedOAuth2Config, String>>;
+
+/// Upper bound on the size of a discovery document
+const MAX_DOCUMENT_BYTES: usize = 1024 * 1024;
+
+/// The subset of the provider metadata Aperture relies on
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderMetadata {
+    pub token_endpoint: String,
+    #[serde(default)]
+    pub authorization_endpoint: Option<String>,
+    #[serde(default)]
+    pub grant_types_supported: Option<Vec<String>>,
+}
+
+impl ProviderMetadata {
+    /// Builds the cached `OAuth2` configuration for the given scopes
+    ///
+    /// The client-credentials grant is used unless the provider explicitly
+    /// omits it from `grant_types_supported`. The authorization-code grant is
+    /// added when an authorization endpoint is advertised.
+    ///
+    /// # Errors
+    ///
+    /// Returns a reason if the provider supports none of these grants.
+    pub fn to_oauth2_config(&self, scopes: Vec<String>) -> Result<CachedOAuth2Config, String> {
+        let advertises = |grant: &str| {
+            self.grant_types_supported
+                .as_ref()
+                .is_none_or(|grants| grants.iter().any(|g| g == grant))
+        };
+
+        let mut grant_types = Vec::new();
+        if advertises(constants::OAUTH2_GRANT_CLIENT_CREDENTIALS) {
+            grant_types.push(constants::OAUTH2_GRANT_CLIENT_CREDENTIALS.to_string());
+        }
+        let authorization_url = self
+            .authorization_endpoint
+            .clone()
+            .filter(|_| advertises(constants::OAUTH2_GRANT_AUTHORIZATION_CODE));
+        if authorization_url.is_some() {
+            grant_types.push(constants::OAUTH2_GRANT_AUTHORIZATION_CODE.to_string());
+        }
+
+        if grant_types.is_empty() {
+            return Err(
+                "the provider supports neither the client_credentials nor the authorization_code grant"
+                    .to_string(),
+            );
+        }
+
+        Ok(CachedOAuth2Config {
+            token_url: self.token_endpoint.clone(),
+            scopes,
+            grant_types,
+            authorization_url,
+        })
+    }
+}
+
+/// Fetches and parses a discovery document
+///
+/// # Errors
+///
+/// Returns an error if the request fails, the response is not successful or
+/// too large, or the document lacks a `token_endpoint`.
+pub async fn fetch_provider_metadata(
+    url: &str,
+    timeout: Duration,
+) -> Result<ProviderMetadata, Error> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| Error::network_request_failed(format!("Failed to create HTTP client: {e}")))?;
+
+    let response = client
+        .get(url)
+        .header(constants::HEADER_ACCEPT, constants::CONTENT_TYPE_JSON)
+        .send()
+        .await
+        .map_err(|e| Error::network_request_failed(format!("Failed to fetch {url}: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Error::request_failed(
+            status,
+            format!("HTTP {status} from {url}"),
+        ));
+    }
+
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| Error::network_request_failed(format!("Failed to read {url}: {e}")))?;
+    if body.len() > MAX_DOCUMENT_BYTES {
+        return Err(Error::network_request_failed(format!(
+            "Discovery document too large: {} bytes (max {MAX_DOCUMENT_BYTES} bytes)",
+            body.len()
+        )));
+    }
+
+    serde_json::from_slice(&body).map_err(|e| {
+        Error::network_request_failed(format!("Invalid discovery document at {url}: {e}"))
+    })
+}
+
+/// Runs discovery for every `openIdConnect` scheme declared in `spec`
+///
+/// Failures are recorded per scheme rather than returned, so validation can
+/// decide whether they are fatal.
+pub async fn discover(spec: &OpenAPI, timeout: Duration) -> OpenIdConnectDiscovery {
+    let mut discovered = HashMap::new();
+
+    for (name, url) in openid_connect_schemes(spec) {
+        let outcome = match fetch_provider_metadata(url, timeout).await {
+            Ok(metadata) => metadata.to_oauth2_config(requested_scopes(spec, name)),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(reason) = &outcome {
+            tracing::debug!(scheme_name = %name, url = %url, reason = %reason, "OpenID Connect discovery failed");
+        }
+        discovered.insert(name.to_string(), outcome);
+    }
+
+    discovered
+}
+
+/// Blocking variant of [`discover`] for synchronous callers
+///
+/// Discovery runs on a dedicated thread with its own runtime, so this works
+/// whether or not the caller is already inside a Tokio runtime.
+#[must_use]
+pub fn discover_blocking(spec: &OpenAPI, timeout: Duration) -> OpenIdConnectDiscovery {
+    if openid_connect_schemes(spec).next().is_none() {
+        return HashMap::new();
+    }
+
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build();
+                match runtime {
+                    Ok(runtime) => runtime.block_on(discover(spec, timeout)),
+                    Err(e) => openid_connect_schemes(spec)
+                        .map(|(name, _)| {
+                            (
+                                name.to_string(),
+                                Err(format!("failed to start discovery runtime: {e}")),
+                            )
+                        })
+                        .collect(),
+                }
+            })
+            .join()
+            .unwrap_or_default()
+    })
+}
+
+/// Yields `(scheme name, discovery URL)` for each `openIdConnect` scheme
+fn openid_connect_schemes(spec: &OpenAPI) -> impl Iterator<Item = (&str, &str)> {
+    spec.components
+        .iter()
+        .flat_map(|components| &components.security_schemes)
+        .filter_map(|(name, scheme)| match scheme {
+            ReferenceOr::Item(SecurityScheme::OpenIDConnect {
+                open_id_connect_url,
+                ..
+            }) => Some((name.as_str(), open_id_connect_url.as_str())),
+            _ => None,
+        })
+}
+
+/// Collects the scopes requested for `scheme_name` by any security requirement
+///
+/// `OpenID` Connect schemes declare no scopes of their own; the requirements
+/// referencing them list what is needed, so the union is requested up front.
+fn requested_scopes(spec: &OpenAPI, scheme_name: &str) -> Vec<String> {
+    let operation_requirements = spec
+        .paths
+        .iter()
+        .filter_map(|(_, item)| item.as_item())
+        .flat_map(|item| crate::spec::http_methods_iter(item).into_iter())
+        .filter_map(|(_, operation)| operation.as_ref().and_then(|op| op.security.as_ref()));
+
+    spec.security
+        .iter()
+        .chain(operation_requirements)
+        .flatten()
+        .filter_map(|requirement| requirement.get(scheme_name))
+        .flatten()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(grants: Option<&[&str]>, authorization: bool) -> ProviderMetadata {
+        ProviderMetadata {
+            token_endpoint: "https://id.example.com/token".to_string(),
+            authorization_endpoint: authorization
+                .then(|| "https://id.example.com/authorize".to_string()),
+            grant_types_supported: grants
+                .map(|grants| grants.iter().map(ToString::to_string).collect()),
+        }
+    }
+
+    #[test]
+    fn test_config_defaults_to_all_grants_when_unadvertised() {
+        let config = metadata(None, true).to_oauth2_config(vec![]).unwrap();
+        assert_eq!(
+            config.grant_types,
+            vec!["client_credentials", "authorization_code"]
+        );
+        assert_eq!(
+            config.authorization_url.as_deref(),
+            Some("https://id.example.com/authorize")
+        );
+    }
+
+    #[test]
+    fn test_config_honors_grant_types_supported() {
+        let config = metadata(Some(&["client_credentials", "refresh_token"]), true)
+            .to_oauth2_config(vec!["openid".to_string()])
+            .unwrap();
+        assert_eq!(config.grant_types, vec!["client_credentials"]);
+        assert!(config.authorization_url.is_none());
+        assert_eq!(config.scopes, vec!["openid"]);
+
+        assert!(metadata(Some(&["implicit"]), true)
+            .to_oauth2_config(vec![])
+            .is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! Interactive authorization-code login with PKCE (RFC 7636).
+//!
+//! [`PendingAuthorization::start`] binds a loopback listener and builds the
+//! authorization URL the user opens in a browser. [`PendingAuthorization::complete`]
+//! waits for the authorization server to redirect back to the listener and
+//! exchanges the returned code for tokens.
+
+use super::{request_token, ClientCredentials, StoredToken};
+use crate::cache::models::CachedSecurityScheme;
+use crate::constants;
+use crate::error::Error;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use sha2::{Digest, Sha256};
+use std::net::Ipv4Addr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Path on the loopback listener that receives the authorization redirect
+const REDIRECT_PATH: &str = "/callback";
+
+/// Upper bound on the size of the redirect request we are willing to read
+const MAX_REQUEST_BYTES: usize = 16 * 1024;
+
+const PAGE_SUCCESS: &str =
+    "<html><body><h1>Aperture login complete</h1><p>You can close this window.</p></body></html>";
+const PAGE_FAILURE: &str =
+    "<html><body><h1>Aperture login failed</h1><p>Return to the terminal for details.</p></body></html>";
+
+/// An authorization-code flow waiting for the browser redirect
+#[derive(Debug)]
+pub struct PendingAuthorization {
+    scheme_name: String,
+    token_url: String,
+    listener: TcpListener,
+    redirect_uri: String,
+    authorization_url: String,
+    state: String,
+    code_verifier: String,
+}
+
+impl PendingAuthorization {
+    /// Binds the loopback redirect listener and prepares the authorization URL.
+    ///
+    /// Pass `port = 0` to let the operating system choose a free port.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scheme has no authorization-code flow, the
+    /// authorization URL is invalid, or the listener cannot be bound.
+    pub async fn start(
+        scheme: &CachedSecurityScheme,
+        credentials: &ClientCredentials,
+        port: u16,
+    ) -> Result<Self, Error> {
+        let config = scheme
+            .oauth2
+            .as_ref()
+            .filter(|config| config.supports_grant(constants::OAUTH2_GRANT_AUTHORIZATION_CODE));
+        let Some((config, authorization_url)) =
+            config.and_then(|c| c.authorization_url.as_deref().map(|url| (c, url)))
+        else {
+            return Err(Error::oauth2_authorization_failed(
+                &scheme.name,
+                "the scheme does not declare an authorizationCode flow",
+            ));
+        };
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .await
+            .map_err(|e| {
+                Error::io_error(format!(
+                    "Failed to listen on 127.0.0.1:{port} for the OAuth2 redirect: {e}"
+                ))
+            })?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| Error::io_error(format!("Failed to read listener address: {e}")))?
+            .port();
+        let redirect_uri = format!("http://127.0.0.1:{port}{REDIRECT_PATH}");
+
+        let code_verifier = random_url_safe(32)?;
+        let state = random_url_safe(16)?;
+
+        let mut url = reqwest::Url::parse(authorization_url).map_err(|e| {
+            Error::oauth2_authorization_failed(
+                &scheme.name,
+                format!("invalid authorization URL '{authorization_url}': {e}"),
+            )
+        })?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &credentials.client_id)
+                .append_pair("redirect_uri", &redirect_uri)
+                .append_pair("state", &state)
+                .append_pair("code_challenge", &pkce_challenge(&code_verifier))
+                .append_pair("code_challenge_method", "S256");
+            if !config.scopes.is_empty() {
+                query.append_pair("scope", &config.scopes.join(" "));
+            }
+        }
+
+        Ok(Self {
+            scheme_name: scheme.name.clone(),
+            token_url: config.token_url.clone(),
+            listener,
+            redirect_uri,
+            authorization_url: url.into(),
+            state,
+            code_verifier,
+        })
+    }
+
+    /// URL the user must open to authorize Aperture
+    #[must_use]
+    pub fn authorization_url(&self) -> &str {
+        &self.authorization_url
+    }
+
+    /// Loopback redirect URI registered with the authorization request
+    #[must_use]
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// Waits for the redirect and exchanges the authorization code for tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no redirect arrives within `timeout`, the
+    /// authorization server reports an error, the `state` does not match, or
+    /// the token request fails.
+    pub async fn complete(
+        self,
+        client: &reqwest::Client,
+        credentials: &ClientCredentials,
+        timeout: Duration,
+    ) -> Result<StoredToken, Error> {
+        let code = tokio::time::timeout(timeout, self.wait_for_code())
+            .await
+            .map_err(|_| {
+                Error::oauth2_authorization_failed(
+                    &self.scheme_name,
+                    format!(
+                        "timed out after {}s waiting for the browser redirect",
+                        timeout.as_secs()
+                    ),
+                )
+            })??;
+
+        let form = vec![
+            (
+                "grant_type",
+                constants::OAUTH2_GRANT_AUTHORIZATION_CODE.to_string(),
+            ),
+            ("code", code),
+            ("redirect_uri", self.redirect_uri.clone()),
+            ("code_verifier", self.code_verifier.clone()),
+        ];
+
+        request_token(
+            client,
+            &self.scheme_name,
+            &self.token_url,
+            credentials,
+            form,
+        )
+        .await
+    }
+
+    /// Accepts connections until the redirect request arrives
+    async fn wait_for_code(&self) -> Result<String, Error> {
+        loop {
+            let (mut stream, _) = self.listener.accept().await.map_err(|e| {
+                Error::io_error(format!("Failed to accept OAuth2 redirect connection: {e}"))
+            })?;
+
+            let Some(target) = read_request_target(&mut stream).await else {
+                continue;
+            };
+            let Ok(url) = reqwest::Url::parse(&format!("http://127.0.0.1{target}")) else {
+                respond(&mut stream, "400 Bad Request", PAGE_FAILURE).await;
+                continue;
+            };
+            if url.path() != REDIRECT_PATH {
+                // Browsers also ask for things like /favicon.ico
+                respond(&mut stream, "404 Not Found", "").await;
+                continue;
+            }
+
+            let result = self.code_from_redirect(&url);
+            let page = if result.is_ok() {
+                PAGE_SUCCESS
+            } else {
+                PAGE_FAILURE
+            };
+            respond(&mut stream, "200 OK", page).await;
+            return result;
+        }
+    }
+
+    /// Extracts the authorization code from the redirect query (RFC 6749 §4.1.2)
+    fn code_from_redirect(&self, url: &reqwest::Url) -> Result<String, Error> {
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        if let Some(error) = param("error") {
+            let reason = match param("error_description") {
+                Some(description) => format!("{error} ({description})"),
+                None => error,
+            };
+            return Err(Error::oauth2_authorization_failed(
+                &self.scheme_name,
+                reason,
+            ));
+        }
+
+        if param("state").as_deref() != Some(self.state.as_str()) {
+            return Err(Error::oauth2_authorization_failed(
+                &self.scheme_name,
+                "state parameter mismatch in redirect",
+            ));
+        }
+
+        param("code").ok_or_else(|| {
+            Error::oauth2_authorization_failed(&self.scheme_name, "redirect carried no code")
+        })
+    }
+}
+
+/// Reads an HTTP request head and returns its request target (path and query)
+async fn read_request_target(stream: &mut TcpStream) -> Option<String> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 || buffer.len() + read > MAX_REQUEST_BYTES {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&buffer);
+    let mut parts = head.lines().next()?.split_whitespace();
+    let _method = parts.next()?;
+    parts.next().map(ToString::to_string)
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    // The browser may already be gone; the login result does not depend on it
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Computes the S256 code challenge for a PKCE verifier
+fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// Returns `len` random bytes encoded as unpadded base64url
+fn random_url_safe(len: usize) -> Result<String, Error> {
+    let mut bytes = vec![0u8; len];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| Error::io_error(format!("Failed to generate random bytes: {e}")))?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_challenge_matches_rfc7636_example() {
+        // RFC 7636 Appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_random_url_safe_is_unpadded_and_unique() {
+        let first = random_url_safe(32).unwrap();
+        let second = random_url_safe(32).unwrap();
+        assert_eq!(first.len(), 43);
+        assert!(!first.contains('='));
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,385 @@
+//! `OAuth2` access token acquisition for security schemes with a token endpoint.
+//!
+//! Two grants are supported:
+//!
+//! - **Client credentials** (RFC 6749 §4.4): tokens are requested on demand
+//!   using the client's own credentials.
+//! - **Authorization code with PKCE** (RFC 6749 §4.1, RFC 7636): a user
+//!   authorizes once with `aperture auth login`, and the resulting refresh
+//!   token is exchanged for new access tokens on later invocations.
+//!
+//! `OpenID` Connect schemes use the same machinery once their endpoints have
+//! been resolved by [`discovery`] when the spec is added.
+//!
+//! Tokens are persisted in a [`TokenStore`] so later invocations can reuse
+//! them until they are close to expiry.
+//!
+//! Client credentials are resolved through the regular secret mappings
+//! (`x-aperture-secret` or `aperture config secret set`). The referenced value
+//! has the form `client_id:client_secret`, mirroring how HTTP basic
+//! credentials are supplied. Public clients using the authorization-code flow
+//! may provide just `client_id`.
+
+pub mod discovery;
+pub mod login;
+pub mod store;
+
+pub use login::PendingAuthorization;
+pub use store::{StoredToken, TokenStore};
+
+use crate::cache::models::{CachedOAuth2Config, CachedSecurityScheme};
+use crate::constants;
+use crate::error::Error;
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+
+/// Client identifier and optional secret used to authenticate against a token endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCredentials {
+    pub client_id: String,
+    /// `None` for public clients, which identify themselves by `client_id` only
+    pub client_secret: Option<String>,
+}
+
+impl ClientCredentials {
+    /// Parses credentials for `scheme` from a `client_id[:client_secret]` secret value
+    ///
+    /// `secret` describes where the value came from and is only used in errors.
+    ///
+    /// A bare `client_id` is only accepted when the scheme supports the
+    /// authorization-code flow, since the client-credentials grant always
+    /// requires a secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client id is empty or a required secret is missing.
+    pub fn for_scheme(
+        scheme: &CachedSecurityScheme,
+        secret: &str,
+        value: &str,
+    ) -> Result<Self, Error> {
+        let allows_public_client = scheme.oauth2.as_ref().is_some_and(|config| {
+            config.supports_grant(constants::OAUTH2_GRANT_AUTHORIZATION_CODE)
+        });
+
+        let (client_id, client_secret) = match value.split_once(':') {
+            Some((id, secret)) => (id, Some(secret.to_string())),
+            None if allows_public_client => (value, None),
+            None => return Err(Error::invalid_oauth2_credentials(&scheme.name, secret)),
+        };
+
+        if client_id.is_empty() {
+            return Err(Error::invalid_oauth2_credentials(&scheme.name, secret));
+        }
+
+        Ok(Self {
+            client_id: client_id.to_string(),
+            client_secret,
+        })
+    }
+
+    /// Builds the HTTP basic `Authorization` value for confidential clients
+    fn basic_authorization(&self) -> Option<String> {
+        let secret = self.client_secret.as_ref()?;
+        let raw = format!(
+            "{}:{}",
+            urlencoding::encode(&self.client_id),
+            urlencoding::encode(secret)
+        );
+        Some(format!("Basic {}", general_purpose::STANDARD.encode(raw)))
+    }
+}
+
+/// Successful token endpoint response (RFC 6749 §5.1)
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    token_type: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+/// Returns a valid access token for `scheme`, fetching a new one when needed.
+///
+/// A stored token is reused while it is more than
+/// [`OAUTH2_TOKEN_REFRESH_SKEW_SECS`](constants::OAUTH2_TOKEN_REFRESH_SKEW_SECS)
+/// away from expiry and was issued to the same client id. Otherwise a stored
+/// refresh token is redeemed, falling back to the client-credentials grant
+/// when the scheme supports it.
+///
+/// # Errors
+///
+/// Returns an error if the scheme has no token endpoint configuration, the
+/// token request fails, or the scheme needs `aperture auth login` first.
+pub async fn access_token(
+    client: &reqwest::Client,
+    store: Option<&TokenStore>,
+    api_name: &str,
+    scheme: &CachedSecurityScheme,
+    credentials: &ClientCredentials,
+) -> Result<String, Error> {
+    let Some(config) = &scheme.oauth2 else {
+        return Err(Error::unsupported_security_scheme(&scheme.scheme_type));
+    };
+
+    let now = now_secs();
+    let stored = store
+        .and_then(|store| store.load(api_name, &scheme.name))
+        .filter(|t| t.client_id == credentials.client_id);
+
+    if let Some(token) = stored
+        .as_ref()
+        .filter(|t| t.is_usable(&credentials.client_id, now))
+    {
+        tracing::debug!(scheme_name = %scheme.name, "Reusing stored OAuth2 access token");
+        return Ok(token.access_token.clone());
+    }
+
+    let supports_client_credentials =
+        config.supports_grant(constants::OAUTH2_GRANT_CLIENT_CREDENTIALS);
+
+    let token = match stored.and_then(|t| t.refresh_token) {
+        Some(refresh_token) => {
+            match refresh_access_token(client, &scheme.name, config, credentials, &refresh_token)
+                .await
+            {
+                Ok(token) => token,
+                Err(e) if supports_client_credentials => {
+                    tracing::warn!(scheme_name = %scheme.name, error = %e, "OAuth2 token refresh failed");
+                    request_client_credentials_token(client, &scheme.name, config, credentials)
+                        .await?
+                }
+                Err(e) => {
+                    tracing::warn!(scheme_name = %scheme.name, error = %e, "OAuth2 token refresh failed");
+                    return Err(Error::oauth2_login_required(api_name, &scheme.name));
+                }
+            }
+        }
+        None if supports_client_credentials => {
+            request_client_credentials_token(client, &scheme.name, config, credentials).await?
+        }
+        None => return Err(Error::oauth2_login_required(api_name, &scheme.name)),
+    };
+
+    if let Some(store) = store {
+        // A failed write only costs an extra token request next time
+        if let Err(e) = store.save(api_name, &scheme.name, &token) {
+            tracing::warn!(scheme_name = %scheme.name, error = %e, "Failed to persist OAuth2 token");
+        }
+    }
+
+    Ok(token.access_token)
+}
+
+/// Requests a new access token using the client-credentials grant
+async fn request_client_credentials_token(
+    client: &reqwest::Client,
+    scheme_name: &str,
+    config: &CachedOAuth2Config,
+    credentials: &ClientCredentials,
+) -> Result<StoredToken, Error> {
+    let mut form = vec![(
+        "grant_type",
+        constants::OAUTH2_GRANT_CLIENT_CREDENTIALS.to_string(),
+    )];
+    if !config.scopes.is_empty() {
+        form.push(("scope", config.scopes.join(" ")));
+    }
+
+    request_token(client, scheme_name, &config.token_url, credentials, form).await
+}
+
+/// Exchanges a refresh token for a new access token (RFC 6749 §6)
+///
+/// The original refresh token is kept when the server does not rotate it.
+async fn refresh_access_token(
+    client: &reqwest::Client,
+    scheme_name: &str,
+    config: &CachedOAuth2Config,
+    credentials: &ClientCredentials,
+    refresh_token: &str,
+) -> Result<StoredToken, Error> {
+    let form = vec![
+        (
+            "grant_type",
+            constants::OAUTH2_GRANT_REFRESH_TOKEN.to_string(),
+        ),
+        ("refresh_token", refresh_token.to_string()),
+    ];
+
+    let mut token =
+        request_token(client, scheme_name, &config.token_url, credentials, form).await?;
+    token
+        .refresh_token
+        .get_or_insert_with(|| refresh_token.to_string());
+    Ok(token)
+}
+
+/// Posts a token request and converts the response into a [`StoredToken`]
+///
+/// Confidential clients authenticate with HTTP basic; public clients send
+/// their `client_id` in the form body (RFC 6749 §2.3.1).
+async fn request_token(
+    client: &reqwest::Client,
+    scheme_name: &str,
+    token_url: &str,
+    credentials: &ClientCredentials,
+    mut form: Vec<(&str, String)>,
+) -> Result<StoredToken, Error> {
+    tracing::debug!(
+        scheme_name = %scheme_name,
+        token_url = %token_url,
+        grant_type = %form.first().map_or("", |(_, v)| v.as_str()),
+        "Requesting OAuth2 access token"
+    );
+
+    let mut request = client
+        .post(token_url)
+        .header(constants::HEADER_CONTENT_TYPE, constants::CONTENT_TYPE_FORM)
+        .header(constants::HEADER_ACCEPT, constants::CONTENT_TYPE_JSON);
+    match credentials.basic_authorization() {
+        Some(authorization) => {
+            request = request.header(constants::HEADER_AUTHORIZATION, authorization);
+        }
+        None => form.push(("client_id", credentials.client_id.clone())),
+    }
+
+    let response = request
+        .body(encode_form(&form))
+        .send()
+        .await
+        .map_err(|e| Error::oauth2_token_request_failed(scheme_name, e.to_string()))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| Error::oauth2_token_request_failed(scheme_name, e.to_string()))?;
+
+    if !status.is_success() {
+        return Err(Error::oauth2_token_request_failed(
+            scheme_name,
+            format!(
+                "token endpoint returned {status}: {}",
+                token_error_summary(&body)
+            ),
+        ));
+    }
+
+    let parsed: TokenResponse = serde_json::from_str(&body).map_err(|e| {
+        Error::oauth2_token_request_failed(scheme_name, format!("invalid token response: {e}"))
+    })?;
+
+    Ok(StoredToken {
+        access_token: parsed.access_token,
+        token_type: parsed.token_type,
+        expires_at: parsed
+            .expires_in
+            .map(|secs| now_secs().saturating_add(secs)),
+        refresh_token: parsed.refresh_token,
+        client_id: credentials.client_id.clone(),
+    })
+}
+
+/// Encodes key/value pairs as `application/x-www-form-urlencoded`
+fn encode_form(pairs: &[(&str, String)]) -> String {
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Extracts the `error`/`error_description` fields from an RFC 6749 §5.2 error body
+fn token_error_summary(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return "no error details".to_string();
+    };
+
+    let error = value.get("error").and_then(serde_json::Value::as_str);
+    let description = value
+        .get("error_description")
+        .and_then(serde_json::Value::as_str);
+
+    match (error, description) {
+        (Some(error), Some(description)) => format!("{error} ({description})"),
+        (Some(error), None) => error.to_string(),
+        _ => "no error details".to_string(),
+    }
+}
+
+/// Current Unix time in seconds
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheme_with_grants(grant_types: &[&str]) -> CachedSecurityScheme {
+        CachedSecurityScheme {
+            name: "oauth".to_string(),
+            scheme_type: constants::AUTH_SCHEME_OAUTH2.to_string(),
+            scheme: Some("bearer".to_string()),
+            location: Some("header".to_string()),
+            parameter_name: Some("Authorization".to_string()),
+            description: None,
+            bearer_format: None,
+            aperture_secret: None,
+            oauth2: Some(CachedOAuth2Config {
+                token_url: "https://auth.example.com/token".to_string(),
+                scopes: vec![],
+                grant_types: grant_types.iter().map(ToString::to_string).collect(),
+                authorization_url: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_parse_client_credentials() {
+        let scheme = scheme_with_grants(&[constants::OAUTH2_GRANT_CLIENT_CREDENTIALS]);
+        let creds = ClientCredentials::for_scheme(&scheme, "CREDS", "my-client:s3cr:et").unwrap();
+        assert_eq!(creds.client_id, "my-client");
+        assert_eq!(creds.client_secret.as_deref(), Some("s3cr:et"));
+    }
+
+    #[test]
+    fn test_parse_client_credentials_rejects_missing_separator() {
+        let scheme = scheme_with_grants(&[constants::OAUTH2_GRANT_CLIENT_CREDENTIALS]);
+        assert!(ClientCredentials::for_scheme(&scheme, "CREDS", "just-a-token").is_err());
+        assert!(ClientCredentials::for_scheme(&scheme, "CREDS", ":secret").is_err());
+    }
+
+    #[test]
+    fn test_parse_public_client_for_authorization_code() {
+        let scheme = scheme_with_grants(&[constants::OAUTH2_GRANT_AUTHORIZATION_CODE]);
+        let creds = ClientCredentials::for_scheme(&scheme, "CREDS", "public-client").unwrap();
+        assert_eq!(creds.client_id, "public-client");
+        assert!(creds.client_secret.is_none());
+        assert!(creds.basic_authorization().is_none());
+    }
+
+    #[test]
+    fn test_encode_form_escapes_values() {
+        let encoded = encode_form(&[
+            ("grant_type", "client_credentials".to_string()),
+            ("scope", "read write".to_string()),
+        ]);
+        assert_eq!(encoded, "grant_type=client_credentials&scope=read%20write");
+    }
+
+    #[test]
+    fn test_token_error_summary() {
+        assert_eq!(
+            token_error_summary(r#"{"error":"invalid_client","error_description":"bad secret"}"#),
+            "invalid_client (bad secret)"
+        );
+        assert_eq!(token_error_summary("<html>"), "no error details");
+    }
+}
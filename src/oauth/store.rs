@@ -0,0 +1,152 @@
+//! On-disk storage for `OAuth2` tokens.
+//!
+//! Each API gets one JSON file (`<tokens_dir>/<api>.json`) mapping security
+//! scheme names to their most recent token. Files are written atomically and,
+//! on Unix, are readable only by the owner from the moment they are created,
+//! in a directory only the owner can list, because they contain bearer
+//! credentials.
+
+use crate::constants;
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A token issued by an `OAuth2` token endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredToken {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    /// Expiry as seconds since the Unix epoch; `None` when the server gave no lifetime
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// Client the token was issued to, so rotated credentials never reuse it
+    pub client_id: String,
+}
+
+impl StoredToken {
+    /// Returns true if the token belongs to `client_id` and is not about to expire
+    #[must_use]
+    pub fn is_usable(&self, client_id: &str, now_secs: u64) -> bool {
+        if self.client_id != client_id {
+            return false;
+        }
+
+        self.expires_at.is_none_or(|expires_at| {
+            now_secs.saturating_add(constants::OAUTH2_TOKEN_REFRESH_SKEW_SECS) < expires_at
+        })
+    }
+}
+
+/// Directory-backed store of `OAuth2` tokens keyed by API and scheme name
+#[derive(Debug, Clone)]
+pub struct TokenStore {
+    dir: PathBuf,
+}
+
+impl TokenStore {
+    /// Creates a store rooted at `dir`. The directory is created on first write.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the directory backing this store
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Loads the stored token for a scheme, if any.
+    ///
+    /// Unreadable or corrupt files are treated as empty so a fresh token is fetched.
+    #[must_use]
+    pub fn load(&self, api_name: &str, scheme_name: &str) -> Option<StoredToken> {
+        self.read_all(api_name).remove(scheme_name)
+    }
+
+    /// Stores `token` for a scheme, replacing any previous token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token file cannot be serialized or written.
+    pub fn save(
+        &self,
+        api_name: &str,
+        scheme_name: &str,
+        token: &StoredToken,
+    ) -> Result<(), Error> {
+        let mut tokens = self.read_all(api_name);
+        tokens.insert(scheme_name.to_string(), token.clone());
+        self.write_all(api_name, &tokens)
+    }
+
+    /// Removes the stored token for a scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token file cannot be rewritten.
+    pub fn remove(&self, api_name: &str, scheme_name: &str) -> Result<(), Error> {
+        let mut tokens = self.read_all(api_name);
+        if tokens.remove(scheme_name).is_none() {
+            return Ok(());
+        }
+        self.write_all(api_name, &tokens)
+    }
+
+    fn path_for(&self, api_name: &str) -> PathBuf {
+        self.dir
+            .join(format!("{api_name}{}", constants::FILE_EXT_JSON))
+    }
+
+    fn read_all(&self, api_name: &str) -> BTreeMap<String, StoredToken> {
+        std::fs::read_to_string(self.path_for(api_name))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn write_all(
+        &self,
+        api_name: &str,
+        tokens: &BTreeMap<String, StoredToken>,
+    ) -> Result<(), Error> {
+        crate::atomic::create_private_dir_all(&self.dir).map_err(|e| {
+            Error::io_error(format!(
+                "Failed to create token directory '{}': {e}",
+                self.dir.display()
+            ))
+        })?;
+
+        let content = serde_json::to_vec_pretty(tokens)
+            .map_err(|e| Error::serialization_error(format!("Failed to serialize tokens: {e}")))?;
+        let path = self.path_for(api_name);
+        crate::atomic::atomic_write_private_sync(&path, &content).map_err(|e| {
+            Error::io_error(format!(
+                "Failed to write token file '{}': {e}",
+                path.display()
+            ))
+        })
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn restrict_to_owner(path: &Path) -> Result<(), Error> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).map_err(|e| {
+        Error::io_error(format!(
+            "Failed to set permissions on '{}': {e}",
+            path.display()
+        ))
+    })
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+pub(crate) const fn restrict_to_owner(_path: &Path) -> Result<(), Error> {
+    Ok(())
+}
@@ -38,6 +38,26 @@ pub fn write_stdout(msg: &str) -> Result<(), Error> {
     write_all(&mut stdout.lock(), msg.as_bytes())
 }
 
+/// Write raw bytes to stdout, such as a binary response body.
+///
+/// Broken pipes are treated as successful termination so commands compose with
+/// consumers like `head` without panicking after the consumer exits early.
+///
+/// # Errors
+///
+/// Returns an error when writing to stdout fails for a reason other than a
+/// broken pipe.
+pub fn write_stdout_bytes(bytes: &[u8]) -> Result<(), Error> {
+    let stdout = io::stdout();
+    let mut lock = stdout.lock();
+    write_all(&mut lock, bytes)?;
+    match lock.flush() {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::BrokenPipe => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
 fn write_line(writer: &mut impl Write, msg: &str) -> Result<(), Error> {
     write_all_fmt(writer, format_args!("{msg}\n"))
 }
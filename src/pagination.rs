@@ -72,6 +72,10 @@ async fn fetch_page_payload<W: std::io::Write + ?Sized>(
             body,
             response_headers: HashMap::new(),
         })),
+        ExecutionResult::Binary { content_type, .. } => Err(Error::invalid_json_body(format!(
+            "Page response is binary ({}), not JSON",
+            content_type.as_deref().unwrap_or("unknown content type")
+        ))),
         ExecutionResult::DryRun { request_info } => {
             write_json_line(writer, &request_info)?;
             Ok(None)
@@ -589,7 +593,11 @@ mod tests {
             path_params: HashMap::new(),
             query_params: HashMap::from([("page".to_string(), "1".to_string())]),
             header_params: HashMap::new(),
+            cookie_params: HashMap::new(),
+            multi_params: HashMap::new(),
             body: None,
+            files: vec![],
+            raw_body: None,
             custom_headers: vec![],
         };
         let has_next = advance_offset_strategy(&mut call, "page", 10, 10);
@@ -604,7 +612,11 @@ mod tests {
             path_params: HashMap::new(),
             query_params: HashMap::from([("page".to_string(), "1".to_string())]),
             header_params: HashMap::new(),
+            cookie_params: HashMap::new(),
+            multi_params: HashMap::new(),
             body: None,
+            files: vec![],
+            raw_body: None,
             custom_headers: vec![],
         };
         let has_next = advance_offset_strategy(&mut call, "page", 3, 10);
@@ -618,7 +630,11 @@ mod tests {
             path_params: HashMap::new(),
             query_params: HashMap::from([("skip".to_string(), "0".to_string())]),
             header_params: HashMap::new(),
+            cookie_params: HashMap::new(),
+            multi_params: HashMap::new(),
             body: None,
+            files: vec![],
+            raw_body: None,
             custom_headers: vec![],
         };
         let has_next = advance_offset_strategy(&mut call, "skip", 10, 10);
@@ -637,7 +653,11 @@ mod tests {
             path_params: HashMap::new(),
             query_params: HashMap::from([("offset".to_string(), "0".to_string())]),
             header_params: HashMap::new(),
+            cookie_params: HashMap::new(),
+            multi_params: HashMap::new(),
             body: None,
+            files: vec![],
+            raw_body: None,
             custom_headers: vec![],
         };
         let has_next = advance_offset_strategy(&mut call, "offset", 5, 5);
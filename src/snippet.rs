@@ -0,0 +1,701 @@
+//! Runnable equivalents of a request, for `--emit`.
+//!
+//! [`render`] turns the description the executor produces with
+//! `secret_references` set into a curl or `HTTPie` command or a short program.
+//! Secrets in that description are [`ValueSegment::Env`] references, which
+//! become environment variable lookups in the target language, so a snippet
+//! can be shared without revealing credentials. Everything else, including a
+//! `${NAME}` the user typed, is sent as written. An HTTP Basic reference names
+//! a variable holding `user:password` and is passed to the tool's own basic
+//! auth support.
+
+use crate::cli::EmitFormat;
+use crate::constants;
+use crate::invocation::ValueSegment;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Renders `request_info` as a runnable request in `format`
+#[must_use]
+pub fn render(format: &EmitFormat, request_info: &Value) -> String {
+    let request = Request::from_description(request_info);
+    match format {
+        EmitFormat::Curl => curl(&request),
+        EmitFormat::Httpie => httpie(&request),
+        EmitFormat::PythonRequests => python_requests(&request),
+        EmitFormat::JsFetch => js_fetch(&request),
+        EmitFormat::RustReqwest => rust_reqwest(&request),
+    }
+}
+
+/// The parts of a request description a snippet needs
+struct Request {
+    method: String,
+    url: Vec<ValueSegment>,
+    headers: Vec<(String, Vec<ValueSegment>)>,
+    /// Variable holding `user:password` for HTTP Basic authentication
+    basic_auth: Option<String>,
+    body: Option<Body>,
+}
+
+enum Body {
+    Text(String),
+    Multipart(Vec<Part>),
+    /// Binary content read from a file, or from stdin without a path
+    File(Option<String>),
+}
+
+struct Part {
+    name: String,
+    content_type: Option<String>,
+    value: PartValue,
+}
+
+enum PartValue {
+    Text(String),
+    File {
+        path: String,
+        filename: Option<String>,
+    },
+}
+
+impl Request {
+    fn from_description(request_info: &Value) -> Self {
+        let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+        let mut headers: Vec<(String, Vec<ValueSegment>)> = request_info["headers"]
+            .as_object()
+            .map(|headers| {
+                headers
+                    .iter()
+                    .map(|(name, value)| (name.clone(), segments(value)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let basic_auth = headers
+            .iter()
+            .position(|(name, value)| {
+                name.eq_ignore_ascii_case(constants::HEADER_AUTHORIZATION)
+                    && basic_reference(value).is_some()
+            })
+            .and_then(|index| basic_reference(&headers.remove(index).1).map(str::to_string));
+
+        let body = match &request_info["body"] {
+            Value::String(body) => {
+                // JSON bodies are sent without an explicit content type header
+                let has_content_type = headers
+                    .iter()
+                    .any(|(name, _)| name.eq_ignore_ascii_case(constants::HEADER_CONTENT_TYPE));
+                if !has_content_type && serde_json::from_str::<Value>(body).is_ok() {
+                    headers.push((
+                        constants::HEADER_CONTENT_TYPE_LC.to_string(),
+                        vec![ValueSegment::Text(constants::CONTENT_TYPE_JSON.to_string())],
+                    ));
+                }
+                Some(Body::Text(body.clone()))
+            }
+            Value::Object(body) if body.contains_key("parts") => {
+                // Every tool writes its own boundary
+                headers
+                    .retain(|(name, _)| !name.eq_ignore_ascii_case(constants::HEADER_CONTENT_TYPE));
+                Some(Body::Multipart(multipart_parts(&body["parts"])))
+            }
+            Value::Object(body) => Some(Body::File(
+                body.get("path").and_then(Value::as_str).map(str::to_string),
+            )),
+            _ => None,
+        };
+
+        Self {
+            method: text(&request_info["method"]),
+            url: segments(&request_info["url"]),
+            headers,
+            basic_auth,
+            body,
+        }
+    }
+
+    fn uses_environment(&self) -> bool {
+        self.basic_auth.is_some()
+            || has_reference(&self.url)
+            || self.headers.iter().any(|(_, value)| has_reference(value))
+    }
+
+    fn reads_files(&self) -> bool {
+        match &self.body {
+            Some(Body::File(_)) => true,
+            Some(Body::Multipart(parts)) => parts
+                .iter()
+                .any(|part| matches!(part.value, PartValue::File { .. })),
+            _ => false,
+        }
+    }
+}
+
+fn multipart_parts(parts: &Value) -> Vec<Part> {
+    parts
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|part| {
+            let name = part["name"].as_str().unwrap_or_default().to_string();
+            let content_type = part["content_type"]
+                .as_str()
+                .filter(|content_type| *content_type != constants::CONTENT_TYPE_TEXT)
+                .map(str::to_string);
+            let value = part["path"].as_str().map_or_else(
+                || PartValue::Text(part["value"].as_str().unwrap_or_default().to_string()),
+                |path| PartValue::File {
+                    path: path.to_string(),
+                    filename: part["filename"].as_str().map(str::to_string),
+                },
+            );
+            Part {
+                name,
+                content_type,
+                value,
+            }
+        })
+        .collect()
+}
+
+/// The segments of a described URL or header value. A plain string is
+/// literal text.
+fn segments(value: &Value) -> Vec<ValueSegment> {
+    match value {
+        Value::String(text) => vec![ValueSegment::Text(text.clone())],
+        value => serde_json::from_value(value.clone()).unwrap_or_default(),
+    }
+}
+
+fn has_reference(segments: &[ValueSegment]) -> bool {
+    segments
+        .iter()
+        .any(|segment| matches!(segment, ValueSegment::Env(_)))
+}
+
+/// `segments` with a literal `prefix`, such as a header name
+fn prefixed(prefix: String, segments: &[ValueSegment]) -> Vec<ValueSegment> {
+    std::iter::once(ValueSegment::Text(prefix))
+        .chain(segments.iter().cloned())
+        .collect()
+}
+
+/// The variable of a `Basic <reference>` header value
+fn basic_reference(segments: &[ValueSegment]) -> Option<&str> {
+    match segments {
+        [ValueSegment::Text(scheme), ValueSegment::Env(name)]
+            if scheme
+                .trim_end()
+                .eq_ignore_ascii_case(constants::AUTH_SCHEME_BASIC) =>
+        {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
+// ── Shell ────────────────────────────────────────────────────────────
+
+/// Quotes a shell word
+fn shell_word(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Quotes a shell word, expanding references from the environment
+fn shell_template(segments: &[ValueSegment]) -> String {
+    if !has_reference(segments) {
+        return shell_word(&literal_text(segments));
+    }
+    let mut word = String::from("\"");
+    for segment in segments {
+        match segment {
+            ValueSegment::Text(text) => {
+                for c in text.chars() {
+                    if matches!(c, '"' | '\\' | '$' | '`') {
+                        word.push('\\');
+                    }
+                    word.push(c);
+                }
+            }
+            ValueSegment::Env(name) => {
+                let _ = write!(word, "${{{name}}}");
+            }
+        }
+    }
+    word.push('"');
+    word
+}
+
+fn shell_command(words: &[String]) -> String {
+    let mut command = String::new();
+    for (index, word) in words.iter().enumerate() {
+        if index == 0 {
+            command.push_str(word);
+        } else if word.starts_with('-') || word.starts_with('<') {
+            let _ = write!(command, " \\\n  {word}");
+        } else {
+            let _ = write!(command, " {word}");
+        }
+    }
+    command
+}
+
+fn curl(request: &Request) -> String {
+    let mut words = vec!["curl".to_string()];
+    if request.method != "GET" || request.body.is_some() {
+        words.extend(["-X".to_string(), request.method.clone()]);
+    }
+    words.push(shell_template(&request.url));
+    for (name, value) in &request.headers {
+        words.extend([
+            "-H".to_string(),
+            shell_template(&prefixed(format!("{name}: "), value)),
+        ]);
+    }
+    if let Some(variable) = &request.basic_auth {
+        words.extend(["--user".to_string(), format!("\"${{{variable}}}\"")]);
+    }
+    match &request.body {
+        Some(Body::Text(text)) => words.extend(["--data-raw".to_string(), shell_word(text)]),
+        Some(Body::Multipart(parts)) => {
+            for part in parts {
+                let mut field = match (&part.value, &part.content_type) {
+                    // Sent as written, even when it starts with `@` or `<`
+                    (PartValue::Text(value), None) => {
+                        let field = format!("{}={value}", part.name);
+                        words.extend(["--form-string".to_string(), shell_word(&field)]);
+                        continue;
+                    }
+                    // Quoted so curl does not read a file or split at `;`
+                    (PartValue::Text(value), Some(_)) => format!(
+                        "{}=\"{}\"",
+                        part.name,
+                        value.replace('\\', "\\\\").replace('"', "\\\"")
+                    ),
+                    (PartValue::File { path, filename }, _) => {
+                        let mut field = format!("{}=@{path}", part.name);
+                        if let Some(filename) = filename {
+                            let _ = write!(field, ";filename={filename}");
+                        }
+                        field
+                    }
+                };
+                if let Some(content_type) = &part.content_type {
+                    let _ = write!(field, ";type={content_type}");
+                }
+                words.extend(["-F".to_string(), shell_word(&field)]);
+            }
+        }
+        Some(Body::File(path)) => words.extend([
+            "--data-binary".to_string(),
+            shell_word(&format!("@{}", path.as_deref().unwrap_or("-"))),
+        ]),
+        None => {}
+    }
+    shell_command(&words)
+}
+
+fn httpie(request: &Request) -> String {
+    let mut words = vec!["http".to_string()];
+    if matches!(request.body, Some(Body::Multipart(_))) {
+        words.push("--multipart".to_string());
+    }
+    if let Some(variable) = &request.basic_auth {
+        words.extend(["--auth".to_string(), format!("\"${{{variable}}}\"")]);
+    }
+    if let Some(Body::Text(text)) = &request.body {
+        words.extend(["--raw".to_string(), shell_word(text)]);
+    }
+    words.push(request.method.clone());
+    words.push(shell_template(&request.url));
+    for (name, value) in &request.headers {
+        words.push(shell_template(&prefixed(format!("{name}:"), value)));
+    }
+    match &request.body {
+        Some(Body::Multipart(parts)) => {
+            for part in parts {
+                let mut field = match &part.value {
+                    // A leading `@` would make `=@` read a file
+                    PartValue::Text(value) if value.starts_with('@') => {
+                        format!("{}=\\{value}", part.name)
+                    }
+                    PartValue::Text(value) => format!("{}={value}", part.name),
+                    PartValue::File { path, .. } => format!("{}@{path}", part.name),
+                };
+                if let Some(content_type) = &part.content_type {
+                    let _ = write!(field, ";type={content_type}");
+                }
+                words.push(shell_word(&field));
+            }
+        }
+        // HTTPie sends what it reads from stdin as the body
+        Some(Body::File(Some(path))) => words.push(format!("< {}", shell_word(path))),
+        _ => {}
+    }
+    shell_command(&words)
+}
+
+// ── Python ───────────────────────────────────────────────────────────
+
+fn python_str(segments: &[ValueSegment]) -> String {
+    match segments {
+        [ValueSegment::Env(name)] => return format!("os.environ[\"{name}\"]"),
+        _ if !has_reference(segments) => return json_literal(&literal_text(segments)),
+        _ => {}
+    }
+    let mut literal = String::from("f\"");
+    for segment in segments {
+        match segment {
+            ValueSegment::Text(text) => {
+                let escaped = json_literal(text);
+                literal.push_str(
+                    &escaped[1..escaped.len() - 1]
+                        .replace('{', "{{")
+                        .replace('}', "}}"),
+                );
+            }
+            ValueSegment::Env(name) => {
+                let _ = write!(literal, "{{os.environ['{name}']}}");
+            }
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn python_requests(request: &Request) -> String {
+    let mut imports = Vec::new();
+    if request.uses_environment() {
+        imports.push("import os");
+    }
+    if matches!(request.body, Some(Body::File(None))) {
+        imports.push("import sys");
+    }
+    let mut script = String::new();
+    if !imports.is_empty() {
+        let _ = writeln!(script, "{}\n", imports.join("\n"));
+    }
+    script.push_str("import requests\n\n");
+
+    script.push_str("response = requests.request(\n");
+    let _ = writeln!(script, "    {},", json_literal(&request.method));
+    let _ = writeln!(script, "    {},", python_str(&request.url));
+    if !request.headers.is_empty() {
+        script.push_str("    headers={\n");
+        for (name, value) in &request.headers {
+            let _ = writeln!(
+                script,
+                "        {}: {},",
+                json_literal(name),
+                python_str(value)
+            );
+        }
+        script.push_str("    },\n");
+    }
+    if let Some(variable) = &request.basic_auth {
+        let _ = writeln!(
+            script,
+            "    auth=tuple(os.environ[\"{variable}\"].split(\":\", 1)),"
+        );
+    }
+    match &request.body {
+        Some(Body::Text(text)) => {
+            let _ = writeln!(script, "    data={}.encode(),", json_literal(text));
+        }
+        Some(Body::Multipart(parts)) => {
+            script.push_str("    files=[\n");
+            for part in parts {
+                let content_type = part
+                    .content_type
+                    .as_deref()
+                    .map_or_else(String::new, |content_type| {
+                        format!(", {}", json_literal(content_type))
+                    });
+                let value = match &part.value {
+                    PartValue::Text(value) => format!("None, {}", json_literal(value)),
+                    PartValue::File { path, filename } => format!(
+                        "{}, open({}, \"rb\")",
+                        json_literal(filename.as_deref().unwrap_or(path)),
+                        json_literal(path)
+                    ),
+                };
+                let _ = writeln!(
+                    script,
+                    "        ({}, ({value}{content_type})),",
+                    json_literal(&part.name)
+                );
+            }
+            script.push_str("    ],\n");
+        }
+        Some(Body::File(Some(path))) => {
+            let _ = writeln!(script, "    data=open({}, \"rb\"),", json_literal(path));
+        }
+        Some(Body::File(None)) => script.push_str("    data=sys.stdin.buffer.read(),\n"),
+        None => {}
+    }
+    script.push_str(")\n");
+    script.push_str("print(response.status_code)\n");
+    script.push_str("print(response.text)");
+    script
+}
+
+// ── JavaScript ───────────────────────────────────────────────────────
+
+fn js_str(segments: &[ValueSegment]) -> String {
+    match segments {
+        [ValueSegment::Env(name)] => return format!("process.env.{name}"),
+        _ if !has_reference(segments) => return json_literal(&literal_text(segments)),
+        _ => {}
+    }
+    let mut literal = String::from("`");
+    for segment in segments {
+        match segment {
+            ValueSegment::Text(text) => literal.push_str(
+                &text
+                    .replace('\\', "\\\\")
+                    .replace('`', "\\`")
+                    .replace("${", "\\${"),
+            ),
+            ValueSegment::Env(name) => {
+                let _ = write!(literal, "${{process.env.{name}}}");
+            }
+        }
+    }
+    literal.push('`');
+    literal
+}
+
+fn js_fetch(request: &Request) -> String {
+    let mut script = String::new();
+    if request.reads_files() {
+        script.push_str("import { readFile } from \"node:fs/promises\";\n\n");
+    }
+
+    let body = match &request.body {
+        Some(Body::Text(text)) => Some(json_literal(text)),
+        Some(Body::Multipart(parts)) => {
+            script.push_str("const form = new FormData();\n");
+            for part in parts {
+                let name = json_literal(&part.name);
+                let options = part
+                    .content_type
+                    .as_deref()
+                    .map_or_else(String::new, |content_type| {
+                        format!(", {{ type: {} }}", json_literal(content_type))
+                    });
+                let _ = match &part.value {
+                    PartValue::Text(value) if options.is_empty() => {
+                        writeln!(script, "form.append({name}, {});", json_literal(value))
+                    }
+                    PartValue::Text(value) => writeln!(
+                        script,
+                        "form.append({name}, new Blob([{}]{options}));",
+                        json_literal(value)
+                    ),
+                    PartValue::File { path, filename } => writeln!(
+                        script,
+                        "form.append({name}, new Blob([await readFile({})]{options}), {});",
+                        json_literal(path),
+                        json_literal(filename.as_deref().unwrap_or(path))
+                    ),
+                };
+            }
+            script.push('\n');
+            Some("form".to_string())
+        }
+        Some(Body::File(path)) => Some(format!(
+            "await readFile({})",
+            json_literal(path.as_deref().unwrap_or("/dev/stdin"))
+        )),
+        None => None,
+    };
+
+    let _ = writeln!(
+        script,
+        "const response = await fetch({}, {{",
+        js_str(&request.url)
+    );
+    let _ = writeln!(script, "  method: {},", json_literal(&request.method));
+    if !request.headers.is_empty() || request.basic_auth.is_some() {
+        script.push_str("  headers: {\n");
+        for (name, value) in &request.headers {
+            let _ = writeln!(script, "    {}: {},", json_literal(name), js_str(value));
+        }
+        if let Some(variable) = &request.basic_auth {
+            let _ = writeln!(
+                script,
+                "    \"authorization\": `Basic ${{Buffer.from(process.env.{variable}).toString(\"base64\")}}`,"
+            );
+        }
+        script.push_str("  },\n");
+    }
+    if let Some(body) = body {
+        let _ = writeln!(script, "  body: {body},");
+    }
+    script.push_str("});\n");
+    script.push_str("console.log(response.status);\n");
+    script.push_str("console.log(await response.text());");
+    script
+}
+
+// ── Rust ─────────────────────────────────────────────────────────────
+
+fn rust_literal(text: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(literal, "\\u{{{:x}}}", u32::from(c));
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn rust_str(segments: &[ValueSegment]) -> String {
+    match segments {
+        [ValueSegment::Env(name)] => return format!("std::env::var(\"{name}\")?"),
+        _ if !has_reference(segments) => return rust_literal(&literal_text(segments)),
+        _ => {}
+    }
+    let mut template = String::new();
+    let mut arguments = Vec::new();
+    for segment in segments {
+        match segment {
+            ValueSegment::Text(text) => {
+                template.push_str(&text.replace('{', "{{").replace('}', "}}"));
+            }
+            ValueSegment::Env(name) => {
+                template.push_str("{}");
+                arguments.push(format!("std::env::var(\"{name}\")?"));
+            }
+        }
+    }
+    format!(
+        "format!({}, {})",
+        rust_literal(&template),
+        arguments.join(", ")
+    )
+}
+
+fn rust_reqwest(request: &Request) -> String {
+    let mut program = String::from(
+        "// Dependencies: reqwest, and tokio with the \"macros\" and \"rt-multi-thread\" features\n",
+    );
+    if matches!(request.body, Some(Body::Multipart(_))) {
+        program.push_str("// reqwest needs its \"multipart\" feature for this request\n");
+    }
+    program.push_str("#[tokio::main]\n");
+    program.push_str("async fn main() -> Result<(), Box<dyn std::error::Error>> {\n");
+
+    if let Some(variable) = &request.basic_auth {
+        let _ = writeln!(
+            program,
+            "    let credentials = std::env::var(\"{variable}\")?;"
+        );
+        program.push_str(
+            "    let (username, password) = credentials.split_once(':').unwrap_or((&credentials, \"\"));\n",
+        );
+    }
+    match &request.body {
+        Some(Body::Multipart(parts)) => {
+            program.push_str("    let form = reqwest::multipart::Form::new()");
+            for part in parts {
+                let name = rust_literal(&part.name);
+                let mime = part
+                    .content_type
+                    .as_deref()
+                    .map_or_else(String::new, |content_type| {
+                        format!(".mime_str({})?", rust_literal(content_type))
+                    });
+                let _ = match &part.value {
+                    PartValue::Text(value) if mime.is_empty() => {
+                        write!(program, "\n        .text({name}, {})", rust_literal(value))
+                    }
+                    PartValue::Text(value) => write!(
+                        program,
+                        "\n        .part({name}, reqwest::multipart::Part::text({}){mime})",
+                        rust_literal(value)
+                    ),
+                    PartValue::File { path, filename } => write!(
+                        program,
+                        "\n        .part({name}, reqwest::multipart::Part::bytes(std::fs::read({})?).file_name({}){mime})",
+                        rust_literal(path),
+                        rust_literal(filename.as_deref().unwrap_or(path))
+                    ),
+                };
+            }
+            program.push_str(";\n");
+        }
+        Some(Body::File(None)) => {
+            program.push_str("    let mut body = Vec::new();\n");
+            program
+                .push_str("    std::io::Read::read_to_end(&mut std::io::stdin(), &mut body)?;\n");
+        }
+        _ => {}
+    }
+
+    program.push_str("    let response = reqwest::Client::new()\n");
+    let _ = writeln!(
+        program,
+        "        .request(reqwest::Method::{}, {})",
+        request.method.to_uppercase(),
+        rust_str(&request.url)
+    );
+    for (name, value) in &request.headers {
+        let _ = writeln!(
+            program,
+            "        .header({}, {})",
+            rust_literal(name),
+            rust_str(value)
+        );
+    }
+    if request.basic_auth.is_some() {
+        program.push_str("        .basic_auth(username, Some(password))\n");
+    }
+    match &request.body {
+        Some(Body::Text(text)) => {
+            let _ = writeln!(program, "        .body({})", rust_literal(text));
+        }
+        Some(Body::Multipart(_)) => program.push_str("        .multipart(form)\n"),
+        Some(Body::File(Some(path))) => {
+            let _ = writeln!(
+                program,
+                "        .body(std::fs::read({})?)",
+                rust_literal(path)
+            );
+        }
+        Some(Body::File(None)) => program.push_str("        .body(body)\n"),
+        None => {}
+    }
+    program.push_str("        .send()\n");
+    program.push_str("        .await?;\n");
+    program.push_str("    println!(\"{}\", response.status());\n");
+    program.push_str("    println!(\"{}\", response.text().await?);\n");
+    program.push_str("    Ok(())\n");
+    program.push('}');
+    program
+}
+
+/// The text of segments without references
+fn literal_text(segments: &[ValueSegment]) -> String {
+    segments
+        .iter()
+        .filter_map(|segment| match segment {
+            ValueSegment::Text(text) => Some(text.as_str()),
+            ValueSegment::Env(_) => None,
+        })
+        .collect()
+}
+
+fn json_literal(text: &str) -> String {
+    Value::String(text.to_string()).to_string()
+}
@@ -0,0 +1,381 @@
+//! Bundling of multi-file specifications
+//!
+//! A specification may be split across files or URLs with references such as
+//! `./schemas/user.yaml#/User`. [`bundle_external_references`] follows those
+//! references from the root document, relative to the document they appear in,
+//! and inlines their targets so the result is a single self-contained document.
+//! References local to the root document are left for
+//! [`inline_references`](super::inline_references) to handle after parsing.
+//!
+//! An external schema that refers back to itself cannot be inlined, so it is
+//! hoisted into the root's `components/schemas` (`definitions` in a Swagger
+//! 2.0 document) and referenced locally instead.
+
+use super::resolver::MAX_INLINE_DEPTH;
+use super::LITERAL_KEYS;
+use crate::config::manager::is_url;
+use crate::error::Error;
+use serde_yaml::{Mapping, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+
+const REF_KEY: &str = "$ref";
+
+/// Where hoisted schemas go in an `OpenAPI` 3 document and in a Swagger 2.0 one
+const OPENAPI_SCHEMAS_SECTION: &[&str] = &["components", "schemas"];
+const SWAGGER2_SCHEMAS_SECTION: &[&str] = &["definitions"];
+
+/// Where a specification document was read from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SpecLocation {
+    /// A local file
+    File(PathBuf),
+    /// A remote document
+    Url(reqwest::Url),
+}
+
+impl SpecLocation {
+    /// Returns the location of a spec given as a file path or an `http(s)` URL
+    ///
+    /// # Errors
+    /// Returns an error if `source` looks like a URL but cannot be parsed
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        if is_url(source) {
+            reqwest::Url::parse(source).map(Self::Url).map_err(|e| {
+                Error::validation_error(format!("Invalid specification URL '{source}': {e}"))
+            })
+        } else {
+            Ok(Self::File(PathBuf::from(source)))
+        }
+    }
+
+    /// Resolves the file part of a reference against this location
+    fn join(&self, reference: &str) -> Result<Self, Error> {
+        if is_url(reference) {
+            return Self::parse(reference);
+        }
+        match self {
+            Self::File(path) => Ok(Self::File(
+                path.parent()
+                    .map_or_else(|| PathBuf::from(reference), |dir| dir.join(reference)),
+            )),
+            Self::Url(url) => url.join(reference).map(Self::Url).map_err(|e| {
+                Error::validation_error(format!(
+                    "Cannot resolve reference '{reference}' against {url}: {e}"
+                ))
+            }),
+        }
+    }
+}
+
+impl fmt::Display for SpecLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Url(url) => write!(f, "{url}"),
+        }
+    }
+}
+
+/// Inlines the external references of the document at `root`
+///
+/// `load` reads the content of every document referenced from the root,
+/// directly or through other external documents.
+///
+/// # Returns
+/// * `Ok(Some(content))` - The bundled document as YAML
+/// * `Ok(None)` - The document has no external references
+///
+/// # Errors
+/// Returns an error if a referenced document cannot be loaded or parsed, a
+/// reference points at a missing fragment, or references nest deeper than
+/// [`MAX_INLINE_DEPTH`]
+pub fn bundle_external_references<L>(
+    content: &str,
+    root: &SpecLocation,
+    load: L,
+) -> Result<Option<String>, Error>
+where
+    L: FnMut(&SpecLocation) -> Result<String, Error>,
+{
+    if !content.contains(REF_KEY) {
+        return Ok(None);
+    }
+    let mut document = parse_document(content, root)?;
+    let schemas_section = if document.get("swagger").is_some() {
+        SWAGGER2_SCHEMAS_SECTION
+    } else {
+        OPENAPI_SCHEMAS_SECTION
+    };
+
+    let mut bundler = Bundler {
+        load,
+        documents: HashMap::new(),
+        stack: Vec::new(),
+        hoisted: HashMap::new(),
+        hoisted_schemas: Vec::new(),
+        taken_names: schema_names(&document, schemas_section),
+        schemas_section,
+    };
+    bundler.bundle(&mut document, root, true, false)?;
+    if bundler.documents.is_empty() {
+        return Ok(None);
+    }
+    bundler.insert_hoisted_schemas(&mut document);
+
+    serde_yaml::to_string(&document)
+        .map(Some)
+        .map_err(|e| Error::serialization_error(format!("Failed to write bundled spec: {e}")))
+}
+
+/// A reference target: the document it lives in and a JSON pointer into it
+type Target = (SpecLocation, String);
+
+struct Bundler<L> {
+    load: L,
+    /// External documents loaded so far
+    documents: HashMap<SpecLocation, Value>,
+    /// Targets currently being inlined, outermost first
+    stack: Vec<Target>,
+    /// Self-referencing targets and the component names they were hoisted to
+    hoisted: HashMap<Target, String>,
+    hoisted_schemas: Vec<(String, Value)>,
+    taken_names: HashSet<String>,
+    /// Path of the root's schema section, which hoisted targets are added to
+    schemas_section: &'static [&'static str],
+}
+
+impl<L> Bundler<L>
+where
+    L: FnMut(&SpecLocation) -> Result<String, Error>,
+{
+    /// Inlines the external references within `value`, which was read from `base`
+    fn bundle(
+        &mut self,
+        value: &mut Value,
+        base: &SpecLocation,
+        in_root: bool,
+        in_properties: bool,
+    ) -> Result<(), Error> {
+        if let Some(reference) = value.get(REF_KEY).and_then(Value::as_str) {
+            // Local references in the root stay valid once bundled
+            if in_root && reference.starts_with('#') {
+                return Ok(());
+            }
+            let target = Self::target(reference, base)?;
+            *value = self.resolve(reference, target)?;
+            return Ok(());
+        }
+
+        match value {
+            Value::Mapping(mapping) => {
+                for (key, child) in mapping.iter_mut() {
+                    let key = key.as_str().unwrap_or_default();
+                    if !in_properties && LITERAL_KEYS.contains(&key) {
+                        continue;
+                    }
+                    let child_in_properties = !in_properties && key == "properties";
+                    self.bundle(child, base, in_root, child_in_properties)?;
+                }
+            }
+            Value::Sequence(items) => {
+                for item in items {
+                    self.bundle(item, base, in_root, false)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Splits `reference` into the document it names and a pointer into it
+    fn target(reference: &str, base: &SpecLocation) -> Result<Target, Error> {
+        let (file, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let location = if file.is_empty() {
+            base.clone()
+        } else {
+            base.join(file)?
+        };
+        Ok((location, fragment.to_string()))
+    }
+
+    /// Returns the fully bundled value `reference` points at
+    fn resolve(&mut self, reference: &str, target: Target) -> Result<Value, Error> {
+        if let Some(name) = self.hoisted.get(&target) {
+            return Ok(self.schema_reference(name));
+        }
+        if self.stack.contains(&target) {
+            let name = self.hoisted_name(&target);
+            self.hoisted.insert(target, name.clone());
+            return Ok(self.schema_reference(&name));
+        }
+        if self.stack.len() >= MAX_INLINE_DEPTH {
+            return Err(Error::validation_error(format!(
+                "External reference '{reference}' is nested more than {MAX_INLINE_DEPTH} levels deep"
+            )));
+        }
+
+        let document = self.document(&target.0)?;
+        let mut value = pointer(document, &target.1)
+            .ok_or_else(|| {
+                Error::validation_error(format!(
+                    "External reference '{reference}' not found: {} has no '#{}'",
+                    target.0, target.1
+                ))
+            })?
+            .clone();
+
+        self.stack.push(target.clone());
+        let bundled = self.bundle(&mut value, &target.0, false, false);
+        self.stack.pop();
+        bundled?;
+
+        if let Some(name) = self.hoisted.get(&target) {
+            let name = name.clone();
+            self.hoisted_schemas.push((name.clone(), value));
+            return Ok(self.schema_reference(&name));
+        }
+        Ok(value)
+    }
+
+    /// Returns the parsed document at `location`, loading it on first use
+    fn document(&mut self, location: &SpecLocation) -> Result<&Value, Error> {
+        if !self.documents.contains_key(location) {
+            let content = (self.load)(location).map_err(|e| {
+                Error::validation_error(format!(
+                    "Failed to load referenced document {location}: {e}"
+                ))
+            })?;
+            let document = parse_document(&content, location)?;
+            self.documents.insert(location.clone(), document);
+        }
+        Ok(&self.documents[location])
+    }
+
+    /// Picks an unused component name for a hoisted target
+    fn hoisted_name(&mut self, (location, fragment): &Target) -> String {
+        let segment = fragment.rsplit('/').find(|segment| !segment.is_empty());
+        let stem = match (segment, location) {
+            (Some(segment), _) => decode_segment(segment),
+            (None, SpecLocation::File(path)) => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            (None, SpecLocation::Url(url)) => url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .and_then(|file| file.split('.').next())
+                .unwrap_or_default()
+                .to_string(),
+        };
+        let base: String = stem
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            .collect();
+        let base = if base.is_empty() {
+            "Schema".to_string()
+        } else {
+            base
+        };
+
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while !self.taken_names.insert(name.clone()) {
+            name = format!("{base}{suffix}");
+            suffix += 1;
+        }
+        name
+    }
+
+    /// Returns a local reference to the hoisted schema `name`
+    fn schema_reference(&self, name: &str) -> Value {
+        let mut reference = Mapping::new();
+        reference.insert(
+            Value::String(REF_KEY.to_string()),
+            Value::String(format!("#/{}/{name}", self.schemas_section.join("/"))),
+        );
+        Value::Mapping(reference)
+    }
+
+    fn insert_hoisted_schemas(&mut self, document: &mut Value) {
+        if self.hoisted_schemas.is_empty() {
+            return;
+        }
+        let Some(root) = document.as_mapping_mut() else {
+            return;
+        };
+        let schemas = self
+            .schemas_section
+            .iter()
+            .fold(root, |mapping, key| mapping_entry(mapping, key));
+        for (name, schema) in self.hoisted_schemas.drain(..) {
+            schemas.insert(Value::String(name), schema);
+        }
+    }
+}
+
+fn parse_document(content: &str, location: &SpecLocation) -> Result<Value, Error> {
+    serde_yaml::from_str(content).map_err(|e| {
+        Error::serialization_error(format!("Failed to parse document {location}: {e}"))
+    })
+}
+
+fn schema_names(document: &Value, section: &[&str]) -> HashSet<String> {
+    section
+        .iter()
+        .try_fold(document, |value, key| value.get(*key))
+        .and_then(Value::as_mapping)
+        .map(|schemas| {
+            schemas
+                .keys()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the mapping under `key`, replacing any non-mapping value
+fn mapping_entry<'a>(mapping: &'a mut Mapping, key: &str) -> &'a mut Mapping {
+    let entry = mapping
+        .entry(Value::String(key.to_string()))
+        .or_insert_with(|| Value::Mapping(Mapping::new()));
+    if !entry.is_mapping() {
+        *entry = Value::Mapping(Mapping::new());
+    }
+    entry
+        .as_mapping_mut()
+        .expect("entry was just made a mapping")
+}
+
+/// Looks up a JSON pointer (without its leading `#`) in a YAML document
+fn pointer<'a>(document: &'a Value, pointer: &str) -> Option<&'a Value> {
+    pointer
+        .split('/')
+        .skip(1)
+        .try_fold(document, |value, segment| {
+            let segment = decode_segment(segment);
+            match value {
+                Value::Mapping(mapping) => mapping.iter().find_map(|(key, child)| {
+                    let matches = match key {
+                        Value::String(key) => *key == segment,
+                        Value::Number(key) => key.to_string() == segment,
+                        Value::Bool(key) => key.to_string() == segment,
+                        _ => false,
+                    };
+                    matches.then_some(child)
+                }),
+                Value::Sequence(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            }
+        })
+}
+
+/// Decodes a JSON pointer segment: percent-escapes, then `~1` and `~0`
+fn decode_segment(segment: &str) -> String {
+    urlencoding::decode(segment)
+        .map_or_else(|_| segment.to_string(), std::borrow::Cow::into_owned)
+        .replace("~1", "/")
+        .replace("~0", "~")
+}
@@ -0,0 +1,130 @@
+//! Version-neutral view of a parsed specification
+//!
+//! The structural `openapiv3` model drives operations, parameters and security
+//! schemes for every `OpenAPI` version. It models 3.0, so for a 3.1 document
+//! it holds a lowered copy, and the document as written is kept alongside it
+//! as the source of schemas. Type arrays, `const`, `examples` arrays and
+//! keywords next to a `$ref` therefore reach the cache and the agent manifest
+//! unchanged, and webhooks remain available.
+
+use crate::error::Error;
+use openapiv3::OpenAPI;
+use serde_json::Value;
+
+/// Keys of a path item that hold operations
+const OPERATION_KEYS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// The `OpenAPI` version a specification declares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecVersion {
+    /// `OpenAPI` 3.0.x, or Swagger 2.0 converted to it
+    OpenApi30,
+    /// `OpenAPI` 3.1.x
+    OpenApi31,
+}
+
+/// A webhook operation: a request the API sends rather than receives
+#[derive(Debug, Clone, Copy)]
+pub struct Webhook<'a> {
+    /// Key of the webhook in the `webhooks` section
+    pub name: &'a str,
+    /// Lowercase HTTP method
+    pub method: &'a str,
+    /// The operation object as written
+    pub operation: &'a Value,
+}
+
+/// A parsed specification with its component references inlined
+#[derive(Debug, Clone)]
+pub struct SpecDocument {
+    version: SpecVersion,
+    openapi: OpenAPI,
+    /// The document as written, kept when the structural model is lossy
+    source: Option<Value>,
+}
+
+impl SpecDocument {
+    /// Parses `OpenAPI` 3.0 or 3.1 content and inlines its component references
+    ///
+    /// # Errors
+    /// Returns an error if the content cannot be parsed, see [`crate::spec::parse_openapi`]
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let (openapi, source) = crate::spec::parser::parse_openapi_with_source(content)?;
+        Self::new(&openapi, source)
+    }
+
+    /// Wraps an already parsed `OpenAPI` 3.0 model
+    ///
+    /// # Errors
+    /// Returns an error if the model cannot be converted to and from JSON
+    pub fn from_openapi(openapi: &OpenAPI) -> Result<Self, Error> {
+        Self::new(openapi, None)
+    }
+
+    fn new(openapi: &OpenAPI, mut source: Option<Value>) -> Result<Self, Error> {
+        let version = if source.is_some() {
+            SpecVersion::OpenApi31
+        } else {
+            SpecVersion::OpenApi30
+        };
+        let openapi = crate::spec::inline_references(openapi)?;
+        if let Some(source) = source.as_mut() {
+            crate::spec::resolver::inline_document_references(source);
+        }
+        Ok(Self {
+            version,
+            openapi,
+            source,
+        })
+    }
+
+    /// The declared `OpenAPI` version
+    #[must_use]
+    pub const fn version(&self) -> SpecVersion {
+        self.version
+    }
+
+    /// The structural model, with 3.1-only keywords lowered to 3.0
+    #[must_use]
+    pub const fn openapi(&self) -> &OpenAPI {
+        &self.openapi
+    }
+
+    /// The operation object as written, when the structural model is lossy
+    ///
+    /// Schemas read from it take precedence over those of the structural
+    /// model. Returns `None` for 3.0 documents, whose model holds every
+    /// keyword Aperture uses.
+    #[must_use]
+    pub fn operation_source(&self, path: &str, method: &str) -> Option<&Value> {
+        self.source
+            .as_ref()?
+            .get("paths")?
+            .get(path)?
+            .get(method.to_lowercase())
+    }
+
+    /// Lists the operations of the `webhooks` section (`OpenAPI` 3.1)
+    #[must_use]
+    pub fn webhooks(&self) -> Vec<Webhook<'_>> {
+        let Some(Value::Object(webhooks)) = self.source.as_ref().and_then(|s| s.get("webhooks"))
+        else {
+            return Vec::new();
+        };
+
+        webhooks
+            .iter()
+            .flat_map(|(name, path_item)| {
+                OPERATION_KEYS.iter().filter_map(move |method| {
+                    path_item.get(*method).map(|operation| Webhook {
+                        name,
+                        method,
+                        operation,
+                    })
+                })
+            })
+            .collect()
+    }
+}
@@ -5,16 +5,26 @@
 
 use crate::constants;
 
+pub mod bundler;
+pub mod document;
+pub mod openapi31;
 pub mod parser;
+pub mod resolver;
+pub mod swagger2;
 pub mod transformer;
 pub mod validator;
 
+pub use bundler::{bundle_external_references, SpecLocation};
+pub use document::{SpecDocument, SpecVersion};
 pub use parser::parse_openapi;
+pub use resolver::inline_references;
+pub use swagger2::convert_swagger2;
 pub use transformer::SpecTransformer;
 pub use validator::SpecValidator;
 
+use crate::cache::models::CachedOAuth2Config;
 use crate::error::Error;
-use openapiv3::{OpenAPI, Operation, Parameter, PathItem, ReferenceOr};
+use openapiv3::{OAuth2Flows, OpenAPI, Operation, Parameter, PathItem, ReferenceOr};
 use std::collections::HashSet;
 
 /// A helper type to iterate over all HTTP methods in a `PathItem`
@@ -41,6 +51,76 @@ pub const fn http_methods_iter(item: &PathItem) -> HttpMethodsIter<'_> {
     ]
 }
 
+/// Checks whether a media type is `multipart/form-data`, ignoring case and parameters
+#[must_use]
+pub fn is_multipart_content_type(content_type: &str) -> bool {
+    has_media_type(content_type, constants::CONTENT_TYPE_MULTIPART)
+}
+
+/// Checks whether a media type is `application/x-www-form-urlencoded`, ignoring
+/// case and parameters
+#[must_use]
+pub fn is_form_urlencoded_content_type(content_type: &str) -> bool {
+    has_media_type(content_type, constants::CONTENT_TYPE_FORM)
+}
+
+/// Checks whether a media type carries raw binary data: `application/octet-stream`,
+/// `application/pdf` or any `image/*` type, ignoring case and parameters
+#[must_use]
+pub fn is_binary_content_type(content_type: &str) -> bool {
+    has_media_type(content_type, constants::CONTENT_TYPE_OCTET_STREAM)
+        || has_media_type(content_type, constants::CONTENT_TYPE_PDF)
+        || content_type
+            .get(..constants::CONTENT_TYPE_PREFIX_IMAGE.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(constants::CONTENT_TYPE_PREFIX_IMAGE))
+}
+
+fn has_media_type(content_type: &str, expected: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .is_some_and(|base| base.trim().eq_ignore_ascii_case(expected))
+}
+
+/// Extracts the `OAuth2` configuration Aperture can drive from a scheme's flows
+///
+/// The client-credentials and authorization-code flows are supported. When a
+/// scheme declares both, the client-credentials token endpoint and scopes take
+/// precedence. Implicit and password flows are ignored.
+///
+/// # Returns
+/// `None` if the scheme declares no flow Aperture can use
+#[must_use]
+pub fn oauth2_config_from_flows(flows: &OAuth2Flows) -> Option<CachedOAuth2Config> {
+    let client_credentials = flows.client_credentials.as_ref();
+    let authorization_code = flows.authorization_code.as_ref();
+
+    let (token_url, scopes) = match (client_credentials, authorization_code) {
+        (Some(flow), _) => (&flow.token_url, &flow.scopes),
+        (None, Some(flow)) => (&flow.token_url, &flow.scopes),
+        (None, None) => return None,
+    };
+
+    let mut grant_types = Vec::new();
+    if client_credentials.is_some() {
+        grant_types.push(constants::OAUTH2_GRANT_CLIENT_CREDENTIALS.to_string());
+    }
+    if authorization_code.is_some() {
+        grant_types.push(constants::OAUTH2_GRANT_AUTHORIZATION_CODE.to_string());
+    }
+
+    Some(CachedOAuth2Config {
+        token_url: token_url.clone(),
+        scopes: scopes.keys().cloned().collect(),
+        grant_types,
+        authorization_url: authorization_code.map(|flow| flow.authorization_url.clone()),
+    })
+}
+
+/// Keys whose values are literal data rather than specification objects, so
+/// a `$ref` inside them is data and never followed or rewritten
+pub(crate) const LITERAL_KEYS: [&str; 4] = ["example", "examples", "enum", "const"];
+
 /// Maximum depth for resolving parameter references to prevent stack overflow
 pub const MAX_REFERENCE_DEPTH: usize = 10;
 
@@ -88,26 +168,11 @@ pub fn resolve_parameter_reference(spec: &OpenAPI, reference: &str) -> Result<Pa
 /// - Circular references are detected
 /// - Maximum reference depth is exceeded
 ///
-/// # Limitations
-///
-/// **Nested references are not resolved**: This function only resolves the
-/// top-level schema reference. If the resolved schema contains nested `$ref`
-/// within its properties, those remain unresolved. For example:
-///
-/// ```json
-/// // #/components/schemas/Order resolves to:
-/// {
-///   "type": "object",
-///   "properties": {
-///     "customer": { "$ref": "#/components/schemas/Customer" }  // NOT resolved
-///   }
-/// }
-/// ```
+/// # Nested References
 ///
-/// Implementing recursive resolution of nested references would require
-/// traversing the entire schema tree, which adds complexity and risk of
-/// infinite loops with self-referential schemas (e.g., a `User` with a
-/// `friends: User[]` property).
+/// Only the top-level reference is resolved; `$ref`s nested within the
+/// resolved schema's properties are returned as-is. Use [`inline_references`]
+/// to inline them across a whole specification.
 pub fn resolve_schema_reference(
     spec: &OpenAPI,
     reference: &str,
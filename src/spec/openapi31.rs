@@ -0,0 +1,289 @@
+//! Lowering of `OpenAPI` 3.1 documents to the 3.0 structural model
+//!
+//! `openapiv3` models `OpenAPI` 3.0, so the operations, parameters and
+//! security schemes of a 3.1 document are read from a copy in which 3.1-only
+//! constructs are rewritten to their nearest 3.0 form:
+//!
+//! - `type: [string, "null"]` becomes `type: string` with `nullable: true`;
+//!   several non-null types leave the type unset
+//! - `const` becomes a single-value `enum` and an `examples` array its first
+//!   entry as `example`
+//! - numeric `exclusiveMinimum`/`exclusiveMaximum` become `minimum`/`maximum`
+//!   with the boolean flag
+//! - boolean schemas become `{}` and `{"not": {}}`
+//! - keywords next to a `$ref` are dropped
+//! - path items referenced from `components/pathItems` are inlined and
+//!   `mutualTLS` security schemes, which Aperture cannot use, are removed
+//!
+//! The lowered copy only drives the structure. Cached schemas are read from the
+//! document as written, see [`crate::spec::SpecDocument`].
+
+use crate::constants;
+use crate::error::Error;
+use crate::spec::LITERAL_KEYS;
+use openapiv3::OpenAPI;
+use serde_json::{json, Map, Value};
+use std::cmp::Ordering;
+
+/// Keywords holding a single subschema
+const SUBSCHEMA_KEYS: [&str; 3] = ["items", "additionalProperties", "not"];
+
+/// Keywords holding a list of subschemas
+const SUBSCHEMA_LIST_KEYS: [&str; 3] = ["allOf", "anyOf", "oneOf"];
+
+/// Builds the 3.0 structural model of a 3.1 document
+///
+/// # Errors
+/// Returns an error if the lowered document is not a valid `OpenAPI` description
+pub fn lower_to_openapi30(document: &Value) -> Result<OpenAPI, Error> {
+    let mut lowered = document.clone();
+    let Some(root) = lowered.as_object_mut() else {
+        return Err(Error::validation_error(
+            "OpenAPI 3.1 spec must be a mapping at the top level",
+        ));
+    };
+    root.entry("paths").or_insert_with(|| json!({}));
+    root.remove("webhooks");
+
+    inline_path_items(&mut lowered);
+    remove_mutual_tls_schemes(&mut lowered);
+    if let Some(schemas) = lowered
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+    {
+        schemas.values_mut().for_each(lower_schema);
+    }
+    lower_schemas_in(&mut lowered);
+
+    serde_json::from_value(lowered)
+        .map_err(|e| Error::validation_error(format!("Failed to parse OpenAPI 3.1 spec: {e}")))
+}
+
+/// Replaces path items that reference `components/pathItems` with their target
+fn inline_path_items(document: &mut Value) {
+    let path_items = document
+        .pointer("/components/pathItems")
+        .cloned()
+        .unwrap_or(Value::Null);
+    let Some(paths) = document.get_mut("paths").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for path_item in paths.values_mut() {
+        let target = path_item
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix("#/components/pathItems/"))
+            .and_then(|name| path_items.get(name));
+        if let Some(target) = target {
+            *path_item = target.clone();
+        }
+    }
+}
+
+fn remove_mutual_tls_schemes(document: &mut Value) {
+    if let Some(schemes) = document
+        .pointer_mut("/components/securitySchemes")
+        .and_then(Value::as_object_mut)
+    {
+        schemes.retain(|_, scheme| scheme.get("type").and_then(Value::as_str) != Some("mutualTLS"));
+    }
+}
+
+/// Lowers every schema found under a `schema` key within `value`
+fn lower_schemas_in(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                if LITERAL_KEYS.contains(&key.as_str()) {
+                    continue;
+                }
+                if key == "schema" {
+                    lower_schema(child);
+                } else {
+                    lower_schemas_in(child);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(lower_schemas_in),
+        _ => {}
+    }
+}
+
+/// Rewrites a JSON Schema 2020-12 schema and its subschemas to `OpenAPI` 3.0
+fn lower_schema(schema: &mut Value) {
+    let object = match schema {
+        Value::Bool(true) => {
+            *schema = json!({});
+            return;
+        }
+        Value::Bool(false) => {
+            *schema = json!({"not": {}});
+            return;
+        }
+        Value::Object(object) => object,
+        _ => return,
+    };
+
+    if object.contains_key("$ref") {
+        object.retain(|key, _| key == "$ref");
+        return;
+    }
+
+    lower_type(object);
+    if let Some(value) = object.remove("const") {
+        object
+            .entry("enum")
+            .or_insert_with(|| Value::Array(vec![value]));
+    }
+    if let Some(Value::Array(mut examples)) = object.remove("examples") {
+        if !examples.is_empty() && !object.contains_key("example") {
+            object.insert("example".to_string(), examples.swap_remove(0));
+        }
+    }
+    lower_exclusive_bound(
+        object,
+        constants::FIELD_EXCLUSIVE_MINIMUM,
+        "minimum",
+        Ordering::Greater,
+    );
+    lower_exclusive_bound(
+        object,
+        constants::FIELD_EXCLUSIVE_MAXIMUM,
+        "maximum",
+        Ordering::Less,
+    );
+
+    if let Some(Value::Object(properties)) = object.get_mut("properties") {
+        properties.values_mut().for_each(lower_schema);
+    }
+    // A tuple-style `items` array has no 3.0 equivalent
+    if object.get("items").is_some_and(Value::is_array) {
+        object.remove("items");
+    }
+    for key in SUBSCHEMA_KEYS {
+        match object.get_mut(key) {
+            // `additionalProperties: false` is valid 3.0
+            Some(Value::Bool(_)) if key == "additionalProperties" => {}
+            Some(subschema) => lower_schema(subschema),
+            None => {}
+        }
+    }
+    for key in SUBSCHEMA_LIST_KEYS {
+        if let Some(Value::Array(subschemas)) = object.get_mut(key) {
+            subschemas.iter_mut().for_each(lower_schema);
+        }
+    }
+}
+
+/// Turns a type array into a single type, marking `"null"` as `nullable`
+fn lower_type(object: &mut Map<String, Value>) {
+    let mut types: Vec<String> = match object.get("type") {
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Some(Value::String(single)) if single == "null" => vec![single.clone()],
+        _ => return,
+    };
+    object.remove("type");
+
+    if let Some(index) = types.iter().position(|t| t == "null") {
+        types.remove(index);
+        object.insert(constants::FIELD_NULLABLE.to_string(), Value::Bool(true));
+    }
+    if let [single] = types.as_slice() {
+        object.insert("type".to_string(), Value::String(single.clone()));
+    }
+}
+
+/// Turns a numeric exclusive bound into the 3.0 bound plus boolean flag.
+///
+/// When the schema also has an inclusive bound, the stricter of the two is
+/// kept; `stricter` is how a stricter inclusive bound compares to the
+/// exclusive one.
+fn lower_exclusive_bound(
+    object: &mut Map<String, Value>,
+    exclusive_key: &str,
+    bound_key: &str,
+    stricter: Ordering,
+) {
+    let Some(exclusive) = object.get(exclusive_key).and_then(Value::as_f64) else {
+        return;
+    };
+    let inclusive = object.get(bound_key).and_then(Value::as_f64);
+    if inclusive.and_then(|inclusive| inclusive.partial_cmp(&exclusive)) == Some(stricter) {
+        object.remove(exclusive_key);
+        return;
+    }
+    if let Some(bound) = object.insert(exclusive_key.to_string(), Value::Bool(true)) {
+        object.insert(bound_key.to_string(), bound);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lower_schema_keywords() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": ["string", "null"], "examples": ["Rex", "Fido"]},
+                "kind": {"const": "dog"},
+                "age": {"type": "integer", "exclusiveMinimum": 0},
+                "weight": {"type": "number", "minimum": 5, "exclusiveMinimum": 3},
+                "height": {"type": "number", "maximum": 5, "exclusiveMaximum": 3},
+                "tags": {"type": "array", "items": true},
+                "id": {"$ref": "#/components/schemas/Id", "description": "The id"},
+                "value": {"type": ["string", "integer"]}
+            }
+        });
+        lower_schema(&mut schema);
+
+        assert_eq!(
+            schema["properties"],
+            json!({
+                "name": {"type": "string", "nullable": true, "example": "Rex"},
+                "kind": {"enum": ["dog"]},
+                "age": {"type": "integer", "minimum": 0, "exclusiveMinimum": true},
+                "weight": {"type": "number", "minimum": 5},
+                "height": {"type": "number", "maximum": 3, "exclusiveMaximum": true},
+                "tags": {"type": "array", "items": {}},
+                "id": {"$ref": "#/components/schemas/Id"},
+                "value": {}
+            })
+        );
+    }
+
+    #[test]
+    fn test_lower_document() {
+        let document = json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1"},
+            "paths": {"/pets": {"$ref": "#/components/pathItems/Pets"}},
+            "webhooks": {"newPet": {"post": {"responses": {"200": {"description": "OK"}}}}},
+            "components": {
+                "pathItems": {
+                    "Pets": {"get": {"operationId": "listPets", "responses": {"200": {"description": "OK"}}}}
+                },
+                "securitySchemes": {
+                    "mtls": {"type": "mutualTLS"},
+                    "token": {"type": "http", "scheme": "bearer"}
+                }
+            }
+        });
+
+        let spec = lower_to_openapi30(&document).unwrap();
+        let openapiv3::ReferenceOr::Item(pets) = &spec.paths.paths["/pets"] else {
+            panic!("path item reference was not inlined");
+        };
+        assert_eq!(
+            pets.get.as_ref().unwrap().operation_id.as_deref(),
+            Some("listPets")
+        );
+        let schemes = &spec.components.unwrap().security_schemes;
+        assert_eq!(schemes.keys().collect::<Vec<_>>(), vec!["token"]);
+    }
+}
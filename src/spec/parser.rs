@@ -2,6 +2,26 @@ use crate::constants;
 use crate::error::Error;
 use openapiv3::OpenAPI;
 use regex::Regex;
+use serde_json::Value;
+
+/// Properties that should be boolean in every `OpenAPI` version but sometimes use 0/1
+const BOOLEAN_FLAGS: &[&str] = &[
+    constants::FIELD_DEPRECATED,
+    constants::FIELD_REQUIRED,
+    constants::FIELD_READ_ONLY,
+    constants::FIELD_WRITE_ONLY,
+    constants::FIELD_NULLABLE,
+    constants::FIELD_UNIQUE_ITEMS,
+    constants::FIELD_ALLOW_EMPTY_VALUE,
+    constants::FIELD_EXPLODE,
+    constants::FIELD_ALLOW_RESERVED,
+];
+
+/// Bounds that are boolean flags in `OpenAPI` 3.0 but numbers in 3.1
+const EXCLUSIVE_BOUNDS: &[&str] = &[
+    constants::FIELD_EXCLUSIVE_MINIMUM,
+    constants::FIELD_EXCLUSIVE_MAXIMUM,
+];
 
 /// Preprocesses `OpenAPI` content to fix common compatibility issues
 ///
@@ -10,37 +30,27 @@ use regex::Regex;
 /// - Works with both YAML and JSON formats
 /// - Preserves multi-digit numbers (e.g., 10, 18, 100)
 fn preprocess_for_compatibility(content: &str) -> String {
-    // Properties that should be boolean in OpenAPI 3.0 but sometimes use 0/1
-    // Note: exclusiveMinimum/Maximum are boolean in 3.0 but numeric in 3.1
-    const BOOLEAN_PROPERTIES: &[&str] = &[
-        constants::FIELD_DEPRECATED,
-        constants::FIELD_REQUIRED,
-        constants::FIELD_READ_ONLY,
-        constants::FIELD_WRITE_ONLY,
-        constants::FIELD_NULLABLE,
-        constants::FIELD_UNIQUE_ITEMS,
-        constants::FIELD_ALLOW_EMPTY_VALUE,
-        constants::FIELD_EXPLODE,
-        constants::FIELD_ALLOW_RESERVED,
-        constants::FIELD_EXCLUSIVE_MINIMUM,
-        constants::FIELD_EXCLUSIVE_MAXIMUM,
-    ];
+    let result = fix_boolean_values(content, BOOLEAN_FLAGS);
+    fix_boolean_values(&result, EXCLUSIVE_BOUNDS)
+}
 
+/// Converts 0/1 values of `properties` to booleans in YAML or JSON content
+fn fix_boolean_values(content: &str, properties: &[&str]) -> String {
     // Detect format to optimize processing
     let is_json = content.trim_start().starts_with('{');
     let mut result = content.to_string();
 
     // Apply appropriate replacements based on format
     if is_json {
-        return fix_json_boolean_values(result, BOOLEAN_PROPERTIES);
+        return fix_json_boolean_values(result, properties);
     }
 
     // Process as YAML
-    result = fix_yaml_boolean_values(result, BOOLEAN_PROPERTIES);
+    result = fix_yaml_boolean_values(result, properties);
 
     // JSON might be embedded in YAML comments or examples, so also check JSON patterns
     if result.contains('"') {
-        result = fix_json_boolean_values(result, BOOLEAN_PROPERTIES);
+        result = fix_json_boolean_values(result, properties);
     }
 
     result
@@ -99,6 +109,7 @@ fn fix_component_indentation(content: &str) -> String {
         constants::COMPONENT_SECURITY_SCHEMES,
         constants::COMPONENT_LINKS,
         constants::COMPONENT_CALLBACKS,
+        constants::COMPONENT_PATH_ITEMS,
     ];
 
     for section in &component_sections {
@@ -109,11 +120,11 @@ fn fix_component_indentation(content: &str) -> String {
     result
 }
 
-/// Parses `OpenAPI` content, supporting both 3.0.x (directly) and 3.1.x (via oas3 fallback).
+/// Parses `OpenAPI` content into the structural `OpenAPI` model.
 ///
-/// This function first attempts to parse the content as `OpenAPI` 3.0.x using the `openapiv3` crate.
-/// If that fails, it falls back to parsing as `OpenAPI` 3.1.x using the `oas3` crate, then attempts
-/// to convert the result to `OpenAPI` 3.0.x format.
+/// `OpenAPI` 3.0.x is parsed directly with the `openapiv3` crate. `OpenAPI`
+/// 3.1.x is lowered to the 3.0 model first;
+/// use [`crate::spec::SpecDocument::parse`] to keep its 3.1 schemas as well.
 ///
 /// # Arguments
 ///
@@ -128,52 +139,39 @@ fn fix_component_indentation(content: &str) -> String {
 /// Returns an error if:
 /// - The content is not valid YAML
 /// - The content is not a valid `OpenAPI` specification
-/// - `OpenAPI` 3.1 features cannot be converted to 3.0 format
-///
-/// # Limitations
-///
-/// When parsing `OpenAPI` 3.1.x specifications:
-/// - Some 3.1-specific features may be lost or downgraded
-/// - Type arrays become single types
-/// - Webhooks are not supported
-/// - JSON Schema 2020-12 features may not be preserved
 pub fn parse_openapi(content: &str) -> Result<OpenAPI, Error> {
-    // Always preprocess for compatibility issues.
-    let mut preprocessed = preprocess_for_compatibility(content);
-
-    let is_openapi_31 = looks_like_openapi_31(content);
-    if is_openapi_31 {
-        // For OpenAPI 3.1 specs, also fix potential indentation issues
-        // (some 3.1 specs like OpenProject have malformed indentation).
-        preprocessed = fix_component_indentation(&preprocessed);
-    }
-
-    #[cfg(feature = "openapi31")]
-    {
-        let parsed_openapi_31 = if is_openapi_31 {
-            parse_with_oas3_direct_with_original(&preprocessed, content).ok()
-        } else {
-            None
-        };
+    parse_openapi_with_source(content).map(|(spec, _)| spec)
+}
 
-        if let Some(spec) = parsed_openapi_31 {
-            return Ok(spec);
-        }
+/// Parses `OpenAPI` content into the structural model, along with the
+/// document as written when the model cannot represent all of it.
+///
+/// The second value is the JSON form of an `OpenAPI` 3.1 document, whose
+/// schemas keep the keywords the structural model lowers; it is `None` for 3.0.
+///
+/// # Errors
+///
+/// Returns the same errors as [`parse_openapi`]
+pub fn parse_openapi_with_source(content: &str) -> Result<(OpenAPI, Option<Value>), Error> {
+    if looks_like_openapi_31(content) {
+        // Some 3.1 specs (like OpenProject) have malformed component
+        // indentation, which is only fixed up when the spec fails to parse
+        return parse_openapi_31(content)
+            .or_else(|err| parse_openapi_31(&fix_component_indentation(content)).map_err(|_| err));
     }
 
-    #[cfg(not(feature = "openapi31"))]
-    if is_openapi_31 {
-        return parse_with_oas3_direct_with_original(&preprocessed, content);
-    }
+    // Always preprocess for compatibility issues.
+    let preprocessed = preprocess_for_compatibility(content);
 
     // Try parsing as OpenAPI 3.0.x (most common case).
     // Detect format based on content structure.
     let trimmed = content.trim();
-    if trimmed.starts_with('{') {
-        parse_json_with_fallback(&preprocessed)
+    let spec = if trimmed.starts_with('{') {
+        parse_json_with_fallback(&preprocessed)?
     } else {
-        parse_yaml_with_fallback(&preprocessed)
-    }
+        parse_yaml_with_fallback(&preprocessed)?
+    };
+    Ok((spec, None))
 }
 
 fn looks_like_openapi_31(content: &str) -> bool {
@@ -220,122 +218,25 @@ fn parse_yaml_with_fallback(content: &str) -> Result<OpenAPI, Error> {
     }
 }
 
-/// Direct parsing with oas3 for known 3.1 specs with original content for security scheme extraction
-#[cfg(feature = "openapi31")]
-fn parse_with_oas3_direct_with_original(
-    preprocessed: &str,
-    original: &str,
-) -> Result<OpenAPI, Error> {
-    // First, extract security schemes from the original content before any conversions
-    let security_schemes_from_yaml = extract_security_schemes_from_yaml(original);
-
-    // Try parsing with oas3 (supports OpenAPI 3.1.x) using preprocessed content
-    // First try as YAML, then as JSON if YAML fails
-    let oas3_spec = match oas3::from_yaml(preprocessed) {
-        Ok(spec) => spec,
-        Err(_yaml_err) => {
-            // Try parsing as JSON
-            oas3::from_json(preprocessed).map_err(|e| {
-                Error::serialization_error(format!(
-                    "Failed to parse OpenAPI 3.1 spec as YAML or JSON: {e}"
-                ))
-            })?
-        }
-    };
-
-    // ast-grep-ignore: no-println
-    eprintln!(
-        "{} OpenAPI 3.1 specification detected. Using compatibility mode.",
-        crate::constants::MSG_WARNING_PREFIX
-    );
-    // ast-grep-ignore: no-println
-    eprintln!("         Some 3.1-specific features may not be available.");
-
-    // Convert oas3 spec to JSON, then attempt to parse as openapiv3
-    let json = oas3::to_json(&oas3_spec).map_err(|e| {
-        Error::serialization_error(format!("Failed to serialize OpenAPI 3.1 spec: {e}"))
-    })?;
-
-    // Parse the JSON as OpenAPI 3.0.x
-    // This may fail if there are incompatible 3.1 features
-    let mut spec = serde_json::from_str::<OpenAPI>(&json).map_err(|e| {
-        Error::validation_error(format!(
-            "OpenAPI 3.1 spec contains features incompatible with 3.0: {e}. \
-            Consider converting the spec to OpenAPI 3.0 format."
-        ))
-    })?;
-
-    // WORKAROUND: The oas3 conversion loses security schemes, so restore them
-    // from the original content that we extracted earlier
-    restore_security_schemes(&mut spec, security_schemes_from_yaml);
-
-    Ok(spec)
+/// Parses an `OpenAPI` 3.1 document, lowering it to the 3.0 structural model
+fn parse_openapi_31(content: &str) -> Result<(OpenAPI, Option<Value>), Error> {
+    // Exclusive bounds are numbers in 3.1, so only the boolean flags are fixed
+    let preprocessed = fix_boolean_values(content, BOOLEAN_FLAGS);
+    let document = parse_content_as_value(&preprocessed)?;
+    let spec = crate::spec::openapi31::lower_to_openapi30(&document)?;
+    Ok((spec, Some(document)))
 }
 
-/// Restore security schemes to the `OpenAPI` spec if they were lost during conversion
-#[cfg(feature = "openapi31")]
-fn restore_security_schemes(
-    spec: &mut OpenAPI,
-    security_schemes: Option<
-        indexmap::IndexMap<String, openapiv3::ReferenceOr<openapiv3::SecurityScheme>>,
-    >,
-) {
-    if let Some(schemes) = security_schemes {
-        spec.components = Some(match spec.components.take() {
-            Some(mut components) => {
-                components.security_schemes = schemes;
-                components
-            }
-            None => openapiv3::Components {
-                security_schemes: schemes,
-                ..Default::default()
-            },
-        });
-    }
-}
-
-/// Extract security schemes from YAML/JSON content before any processing
-///
-/// This function is needed because the `oas3` library's conversion from `OpenAPI` 3.1 to 3.0
-/// sometimes loses security scheme definitions. We extract them from the original content
-/// to restore them after conversion.
-#[cfg(feature = "openapi31")]
-fn extract_security_schemes_from_yaml(
-    content: &str,
-) -> Option<indexmap::IndexMap<String, openapiv3::ReferenceOr<openapiv3::SecurityScheme>>> {
-    // Parse content as either YAML or JSON
-    let value = parse_content_as_value(content)?;
-
-    // Navigate to components.securitySchemes
-    let security_schemes = value.get("components")?.get("securitySchemes")?;
-
-    // Convert to the expected type
-    serde_yaml::from_value(security_schemes.clone()).ok()
-}
-
-/// Parse content as either YAML or JSON into a generic Value type
-#[cfg(feature = "openapi31")]
-fn parse_content_as_value(content: &str) -> Option<serde_yaml::Value> {
-    // Try YAML first (more common for OpenAPI specs)
-    if let Ok(value) = serde_yaml::from_str::<serde_yaml::Value>(content) {
-        return Some(value);
+/// Parse YAML or JSON content into a JSON value
+fn parse_content_as_value(content: &str) -> Result<Value, Error> {
+    // YAML mappings may have non-string keys (e.g. `200:`), which the JSON
+    // serializer turns into strings
+    match serde_yaml::from_str::<serde_yaml::Value>(content) {
+        Ok(value) => serde_json::to_value(value).map_err(|e| {
+            Error::serialization_error(format!("Failed to read OpenAPI 3.1 spec: {e}"))
+        }),
+        Err(yaml_err) => serde_json::from_str(content).map_err(|_| Error::Yaml(yaml_err)),
     }
-
-    // Fallback to JSON
-    serde_json::from_str::<serde_json::Value>(content)
-        .ok()
-        .and_then(|json| serde_yaml::to_value(json).ok())
-}
-
-/// Fallback for when `OpenAPI` 3.1 support is not compiled in
-#[cfg(not(feature = "openapi31"))]
-fn parse_with_oas3_direct_with_original(
-    _preprocessed: &str,
-    _original: &str,
-) -> Result<OpenAPI, Error> {
-    Err(Error::validation_error(
-        "OpenAPI 3.1 support is not enabled. Rebuild with --features openapi31 to enable 3.1 support."
-    ))
 }
 
 #[cfg(test)]
@@ -370,29 +271,9 @@ paths: {}
 
         let result = parse_openapi(spec_31);
 
-        #[cfg(feature = "openapi31")]
-        {
-            // With the feature, it should parse successfully
-            assert!(result.is_ok());
-            if let Ok(spec) = result {
-                assert!(spec.openapi.starts_with("3."));
-            }
-        }
-
-        #[cfg(not(feature = "openapi31"))]
-        {
-            // Without the feature, it should return an error about missing support
-            assert!(result.is_err());
-            if let Err(Error::Internal {
-                kind: crate::error::ErrorKind::Validation,
-                message,
-                ..
-            }) = result
-            {
-                assert!(message.contains("OpenAPI 3.1 support is not enabled"));
-            } else {
-                panic!("Expected validation error about missing 3.1 support");
-            }
+        assert!(result.is_ok());
+        if let Ok(spec) = result {
+            assert!(spec.openapi.starts_with("3."));
         }
     }
 
@@ -402,27 +283,9 @@ paths: {}
 
         let result = parse_openapi(spec_31_json);
 
-        #[cfg(feature = "openapi31")]
-        {
-            assert!(result.is_ok());
-            if let Ok(spec) = result {
-                assert!(spec.openapi.starts_with("3."));
-            }
-        }
-
-        #[cfg(not(feature = "openapi31"))]
-        {
-            assert!(result.is_err());
-            if let Err(Error::Internal {
-                kind: crate::error::ErrorKind::Validation,
-                message,
-                ..
-            }) = result
-            {
-                assert!(message.contains("OpenAPI 3.1 support is not enabled"));
-            } else {
-                panic!("Expected validation error about missing 3.1 support");
-            }
+        assert!(result.is_ok());
+        if let Ok(spec) = result {
+            assert!(spec.openapi.starts_with("3."));
         }
     }
 
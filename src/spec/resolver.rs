@@ -0,0 +1,188 @@
+//! Recursive inlining of local component references
+//!
+//! `$ref`s to `#/components/{schemas,responses,parameters,requestBodies,pathItems}`
+//! are replaced by the component they point at, including references nested in
+//! object properties, array items and composition keywords. The resulting
+//! operations are self-contained, so cached schemas and the agent manifest can
+//! be consumed without the original document.
+//!
+//! `OpenAPI` 3.1 allows keywords next to a `$ref`. Annotations such as
+//! `description` override the target's own; any other keyword further
+//! constrains the value, so the target is wrapped in an `allOf` beside it.
+//!
+//! A reference that points back at a component already being inlined (for
+//! example a `User` with a `friends: User[]` property) is left as a `$ref`, as
+//! are references nested deeper than [`MAX_INLINE_DEPTH`] and references
+//! Aperture does not inline. Components reused at every level would otherwise
+//! expand exponentially, so each reference written in an operation expands at
+//! most [`MAX_EXPANDED_REFERENCES`] references in total; the rest are left as
+//! well. The `components` section itself is kept intact so those remaining
+//! references still resolve.
+
+use crate::error::Error;
+use crate::spec::LITERAL_KEYS;
+use openapiv3::OpenAPI;
+use serde_json::{Map, Value};
+
+/// Maximum number of nested references inlined into a single operation
+pub const MAX_INLINE_DEPTH: usize = 32;
+
+/// Maximum number of references expanded while inlining one reference
+/// written in an operation, nested ones included
+pub const MAX_EXPANDED_REFERENCES: usize = 2_000;
+
+/// Component sections whose references are inlined
+const INLINED_COMPONENTS: [&str; 5] = [
+    "#/components/schemas/",
+    "#/components/responses/",
+    "#/components/parameters/",
+    "#/components/requestBodies/",
+    "#/components/pathItems/",
+];
+
+/// Sections holding operations, whose references are inlined
+const OPERATION_SECTIONS: [&str; 2] = ["paths", "webhooks"];
+
+/// Keywords next to a `$ref` that describe rather than constrain the value
+const ANNOTATION_KEYS: [&str; 10] = [
+    "$comment",
+    "title",
+    "summary",
+    "description",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "default",
+    "example",
+    "examples",
+];
+
+/// Returns a copy of `spec` with every operation's component references inlined
+///
+/// # Errors
+/// Returns an error if the specification cannot be converted to and from JSON
+pub fn inline_references(spec: &OpenAPI) -> Result<OpenAPI, Error> {
+    let mut document = serde_json::to_value(spec).map_err(|e| {
+        Error::serialization_error(format!("Failed to serialize OpenAPI spec: {e}"))
+    })?;
+    inline_document_references(&mut document);
+
+    serde_json::from_value(document)
+        .map_err(|e| Error::serialization_error(format!("Failed to rebuild OpenAPI spec: {e}")))
+}
+
+/// Inlines the component references of every operation in a JSON document
+///
+/// This is the form used for `OpenAPI` 3.1 documents, whose schemas the
+/// structural model cannot hold; see [`crate::spec::SpecDocument`].
+pub fn inline_document_references(document: &mut Value) {
+    for section in OPERATION_SECTIONS {
+        let mut operations = document.get_mut(section).map(Value::take);
+        if let Some(operations) = operations.as_mut() {
+            let mut inliner = Inliner {
+                document: &*document,
+                stack: Vec::new(),
+                expanded: 0,
+            };
+            inliner.inline(operations, false);
+        }
+        if let (Some(operations), Some(object)) = (operations, document.as_object_mut()) {
+            object.insert(section.to_string(), operations);
+        }
+    }
+}
+
+/// Applies the keywords written next to a `$ref` to its inlined target
+fn with_siblings(target: Value, mut siblings: Map<String, Value>) -> Value {
+    siblings.remove("$ref");
+    if siblings.is_empty() {
+        return target;
+    }
+    match target {
+        Value::Object(mut object)
+            if siblings
+                .keys()
+                .all(|key| ANNOTATION_KEYS.contains(&key.as_str())) =>
+        {
+            object.extend(siblings);
+            Value::Object(object)
+        }
+        target => {
+            siblings.insert("allOf".to_string(), Value::Array(vec![target]));
+            Value::Object(siblings)
+        }
+    }
+}
+
+struct Inliner<'a> {
+    document: &'a Value,
+    /// References currently being inlined, outermost first
+    stack: Vec<String>,
+    /// References expanded for the outermost one being inlined
+    expanded: usize,
+}
+
+impl Inliner<'_> {
+    /// Inlines the references within `value`
+    ///
+    /// `in_properties` is set while walking a schema's `properties` map, whose
+    /// keys are property names rather than keywords.
+    fn inline(&mut self, value: &mut Value, in_properties: bool) {
+        if let Some(reference) = value.get("$ref").and_then(Value::as_str) {
+            if let Some(target) = self.resolve(reference) {
+                let siblings = value
+                    .as_object_mut()
+                    .map(std::mem::take)
+                    .unwrap_or_default();
+                *value = with_siblings(target, siblings);
+            }
+            return;
+        }
+
+        match value {
+            Value::Object(object) => {
+                for (key, child) in object.iter_mut() {
+                    if !in_properties && LITERAL_KEYS.contains(&key.as_str()) {
+                        continue;
+                    }
+                    self.inline(child, !in_properties && key == "properties");
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.inline(item, false);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the fully inlined target of `reference`, or `None` if it must
+    /// stay a `$ref`
+    fn resolve(&mut self, reference: &str) -> Option<Value> {
+        if !INLINED_COMPONENTS
+            .iter()
+            .any(|prefix| reference.starts_with(prefix))
+        {
+            return None;
+        }
+        if self.stack.len() >= MAX_INLINE_DEPTH || self.stack.iter().any(|seen| seen == reference) {
+            return None;
+        }
+        if self.stack.is_empty() {
+            self.expanded = 0;
+        }
+        if self.expanded >= MAX_EXPANDED_REFERENCES {
+            return None;
+        }
+        self.expanded += 1;
+
+        let pointer = reference.strip_prefix('#')?;
+        let mut target = self.document.pointer(pointer)?.clone();
+
+        self.stack.push(reference.to_string());
+        self.inline(&mut target, false);
+        self.stack.pop();
+        Some(target)
+    }
+}
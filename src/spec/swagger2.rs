@@ -0,0 +1,742 @@
+//! Conversion of Swagger 2.0 documents to `OpenAPI` 3.0
+//!
+//! [`convert_swagger2`] rewrites a `swagger: "2.0"` document into the
+//! equivalent `OpenAPI` 3.0 document, so it can go through the same validator
+//! and transformer as any other spec:
+//!
+//! - `host`, `basePath` and `schemes` become `servers`
+//! - `definitions`, `responses` and `securityDefinitions` move under `components`
+//! - `in: body` and `in: formData` parameters become request bodies, using the
+//!   operation's `consumes`; response schemas use its `produces`
+//! - other parameters get a `schema`, and `collectionFormat` becomes `style`/`explode`
+//! - `x-nullable`, `type: file` and string discriminators become their 3.0 forms
+//!
+//! Anything that cannot be carried over exactly is reported as a
+//! [`ValidationWarning`] whose reason starts with [`CONVERSION_REASON_PREFIX`].
+
+use crate::constants;
+use crate::error::Error;
+use crate::spec::validator::{UnsupportedEndpoint, ValidationWarning};
+use crate::spec::LITERAL_KEYS;
+use serde_json::{json, Map, Value};
+
+/// Prefix of the warning reason for notes raised while converting a Swagger 2.0 document
+pub const CONVERSION_REASON_PREFIX: &str = "Swagger 2.0 conversion: ";
+
+const OPENAPI_VERSION: &str = "3.0.3";
+
+const HTTP_METHODS: [&str; 7] = ["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Parameter keywords that move into the parameter's `schema` in `OpenAPI` 3.0
+const SCHEMA_KEYWORDS: [&str; 16] = [
+    "type",
+    "format",
+    "items",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "enum",
+    "multipleOf",
+];
+
+/// Converts `content` to `OpenAPI` 3.0 if it is a Swagger 2.0 document
+///
+/// # Returns
+/// The converted document as YAML with any conversion warnings, or `content`
+/// unchanged with no warnings if it is not a Swagger 2.0 document
+///
+/// # Errors
+/// Returns an error if a Swagger 2.0 document cannot be parsed or written back
+pub fn convert_swagger2(content: String) -> Result<(String, Vec<ValidationWarning>), Error> {
+    if !content.contains("swagger") {
+        return Ok((content, Vec::new()));
+    }
+    let document: Value = if content.trim_start().starts_with('{') {
+        serde_json::from_str(&content).ok()
+    } else {
+        serde_yaml::from_str(&content).ok()
+    }
+    .unwrap_or(Value::Null);
+    if !is_swagger2(&document) {
+        return Ok((content, Vec::new()));
+    }
+
+    let mut converter = Converter {
+        source: &document,
+        warnings: Vec::new(),
+    };
+    let openapi = converter.convert();
+    let content = serde_yaml::to_string(&openapi).map_err(|e| {
+        Error::serialization_error(format!("Failed to write converted Swagger 2.0 spec: {e}"))
+    })?;
+    Ok((content, converter.warnings))
+}
+
+/// Checks whether a parsed document declares `swagger: "2.0"`
+#[must_use]
+pub fn is_swagger2(document: &Value) -> bool {
+    match document.get("swagger") {
+        Some(Value::String(version)) => version == "2.0",
+        Some(Value::Number(version)) => version.as_f64() == Some(2.0),
+        _ => false,
+    }
+}
+
+struct Converter<'a> {
+    source: &'a Value,
+    warnings: Vec<ValidationWarning>,
+}
+
+impl Converter<'_> {
+    fn convert(&mut self) -> Value {
+        let source = self.source;
+        let mut root = Map::new();
+        root.insert("openapi".to_string(), json!(OPENAPI_VERSION));
+        copy_fields(
+            source,
+            &mut root,
+            &["info", "tags", "externalDocs", "security"],
+        );
+        root.insert("servers".to_string(), self.servers());
+        root.insert("paths".to_string(), self.paths());
+        let components = self.components();
+        if !components.is_empty() {
+            root.insert("components".to_string(), Value::Object(components));
+        }
+        copy_extensions(source, &mut root);
+
+        let mut root = Value::Object(root);
+        rewrite_references(&mut root);
+        root
+    }
+
+    /// Builds `servers` from `host`, `basePath` and `schemes`
+    fn servers(&mut self) -> Value {
+        let base_path = self
+            .source
+            .get("basePath")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+
+        let Some(host) = self.source.get("host").and_then(Value::as_str) else {
+            self.warn(
+                "",
+                "",
+                "no host is declared, so the server URL is relative; \
+                 set one with 'aperture config set-url'",
+            );
+            let url = if base_path.is_empty() { "/" } else { base_path };
+            return json!([{ "url": url }]);
+        };
+
+        let schemes: Vec<&str> = self
+            .source
+            .get("schemes")
+            .and_then(Value::as_array)
+            .map(|schemes| schemes.iter().filter_map(Value::as_str).collect())
+            .filter(|schemes: &Vec<&str>| !schemes.is_empty())
+            .unwrap_or_else(|| vec!["https"]);
+        Value::Array(
+            schemes
+                .into_iter()
+                .map(|scheme| json!({ "url": format!("{scheme}://{host}{base_path}") }))
+                .collect(),
+        )
+    }
+
+    fn components(&mut self) -> Map<String, Value> {
+        let source = self.source;
+        let mut components = Map::new();
+
+        if let Some(definitions) = source.get("definitions").and_then(Value::as_object) {
+            let schemas = definitions
+                .iter()
+                .map(|(name, schema)| (name.clone(), convert_schema(schema)))
+                .collect();
+            components.insert("schemas".to_string(), Value::Object(schemas));
+        }
+
+        if let Some(responses) = source.get("responses").and_then(Value::as_object) {
+            let produces = self.media_types(None, "produces");
+            let responses = responses
+                .iter()
+                .map(|(name, response)| (name.clone(), convert_response(response, &produces)))
+                .collect();
+            components.insert("responses".to_string(), Value::Object(responses));
+        }
+
+        if let Some(definitions) = source.get("securityDefinitions").and_then(Value::as_object) {
+            let schemes = definitions
+                .iter()
+                .map(|(name, scheme)| (name.clone(), self.security_scheme(name, scheme)))
+                .collect();
+            components.insert("securitySchemes".to_string(), Value::Object(schemes));
+        }
+
+        components
+    }
+
+    fn security_scheme(&mut self, name: &str, scheme: &Value) -> Value {
+        let mut converted = Map::new();
+        match scheme.get("type").and_then(Value::as_str) {
+            Some("basic") => {
+                converted.insert("type".to_string(), json!("http"));
+                converted.insert("scheme".to_string(), json!("basic"));
+            }
+            Some("apiKey") => {
+                converted.insert("type".to_string(), json!("apiKey"));
+                copy_fields(scheme, &mut converted, &["name", "in"]);
+            }
+            Some("oauth2") => {
+                let flow = scheme
+                    .get("flow")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let (flow_name, fields): (&str, &[&str]) = match flow {
+                    "implicit" => ("implicit", &["authorizationUrl"]),
+                    "password" => ("password", &["tokenUrl"]),
+                    "application" => ("clientCredentials", &["tokenUrl"]),
+                    "accessCode" => ("authorizationCode", &["authorizationUrl", "tokenUrl"]),
+                    _ => {
+                        self.warn(
+                            "",
+                            "",
+                            &format!("security scheme '{name}' has unknown OAuth2 flow '{flow}'"),
+                        );
+                        return scheme.clone();
+                    }
+                };
+                let mut converted_flow = Map::new();
+                copy_fields(scheme, &mut converted_flow, fields);
+                converted_flow.insert(
+                    "scopes".to_string(),
+                    scheme.get("scopes").cloned().unwrap_or_else(|| json!({})),
+                );
+                converted.insert("type".to_string(), json!("oauth2"));
+                converted.insert("flows".to_string(), json!({ flow_name: converted_flow }));
+            }
+            other => {
+                self.warn(
+                    "",
+                    "",
+                    &format!(
+                        "security scheme '{name}' has unknown type '{}'",
+                        other.unwrap_or_default()
+                    ),
+                );
+                return scheme.clone();
+            }
+        }
+        copy_fields(scheme, &mut converted, &["description"]);
+        copy_extensions(scheme, &mut converted);
+        Value::Object(converted)
+    }
+
+    fn paths(&mut self) -> Value {
+        let mut paths = Map::new();
+        let Some(source_paths) = self.source.get("paths").and_then(Value::as_object) else {
+            return Value::Object(paths);
+        };
+
+        for (path, item) in source_paths {
+            let Some(item_fields) = item.as_object() else {
+                paths.insert(path.clone(), item.clone());
+                continue;
+            };
+            let path_parameters = self.resolve_parameters(item.get("parameters"));
+
+            let mut converted = Map::new();
+            for (key, value) in item_fields {
+                if HTTP_METHODS.contains(&key.as_str()) {
+                    let operation = self.operation(path, key, value, &path_parameters);
+                    converted.insert(key.clone(), operation);
+                } else if key == "$ref" || key.starts_with("x-") {
+                    converted.insert(key.clone(), value.clone());
+                }
+            }
+            paths.insert(path.clone(), Value::Object(converted));
+        }
+        Value::Object(paths)
+    }
+
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        operation: &Value,
+        path_parameters: &[Value],
+    ) -> Value {
+        let mut converted = Map::new();
+        copy_fields(
+            operation,
+            &mut converted,
+            &[
+                "tags",
+                "summary",
+                "description",
+                "externalDocs",
+                "operationId",
+                "deprecated",
+                "security",
+            ],
+        );
+
+        let consumes = self.media_types(Some(operation), "consumes");
+        let produces = self.media_types(Some(operation), "produces");
+
+        // Operation parameters override path-level ones with the same name and location
+        let operation_parameters = self.resolve_parameters(operation.get("parameters"));
+        let mut parameters: Vec<Value> = path_parameters
+            .iter()
+            .filter(|inherited| {
+                !operation_parameters.iter().any(|own| {
+                    own.get("name") == inherited.get("name") && own.get("in") == inherited.get("in")
+                })
+            })
+            .cloned()
+            .collect();
+        parameters.extend(operation_parameters);
+
+        let mut converted_parameters = Vec::new();
+        let mut form_fields = Vec::new();
+        let mut body = None;
+        for parameter in parameters {
+            match parameter.get("in").and_then(Value::as_str) {
+                Some("body") => body = Some(parameter),
+                Some("formData") => form_fields.push(parameter),
+                _ => converted_parameters.push(self.parameter(path, method, &parameter)),
+            }
+        }
+        if !converted_parameters.is_empty() {
+            converted.insert("parameters".to_string(), Value::Array(converted_parameters));
+        }
+
+        if let Some(body) = body {
+            converted.insert("requestBody".to_string(), body_request(&body, &consumes));
+        } else if !form_fields.is_empty() {
+            let request_body = self.form_request(path, method, &form_fields, &consumes);
+            converted.insert("requestBody".to_string(), request_body);
+        }
+
+        let responses = operation
+            .get("responses")
+            .and_then(Value::as_object)
+            .map(|responses| {
+                responses
+                    .iter()
+                    .map(|(status, response)| {
+                        let response = if status.starts_with("x-") {
+                            response.clone()
+                        } else {
+                            convert_response(response, &produces)
+                        };
+                        (status.clone(), response)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        converted.insert("responses".to_string(), Value::Object(responses));
+
+        copy_extensions(operation, &mut converted);
+        Value::Object(converted)
+    }
+
+    /// Returns the parameters in `parameters`, with `#/parameters/...` references inlined
+    fn resolve_parameters(&mut self, parameters: Option<&Value>) -> Vec<Value> {
+        let Some(parameters) = parameters.and_then(Value::as_array) else {
+            return Vec::new();
+        };
+        parameters
+            .iter()
+            .filter_map(|parameter| {
+                let Some(reference) = parameter.get("$ref").and_then(Value::as_str) else {
+                    return Some(parameter.clone());
+                };
+                let resolved = reference
+                    .strip_prefix("#/parameters/")
+                    .and_then(|name| self.source.get("parameters")?.get(name))
+                    .cloned();
+                if resolved.is_none() {
+                    self.warn(
+                        "",
+                        "",
+                        &format!("parameter reference '{reference}' could not be resolved"),
+                    );
+                }
+                resolved
+            })
+            .collect()
+    }
+
+    /// Returns the operation's media types for `field`, falling back to the
+    /// document's and then to JSON
+    fn media_types(&self, operation: Option<&Value>, field: &str) -> Vec<String> {
+        operation
+            .and_then(|operation| operation.get(field))
+            .or_else(|| self.source.get(field))
+            .and_then(Value::as_array)
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|types| !types.is_empty())
+            .unwrap_or_else(|| vec![constants::CONTENT_TYPE_JSON.to_string()])
+    }
+
+    /// Converts a non-body parameter
+    fn parameter(&mut self, path: &str, method: &str, parameter: &Value) -> Value {
+        let mut converted = Map::new();
+        let mut schema = Map::new();
+        if let Some(fields) = parameter.as_object() {
+            for (key, value) in fields {
+                match key.as_str() {
+                    "name" | "in" | "description" | "required" | "allowEmptyValue" => {
+                        converted.insert(key.clone(), value.clone());
+                    }
+                    key if key.starts_with("x-") => {
+                        converted.insert(key.to_string(), value.clone());
+                    }
+                    key if SCHEMA_KEYWORDS.contains(&key) => {
+                        schema.insert(key.to_string(), value.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let location = parameter
+            .get("in")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let name = parameter
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if schema.get("type").and_then(Value::as_str) == Some("array") {
+            let format = collection_format(parameter);
+            match (location, format) {
+                ("query", "csv") => set_style(&mut converted, "form", false),
+                ("query", "ssv") => set_style(&mut converted, "spaceDelimited", false),
+                ("query", "pipes") => set_style(&mut converted, "pipeDelimited", false),
+                ("query", "multi") => set_style(&mut converted, "form", true),
+                (_, "csv") => {}
+                _ => {
+                    self.warn(
+                        path,
+                        method,
+                        &format!(
+                            "{location} parameter '{name}' uses collectionFormat '{format}', \
+                             which has no OpenAPI 3.0 equivalent; values are comma-separated instead"
+                        ),
+                    );
+                    if location == "query" {
+                        set_style(&mut converted, "form", false);
+                    }
+                }
+            }
+        }
+
+        converted.insert("schema".to_string(), convert_schema(&Value::Object(schema)));
+        Value::Object(converted)
+    }
+
+    /// Builds a request body from `in: formData` parameters
+    fn form_request(
+        &mut self,
+        path: &str,
+        method: &str,
+        fields: &[Value],
+        consumes: &[String],
+    ) -> Value {
+        let has_file = fields
+            .iter()
+            .any(|field| field.get("type").and_then(Value::as_str) == Some("file"));
+        let mut media_types: Vec<&str> = consumes
+            .iter()
+            .map(String::as_str)
+            .filter(|media_type| {
+                crate::spec::is_multipart_content_type(media_type)
+                    || crate::spec::is_form_urlencoded_content_type(media_type)
+            })
+            .collect();
+        if has_file
+            && !media_types
+                .iter()
+                .any(|t| crate::spec::is_multipart_content_type(t))
+        {
+            if !media_types.is_empty() {
+                self.warn(
+                    path,
+                    method,
+                    "file parameters require multipart/form-data, which is not in consumes",
+                );
+            }
+            media_types = vec![constants::CONTENT_TYPE_MULTIPART];
+        } else if media_types.is_empty() {
+            media_types = vec![constants::CONTENT_TYPE_FORM];
+        }
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut encoding = Map::new();
+        for field in fields {
+            let Some(name) = field.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let mut property = Map::new();
+            if let Some(fields) = field.as_object() {
+                for (key, value) in fields {
+                    if SCHEMA_KEYWORDS.contains(&key.as_str()) || key == "description" {
+                        property.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            properties.insert(name.to_string(), convert_schema(&Value::Object(property)));
+
+            if field.get("required").and_then(Value::as_bool) == Some(true) {
+                required.push(json!(name));
+            }
+            if field.get("type").and_then(Value::as_str) == Some("array")
+                && collection_format(field) != "multi"
+            {
+                encoding.insert(
+                    name.to_string(),
+                    json!({ "style": "form", "explode": false }),
+                );
+            }
+        }
+
+        let is_required = !required.is_empty();
+        let mut schema = json!({ "type": "object", "properties": properties });
+        if is_required {
+            schema["required"] = Value::Array(required);
+        }
+        let mut media_type = json!({ "schema": schema });
+        if !encoding.is_empty() {
+            media_type["encoding"] = Value::Object(encoding);
+        }
+        let content: Map<String, Value> = media_types
+            .into_iter()
+            .map(|content_type| (content_type.to_string(), media_type.clone()))
+            .collect();
+        json!({ "required": is_required, "content": content })
+    }
+
+    fn warn(&mut self, path: &str, method: &str, detail: &str) {
+        self.warnings.push(ValidationWarning {
+            endpoint: UnsupportedEndpoint {
+                path: path.to_string(),
+                method: method.to_uppercase(),
+                content_type: String::new(),
+            },
+            reason: format!("{CONVERSION_REASON_PREFIX}{detail}"),
+        });
+    }
+}
+
+/// Builds a request body from an `in: body` parameter
+fn body_request(body: &Value, consumes: &[String]) -> Value {
+    let schema = body.get("schema").map_or_else(|| json!({}), convert_schema);
+    let mut content: Map<String, Value> = consumes
+        .iter()
+        .filter(|media_type| {
+            !crate::spec::is_multipart_content_type(media_type)
+                && !crate::spec::is_form_urlencoded_content_type(media_type)
+        })
+        .map(|media_type| (media_type.clone(), json!({ "schema": schema })))
+        .collect();
+    if content.is_empty() {
+        content.insert(
+            constants::CONTENT_TYPE_JSON.to_string(),
+            json!({ "schema": schema }),
+        );
+    }
+
+    let mut converted = Map::new();
+    copy_fields(body, &mut converted, &["description", "required"]);
+    converted.insert("content".to_string(), Value::Object(content));
+    copy_extensions(body, &mut converted);
+    Value::Object(converted)
+}
+
+fn convert_response(response: &Value, produces: &[String]) -> Value {
+    if response.get("$ref").is_some() {
+        return response.clone();
+    }
+
+    let mut converted = Map::new();
+    converted.insert(
+        "description".to_string(),
+        response
+            .get("description")
+            .cloned()
+            .unwrap_or_else(|| json!("")),
+    );
+
+    if let Some(headers) = response.get("headers").and_then(Value::as_object) {
+        let headers = headers
+            .iter()
+            .map(|(name, header)| (name.clone(), convert_header(header)))
+            .collect();
+        converted.insert("headers".to_string(), Value::Object(headers));
+    }
+
+    if let Some(schema) = response.get("schema") {
+        let schema = convert_schema(schema);
+        let examples = response.get("examples");
+        let content: Map<String, Value> = produces
+            .iter()
+            .map(|media_type| {
+                let mut converted_type = json!({ "schema": schema });
+                if let Some(example) = examples.and_then(|examples| examples.get(media_type)) {
+                    converted_type["example"] = example.clone();
+                }
+                (media_type.clone(), converted_type)
+            })
+            .collect();
+        converted.insert("content".to_string(), Value::Object(content));
+    }
+
+    copy_extensions(response, &mut converted);
+    Value::Object(converted)
+}
+
+fn convert_header(header: &Value) -> Value {
+    let mut converted = Map::new();
+    let mut schema = Map::new();
+    if let Some(fields) = header.as_object() {
+        for (key, value) in fields {
+            if SCHEMA_KEYWORDS.contains(&key.as_str()) {
+                schema.insert(key.clone(), value.clone());
+            } else if key == "description" || key.starts_with("x-") {
+                converted.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    converted.insert("schema".to_string(), convert_schema(&Value::Object(schema)));
+    Value::Object(converted)
+}
+
+/// Converts the Swagger-specific keywords of a schema and its subschemas
+fn convert_schema(schema: &Value) -> Value {
+    let Some(fields) = schema.as_object() else {
+        return schema.clone();
+    };
+
+    let mut converted = Map::new();
+    for (key, value) in fields {
+        match key.as_str() {
+            "x-nullable" => {
+                converted.insert("nullable".to_string(), value.clone());
+            }
+            "collectionFormat" => {}
+            "type" if value == "file" => {
+                converted.insert("type".to_string(), json!("string"));
+                converted.insert("format".to_string(), json!("binary"));
+            }
+            "format" if converted.get("format") == Some(&json!("binary")) => {}
+            "discriminator" if value.is_string() => {
+                converted.insert(key.clone(), json!({ "propertyName": value }));
+            }
+            "properties" => {
+                let properties = value
+                    .as_object()
+                    .map(|properties| {
+                        properties
+                            .iter()
+                            .map(|(name, property)| (name.clone(), convert_schema(property)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                converted.insert(key.clone(), Value::Object(properties));
+            }
+            "items" | "additionalProperties" | "not" => {
+                converted.insert(key.clone(), convert_schema(value));
+            }
+            "allOf" | "anyOf" | "oneOf" => {
+                let subschemas = value
+                    .as_array()
+                    .map(|subschemas| subschemas.iter().map(convert_schema).collect())
+                    .unwrap_or_default();
+                converted.insert(key.clone(), Value::Array(subschemas));
+            }
+            _ => {
+                converted.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Value::Object(converted)
+}
+
+/// Points `#/definitions/...` and `#/responses/...` references at `components`
+fn rewrite_references(value: &mut Value) {
+    const MOVED_SECTIONS: [(&str, &str); 3] = [
+        ("#/definitions/", "#/components/schemas/"),
+        ("#/responses/", "#/components/responses/"),
+        ("#/parameters/", "#/components/parameters/"),
+    ];
+
+    match value {
+        Value::Object(fields) => {
+            for (key, child) in fields.iter_mut() {
+                if key == "$ref" {
+                    let Value::String(reference) = child else {
+                        continue;
+                    };
+                    if let Some((from, to)) = MOVED_SECTIONS
+                        .iter()
+                        .find(|(from, _)| reference.starts_with(from))
+                    {
+                        *reference = format!("{to}{}", &reference[from.len()..]);
+                    }
+                } else if !LITERAL_KEYS.contains(&key.as_str()) {
+                    rewrite_references(child);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(rewrite_references),
+        _ => {}
+    }
+}
+
+fn collection_format(parameter: &Value) -> &str {
+    parameter
+        .get("collectionFormat")
+        .and_then(Value::as_str)
+        .unwrap_or("csv")
+}
+
+fn set_style(parameter: &mut Map<String, Value>, style: &str, explode: bool) {
+    parameter.insert("style".to_string(), json!(style));
+    parameter.insert("explode".to_string(), json!(explode));
+}
+
+fn copy_fields(source: &Value, target: &mut Map<String, Value>, fields: &[&str]) {
+    for field in fields {
+        if let Some(value) = source.get(*field) {
+            target.insert((*field).to_string(), value.clone());
+        }
+    }
+}
+
+fn copy_extensions(source: &Value, target: &mut Map<String, Value>) {
+    let Some(fields) = source.as_object() else {
+        return;
+    };
+    for (key, value) in fields {
+        if key.starts_with("x-") && !target.contains_key(key) {
+            target.insert(key.clone(), value.clone());
+        }
+    }
+}
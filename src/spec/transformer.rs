@@ -1,13 +1,18 @@
 use crate::cache::models::{
-    CachedApertureSecret, CachedCommand, CachedParameter, CachedRequestBody, CachedResponse,
-    CachedSecurityScheme, CachedSpec, CommandExample, PaginationInfo, PaginationStrategy,
-    SkippedEndpoint, CACHE_FORMAT_VERSION,
+    CachedApertureSecret, CachedCommand, CachedEncoding, CachedParameter, CachedRequestBody,
+    CachedResponse, CachedSecurityScheme, CachedSpec, CommandExample, PaginationInfo,
+    PaginationStrategy, SkippedEndpoint, CACHE_FORMAT_VERSION,
 };
 use crate::constants;
 use crate::error::Error;
+use crate::oauth::discovery::OpenIdConnectDiscovery;
+use crate::spec::SpecDocument;
 use crate::utils::to_kebab_case;
-use openapiv3::{OpenAPI, Operation, Parameter, ReferenceOr, RequestBody, SecurityScheme};
-use serde_json;
+use openapiv3::{
+    OpenAPI, Operation, Parameter, PathStyle, QueryStyle, ReferenceOr, RequestBody,
+    SecurityRequirement, SecurityScheme,
+};
+use serde_json::{self, Value};
 use std::collections::HashMap;
 use std::fmt::Write;
 
@@ -34,6 +39,8 @@ pub struct TransformOptions {
     pub skip_endpoints: Vec<(String, String)>,
     /// Validation warnings to include in the cached spec
     pub warnings: Vec<crate::spec::validator::ValidationWarning>,
+    /// `OpenID` Connect discovery results for `openIdConnect` schemes
+    pub openid_connect: OpenIdConnectDiscovery,
 }
 
 impl TransformOptions {
@@ -44,6 +51,7 @@ impl TransformOptions {
             name: name.into(),
             skip_endpoints: Vec::new(),
             warnings: Vec::new(),
+            openid_connect: OpenIdConnectDiscovery::new(),
         }
     }
 
@@ -63,6 +71,13 @@ impl TransformOptions {
         self.warnings = warnings;
         self
     }
+
+    /// Sets the `OpenID` Connect discovery results
+    #[must_use]
+    pub fn with_openid_connect(mut self, discovered: OpenIdConnectDiscovery) -> Self {
+        self.openid_connect = discovered;
+        self
+    }
 }
 
 /// Transforms `OpenAPI` specifications into Aperture's cached format
@@ -88,11 +103,36 @@ impl SpecTransformer {
         spec: &OpenAPI,
         options: &TransformOptions,
     ) -> Result<CachedSpec, Error> {
-        self.transform_with_warnings(
+        Self::build_cached_spec(
             &options.name,
             spec,
+            None,
+            &options.skip_endpoints,
+            &options.warnings,
+            &options.openid_connect,
+        )
+    }
+
+    /// Transforms a parsed specification of any supported `OpenAPI` version
+    ///
+    /// Schemas are taken from the document as written where the structural
+    /// model is lossy, so `OpenAPI` 3.1 keywords are kept in the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parameter reference resolution fails
+    pub fn transform_document(
+        &self,
+        document: &SpecDocument,
+        options: &TransformOptions,
+    ) -> Result<CachedSpec, Error> {
+        Self::build_cached_spec(
+            &options.name,
+            document.openapi(),
+            Some(document),
             &options.skip_endpoints,
             &options.warnings,
+            &options.openid_connect,
         )
     }
 
@@ -149,13 +189,31 @@ impl SpecTransformer {
         spec: &OpenAPI,
         skip_endpoints: &[(String, String)],
         warnings: &[crate::spec::validator::ValidationWarning],
+    ) -> Result<CachedSpec, Error> {
+        Self::build_cached_spec(
+            name,
+            spec,
+            None,
+            skip_endpoints,
+            warnings,
+            &OpenIdConnectDiscovery::new(),
+        )
+    }
+
+    fn build_cached_spec(
+        name: &str,
+        spec: &OpenAPI,
+        document: Option<&SpecDocument>,
+        skip_endpoints: &[(String, String)],
+        warnings: &[crate::spec::validator::ValidationWarning],
+        openid_connect: &OpenIdConnectDiscovery,
     ) -> Result<CachedSpec, Error> {
         let servers: Vec<String> = spec
             .servers
             .iter()
             .map(|server| server.url.clone())
             .collect();
-        let commands = Self::collect_commands(spec, skip_endpoints)?;
+        let commands = Self::collect_commands(spec, document, skip_endpoints)?;
 
         Ok(CachedSpec {
             cache_format_version: CACHE_FORMAT_VERSION,
@@ -164,7 +222,7 @@ impl SpecTransformer {
             commands,
             base_url: servers.first().cloned(),
             servers,
-            security_schemes: Self::extract_security_schemes(spec),
+            security_schemes: Self::extract_security_schemes(spec, openid_connect),
             skipped_endpoints: Self::build_skipped_endpoints(warnings),
             server_variables: Self::extract_server_variables(spec),
         })
@@ -194,19 +252,24 @@ impl SpecTransformer {
             .unwrap_or_default()
     }
 
-    fn extract_global_security_requirements(spec: &OpenAPI) -> Vec<String> {
+    fn extract_global_security_requirements(spec: &OpenAPI) -> Vec<Vec<String>> {
         spec.security
+            .as_deref()
+            .map(Self::security_requirement_sets)
+            .unwrap_or_default()
+    }
+
+    /// Converts `security` requirement objects into sets of scheme names
+    fn security_requirement_sets(requirements: &[SecurityRequirement]) -> Vec<Vec<String>> {
+        requirements
             .iter()
-            .flat_map(|security_group| {
-                security_group
-                    .iter()
-                    .flat_map(|security_req| security_req.keys().cloned())
-            })
+            .map(|requirement| requirement.keys().cloned().collect())
             .collect()
     }
 
     fn collect_commands(
         spec: &OpenAPI,
+        document: Option<&SpecDocument>,
         skip_endpoints: &[(String, String)],
     ) -> Result<Vec<CachedCommand>, Error> {
         let global_security_requirements = Self::extract_global_security_requirements(spec);
@@ -215,6 +278,7 @@ impl SpecTransformer {
         for (path, path_item) in spec.paths.iter() {
             Self::process_path_item(
                 spec,
+                document,
                 path,
                 path_item,
                 skip_endpoints,
@@ -243,10 +307,11 @@ impl SpecTransformer {
     /// Process a single path item and its operations
     fn process_path_item(
         spec: &OpenAPI,
+        document: Option<&SpecDocument>,
         path: &str,
         path_item: &ReferenceOr<openapiv3::PathItem>,
         skip_endpoints: &[(String, String)],
-        global_security_requirements: &[String],
+        global_security_requirements: &[Vec<String>],
         commands: &mut Vec<CachedCommand>,
     ) -> Result<(), Error> {
         let ReferenceOr::Item(item) = path_item else {
@@ -263,8 +328,15 @@ impl SpecTransformer {
                 continue;
             }
 
-            let command =
-                Self::transform_operation(spec, method, path, op, global_security_requirements)?;
+            let source = document.and_then(|document| document.operation_source(path, method));
+            let command = Self::transform_operation(
+                spec,
+                method,
+                path,
+                op,
+                source,
+                global_security_requirements,
+            )?;
             commands.push(command);
         }
 
@@ -279,13 +351,17 @@ impl SpecTransformer {
     }
 
     /// Transforms a single operation into a cached command
+    ///
+    /// `source` is the operation as written, whose schemas are preferred when
+    /// present (see [`SpecDocument::operation_source`]).
     #[allow(clippy::too_many_lines)]
     fn transform_operation(
         spec: &OpenAPI,
         method: &str,
         path: &str,
         operation: &Operation,
-        global_security_requirements: &[String],
+        source: Option<&Value>,
+        global_security_requirements: &[Vec<String>],
     ) -> Result<CachedCommand, Error> {
         let operation_id = operation
             .operation_id
@@ -298,14 +374,29 @@ impl SpecTransformer {
             .cloned()
             .unwrap_or_else(|| constants::DEFAULT_GROUP.to_string());
 
-        let parameters = Self::collect_operation_parameters(spec, &operation.parameters)?;
+        let source_field = |field: &str| source.and_then(|operation| operation.get(field));
+        let parameters = Self::collect_operation_parameters(
+            spec,
+            &operation.parameters,
+            source_field("parameters"),
+        )?;
         let request_body = operation
             .request_body
             .as_ref()
-            .and_then(Self::transform_request_body);
-        let responses = Self::collect_operation_responses(spec, &operation.responses.responses);
-        let security_requirements =
+            .and_then(|body| Self::transform_request_body(spec, body, source_field("requestBody")));
+        let responses = Self::collect_operation_responses(
+            spec,
+            &operation.responses.responses,
+            source_field("responses"),
+        );
+        let security_requirement_sets =
             Self::resolve_security_requirements(operation, global_security_requirements);
+        let mut security_requirements: Vec<String> = Vec::new();
+        for scheme_name in security_requirement_sets.iter().flatten() {
+            if !security_requirements.contains(scheme_name) {
+                security_requirements.push(scheme_name.clone());
+            }
+        }
         let examples = Self::generate_command_examples(
             &name,
             &operation_id,
@@ -339,20 +430,26 @@ impl SpecTransformer {
             aliases: vec![],
             hidden: false,
             pagination,
+            security_requirement_sets,
         })
     }
 
     fn collect_operation_parameters(
         spec: &OpenAPI,
         parameters: &[ReferenceOr<Parameter>],
+        source: Option<&Value>,
     ) -> Result<Vec<CachedParameter>, Error> {
         parameters
             .iter()
-            .map(|param_ref| match param_ref {
-                ReferenceOr::Item(param) => Ok(Self::transform_parameter(param)),
-                ReferenceOr::Reference { reference } => {
-                    let param = Self::resolve_parameter_reference(spec, reference)?;
-                    Ok(Self::transform_parameter(&param))
+            .enumerate()
+            .map(|(index, param_ref)| {
+                let source = source.and_then(|parameters| parameters.get(index));
+                match param_ref {
+                    ReferenceOr::Item(param) => Ok(Self::transform_parameter(param, source)),
+                    ReferenceOr::Reference { reference } => {
+                        let param = Self::resolve_parameter_reference(spec, reference)?;
+                        Ok(Self::transform_parameter(&param, source))
+                    }
                 }
             })
             .collect()
@@ -361,51 +458,88 @@ impl SpecTransformer {
     fn collect_operation_responses(
         spec: &OpenAPI,
         responses: &indexmap::IndexMap<openapiv3::StatusCode, ReferenceOr<openapiv3::Response>>,
+        source: Option<&Value>,
     ) -> Vec<CachedResponse> {
         responses
             .iter()
             .map(|(code, response_ref)| {
-                Self::transform_response(spec, code.to_string(), response_ref)
+                let code = code.to_string();
+                let source = source.and_then(|responses| responses.get(&code));
+                Self::transform_response(spec, code, response_ref, source)
             })
             .collect()
     }
 
+    /// Serializes the schema of a media type as written in the source document
+    fn source_media_type_schema(source: Option<&Value>, content_type: &str) -> Option<String> {
+        source?
+            .get("content")?
+            .get(content_type)?
+            .get("schema")
+            .map(Value::to_string)
+    }
+
+    /// Returns the operation's security requirement sets, falling back to the
+    /// global ones. An explicit `security: []` yields no sets (no authentication).
     fn resolve_security_requirements(
         operation: &Operation,
-        global_security_requirements: &[String],
-    ) -> Vec<String> {
-        operation.security.as_ref().map_or_else(
+        global_security_requirements: &[Vec<String>],
+    ) -> Vec<Vec<String>> {
+        operation.security.as_deref().map_or_else(
             || global_security_requirements.to_vec(),
-            |security_reqs| {
-                security_reqs
-                    .iter()
-                    .flat_map(|security_req| security_req.keys().cloned())
-                    .collect()
-            },
+            Self::security_requirement_sets,
         )
     }
 
     /// Transforms a parameter into cached format
     #[allow(clippy::too_many_lines)]
-    fn transform_parameter(param: &Parameter) -> CachedParameter {
-        let (param_data, location_str) = match param {
-            Parameter::Query { parameter_data, .. } => {
-                (parameter_data, constants::PARAM_LOCATION_QUERY)
+    fn transform_parameter(param: &Parameter, source: Option<&Value>) -> CachedParameter {
+        let (param_data, location_str, style) = match param {
+            Parameter::Query {
+                parameter_data,
+                style,
+                ..
+            } => {
+                let style = match style {
+                    QueryStyle::Form => "form",
+                    QueryStyle::SpaceDelimited => "spaceDelimited",
+                    QueryStyle::PipeDelimited => "pipeDelimited",
+                    QueryStyle::DeepObject => "deepObject",
+                };
+                (parameter_data, constants::PARAM_LOCATION_QUERY, style)
             }
             Parameter::Header { parameter_data, .. } => {
-                (parameter_data, constants::PARAM_LOCATION_HEADER)
+                (parameter_data, constants::PARAM_LOCATION_HEADER, "simple")
             }
-            Parameter::Path { parameter_data, .. } => {
-                (parameter_data, constants::PARAM_LOCATION_PATH)
+            Parameter::Path {
+                parameter_data,
+                style,
+            } => {
+                let style = match style {
+                    PathStyle::Simple => "simple",
+                    PathStyle::Label => "label",
+                    PathStyle::Matrix => "matrix",
+                };
+                (parameter_data, constants::PARAM_LOCATION_PATH, style)
             }
             Parameter::Cookie { parameter_data, .. } => {
-                (parameter_data, constants::PARAM_LOCATION_COOKIE)
+                (parameter_data, constants::PARAM_LOCATION_COOKIE, "form")
             }
         };
 
+        // `explode` defaults to true only for the form style
+        let encoding = CachedEncoding {
+            style: style.to_string(),
+            explode: param_data.explode.unwrap_or(style == "form"),
+        };
+
         // Extract schema information from parameter
         let (schema_json, schema_type, format, default_value, enum_values) =
             Self::extract_parameter_schema_info(&param_data.format);
+        let schema_json = source
+            .and_then(|param| param.get("schema"))
+            .map(Value::to_string)
+            .or(schema_json);
 
         // Extract example value
         let example = param_data
@@ -424,6 +558,7 @@ impl SpecTransformer {
             default_value,
             enum_values,
             example,
+            encoding: Some(encoding),
         }
     }
 
@@ -574,6 +709,7 @@ impl SpecTransformer {
         spec: &OpenAPI,
         status_code: String,
         response_ref: &ReferenceOr<openapiv3::Response>,
+        source: Option<&Value>,
     ) -> CachedResponse {
         let ReferenceOr::Item(response) = response_ref else {
             return CachedResponse {
@@ -603,9 +739,11 @@ impl SpecTransformer {
         let (content_type, schema, example) =
             preferred_content_type.map_or((None, None, None), |ct| {
                 let media_type = response.content.get(ct);
-                let schema = media_type
-                    .and_then(|mt| mt.schema.as_ref())
-                    .and_then(|schema_ref| Self::resolve_and_serialize_schema(spec, schema_ref));
+                let schema = Self::source_media_type_schema(source, ct).or_else(|| {
+                    media_type
+                        .and_then(|mt| mt.schema.as_ref())
+                        .and_then(|schema_ref| Self::resolve_and_serialize_schema(spec, schema_ref))
+                });
                 let example = media_type
                     .and_then(|mt| mt.example.as_ref())
                     .map(|ex| serde_json::to_string(ex).unwrap_or_else(|_| ex.to_string()));
@@ -638,34 +776,111 @@ impl SpecTransformer {
     }
 
     /// Transforms a request body into cached format
+    ///
+    /// Multipart and form-encoded schemas are resolved through `$ref` because
+    /// their top-level properties become the command's form-field flags.
     fn transform_request_body(
+        spec: &OpenAPI,
         request_body: &ReferenceOr<RequestBody>,
+        source: Option<&Value>,
     ) -> Option<CachedRequestBody> {
         match request_body {
             ReferenceOr::Item(body) => {
                 let content_type = Self::preferred_request_body_content_type(body)?;
                 let media_type = body.content.get(content_type)?;
-                let schema = Self::request_body_schema(media_type);
+                let form_content_type = if crate::spec::is_multipart_content_type(content_type) {
+                    Some(constants::CONTENT_TYPE_MULTIPART)
+                } else if crate::spec::is_form_urlencoded_content_type(content_type) {
+                    Some(constants::CONTENT_TYPE_FORM)
+                } else {
+                    None
+                };
+                let schema =
+                    Self::source_media_type_schema(source, content_type).unwrap_or_else(|| {
+                        if form_content_type.is_some() {
+                            media_type
+                                .schema
+                                .as_ref()
+                                .and_then(|schema_ref| {
+                                    Self::resolve_and_serialize_schema(spec, schema_ref)
+                                })
+                                .unwrap_or_else(|| "{}".to_string())
+                        } else {
+                            Self::request_body_schema(media_type)
+                        }
+                    });
+                let encoding = if form_content_type == Some(constants::CONTENT_TYPE_FORM) {
+                    Self::transform_form_encoding(media_type)
+                } else {
+                    HashMap::new()
+                };
                 let example = Self::request_body_example(media_type);
 
                 Some(CachedRequestBody {
-                    content_type: content_type.to_string(),
+                    content_type: form_content_type
+                        .map_or_else(|| content_type.to_string(), ToString::to_string),
                     schema,
                     required: body.required,
                     description: body.description.clone(),
                     example,
+                    encoding,
                 })
             }
             ReferenceOr::Reference { .. } => None, // Skip references for now
         }
     }
 
+    /// Picks the content type a command sends: JSON, then multipart, then
+    /// form-encoded, then binary, falling back to the first declared type.
     fn preferred_request_body_content_type(body: &RequestBody) -> Option<&str> {
         if body.content.contains_key(constants::CONTENT_TYPE_JSON) {
-            Some(constants::CONTENT_TYPE_JSON)
-        } else {
-            body.content.keys().next().map(String::as_str)
+            return Some(constants::CONTENT_TYPE_JSON);
         }
+        let preferences: [fn(&str) -> bool; 4] = [
+            constants::is_json_content_type,
+            crate::spec::is_multipart_content_type,
+            crate::spec::is_form_urlencoded_content_type,
+            crate::spec::is_binary_content_type,
+        ];
+        preferences
+            .iter()
+            .find_map(|matches| {
+                body.content
+                    .keys()
+                    .find(|content_type| matches(content_type))
+            })
+            .or_else(|| body.content.keys().next())
+            .map(String::as_str)
+    }
+
+    /// Resolves the effective `style`/`explode` of each property listed in a
+    /// form-encoded media type's `encoding` object.
+    ///
+    /// `openapiv3` reads a missing `explode` as `false`, so the `OpenAPI`
+    /// default of exploding `form` values is applied when no style is given.
+    fn transform_form_encoding(
+        media_type: &openapiv3::MediaType,
+    ) -> HashMap<String, CachedEncoding> {
+        media_type
+            .encoding
+            .iter()
+            .map(|(property, encoding)| {
+                let (style, explode) = match encoding.style {
+                    None => ("form", true),
+                    Some(QueryStyle::Form) => ("form", encoding.explode),
+                    Some(QueryStyle::SpaceDelimited) => ("spaceDelimited", encoding.explode),
+                    Some(QueryStyle::PipeDelimited) => ("pipeDelimited", encoding.explode),
+                    Some(QueryStyle::DeepObject) => ("deepObject", true),
+                };
+                (
+                    property.clone(),
+                    CachedEncoding {
+                        style: style.to_string(),
+                        explode,
+                    },
+                )
+            })
+            .collect()
     }
 
     fn request_body_schema(media_type: &openapiv3::MediaType) -> String {
@@ -687,7 +902,10 @@ impl SpecTransformer {
     }
 
     /// Extracts and transforms security schemes from the `OpenAPI` spec
-    fn extract_security_schemes(spec: &OpenAPI) -> HashMap<String, CachedSecurityScheme> {
+    fn extract_security_schemes(
+        spec: &OpenAPI,
+        openid_connect: &OpenIdConnectDiscovery,
+    ) -> HashMap<String, CachedSecurityScheme> {
         let mut security_schemes = HashMap::new();
 
         let Some(components) = &spec.components else {
@@ -699,7 +917,8 @@ impl SpecTransformer {
                 continue;
             };
 
-            let Some(cached_scheme) = Self::transform_security_scheme(name, scheme) else {
+            let Some(cached_scheme) = Self::transform_security_scheme(name, scheme, openid_connect)
+            else {
                 continue;
             };
 
@@ -713,6 +932,7 @@ impl SpecTransformer {
     fn transform_security_scheme(
         name: &str,
         scheme: &SecurityScheme,
+        openid_connect: &OpenIdConnectDiscovery,
     ) -> Option<CachedSecurityScheme> {
         match scheme {
             SecurityScheme::APIKey {
@@ -737,6 +957,7 @@ impl SpecTransformer {
                     description: description.clone(),
                     bearer_format: None,
                     aperture_secret,
+                    oauth2: None,
                 })
             }
             SecurityScheme::HTTP {
@@ -755,28 +976,61 @@ impl SpecTransformer {
                     description: description.clone(),
                     bearer_format: bearer_format.clone(),
                     aperture_secret,
+                    oauth2: None,
+                })
+            }
+            SecurityScheme::OAuth2 {
+                flows, description, ..
+            } => {
+                // Flows without a supported grant are rejected in validation
+                let oauth2 = crate::spec::oauth2_config_from_flows(flows)?;
+                let aperture_secret = Self::extract_aperture_secret(scheme);
+                Some(CachedSecurityScheme {
+                    name: name.to_string(),
+                    scheme_type: constants::AUTH_SCHEME_OAUTH2.to_string(),
+                    scheme: Some(constants::AUTH_SCHEME_BEARER.to_string()),
+                    location: Some(constants::LOCATION_HEADER.to_string()),
+                    parameter_name: Some(constants::HEADER_AUTHORIZATION.to_string()),
+                    description: description.clone(),
+                    bearer_format: None,
+                    aperture_secret,
+                    oauth2: Some(oauth2),
+                })
+            }
+            SecurityScheme::OpenIDConnect { description, .. } => {
+                // Schemes whose discovery failed are rejected in validation
+                let oauth2 = openid_connect.get(name)?.as_ref().ok()?.clone();
+                let aperture_secret = Self::extract_aperture_secret(scheme);
+                Some(CachedSecurityScheme {
+                    name: name.to_string(),
+                    scheme_type: constants::AUTH_SCHEME_OPENID.to_string(),
+                    scheme: Some(constants::AUTH_SCHEME_BEARER.to_string()),
+                    location: Some(constants::LOCATION_HEADER.to_string()),
+                    parameter_name: Some(constants::HEADER_AUTHORIZATION.to_string()),
+                    description: description.clone(),
+                    bearer_format: None,
+                    aperture_secret,
+                    oauth2: Some(oauth2),
                 })
             }
-            // OAuth2 and OpenID Connect should be rejected in validation
-            SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. } => None,
         }
     }
 
     /// Extracts x-aperture-secret extension from a security scheme
     fn extract_aperture_secret(scheme: &SecurityScheme) -> Option<CachedApertureSecret> {
         Self::security_scheme_extensions(scheme)
-            .and_then(|extensions| extensions.get(crate::constants::EXT_APERTURE_SECRET))
+            .get(crate::constants::EXT_APERTURE_SECRET)
             .and_then(Self::parse_aperture_secret_extension)
     }
 
     const fn security_scheme_extensions(
         scheme: &SecurityScheme,
-    ) -> Option<&indexmap::IndexMap<String, serde_json::Value>> {
+    ) -> &indexmap::IndexMap<String, serde_json::Value> {
         match scheme {
-            SecurityScheme::APIKey { extensions, .. } | SecurityScheme::HTTP { extensions, .. } => {
-                Some(extensions)
-            }
-            SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. } => None,
+            SecurityScheme::APIKey { extensions, .. }
+            | SecurityScheme::HTTP { extensions, .. }
+            | SecurityScheme::OAuth2 { extensions, .. }
+            | SecurityScheme::OpenIDConnect { extensions, .. } => extensions,
         }
     }
 
@@ -785,9 +1039,8 @@ impl SpecTransformer {
         let source = obj.get(crate::constants::EXT_KEY_SOURCE)?.as_str()?;
         let name = obj.get(crate::constants::EXT_KEY_NAME)?.as_str()?;
 
-        if source != constants::SOURCE_ENV {
-            return None;
-        }
+        // Unknown sources are rejected during validation
+        source.parse::<crate::config::models::SecretSource>().ok()?;
 
         Some(CachedApertureSecret {
             source: source.to_string(),
@@ -844,7 +1097,7 @@ impl SpecTransformer {
         required_params: &[&CachedParameter],
         request_body: Option<&CachedRequestBody>,
     ) -> Option<CommandExample> {
-        request_body?;
+        let request_body = request_body?;
 
         let path_query_params: Vec<&CachedParameter> = required_params
             .iter()
@@ -853,12 +1106,18 @@ impl SpecTransformer {
             .collect();
 
         let mut cmd = Self::build_command_example_command_line(base_cmd, &path_query_params, "123");
-        cmd.push_str(r#" --body '{"name": "example", "value": 42}'"#);
+        let explanation = if crate::spec::is_binary_content_type(&request_body.content_type) {
+            cmd.push_str(" --body-file ./upload.bin");
+            "Sends a file as the raw request body"
+        } else {
+            cmd.push_str(r#" --body '{"name": "example", "value": 42}'"#);
+            "Sends JSON data in the request body"
+        };
 
         Some(CommandExample {
             description: "With request body".to_string(),
             command_line: cmd,
-            explanation: Some("Sends JSON data in the request body".to_string()),
+            explanation: Some(explanation.to_string()),
         })
     }
 
@@ -1,6 +1,8 @@
+use crate::config::models::SecretSource;
 use crate::constants;
 #[allow(unused_imports)]
 use crate::error::{Error, ErrorKind};
+use crate::oauth::discovery::OpenIdConnectDiscovery;
 use openapiv3::{OpenAPI, Operation, Parameter, ReferenceOr, RequestBody, SecurityScheme};
 use std::collections::HashMap;
 
@@ -86,10 +88,14 @@ pub struct UnsupportedEndpoint {
     pub path: String,
     /// HTTP method (e.g., "POST")
     pub method: String,
-    /// Content type that caused the skip (e.g., "multipart/form-data")
+    /// Content type that caused the skip (e.g., "application/xml")
     pub content_type: String,
 }
 
+/// Prefix of the warning reason for endpoints that are kept but also declare
+/// unsupported content types; it is followed by the supported one
+pub const PARTIAL_SUPPORT_REASON_PREFIX: &str = "endpoint has unsupported content types alongside ";
+
 /// Validates `OpenAPI` specifications for compatibility with Aperture
 pub struct SpecValidator;
 
@@ -102,31 +108,11 @@ impl SpecValidator {
 
     /// Returns a human-readable reason for why a content type is not supported
     fn get_unsupported_content_type_reason(content_type: &str) -> &'static str {
-        let reason_rules: [ContentTypeReasonRule; 10] = [
-            (
-                Self::is_multipart_content_type as fn(&str) -> bool,
-                "file uploads are not supported",
-            ),
-            (
-                Self::is_octet_stream_content_type as fn(&str) -> bool,
-                "binary data uploads are not supported",
-            ),
-            (
-                Self::is_image_upload_content_type as fn(&str) -> bool,
-                "image uploads are not supported",
-            ),
-            (
-                Self::is_pdf_content_type as fn(&str) -> bool,
-                "PDF uploads are not supported",
-            ),
+        let reason_rules: [ContentTypeReasonRule; 5] = [
             (
                 Self::is_xml_content_type as fn(&str) -> bool,
                 "XML content is not supported",
             ),
-            (
-                Self::is_form_content_type as fn(&str) -> bool,
-                "form-encoded data is not supported",
-            ),
             (
                 Self::is_text_content_type as fn(&str) -> bool,
                 "plain text content is not supported",
@@ -151,22 +137,6 @@ impl SpecValidator {
             .unwrap_or("is not supported")
     }
 
-    fn is_multipart_content_type(content_type: &str) -> bool {
-        content_type == constants::CONTENT_TYPE_MULTIPART
-    }
-
-    fn is_octet_stream_content_type(content_type: &str) -> bool {
-        content_type == constants::CONTENT_TYPE_OCTET_STREAM
-    }
-
-    fn is_image_upload_content_type(content_type: &str) -> bool {
-        content_type.starts_with(constants::CONTENT_TYPE_PREFIX_IMAGE)
-    }
-
-    fn is_pdf_content_type(content_type: &str) -> bool {
-        content_type == constants::CONTENT_TYPE_PDF
-    }
-
     fn is_xml_content_type(content_type: &str) -> bool {
         matches!(
             content_type,
@@ -174,10 +144,6 @@ impl SpecValidator {
         )
     }
 
-    fn is_form_content_type(content_type: &str) -> bool {
-        content_type == constants::CONTENT_TYPE_FORM
-    }
-
     fn is_text_content_type(content_type: &str) -> bool {
         content_type == constants::CONTENT_TYPE_TEXT
     }
@@ -199,7 +165,7 @@ impl SpecValidator {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - The spec contains unsupported security schemes (`OAuth2`, `OpenID` Connect)
+    /// - The spec contains unsupported security schemes (implicit or password `OAuth2` flows, `OpenID` Connect)
     /// - The spec uses $ref references in security schemes, parameters, or request bodies
     /// - Required x-aperture-secret extensions are missing
     /// - Parameters use content-based serialization
@@ -224,6 +190,20 @@ impl SpecValidator {
     /// Returns a `ValidationResult` containing any errors and/or warnings found
     #[must_use]
     pub fn validate_with_mode(&self, spec: &OpenAPI, strict: bool) -> ValidationResult {
+        self.validate_with_discovery(spec, strict, &OpenIdConnectDiscovery::new())
+    }
+
+    /// Validates an `OpenAPI` specification using `OpenID` Connect discovery results
+    ///
+    /// `openIdConnect` schemes are supported only when `discovered` holds a
+    /// successful result for them; see [`crate::oauth::discovery::discover`].
+    #[must_use]
+    pub fn validate_with_discovery(
+        &self,
+        spec: &OpenAPI,
+        strict: bool,
+        discovered: &OpenIdConnectDiscovery,
+    ) -> ValidationResult {
         let mut result = ValidationResult::new();
 
         // Validate security schemes and track unsupported ones
@@ -237,7 +217,7 @@ impl SpecValidator {
                     continue;
                 };
 
-                let Err(e) = Self::validate_security_scheme(name, scheme) else {
+                let Err(e) = Self::validate_security_scheme(name, scheme, discovered) else {
                     continue;
                 };
 
@@ -278,7 +258,11 @@ impl SpecValidator {
     }
 
     /// Validates a single security scheme and returns the scheme type for tracking
-    fn unsupported_security_scheme_reason(scheme: &SecurityScheme) -> Option<String> {
+    fn unsupported_security_scheme_reason(
+        name: &str,
+        scheme: &SecurityScheme,
+        discovered: &OpenIdConnectDiscovery,
+    ) -> Option<String> {
         match scheme {
             SecurityScheme::APIKey { .. } => None, // API Key schemes are supported
             SecurityScheme::HTTP {
@@ -296,12 +280,21 @@ impl SpecValidator {
                     None // Any other HTTP scheme (bearer, basic, token, apikey, custom, etc.) is allowed
                 }
             }
-            SecurityScheme::OAuth2 { .. } => {
-                Some("OAuth2 authentication is not supported".to_string())
-            }
-            SecurityScheme::OpenIDConnect { .. } => {
-                Some("OpenID Connect authentication is not supported".to_string())
+            SecurityScheme::OAuth2 { flows, .. } => {
+                if crate::spec::oauth2_config_from_flows(flows).is_some() {
+                    None
+                } else {
+                    Some(
+                        "OAuth2 authentication is not supported without a clientCredentials or authorizationCode flow"
+                            .to_string(),
+                    )
+                }
             }
+            SecurityScheme::OpenIDConnect { .. } => match discovered.get(name) {
+                Some(Ok(_)) => None,
+                Some(Err(reason)) => Some(format!("OpenID Connect discovery failed: {reason}")),
+                None => Some("OpenID Connect discovery was not performed".to_string()),
+            },
         }
     }
 
@@ -328,9 +321,7 @@ impl SpecValidator {
         name: &str,
         scheme: &SecurityScheme,
     ) -> Result<(), Error> {
-        let Some(extensions) = Self::aperture_secret_extensions(scheme) else {
-            return Ok(());
-        };
+        let extensions = Self::aperture_secret_extensions(scheme);
 
         let Some(secret_obj) = Self::aperture_secret_object(name, extensions)? else {
             return Ok(());
@@ -338,27 +329,43 @@ impl SpecValidator {
 
         let source =
             Self::required_secret_string_field(name, secret_obj, crate::constants::EXT_KEY_SOURCE)?;
-        if source != crate::constants::SOURCE_ENV {
+        let Ok(source) = source.parse::<SecretSource>() else {
+            return Err(Error::validation_error(format!(
+                "Unsupported source '{source}' in x-aperture-secret for security scheme '{name}'. Supported sources: {}.",
+                SecretSource::spec_sources().join(", ")
+            )));
+        };
+
+        if !source.allowed_in_spec() {
             return Err(Error::validation_error(format!(
-                "Unsupported source '{source}' in x-aperture-secret for security scheme '{name}'. Only 'env' is supported."
+                "Source '{source}' in x-aperture-secret for security scheme '{name}' is only allowed in mappings set with 'aperture config secret set'. Supported sources: {}.",
+                SecretSource::spec_sources().join(", ")
             )));
         }
 
-        let env_name =
+        let secret_name =
             Self::required_secret_string_field(name, secret_obj, crate::constants::EXT_KEY_NAME)?;
 
-        Self::validate_env_var_name(name, env_name)
+        match source {
+            // Dotenv keys follow environment variable naming
+            SecretSource::Env | SecretSource::Dotenv => {
+                Self::validate_env_var_name(name, secret_name)
+            }
+            _ if secret_name.trim().is_empty() => Err(Error::validation_error(format!(
+                "Empty 'name' field in x-aperture-secret for security scheme '{name}'"
+            ))),
+            _ => Ok(()),
+        }
     }
 
-    #[allow(clippy::missing_const_for_fn)]
-    fn aperture_secret_extensions(
+    const fn aperture_secret_extensions(
         scheme: &SecurityScheme,
-    ) -> Option<&indexmap::IndexMap<String, serde_json::Value>> {
+    ) -> &indexmap::IndexMap<String, serde_json::Value> {
         match scheme {
-            SecurityScheme::APIKey { extensions, .. } | SecurityScheme::HTTP { extensions, .. } => {
-                Some(extensions)
-            }
-            SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. } => None,
+            SecurityScheme::APIKey { extensions, .. }
+            | SecurityScheme::HTTP { extensions, .. }
+            | SecurityScheme::OAuth2 { extensions, .. }
+            | SecurityScheme::OpenIDConnect { extensions, .. } => extensions,
         }
     }
 
@@ -402,8 +409,9 @@ impl SpecValidator {
     fn validate_security_scheme(
         name: &str,
         scheme: &SecurityScheme,
+        discovered: &OpenIdConnectDiscovery,
     ) -> Result<Option<String>, Error> {
-        if let Some(reason) = Self::unsupported_security_scheme_reason(scheme) {
+        if let Some(reason) = Self::unsupported_security_scheme_reason(name, scheme, discovered) {
             return Err(Error::validation_error(format!(
                 "Security scheme '{name}' uses unsupported authentication: {reason}"
             )));
@@ -506,10 +514,10 @@ impl SpecValidator {
     }
 
     fn format_unsupported_scheme_detail(scheme_name: &str, msg: &str) -> String {
-        if msg.contains("OAuth2") {
-            format!("{scheme_name} (OAuth2)")
-        } else if msg.contains("OpenID Connect") {
+        if msg.contains("OpenID Connect") {
             format!("{scheme_name} (OpenID Connect)")
+        } else if msg.contains("OAuth2") {
+            format!("{scheme_name} (OAuth2)")
         } else if msg.contains("complex authentication flows") {
             format!("{scheme_name} (requires complex flow)")
         } else {
@@ -644,7 +652,7 @@ impl SpecValidator {
         result: &mut ValidationResult,
         strict: bool,
     ) {
-        let (has_json, unsupported_types) = Self::categorize_content_types(request_body);
+        let (supported, unsupported_types) = Self::categorize_content_types(request_body);
 
         if unsupported_types.is_empty() {
             return;
@@ -653,24 +661,50 @@ impl SpecValidator {
         if strict {
             Self::add_strict_mode_errors(path, method, &unsupported_types, result);
         } else {
-            Self::add_non_strict_warning(path, method, has_json, &unsupported_types, result);
+            Self::add_non_strict_warning(path, method, supported, &unsupported_types, result);
         }
     }
 
-    /// Categorize content types into JSON and unsupported
-    fn categorize_content_types(request_body: &RequestBody) -> (bool, Vec<&String>) {
+    /// Categorize content types into supported and unsupported
+    ///
+    /// Returns a label for the preferred supported content type (JSON, then
+    /// multipart, then form-encoded, then binary), if any, together with the
+    /// unsupported content types.
+    fn categorize_content_types(
+        request_body: &RequestBody,
+    ) -> (Option<&'static str>, Vec<&String>) {
         let mut has_json = false;
+        let mut has_multipart = false;
+        let mut has_form = false;
+        let mut has_binary = false;
         let mut unsupported_types = Vec::new();
 
         for content_type in request_body.content.keys() {
             if Self::is_json_content_type(content_type) {
                 has_json = true;
+            } else if super::is_multipart_content_type(content_type) {
+                has_multipart = true;
+            } else if super::is_form_urlencoded_content_type(content_type) {
+                has_form = true;
+            } else if super::is_binary_content_type(content_type) {
+                has_binary = true;
             } else {
                 unsupported_types.push(content_type);
             }
         }
 
-        (has_json, unsupported_types)
+        let supported = if has_json {
+            Some("JSON")
+        } else if has_multipart {
+            Some(constants::CONTENT_TYPE_MULTIPART)
+        } else if has_form {
+            Some(constants::CONTENT_TYPE_FORM)
+        } else if has_binary {
+            Some("binary data")
+        } else {
+            None
+        };
+        (supported, unsupported_types)
     }
 
     /// Add errors for unsupported content types in strict mode
@@ -682,7 +716,7 @@ impl SpecValidator {
     ) {
         for content_type in unsupported_types {
             let error = Error::validation_error(format!(
-                "Unsupported request body content type '{content_type}' in {method} {path}. Only 'application/json' is supported in v1.0."
+                "Unsupported request body content type '{content_type}' in {method} {path}. Only 'application/json', 'multipart/form-data', 'application/x-www-form-urlencoded' and binary ('application/octet-stream', 'application/pdf', 'image/*') are supported."
             ));
             result.add_error(error);
         }
@@ -692,7 +726,7 @@ impl SpecValidator {
     fn add_non_strict_warning(
         path: &str,
         method: &str,
-        has_json: bool,
+        supported: Option<&str>,
         unsupported_types: &[&String],
         result: &mut ValidationResult,
     ) {
@@ -704,11 +738,10 @@ impl SpecValidator {
             })
             .collect();
 
-        let reason = if has_json {
-            "endpoint has unsupported content types alongside JSON"
-        } else {
-            "endpoint has no supported content types"
-        };
+        let reason = supported.map_or_else(
+            || "endpoint has no supported content types".to_string(),
+            |supported| format!("{PARTIAL_SUPPORT_REASON_PREFIX}{supported}"),
+        );
 
         let warning = ValidationWarning {
             endpoint: UnsupportedEndpoint {
@@ -716,7 +749,7 @@ impl SpecValidator {
                 method: method.to_uppercase(),
                 content_type: content_types.join(", "),
             },
-            reason: reason.to_string(),
+            reason,
         };
 
         result.add_warning(warning);
@@ -792,6 +825,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_oauth2_client_credentials_accepted() {
+        let validator = SpecValidator::new();
+        let mut spec = create_test_spec();
+        let mut components = Components::default();
+        components.security_schemes.insert(
+            "oauth".to_string(),
+            ReferenceOr::Item(SecurityScheme::OAuth2 {
+                flows: openapiv3::OAuth2Flows {
+                    client_credentials: Some(openapiv3::ClientCredentialsOAuth2Flow {
+                        refresh_url: None,
+                        token_url: "https://example.com/token".to_string(),
+                        scopes: Default::default(),
+                        extensions: Default::default(),
+                    }),
+                    ..Default::default()
+                },
+                description: None,
+                extensions: Default::default(),
+            }),
+        );
+        spec.components = Some(components);
+
+        assert!(validator
+            .validate_with_mode(&spec, true)
+            .into_result()
+            .is_ok());
+    }
+
     #[test]
     fn test_validate_reference_rejected() {
         let validator = SpecValidator::new();
@@ -871,11 +933,12 @@ mod tests {
         let validator = SpecValidator::new();
         let mut spec = create_test_spec();
 
-        // Endpoint with both JSON and multipart - should be accepted without warnings
+        // Endpoint with both JSON and XML - should be accepted with a warning
         let mut request_body = RequestBody::default();
-        request_body
-            .content
-            .insert("multipart/form-data".to_string(), MediaType::default());
+        request_body.content.insert(
+            constants::CONTENT_TYPE_XML.to_string(),
+            MediaType::default(),
+        );
         request_body.content.insert(
             constants::CONTENT_TYPE_JSON.to_string(),
             MediaType::default(),
@@ -912,7 +975,7 @@ mod tests {
         assert!(warning
             .endpoint
             .content_type
-            .contains("multipart/form-data"));
+            .contains(constants::CONTENT_TYPE_XML));
         assert!(warning
             .reason
             .contains("unsupported content types alongside JSON"));
@@ -925,9 +988,10 @@ mod tests {
 
         // Endpoint with only unsupported content type - should produce warning
         let mut request_body = RequestBody::default();
-        request_body
-            .content
-            .insert("multipart/form-data".to_string(), MediaType::default());
+        request_body.content.insert(
+            constants::CONTENT_TYPE_CSV.to_string(),
+            MediaType::default(),
+        );
         request_body.required = true;
 
         let mut path_item = PathItem::default();
@@ -955,7 +1019,7 @@ mod tests {
         assert!(warning
             .endpoint
             .content_type
-            .contains("multipart/form-data"));
+            .contains(constants::CONTENT_TYPE_CSV));
         assert!(warning.reason.contains("no supported content types"));
     }
 
@@ -965,9 +1029,10 @@ mod tests {
         let mut spec = create_test_spec();
 
         let mut request_body = RequestBody::default();
-        request_body
-            .content
-            .insert("multipart/form-data".to_string(), MediaType::default());
+        request_body.content.insert(
+            constants::CONTENT_TYPE_CSV.to_string(),
+            MediaType::default(),
+        );
         request_body.required = true;
 
         let mut path_item = PathItem::default();
@@ -995,8 +1060,8 @@ mod tests {
                 message: msg,
                 ..
             } => {
-                assert!(msg.contains("multipart/form-data"));
-                assert!(msg.contains("v1.0"));
+                assert!(msg.contains(constants::CONTENT_TYPE_CSV));
+                assert!(msg.contains("Only 'application/json', 'multipart/form-data', 'application/x-www-form-urlencoded' and binary"));
             }
             _ => panic!("Expected Validation error"),
         }
@@ -1063,9 +1128,10 @@ mod tests {
 
         // Endpoint with multiple unsupported content types - should produce single warning
         let mut request_body = RequestBody::default();
-        request_body
-            .content
-            .insert("multipart/form-data".to_string(), MediaType::default());
+        request_body.content.insert(
+            constants::CONTENT_TYPE_CSV.to_string(),
+            MediaType::default(),
+        );
         request_body.content.insert(
             constants::CONTENT_TYPE_XML.to_string(),
             MediaType::default(),
@@ -1102,7 +1168,7 @@ mod tests {
         assert!(warning
             .endpoint
             .content_type
-            .contains("multipart/form-data"));
+            .contains(constants::CONTENT_TYPE_CSV));
         assert!(warning
             .endpoint
             .content_type
@@ -1114,6 +1180,55 @@ mod tests {
         assert!(warning.reason.contains("no supported content types"));
     }
 
+    #[test]
+    fn test_validate_multipart_request_body_is_supported() {
+        let validator = SpecValidator::new();
+        let mut spec = create_test_spec();
+
+        let mut multipart_only = RequestBody::default();
+        multipart_only.content.insert(
+            constants::CONTENT_TYPE_MULTIPART.to_string(),
+            MediaType::default(),
+        );
+        let mut multipart_and_xml = multipart_only.clone();
+        multipart_and_xml.content.insert(
+            constants::CONTENT_TYPE_XML.to_string(),
+            MediaType::default(),
+        );
+
+        for (path, operation_id, body) in [
+            ("/upload", "uploadFile", multipart_only),
+            ("/import", "importFile", multipart_and_xml),
+        ] {
+            let mut path_item = PathItem::default();
+            path_item.post = Some(Operation {
+                operation_id: Some(operation_id.to_string()),
+                request_body: Some(ReferenceOr::Item(body)),
+                responses: Responses::default(),
+                ..Default::default()
+            });
+            spec.paths
+                .paths
+                .insert(path.to_string(), PathRef::Item(path_item));
+        }
+
+        // Only the XML alternative is reported; the endpoint itself is kept
+        let result = validator.validate_with_mode(&spec, false);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        let warning = &result.warnings[0];
+        assert_eq!(warning.endpoint.path, "/import");
+        assert_eq!(
+            warning.endpoint.content_type,
+            "application/xml (XML content is not supported)"
+        );
+        assert_eq!(
+            warning.reason,
+            "endpoint has unsupported content types alongside multipart/form-data"
+        );
+        assert!(!warning.should_skip_endpoint());
+    }
+
     #[test]
     fn test_validate_unsupported_http_scheme() {
         let validator = SpecValidator::new();
@@ -1400,7 +1515,7 @@ mod tests {
         extensions.insert(
             crate::constants::EXT_APERTURE_SECRET.to_string(),
             serde_json::json!({
-                "source": "file",  // Not supported
+                "source": "keychain",  // Not supported
                 "name": "API_TOKEN"
             }),
         );
@@ -1424,9 +1539,54 @@ mod tests {
                 message: msg,
                 ..
             } => {
-                assert!(msg.contains("Unsupported source 'file'"));
+                assert!(msg.contains("Unsupported source 'keychain'"));
+                assert!(msg.contains("env, dotenv, vault"));
             }
             _ => panic!("Expected Validation error"),
         }
     }
+
+    #[test]
+    fn test_validate_x_aperture_secret_rejects_user_only_sources() {
+        let validator = SpecValidator::new();
+
+        // A spec added from a URL must not run commands or read files
+        for (source, secret_name) in [
+            ("command", "touch /tmp/pwned; echo token"),
+            ("file", "~/.ssh/id_rsa"),
+        ] {
+            let mut spec = create_test_spec();
+            let mut components = Components::default();
+            let mut extensions = serde_json::Map::new();
+            extensions.insert(
+                crate::constants::EXT_APERTURE_SECRET.to_string(),
+                serde_json::json!({
+                    "source": source,
+                    "name": secret_name
+                }),
+            );
+            components.security_schemes.insert(
+                "bearerAuth".to_string(),
+                ReferenceOr::Item(SecurityScheme::HTTP {
+                    scheme: constants::AUTH_SCHEME_BEARER.to_string(),
+                    bearer_format: None,
+                    description: None,
+                    extensions: extensions.into_iter().collect(),
+                }),
+            );
+            spec.components = Some(components);
+
+            match validator.validate_with_mode(&spec, true).into_result() {
+                Err(Error::Internal {
+                    kind: ErrorKind::Validation,
+                    message: msg,
+                    ..
+                }) => {
+                    assert!(msg.contains(&format!("Source '{source}'")), "{msg}");
+                    assert!(msg.contains("aperture config secret set"), "{msg}");
+                }
+                other => panic!("Expected Validation error for {source}, got {other:?}"),
+            }
+        }
+    }
 }
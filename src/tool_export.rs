@@ -0,0 +1,282 @@
+//! Tool definitions for LLM function calling.
+//!
+//! `--describe-json --as FORMAT` describes every visible operation as a tool
+//! that model providers accept as-is. A tool is named `{group}__{operation}`
+//! after its command path, and its input schema has one property per flag,
+//! keyed by the flag name without its leading dashes, plus `body` for a JSON or
+//! form body (`--body`) or `body-file` for a binary one. A tool call for
+//! `users__get-user` with `{"id": "42"}` is therefore
+//! `aperture api CONTEXT users get-user --id 42`. Names longer than 64
+//! characters, or that two operations would share, are shortened and end with
+//! a hash instead.
+//!
+//! Descriptions are trimmed to a token budget so that large specs stay
+//! within a model's context.
+
+use crate::cache::models::{CachedCommand, CachedSpec};
+use crate::cli::ToolFormat;
+use crate::engine::generator::{effective_group_name, effective_subcommand_name};
+use crate::error::Error;
+use crate::mcp::{self, ToolNames};
+use crate::utils::to_kebab_case;
+use serde_json::{json, Map, Value};
+
+/// Default description budget, in tokens
+pub const DEFAULT_DESCRIPTION_TOKENS: usize = 120;
+
+/// Rough size of a token, used to turn a token budget into characters
+const CHARS_PER_TOKEN: usize = 4;
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Property for a binary request body, given as a file path
+const BODY_FILE_ARGUMENT: &str = "body-file";
+
+/// Keywords `OpenAI` strict mode accepts; others are dropped
+const STRICT_KEYWORDS: &[&str] = &[
+    "type",
+    "description",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+    "enum",
+    "const",
+    "anyOf",
+];
+
+/// Builds one tool definition per visible operation of `spec`.
+///
+/// `description_tokens` caps every description; `0` keeps them whole.
+///
+/// # Errors
+///
+/// Returns an error if two parameters of an operation have the same flag, or
+/// if two operations cannot be given distinct tool names.
+pub fn generate_tools(
+    spec: &CachedSpec,
+    format: &ToolFormat,
+    description_tokens: usize,
+) -> Result<Value, Error> {
+    let mut names = ToolNames::default();
+    let tools = spec
+        .commands
+        .iter()
+        .filter(|command| !command.hidden)
+        .map(|command| {
+            let name = names.assign(
+                &to_kebab_case(&effective_group_name(command)),
+                &effective_subcommand_name(command),
+            )?;
+            let description = trim_description(&mcp::tool_description(command), description_tokens);
+            let mut schema = input_schema(command)?;
+            trim_schema_descriptions(&mut schema, description_tokens);
+
+            Ok(match format {
+                ToolFormat::OpenaiTools => {
+                    let strict = strict_schema(&schema, true);
+                    json!({
+                        "type": "function",
+                        "function": {
+                            "name": name,
+                            "description": description,
+                            "parameters": strict.as_ref().unwrap_or(&schema),
+                            "strict": strict.is_some(),
+                        },
+                    })
+                }
+                ToolFormat::AnthropicTools => json!({
+                    "name": name,
+                    "description": description,
+                    "input_schema": schema,
+                }),
+                ToolFormat::JsonSchema => {
+                    let mut document = json!({
+                        "$schema": JSON_SCHEMA_DIALECT,
+                        "title": name,
+                        "description": description,
+                    });
+                    if let (Some(document), Value::Object(schema)) =
+                        (document.as_object_mut(), schema)
+                    {
+                        document.extend(schema);
+                    }
+                    document
+                }
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(Value::Array(tools))
+}
+
+/// Builds the closed schema of a command's flags and body
+fn input_schema(command: &CachedCommand) -> Result<Value, Error> {
+    let (mut properties, mut required) = mcp::parameter_properties(command, to_kebab_case)?;
+
+    if let Some(request_body) = &command.request_body {
+        let (name, schema) = if crate::spec::is_binary_content_type(&request_body.content_type) {
+            (
+                BODY_FILE_ARGUMENT,
+                json!({
+                    "type": "string",
+                    "description": format!(
+                        "Path of the file to send as the {} request body",
+                        request_body.content_type
+                    ),
+                }),
+            )
+        } else {
+            (mcp::BODY_ARGUMENT, mcp::body_schema(request_body))
+        };
+        if request_body.required {
+            required.push(Value::String(name.to_string()));
+        }
+        properties.insert(name.to_string(), schema);
+    }
+
+    Ok(json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    }))
+}
+
+/// Shortens `text` to about `tokens` tokens, cutting at a word boundary
+fn trim_description(text: &str, tokens: usize) -> String {
+    let limit = tokens.saturating_mul(CHARS_PER_TOKEN);
+    if tokens == 0 || text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(limit).collect();
+    let cut = cut
+        .rfind(char::is_whitespace)
+        .map_or(cut.as_str(), |end| &cut[..end]);
+    format!("{}…", cut.trim_end())
+}
+
+/// Trims the descriptions of a schema and its subschemas
+fn trim_schema_descriptions(schema: &mut Value, tokens: usize) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    if let Some(Value::String(description)) = object.get_mut("description") {
+        *description = trim_description(description, tokens);
+    }
+    for (keyword, value) in object.iter_mut() {
+        match (keyword.as_str(), value) {
+            ("properties" | "patternProperties" | "$defs" | "definitions", Value::Object(map)) => {
+                for subschema in map.values_mut() {
+                    trim_schema_descriptions(subschema, tokens);
+                }
+            }
+            ("items" | "additionalProperties" | "not", subschema) => {
+                trim_schema_descriptions(subschema, tokens);
+            }
+            ("anyOf" | "oneOf" | "allOf" | "prefixItems", Value::Array(subschemas)) => {
+                for subschema in subschemas {
+                    trim_schema_descriptions(subschema, tokens);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Rewrites a schema for `OpenAI` strict mode: every object lists all of its
+/// properties as required and allows no others, and optional properties
+/// accept `null` instead. Returns `None` for schemas strict mode cannot
+/// express, such as free-form objects or `allOf`.
+fn strict_schema(schema: &Value, required: bool) -> Option<Value> {
+    let Value::Object(object) = schema else {
+        return None;
+    };
+    if object.contains_key("allOf") || object.contains_key("$ref") {
+        return None;
+    }
+
+    let mut strict = Map::new();
+    for (keyword, value) in object {
+        match keyword.as_str() {
+            "properties" => {
+                let properties = value.as_object()?;
+                let listed: Vec<&str> = object
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .map(|names| names.iter().filter_map(Value::as_str).collect())
+                    .unwrap_or_default();
+                let mut strict_properties = Map::new();
+                for (name, property) in properties {
+                    let property = strict_schema(property, listed.contains(&name.as_str()))?;
+                    strict_properties.insert(name.clone(), property);
+                }
+                strict.insert(
+                    "required".to_string(),
+                    json!(properties.keys().collect::<Vec<_>>()),
+                );
+                strict.insert("properties".to_string(), Value::Object(strict_properties));
+                strict.insert("additionalProperties".to_string(), Value::Bool(false));
+            }
+            "items" => {
+                strict.insert(keyword.clone(), strict_schema(value, true)?);
+            }
+            "anyOf" | "oneOf" => {
+                let variants = value
+                    .as_array()?
+                    .iter()
+                    .map(|variant| strict_schema(variant, true))
+                    .collect::<Option<Vec<_>>>()?;
+                strict.insert("anyOf".to_string(), Value::Array(variants));
+            }
+            "required" | "additionalProperties" => {}
+            _ if STRICT_KEYWORDS.contains(&keyword.as_str()) => {
+                strict.insert(keyword.clone(), value.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let is_object = strict.get("type").and_then(Value::as_str) == Some("object");
+    if is_object && !strict.contains_key("properties") {
+        // A free-form object has no fixed set of properties to require
+        return None;
+    }
+    if !["type", "anyOf", "enum", "const"]
+        .iter()
+        .any(|keyword| strict.contains_key(*keyword))
+    {
+        return None;
+    }
+
+    let nullable = object.get("nullable").and_then(Value::as_bool) == Some(true);
+    if !required || nullable {
+        allow_null(&mut strict);
+    }
+    Some(Value::Object(strict))
+}
+
+/// Lets a strict schema also accept `null`
+fn allow_null(schema: &mut Map<String, Value>) {
+    match schema.get_mut("type") {
+        Some(Value::String(type_name)) => {
+            let type_name = std::mem::take(type_name);
+            schema.insert("type".to_string(), json!([type_name, "null"]));
+        }
+        Some(Value::Array(types)) => {
+            if !types.contains(&json!("null")) {
+                types.push(json!("null"));
+            }
+        }
+        _ => {
+            if let Some(Value::Array(variants)) = schema.get_mut("anyOf") {
+                variants.push(json!({ "type": "null" }));
+                return;
+            }
+        }
+    }
+    if let Some(Value::Array(values)) = schema.get_mut("enum") {
+        if !values.contains(&Value::Null) {
+            values.push(Value::Null);
+        }
+    }
+}
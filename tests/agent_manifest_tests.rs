@@ -57,6 +57,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                 source: "env".to_string(),
                 name: "API_TOKEN".to_string(),
             }),
+            oauth2: None,
         },
     );
 
@@ -75,6 +76,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                 source: "env".to_string(),
                 name: "API_KEY".to_string(),
             }),
+            oauth2: None,
         },
     );
 
@@ -90,6 +92,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
             description: None,
             bearer_format: None,
             aperture_secret: None, // No x-aperture-secret defined
+            oauth2: None,
         },
     );
 
@@ -114,6 +117,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: Some("12345".to_string()),
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "include".to_string(),
@@ -126,6 +130,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                     default_value: Some("profile".to_string()),
                     enum_values: vec!["profile".to_string(), "settings".to_string(), "history".to_string()],
                     example: Some("profile,settings".to_string()),
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -155,6 +160,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         },
         // Deprecated command with request body
         CachedCommand {
@@ -171,6 +177,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                 required: true,
                 description: Some("User data for creation".to_string()),
                 example: Some(r#"{"name": "John Doe", "email": "john@example.com"}"#.to_string()),
+                encoding: HashMap::new(),
             }),
             responses: vec![CachedResponse {
                 status_code: "201".to_string(),
@@ -189,6 +196,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         },
         // Public endpoint with no auth
         CachedCommand {
@@ -217,6 +225,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         },
     ];
 
@@ -405,6 +414,7 @@ fn test_manifest_with_global_config() {
             strict_mode: false,
             secrets: HashMap::new(),
             command_mapping: None,
+            response_validation: None,
         },
     );
 
@@ -1027,6 +1037,7 @@ fn test_manifest_from_cached_with_response_schema() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
@@ -1170,6 +1181,7 @@ fn test_manifest_cached_status_code_fallback() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
@@ -1201,11 +1213,10 @@ fn test_manifest_cached_status_code_fallback() {
 }
 
 #[test]
-fn test_manifest_response_reference_not_resolved() {
+fn test_manifest_response_reference_is_resolved() {
     use openapiv3::Response;
 
     // Create OpenAPI spec with a response reference ($ref to #/components/responses/...)
-    // This tests the documented limitation that response references are not resolved
     let mut responses = Responses::default();
 
     // Use a reference to a response in components
@@ -1290,12 +1301,16 @@ fn test_manifest_response_reference_not_resolved() {
     let manifest: serde_json::Value =
         serde_json::from_str(&manifest_json).expect("Failed to parse manifest JSON");
 
-    // Verify response_schema is NOT present because response references are not resolved
-    // This is a documented limitation
-    let command = &manifest["commands"]["users"][0];
-    assert!(
-        command.get("response_schema").is_none() || command["response_schema"].is_null(),
-        "response_schema should not be present when response is a $ref (documented limitation)"
+    // Verify the referenced response was inlined
+    let response_schema = &manifest["commands"]["users"][0]["response_schema"];
+    assert_eq!(
+        response_schema["content_type"],
+        constants::CONTENT_TYPE_JSON
+    );
+    assert_eq!(response_schema["schema"]["type"], "object");
+    assert_eq!(
+        response_schema["schema"]["properties"]["id"]["type"],
+        "integer"
     );
 }
 
@@ -1387,6 +1402,7 @@ fn test_manifest_from_openapi_includes_command_mappings() {
                 aliases: vec!["get".to_string(), "show".to_string()],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "User Management".to_string(),
@@ -1408,6 +1424,7 @@ fn test_manifest_from_openapi_includes_command_mappings() {
                 aliases: vec![],
                 hidden: true,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         base_url: Some("https://api.example.com".to_string()),
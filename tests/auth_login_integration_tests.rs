@@ -0,0 +1,201 @@
+#![cfg(feature = "integration")]
+
+mod common;
+mod test_helpers;
+
+use common::{aperture_cmd, APERTURE_BIN};
+use predicates::prelude::*;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn write_spec(dir: &Path, server_uri: &str) -> std::path::PathBuf {
+    let spec = format!(
+        r"
+openapi: 3.0.0
+info:
+  title: User API
+  version: 1.0.0
+servers:
+  - url: {server_uri}
+components:
+  securitySchemes:
+    userAuth:
+      type: oauth2
+      x-aperture-secret:
+        source: env
+        name: AUTH_LOGIN_TEST_CLIENT
+      flows:
+        authorizationCode:
+          authorizationUrl: {server_uri}/oauth/authorize
+          tokenUrl: {server_uri}/oauth/token
+          scopes:
+            profile: Read profile
+paths:
+  /me:
+    get:
+      tags: [me]
+      operationId: getMe
+      security:
+        - userAuth: [profile]
+      responses:
+        '200':
+          description: Success
+"
+    );
+    let spec_path = dir.join("user-api.yaml");
+    std::fs::write(&spec_path, spec).unwrap();
+    spec_path
+}
+
+fn add_spec(config_dir: &Path, spec_path: &Path) {
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", config_dir)
+        .args(["config", "api", "add", "user-api"])
+        .arg(spec_path)
+        .assert()
+        .success();
+}
+
+fn query_param(url: &str, name: &str) -> String {
+    reqwest::Url::parse(url)
+        .unwrap()
+        .query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_auth_login_stores_refresh_token_used_by_api_calls() {
+    let server = MockServer::start().await;
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("config");
+    add_spec(&config_dir, &write_spec(temp_dir.path(), &server.uri()));
+
+    // The first access token is already inside the refresh window, so the
+    // following API call must redeem the refresh token.
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=authorization_code"))
+        .and(body_string_contains("code=browser-code"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "login-access",
+            "token_type": "Bearer",
+            "expires_in": 30,
+            "refresh_token": "login-refresh"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .and(body_string_contains("refresh_token=login-refresh"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "refreshed-access",
+            "expires_in": 3600
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me"))
+        .and(header("authorization", "Bearer refreshed-access"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 7})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut child = Command::new(&*APERTURE_BIN)
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .env("AUTH_LOGIN_TEST_CLIENT", "cli-client")
+        .args(["auth", "login", "user-api"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let stderr = child.stderr.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            let _ = tx.send(line);
+        }
+    });
+    let authorization_url = loop {
+        let line = rx
+            .recv_timeout(Duration::from_secs(20))
+            .expect("login should print the authorization URL");
+        if line.contains("/oauth/authorize?") {
+            break line.trim().to_string();
+        }
+    };
+
+    let redirect_uri = query_param(&authorization_url, "redirect_uri");
+    let state = query_param(&authorization_url, "state");
+    let status = reqwest::get(format!("{redirect_uri}?code=browser-code&state={state}"))
+        .await
+        .unwrap()
+        .status();
+    assert!(status.is_success());
+
+    let exit = tokio::task::spawn_blocking(move || child.wait().unwrap())
+        .await
+        .unwrap();
+    assert!(exit.success(), "auth login exited with {exit}");
+    assert!(config_dir.join("tokens").join("user-api.json").exists());
+
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .env("AUTH_LOGIN_TEST_CLIENT", "cli-client")
+        .args(["api", "user-api", "me", "get-me"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("7"));
+}
+
+#[test]
+fn test_auth_login_requires_authorization_code_scheme() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("config");
+    let spec_path = temp_dir.path().join("plain.yaml");
+    std::fs::write(
+        &spec_path,
+        r"
+openapi: 3.0.0
+info:
+  title: Plain API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /ping:
+    get:
+      operationId: ping
+      responses:
+        '200':
+          description: Success
+",
+    )
+    .unwrap();
+
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .args(["config", "api", "add", "plain-api"])
+        .arg(&spec_path)
+        .assert()
+        .success();
+
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .args(["auth", "login", "plain-api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("authorizationCode"));
+}
@@ -55,6 +55,7 @@ fn create_test_spec_with_auth(bearer_env_var: &str, api_key_env_var: &str) -> Ca
                 source: "env".to_string(),
                 name: bearer_env_var.to_string(),
             }),
+            oauth2: None,
         },
     );
 
@@ -72,6 +73,7 @@ fn create_test_spec_with_auth(bearer_env_var: &str, api_key_env_var: &str) -> Ca
                 source: "env".to_string(),
                 name: api_key_env_var.to_string(),
             }),
+            oauth2: None,
         },
     );
 
@@ -97,6 +99,7 @@ fn create_test_spec_with_auth(bearer_env_var: &str, api_key_env_var: &str) -> Ca
                 default_value: None,
                 enum_values: vec![],
                 example: None,
+                encoding: None,
             }],
             request_body: None,
             responses: vec![],
@@ -110,6 +113,7 @@ fn create_test_spec_with_auth(bearer_env_var: &str, api_key_env_var: &str) -> Ca
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: None,
         servers: vec![],
@@ -150,6 +154,7 @@ fn create_global_config_with_secrets(
             strict_mode: false,
             secrets,
             command_mapping: None,
+            response_validation: None,
         },
     );
 
@@ -491,6 +496,7 @@ async fn test_partial_config_override() {
             strict_mode: false,
             secrets,
             command_mapping: None,
+            response_validation: None,
         },
     );
 
@@ -580,6 +586,7 @@ async fn test_no_authentication_configured() {
                 default_value: None,
                 enum_values: vec![],
                 example: None,
+                encoding: None,
             }],
             request_body: None,
             responses: vec![],
@@ -593,6 +600,7 @@ async fn test_no_authentication_configured() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: None,
         servers: vec![],
@@ -36,6 +36,7 @@ fn test_spec(base_url: &str) -> CachedSpec {
                     content_type: constants::CONTENT_TYPE_JSON.into(),
                     schema: r#"{"type": "object"}"#.into(),
                     example: None,
+                    encoding: HashMap::new(),
                 }),
                 responses: vec![],
                 security_requirements: vec![],
@@ -48,6 +49,7 @@ fn test_spec(base_url: &str) -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "users".into(),
@@ -67,6 +69,7 @@ fn test_spec(base_url: &str) -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 }],
                 request_body: None,
                 responses: vec![],
@@ -80,6 +83,7 @@ fn test_spec(base_url: &str) -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "groups".into(),
@@ -99,6 +103,7 @@ fn test_spec(base_url: &str) -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 }],
                 request_body: Some(CachedRequestBody {
                     description: Some("Member data".into()),
@@ -106,6 +111,7 @@ fn test_spec(base_url: &str) -> CachedSpec {
                     content_type: constants::CONTENT_TYPE_JSON.into(),
                     schema: r#"{"type": "object"}"#.into(),
                     example: None,
+                    encoding: HashMap::new(),
                 }),
                 responses: vec![],
                 security_requirements: vec![],
@@ -118,6 +124,7 @@ fn test_spec(base_url: &str) -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         base_url: Some(base_url.into()),
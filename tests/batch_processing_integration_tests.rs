@@ -32,6 +32,7 @@ fn create_test_spec() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 }],
                 request_body: None,
                 responses: vec![],
@@ -45,6 +46,7 @@ fn create_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "users".to_string(),
@@ -62,6 +64,7 @@ fn create_test_spec() -> CachedSpec {
                     example: Some(
                         r#"{"name": "John Doe", "email": "john@example.com"}"#.to_string(),
                     ),
+                    encoding: HashMap::new(),
                 }),
                 responses: vec![],
                 security_requirements: vec![],
@@ -74,6 +77,7 @@ fn create_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         base_url: Some("https://api.example.com".to_string()),
@@ -0,0 +1,237 @@
+mod test_helpers;
+
+use aperture_cli::cli::render::render_result_to_file;
+use aperture_cli::cli::translate::matches_to_operation_call;
+use aperture_cli::cli::OutputFormat;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::engine::generator::generate_command_tree_with_flags;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall, RawBody};
+use std::collections::HashMap;
+use test_helpers::cache_spec;
+use wiremock::matchers::{body_bytes, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const BINARY_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Files API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /files/{name}:
+    put:
+      operationId: putFile
+      tags:
+        - files
+      parameters:
+        - name: name
+          in: path
+          required: true
+          schema:
+            type: string
+      requestBody:
+        required: true
+        content:
+          application/octet-stream:
+            schema:
+              type: string
+              format: binary
+      responses:
+        '204':
+          description: Stored
+  /reports/{id}:
+    get:
+      operationId: getReport
+      tags:
+        - reports
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        '200':
+          description: The report
+          content:
+            application/pdf:
+              schema:
+                type: string
+                format: binary
+";
+
+/// Non-UTF-8 payload, so any text decoding along the way would corrupt it
+const PAYLOAD: &[u8] = &[0x25, 0x50, 0x44, 0x46, 0x00, 0xff, 0xfe, 0x80, 0x0a];
+
+fn call(operation_id: &str, param: (&str, &str), raw_body: Option<RawBody>) -> OperationCall {
+    OperationCall {
+        operation_id: operation_id.to_string(),
+        path_params: HashMap::from([(param.0.to_string(), param.1.to_string())]),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body,
+        custom_headers: vec![],
+    }
+}
+
+#[test]
+fn test_binary_body_takes_body_file_only() {
+    let (spec, temp_dir) = cache_spec("files-api", BINARY_SPEC);
+    let upload = temp_dir.path().join("blob.bin");
+
+    let command = generate_command_tree_with_flags(&spec, false);
+    let matches = command
+        .clone()
+        .try_get_matches_from([
+            "api",
+            "files",
+            "put-file",
+            "--name",
+            "blob.bin",
+            "--body-file",
+            upload.to_str().unwrap(),
+        ])
+        .unwrap();
+    let call = matches_to_operation_call(&spec, &matches).unwrap();
+    assert_eq!(call.raw_body, Some(RawBody::File(upload)));
+    assert_eq!(call.body, None);
+
+    // The body is raw bytes, so there is no JSON --body flag
+    assert!(command
+        .try_get_matches_from(["api", "files", "put-file", "--name", "x", "--body", "{}"])
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_raw_file_is_uploaded_unchanged() {
+    let server = MockServer::start().await;
+    let (spec, temp_dir) = cache_spec("files-api", BINARY_SPEC);
+    let upload = temp_dir.path().join("blob.bin");
+    std::fs::write(&upload, PAYLOAD).unwrap();
+
+    Mock::given(method("PUT"))
+        .and(path("/files/blob.bin"))
+        .and(header("content-type", "application/octet-stream"))
+        .and(header("content-length", "9"))
+        .and(body_bytes(PAYLOAD))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(
+        &spec,
+        call("putFile", ("name", "blob.bin"), Some(RawBody::File(upload))),
+        ctx,
+    )
+    .await
+    .expect("upload should succeed");
+    assert!(matches!(result, ExecutionResult::Empty));
+}
+
+#[tokio::test]
+async fn test_custom_content_type_header_is_kept_for_binary_body() {
+    let server = MockServer::start().await;
+    let (spec, _temp_dir) = cache_spec("files-api", BINARY_SPEC);
+
+    Mock::given(method("PUT"))
+        .and(path("/files/photo.png"))
+        .and(header("content-type", "image/png"))
+        .and(body_bytes(PAYLOAD))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut call = call(
+        "putFile",
+        ("name", "photo.png"),
+        Some(RawBody::Bytes(PAYLOAD.to_vec())),
+    );
+    call.custom_headers = vec!["Content-Type: image/png".to_string()];
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        ..ExecutionContext::default()
+    };
+    execute(&spec, call, ctx)
+        .await
+        .expect("upload should succeed");
+}
+
+#[tokio::test]
+async fn test_binary_response_is_returned_as_bytes_and_saved() {
+    let server = MockServer::start().await;
+    let (spec, temp_dir) = cache_spec("files-api", BINARY_SPEC);
+
+    Mock::given(method("GET"))
+        .and(path("/reports/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(PAYLOAD.to_vec(), "application/pdf"))
+        .mount(&server)
+        .await;
+
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(&spec, call("getReport", ("id", "42"), None), ctx)
+        .await
+        .expect("download should succeed");
+
+    let ExecutionResult::Binary {
+        body,
+        content_type,
+        status,
+        ..
+    } = &result
+    else {
+        panic!("Expected a binary result, got {result:?}");
+    };
+    assert_eq!(body, PAYLOAD);
+    assert_eq!(content_type.as_deref(), Some("application/pdf"));
+    assert_eq!(*status, 200);
+
+    let output = temp_dir.path().join("report.pdf");
+    render_result_to_file(&result, &output, &OutputFormat::Json, None).unwrap();
+    assert_eq!(std::fs::read(&output).unwrap(), PAYLOAD);
+}
+
+#[tokio::test]
+async fn test_dry_run_describes_binary_body_without_contents() {
+    let (spec, temp_dir) = cache_spec("files-api", BINARY_SPEC);
+    let upload = temp_dir.path().join("blob.bin");
+    std::fs::write(&upload, PAYLOAD).unwrap();
+
+    let ctx = ExecutionContext {
+        dry_run: true,
+        base_url: Some("https://example.test".to_string()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(
+        &spec,
+        call("putFile", ("name", "blob.bin"), Some(RawBody::File(upload))),
+        ctx,
+    )
+    .await
+    .unwrap();
+
+    let ExecutionResult::DryRun { request_info } = result else {
+        panic!("Expected DryRun result");
+    };
+    assert_eq!(
+        request_info["body"],
+        serde_json::json!({
+            "content_type": "application/octet-stream",
+            "size": 9,
+            "filename": "blob.bin"
+        })
+    );
+}
@@ -41,6 +41,7 @@ fn create_spec_with_boolean_headers(
             default_value: None,
             enum_values: vec![],
             example: None,
+            encoding: None,
         });
     }
 
@@ -56,6 +57,7 @@ fn create_spec_with_boolean_headers(
             default_value: None,
             enum_values: vec![],
             example: None,
+            encoding: None,
         });
     }
 
@@ -87,6 +89,7 @@ fn create_spec_with_boolean_headers(
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: HashMap::new(),
     }
@@ -294,6 +297,7 @@ async fn test_mixed_boolean_and_string_headers() {
         default_value: None,
         enum_values: vec![],
         example: None,
+        encoding: None,
     });
 
     spec.base_url = Some(mock_server.uri());
@@ -362,6 +366,7 @@ async fn test_kebab_case_boolean_header_conversion() {
                 default_value: None,
                 enum_values: vec![],
                 example: None,
+                encoding: None,
             }],
             request_body: None,
             security_requirements: vec![],
@@ -375,6 +380,7 @@ async fn test_kebab_case_boolean_header_conversion() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: HashMap::new(),
     };
@@ -453,6 +459,7 @@ async fn test_boolean_header_with_query_and_path_params() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "verbose".to_string(),
@@ -465,6 +472,7 @@ async fn test_boolean_header_with_query_and_path_params() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "X-Include-Metadata".to_string(),
@@ -477,6 +485,7 @@ async fn test_boolean_header_with_query_and_path_params() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -491,6 +500,7 @@ async fn test_boolean_header_with_query_and_path_params() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: HashMap::new(),
     };
@@ -573,6 +583,7 @@ async fn test_multiple_boolean_headers() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "X-Verbose".to_string(),
@@ -585,6 +596,7 @@ async fn test_multiple_boolean_headers() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "X-Debug".to_string(),
@@ -597,6 +609,7 @@ async fn test_multiple_boolean_headers() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -611,6 +624,7 @@ async fn test_multiple_boolean_headers() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: HashMap::new(),
     };
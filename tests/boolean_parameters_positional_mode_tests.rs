@@ -50,6 +50,7 @@ fn create_spec_with_mixed_path_params() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "active".to_string(),
@@ -62,6 +63,7 @@ fn create_spec_with_mixed_path_params() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -76,6 +78,7 @@ fn create_spec_with_mixed_path_params() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     }
@@ -110,6 +113,7 @@ fn create_spec_with_boolean_query_param() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "verbose".to_string(),
@@ -122,6 +126,7 @@ fn create_spec_with_boolean_query_param() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -136,6 +141,7 @@ fn create_spec_with_boolean_query_param() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     }
@@ -415,6 +421,7 @@ fn test_multiple_boolean_path_params_in_positional_mode() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "active".to_string(),
@@ -427,6 +434,7 @@ fn test_multiple_boolean_path_params_in_positional_mode() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "verified".to_string(),
@@ -439,6 +447,7 @@ fn test_multiple_boolean_path_params_in_positional_mode() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -453,6 +462,7 @@ fn test_multiple_boolean_path_params_in_positional_mode() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     };
@@ -524,6 +534,7 @@ async fn test_mixed_boolean_flags_url_substitution_positional_mode() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "active".to_string(),
@@ -536,6 +547,7 @@ async fn test_mixed_boolean_flags_url_substitution_positional_mode() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "verified".to_string(),
@@ -548,6 +560,7 @@ async fn test_mixed_boolean_flags_url_substitution_positional_mode() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -562,6 +575,7 @@ async fn test_mixed_boolean_flags_url_substitution_positional_mode() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     };
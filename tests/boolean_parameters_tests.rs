@@ -32,6 +32,7 @@ fn create_test_spec_with_boolean_params() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "verbose".to_string(),
@@ -44,6 +45,7 @@ fn create_test_spec_with_boolean_params() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "limit".to_string(),
@@ -56,6 +58,7 @@ fn create_test_spec_with_boolean_params() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -70,6 +73,7 @@ fn create_test_spec_with_boolean_params() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     }
@@ -104,6 +108,7 @@ fn create_test_spec_with_examples_param() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "page".to_string(),
@@ -116,6 +121,7 @@ fn create_test_spec_with_examples_param() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -130,6 +136,7 @@ fn create_test_spec_with_examples_param() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     }
@@ -335,6 +342,7 @@ fn test_boolean_path_parameters() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "active".to_string(),
@@ -347,6 +355,7 @@ fn test_boolean_path_parameters() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -361,6 +370,7 @@ fn test_boolean_path_parameters() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     };
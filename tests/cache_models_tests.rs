@@ -34,6 +34,7 @@ fn test_cached_spec_serialization_deserialization() {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "create-user".to_string(),
@@ -61,6 +62,7 @@ fn test_cached_spec_serialization_deserialization() {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         base_url: Some("https://api.example.com".to_string()),
@@ -113,6 +115,7 @@ fn test_postcard_roundtrip_with_pagination() {
                 page_param: None,
                 limit_param: None,
             },
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
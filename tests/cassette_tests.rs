@@ -0,0 +1,278 @@
+mod test_helpers;
+
+use aperture_cli::cassette::Cassette;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use test_helpers::cache_spec;
+use wiremock::matchers::{body_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY_ENV: &str = "APERTURE_TEST_CASSETTE_API_KEY";
+const API_KEY: &str = "cassette-secret-456";
+
+const NOTES_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Notes API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+components:
+  securitySchemes:
+    apiKey:
+      type: apiKey
+      in: header
+      name: X-Api-Key
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_CASSETTE_API_KEY
+security:
+  - apiKey: []
+paths:
+  /notes:
+    post:
+      operationId: createNote
+      tags:
+        - notes
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                text:
+                  type: string
+      responses:
+        '201':
+          description: Created
+  /notes/search:
+    get:
+      operationId: searchNotes
+      tags:
+        - notes
+      security: []
+      parameters:
+        - name: q
+          in: query
+          schema:
+            type: string
+        - name: X-Locale
+          in: header
+          schema:
+            type: string
+      responses:
+        '200':
+          description: Matching notes
+  /notes/{noteId}/attachments:
+    post:
+      operationId: attachToNote
+      tags:
+        - notes
+      security: []
+      parameters:
+        - name: noteId
+          in: path
+          required: true
+          schema:
+            type: integer
+      requestBody:
+        required: true
+        content:
+          multipart/form-data:
+            schema:
+              type: object
+              properties:
+                caption:
+                  type: string
+      responses:
+        '201':
+          description: Attached
+";
+
+fn create_note_call(text: &str) -> OperationCall {
+    OperationCall {
+        operation_id: "createNote".to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: Some(json!({ "text": text }).to_string()),
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+fn cassette_files(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_recorded_exchange_replays_without_network_or_secret() {
+    let (spec, temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let cassette_dir = temp_dir.path().join("cassettes");
+    let server = MockServer::start().await;
+    let base_url = server.uri();
+
+    Mock::given(method("POST"))
+        .and(path("/notes"))
+        .and(header("X-Api-Key", API_KEY))
+        .and(body_json(json!({ "text": "hello" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": 1, "text": "hello" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    std::env::set_var(API_KEY_ENV, API_KEY);
+    let ctx = ExecutionContext {
+        base_url: Some(base_url.clone()),
+        cassette: Some(Cassette::record(&cassette_dir)),
+        ..ExecutionContext::default()
+    };
+    let recorded = execute(&spec, create_note_call("hello"), ctx)
+        .await
+        .unwrap();
+    assert!(matches!(
+        recorded,
+        ExecutionResult::Success { status: 201, .. }
+    ));
+
+    let files = cassette_files(&cassette_dir);
+    assert_eq!(files.len(), 1);
+    let content = std::fs::read_to_string(&files[0]).unwrap();
+    assert!(!content.contains(API_KEY), "{content}");
+    let interaction: Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(interaction["request"]["headers"]["x-api-key"], "[REDACTED]");
+    assert_eq!(interaction["response"]["status"], 201);
+
+    // The server is gone and the secret unset: the response comes from the cassette
+    drop(server);
+    std::env::remove_var(API_KEY_ENV);
+    let ctx = ExecutionContext {
+        base_url: Some(base_url),
+        cassette: Some(Cassette::replay(&cassette_dir)),
+        ..ExecutionContext::default()
+    };
+    let replayed = execute(&spec, create_note_call("hello"), ctx)
+        .await
+        .unwrap();
+    let ExecutionResult::Success { status, body, .. } = replayed else {
+        panic!("Expected Success result");
+    };
+    assert_eq!(status, 201);
+    assert_eq!(
+        serde_json::from_str::<Value>(&body).unwrap(),
+        json!({ "id": 1, "text": "hello" })
+    );
+}
+
+#[tokio::test]
+async fn test_replay_fails_for_unrecorded_request() {
+    let (spec, temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let cassette_dir = temp_dir.path().join("cassettes");
+
+    let ctx = ExecutionContext {
+        base_url: Some("https://api.example.com".to_string()),
+        cassette: Some(Cassette::replay(&cassette_dir)),
+        ..ExecutionContext::default()
+    };
+    let error = execute(&spec, create_note_call("never recorded"), ctx)
+        .await
+        .unwrap_err();
+
+    let message = error.to_string();
+    assert!(
+        message.contains("No recorded response")
+            && message.contains("POST https://api.example.com/notes"),
+        "{message}"
+    );
+}
+
+fn search_notes_call(locale: &str, trace: &str) -> OperationCall {
+    let mut call = create_note_call("unused");
+    call.operation_id = "searchNotes".to_string();
+    call.body = None;
+    call.query_params
+        .insert("q".to_string(), "groceries".to_string());
+    call.header_params
+        .insert("X-Locale".to_string(), locale.to_string());
+    call.custom_headers.push(format!("X-Trace: {trace}"));
+    call
+}
+
+#[tokio::test]
+async fn test_replay_ignores_host_and_undeclared_headers() {
+    let (spec, temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let cassette_dir = temp_dir.path().join("cassettes");
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/notes/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        cassette: Some(Cassette::record(&cassette_dir)),
+        ..ExecutionContext::default()
+    };
+    execute(&spec, search_notes_call("en", "first"), ctx)
+        .await
+        .unwrap();
+
+    // Another server and another undeclared header still match
+    let replay = || ExecutionContext {
+        base_url: Some("https://staging.example.com".to_string()),
+        cassette: Some(Cassette::replay(&cassette_dir)),
+        ..ExecutionContext::default()
+    };
+    let replayed = execute(&spec, search_notes_call("en", "second"), replay())
+        .await
+        .unwrap();
+    assert!(matches!(
+        replayed,
+        ExecutionResult::Success { status: 200, .. }
+    ));
+
+    // A declared header parameter is part of the request
+    let error = execute(&spec, search_notes_call("fr", "first"), replay())
+        .await
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("No recorded response"),
+        "{error}"
+    );
+}
+
+#[tokio::test]
+async fn test_streamed_bodies_are_not_recorded() {
+    let (spec, temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let cassette_dir = temp_dir.path().join("cassettes");
+
+    let mut call = create_note_call("unused");
+    call.operation_id = "attachToNote".to_string();
+    call.path_params
+        .insert("noteId".to_string(), "1".to_string());
+    call.body = Some(json!({ "caption": "first" }).to_string());
+    let ctx = ExecutionContext {
+        base_url: Some("https://api.example.com".to_string()),
+        cassette: Some(Cassette::record(&cassette_dir)),
+        ..ExecutionContext::default()
+    };
+    let error = execute(&spec, call, ctx).await.unwrap_err();
+
+    assert!(
+        error.to_string().contains("cannot be recorded or replayed"),
+        "{error}"
+    );
+    assert!(!cassette_dir.exists());
+}
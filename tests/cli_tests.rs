@@ -66,7 +66,7 @@ fn test_config_add_new_spec() {
 fn test_config_add_partial_spec_reports_skipped_endpoint_summary() {
     let config_dir =
         setup_temp_config_dir("test_config_add_partial_spec_reports_skipped_endpoint_summary");
-    let spec_file = PathBuf::from("tests/fixtures/openapi/spec-with-multipart.yaml")
+    let spec_file = PathBuf::from("tests/fixtures/openapi/spec-with-xml.yaml")
         .canonicalize()
         .unwrap();
 
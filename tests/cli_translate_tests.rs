@@ -29,6 +29,7 @@ fn cached_parameter(
         default_value: None,
         enum_values: vec![],
         example: None,
+        encoding: None,
     }
 }
 
@@ -51,6 +52,7 @@ fn build_spec(parameters: Vec<CachedParameter>, with_body: bool) -> CachedSpec {
                 required: false,
                 description: None,
                 example: None,
+                encoding: HashMap::new(),
             }),
             responses: vec![CachedResponse {
                 status_code: "200".to_string(),
@@ -69,6 +71,7 @@ fn build_spec(parameters: Vec<CachedParameter>, with_body: bool) -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
@@ -133,12 +136,21 @@ fn build_matches(include_show_examples: bool) -> clap::ArgMatches {
 fn base_execution_flags() -> ExecutionFlags {
     ExecutionFlags {
         describe_json: false,
+        describe_as: None,
+        max_description_tokens: None,
         dry_run: false,
+        emit: None,
         idempotency_key: None,
+        auth_scheme: None,
+        no_validate: false,
+        validate_response: None,
         proxy: None,
         no_proxy: false,
+        record: None,
+        replay: None,
         format: OutputFormat::Json,
         jq: None,
+        output_file: None,
         batch_file: None,
         batch_concurrency: 5,
         batch_rate_limit: None,
@@ -495,6 +507,7 @@ fn build_spec_with_required_body() -> CachedSpec {
                 required: true,
                 description: None,
                 example: None,
+                encoding: HashMap::new(),
             }),
             responses: vec![],
             security_requirements: vec![],
@@ -507,6 +520,7 @@ fn build_spec_with_required_body() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
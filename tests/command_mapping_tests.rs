@@ -40,6 +40,7 @@ fn make_command(tag: &str, operation_id: &str, method: &str, path: &str) -> Cach
         aliases: vec![],
         hidden: false,
         pagination: PaginationInfo::default(),
+        security_requirement_sets: vec![],
     }
 }
 
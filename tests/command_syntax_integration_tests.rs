@@ -12,6 +12,7 @@ use tempfile::TempDir;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+#[allow(clippy::too_many_lines)]
 fn create_comprehensive_test_spec() -> CachedSpec {
     CachedSpec {
         cache_format_version: aperture_cli::cache::models::CACHE_FORMAT_VERSION,
@@ -37,6 +38,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                         default_value: None,
                         enum_values: vec![],
                         example: None,
+                        encoding: None,
                     },
                     CachedParameter {
                         name: "include_profile".to_string(),
@@ -49,6 +51,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                         default_value: None,
                         enum_values: vec![],
                         example: None,
+                        encoding: None,
                     },
                     CachedParameter {
                         name: "x-request-id".to_string(),
@@ -61,6 +64,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                         default_value: None,
                         enum_values: vec![],
                         example: None,
+                        encoding: None,
                     },
                 ],
                 request_body: None,
@@ -75,6 +79,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "posts".to_string(),
@@ -92,6 +97,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                     example: Some(
                         r#"{"title": "Test Post", "content": "This is a test post"}"#.to_string(),
                     ),
+                    encoding: HashMap::new(),
                 }),
                 responses: vec![],
                 security_requirements: vec![],
@@ -104,6 +110,7 @@ fn create_comprehensive_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         base_url: Some("https://api.example.com".to_string()),
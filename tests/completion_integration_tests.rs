@@ -49,6 +49,7 @@ fn write_completion_fixture() -> TempDir {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "limit".to_string(),
@@ -61,6 +62,7 @@ fn write_completion_fixture() -> TempDir {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -81,6 +83,7 @@ fn write_completion_fixture() -> TempDir {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
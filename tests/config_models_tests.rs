@@ -91,3 +91,19 @@ fn test_aperture_secret_deserialization() {
     assert_eq!(secret.source, SecretSource::Env);
     assert_eq!(secret.name, "MY_API_KEY");
 }
+
+#[test]
+fn test_aperture_secret_source_variants() {
+    for (source, expected) in [
+        ("file", SecretSource::File),
+        ("command", SecretSource::Command),
+        ("dotenv", SecretSource::Dotenv),
+    ] {
+        let yaml_str = format!("source: {source}\nname: ref\n");
+        let secret: ApertureSecret = serde_yaml::from_str(&yaml_str).unwrap();
+        assert_eq!(secret.source, expected);
+        assert_eq!(source.parse::<SecretSource>(), Ok(expected));
+        assert_eq!(expected.to_string(), source);
+    }
+    assert!("keychain".parse::<SecretSource>().is_err());
+}
@@ -155,10 +155,107 @@ fn test_config_set_secret_invalid_arguments() {
         .assert()
         .failure()
         .stderr(predicate::str::contains(
-            "Either provide --scheme and --env, or use --interactive",
+            "Either provide --scheme and --env (or --source and --name), or use --interactive",
         ));
 }
 
+#[test]
+fn test_config_secret_set_with_source() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join(".config").join("aperture");
+
+    let spec_content = r"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+components:
+  securitySchemes:
+    bearerAuth:
+      type: http
+      scheme: bearer
+paths:
+  /test:
+    get:
+      security:
+        - bearerAuth: []
+      responses:
+        '200':
+          description: Success
+servers:
+  - url: https://api.example.com
+";
+    let spec_file = temp_dir.path().join("test-spec.yaml");
+    fs::write(&spec_file, spec_content).unwrap();
+
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .args(["config", "api", "add", "test-api"])
+        .arg(&spec_file)
+        .assert()
+        .success();
+
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .args([
+            "config",
+            "secret",
+            "set",
+            "test-api",
+            "bearerAuth",
+            "--source",
+            "command",
+            "--name",
+            "pass show test-api",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "to use command 'pass show test-api'",
+        ));
+
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .args(["config", "secret", "list", "test-api"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "bearerAuth: command 'pass show test-api'",
+        ));
+
+    // A source needs a reference, and unknown sources are rejected by clap
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .args([
+            "config",
+            "secret",
+            "set",
+            "test-api",
+            "bearerAuth",
+            "--source",
+            "file",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--name"));
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .args([
+            "config",
+            "secret",
+            "set",
+            "test-api",
+            "bearerAuth",
+            "--source",
+            "keychain",
+            "--name",
+            "x",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown secret source 'keychain'"));
+}
+
 #[test]
 fn test_config_remove_secret_success() {
     let temp_dir = TempDir::new().unwrap();
@@ -563,3 +660,63 @@ servers:
             "No secrets configured for API 'test-api'",
         ));
 }
+
+#[test]
+fn test_config_secret_vault_commands() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join(".config").join("aperture");
+    let vault = |args: &[&str]| {
+        let mut cmd = aperture_cmd();
+        cmd.env("APERTURE_CONFIG_DIR", &config_dir)
+            .env("APERTURE_VAULT_PASSPHRASE", "integration-passphrase")
+            .args(["config", "secret", "vault"])
+            .args(args);
+        cmd
+    };
+
+    vault(&["list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No secret vault found"));
+
+    vault(&["init"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created secret vault"));
+    vault(&["init"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    vault(&["put", "github-token"])
+        .write_stdin("ghp_from_stdin\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Stored 'github-token'"));
+    assert!(!fs::read_to_string(config_dir.join("vault.json"))
+        .unwrap()
+        .contains("ghp_from_stdin"));
+
+    vault(&["get", "github-token"])
+        .assert()
+        .success()
+        .stdout("ghp_from_stdin\n");
+    vault(&["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("github-token"));
+
+    aperture_cmd()
+        .env("APERTURE_CONFIG_DIR", &config_dir)
+        .env("APERTURE_VAULT_PASSPHRASE", "wrong-passphrase")
+        .args(["config", "secret", "vault", "get", "github-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("wrong passphrase"));
+
+    vault(&["rm", "github-token"]).assert().success();
+    vault(&["get", "github-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No entry named 'github-token'"));
+}
@@ -210,8 +210,9 @@ paths:
     let cache_dir = _temp_dir.path().join(".cache");
     let cached_spec = load_cached_spec(&cache_dir, "content-test").unwrap();
 
-    // Should have 2 endpoints (standard JSON and the custom+json)
-    assert_eq!(cached_spec.commands.len(), 2);
+    // Should have 7 endpoints (standard JSON, the custom+json, multipart, form
+    // and the three binary bodies)
+    assert_eq!(cached_spec.commands.len(), 7);
 
     let operation_ids: Vec<&str> = cached_spec
         .commands
@@ -220,6 +221,11 @@ paths:
         .collect();
     assert!(operation_ids.contains(&"postJson"));
     assert!(operation_ids.contains(&"postCustom")); // application/vnd.custom+json is now accepted
+    assert!(operation_ids.contains(&"uploadFile")); // multipart/form-data is accepted
+    assert!(operation_ids.contains(&"postForm")); // application/x-www-form-urlencoded is accepted
+    assert!(operation_ids.contains(&"uploadBinary")); // raw binary bodies are accepted
+    assert!(operation_ids.contains(&"uploadImage"));
+    assert!(operation_ids.contains(&"uploadPdf"));
 
     // Try in strict mode - should fail
     let result_strict =
@@ -424,27 +430,26 @@ info:
 servers:
   - url: https://api.example.com
 paths:
-  /file:
+  /csv:
     post:
-      operationId: uploadFile
+      operationId: postCsv
       requestBody:
         content:
-          multipart/form-data:
+          text/csv:
             schema:
-              type: object
+              type: string
         required: true
       responses:
         '200':
           description: Success
-  /image:
+  /notes:
     post:
-      operationId: uploadImage
+      operationId: postNotes
       requestBody:
         content:
-          image/jpeg:
+          text/plain:
             schema:
               type: string
-              format: binary
         required: true
       responses:
         '200':
@@ -461,14 +466,14 @@ paths:
       responses:
         '200':
           description: Success
-  /form:
+  /events:
     post:
-      operationId: submitForm
+      operationId: streamEvents
       requestBody:
         content:
-          application/x-www-form-urlencoded:
+          application/x-ndjson:
             schema:
-              type: object
+              type: string
         required: true
       responses:
         '200':
@@ -498,20 +503,20 @@ paths:
         "Should show correct count of skipped endpoints with available count. Actual stderr: {stderr}"
     );
     assert!(
-        stderr.contains("file uploads are not supported"),
-        "Should show specific message for multipart/form-data"
+        stderr.contains("CSV content is not supported"),
+        "Should show specific message for CSV"
     );
     assert!(
-        stderr.contains("image uploads are not supported"),
-        "Should show specific message for image types"
+        stderr.contains("plain text content is not supported"),
+        "Should show specific message for plain text"
     );
     assert!(
         stderr.contains("XML content is not supported"),
         "Should show specific message for XML"
     );
     assert!(
-        stderr.contains("form-encoded data is not supported"),
-        "Should show specific message for form-urlencoded"
+        stderr.contains("newline-delimited JSON is not supported"),
+        "Should show specific message for NDJSON"
     );
 }
 
@@ -589,13 +594,13 @@ paths:
     let result = config_manager.add_spec(&name("image-test"), &spec_file, false, false);
     assert!(result.is_ok(), "Should accept spec in non-strict mode");
 
-    // All image endpoints should be skipped
+    // All image endpoints are accepted as binary bodies
     let cache_dir = _temp_dir.path().join(".cache");
     let cached_spec = load_cached_spec(&cache_dir, "image-test").unwrap();
     assert_eq!(
         cached_spec.commands.len(),
-        0,
-        "All image endpoints should be skipped"
+        4,
+        "All image endpoints should be accepted"
     );
 }
 
@@ -862,9 +867,9 @@ paths:
       operationId: postNoJson
       requestBody:
         content:
-          multipart/form-data:
+          text/csv:
             schema:
-              type: object
+              type: string
         required: true
       responses:
         '200':
@@ -897,7 +902,10 @@ paths:
     // Check for mixed content warnings - updated to match new format
     assert!(stderr.contains("Endpoints with partial content type support:"));
     assert!(stderr.contains("POST /upload supports JSON but not:"));
-    assert!(stderr.contains("multipart/form-data"));
+    assert!(
+        !stderr.contains("multipart/form-data"),
+        "multipart/form-data bodies are supported"
+    );
     assert!(stderr.contains("application/xml"));
     assert!(stderr.contains("PUT /data supports JSON but not: text/plain"));
 
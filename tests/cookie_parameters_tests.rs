@@ -0,0 +1,157 @@
+mod test_helpers;
+
+use aperture_cli::cli::translate::matches_to_operation_call;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::engine::generator::generate_command_tree_with_flags;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall};
+use std::collections::HashMap;
+use test_helpers::cache_spec;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const SESSION_ENV: &str = "APERTURE_TEST_COOKIE_SESSION";
+
+const COOKIE_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Session API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+components:
+  securitySchemes:
+    sessionCookie:
+      type: apiKey
+      in: cookie
+      name: session
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_COOKIE_SESSION
+paths:
+  /cart:
+    get:
+      operationId: getCart
+      tags:
+        - cart
+      security:
+        - sessionCookie: []
+      parameters:
+        - name: locale
+          in: cookie
+          required: true
+          schema:
+            type: string
+        - name: tracking
+          in: cookie
+          schema:
+            type: boolean
+      responses:
+        '200':
+          description: The cart
+";
+
+fn cart_call(cookie_params: HashMap<String, String>) -> OperationCall {
+    OperationCall {
+        operation_id: "getCart".to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params,
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+#[test]
+fn test_cookie_parameters_become_flags() {
+    let (spec, _temp_dir) = cache_spec("session-api", COOKIE_SPEC);
+
+    let command = generate_command_tree_with_flags(&spec, false);
+    let matches = command
+        .clone()
+        .try_get_matches_from(["api", "cart", "get-cart", "--locale", "en-GB", "--tracking"])
+        .unwrap();
+    let call = matches_to_operation_call(&spec, &matches).unwrap();
+    assert_eq!(
+        call.cookie_params,
+        HashMap::from([
+            ("locale".to_string(), "en-GB".to_string()),
+            ("tracking".to_string(), "true".to_string()),
+        ])
+    );
+    assert!(call.header_params.is_empty());
+
+    // Required cookie parameters are enforced like any other parameter
+    assert!(command
+        .try_get_matches_from(["api", "cart", "get-cart"])
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_cookie_header_combines_parameters_and_api_key() {
+    let server = MockServer::start().await;
+    let (spec, _temp_dir) = cache_spec("session-api", COOKIE_SPEC);
+    std::env::set_var(SESSION_ENV, "s3cr3t/value");
+
+    Mock::given(method("GET"))
+        .and(path("/cart"))
+        .and(header(
+            "cookie",
+            "locale=en%20GB%3B%20x; tracking=true; session=s3cr3t%2Fvalue",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        ..ExecutionContext::default()
+    };
+    let call = cart_call(HashMap::from([
+        ("tracking".to_string(), "true".to_string()),
+        ("locale".to_string(), "en GB; x".to_string()),
+    ]));
+    let result = execute(&spec, call, ctx)
+        .await
+        .expect("request should succeed");
+    assert!(matches!(
+        result,
+        ExecutionResult::Success { status: 200, .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_dry_run_redacts_cookie_header() {
+    let (spec, _temp_dir) = cache_spec("session-api", COOKIE_SPEC);
+    std::env::set_var(SESSION_ENV, "s3cr3t/value");
+
+    let ctx = ExecutionContext {
+        dry_run: true,
+        base_url: Some("https://example.test".to_string()),
+        ..ExecutionContext::default()
+    };
+    let call = cart_call(HashMap::from([("locale".to_string(), "en-GB".to_string())]));
+    let result = execute(&spec, call, ctx).await.unwrap();
+
+    let ExecutionResult::DryRun { request_info } = result else {
+        panic!("Expected DryRun result");
+    };
+    assert_eq!(request_info["headers"]["cookie"], "[REDACTED]");
+    assert!(!request_info.to_string().contains("s3cr3t"));
+}
+
+#[tokio::test]
+async fn test_invalid_cookie_name_is_rejected() {
+    let (spec, _temp_dir) = cache_spec("session-api", COOKIE_SPEC);
+    std::env::set_var(SESSION_ENV, "s3cr3t/value");
+
+    let call = cart_call(HashMap::from([("bad name".to_string(), "x".to_string())]));
+    let err = execute(&spec, call, ExecutionContext::default())
+        .await
+        .expect_err("an invalid cookie name should fail before sending");
+    assert!(err.to_string().contains("bad name"), "{err}");
+}
@@ -0,0 +1,264 @@
+#![cfg(unix)]
+
+mod test_helpers;
+
+use aperture_cli::config::context_name::ApiContextName;
+use aperture_cli::config::manager::ConfigManager;
+use aperture_cli::daemon::{self, Daemon};
+use aperture_cli::fs::OsFileSystem;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn tasks_spec(base_url: &str, extra_operation: bool) -> String {
+    let mut spec = format!(
+        r"
+openapi: 3.0.3
+info:
+  title: Tasks API
+  version: 1.0.0
+servers:
+  - url: {base_url}
+paths:
+  /tasks/{{taskId}}:
+    get:
+      operationId: getTask
+      summary: Fetch a single task
+      tags: [tasks]
+      parameters:
+        - name: taskId
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: The task
+"
+    );
+    if extra_operation {
+        spec.push_str(
+            r"
+  /tasks:
+    delete:
+      operationId: purgeTasks
+      tags: [tasks]
+      responses:
+        '204':
+          description: Purged
+",
+        );
+    }
+    spec
+}
+
+fn add_spec(config_dir: &Path, content: &str) {
+    let spec_path = config_dir.join("tasks-source.yaml");
+    std::fs::write(&spec_path, content).unwrap();
+    ConfigManager::with_fs(OsFileSystem, config_dir.to_path_buf())
+        .add_spec(
+            &ApiContextName::new("tasks").unwrap(),
+            &spec_path,
+            true,
+            true,
+        )
+        .unwrap();
+}
+
+async fn request(daemon: &Daemon, method: &str, params: Value) -> Value {
+    let message = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let response = daemon.handle_message(&message.to_string()).await.unwrap();
+    serde_json::from_str(&response).unwrap()
+}
+
+#[tokio::test]
+async fn test_daemon_executes_like_aperture_api() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tasks/7"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "id": 7, "title": "Write docs" })),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    add_spec(temp_dir.path(), &tasks_spec(&server.uri(), false));
+    let daemon = Daemon::new(temp_dir.path().to_path_buf()).unwrap();
+
+    let response = request(
+        &daemon,
+        "execute",
+        json!({ "args": ["tasks", "tasks", "get-task", "--task-id", "7"] }),
+    )
+    .await;
+    assert_eq!(response["result"]["status"], 200, "{response}");
+    assert_eq!(
+        response["result"]["body"],
+        json!({ "id": 7, "title": "Write docs" })
+    );
+
+    // Execution flags go before the operation path, as on the command line
+    let response = request(
+        &daemon,
+        "execute",
+        json!({ "args": ["tasks", "--jq", ".title", "tasks", "get-task", "--task-id", "7"] }),
+    )
+    .await;
+    assert_eq!(response["result"]["body"], "Write docs", "{response}");
+
+    let response = request(
+        &daemon,
+        "execute",
+        json!({ "args": ["tasks", "--dry-run", "tasks", "get-task", "--task-id", "8"] }),
+    )
+    .await;
+    assert_eq!(response["result"]["dry_run"]["method"], "GET", "{response}");
+}
+
+#[tokio::test]
+async fn test_daemon_reports_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    add_spec(
+        temp_dir.path(),
+        &tasks_spec("https://api.example.com", false),
+    );
+    let daemon = Daemon::new(temp_dir.path().to_path_buf()).unwrap();
+
+    let response = request(
+        &daemon,
+        "execute",
+        json!({ "args": ["tasks", "tasks", "get-task"] }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602, "{response}");
+
+    let response = request(
+        &daemon,
+        "execute",
+        json!({ "args": ["missing", "tasks", "get-task"] }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32000, "{response}");
+    assert!(response["error"]["data"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("missing"));
+
+    let response = request(
+        &daemon,
+        "execute",
+        json!({ "args": ["tasks", "--auto-paginate", "tasks", "get-task", "--task-id", "1"] }),
+    )
+    .await;
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("--auto-paginate"));
+
+    let response = request(&daemon, "shutdown", json!({})).await;
+    assert_eq!(response["error"]["code"], -32601);
+}
+
+#[tokio::test]
+async fn test_daemon_describe_and_search_reload_changed_specs() {
+    let temp_dir = TempDir::new().unwrap();
+    add_spec(
+        temp_dir.path(),
+        &tasks_spec("https://api.example.com", false),
+    );
+    let daemon = Daemon::new(temp_dir.path().to_path_buf()).unwrap();
+
+    let response = request(&daemon, "describe", json!({ "api": "tasks" })).await;
+    assert_eq!(
+        response["result"]["endpoints"]["available"], 1,
+        "{response}"
+    );
+
+    let response = request(
+        &daemon,
+        "describe",
+        json!({ "api": "tasks", "as": "anthropic-tools" }),
+    )
+    .await;
+    assert_eq!(response["result"][0]["name"], "tasks__get-task");
+
+    let response = request(&daemon, "search", json!({ "query": "single task" })).await;
+    let results = response["result"].as_array().unwrap();
+    assert_eq!(results[0]["operation_id"], "getTask");
+    assert_eq!(results[0]["api"], "tasks");
+
+    // Re-adding the spec is picked up without restarting the daemon
+    add_spec(
+        temp_dir.path(),
+        &tasks_spec("https://api.example.com", true),
+    );
+    let response = request(&daemon, "describe", json!({ "api": "tasks" })).await;
+    assert_eq!(
+        response["result"]["endpoints"]["available"], 2,
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn test_daemon_serves_unix_socket() {
+    let temp_dir = TempDir::new().unwrap();
+    add_spec(
+        temp_dir.path(),
+        &tasks_spec("https://api.example.com", false),
+    );
+    let socket = temp_dir.path().join("daemon.sock");
+
+    let listener = daemon::bind(&socket).await.unwrap();
+    let daemon = Arc::new(Daemon::new(temp_dir.path().to_path_buf()).unwrap());
+    tokio::spawn(daemon.run(listener));
+
+    // A second daemon cannot take over a socket that is in use
+    assert!(daemon::bind(&socket).await.is_err());
+
+    let stream = UnixStream::connect(&socket).await.unwrap();
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    for id in 1..=2 {
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": "ping" });
+        writer
+            .write_all(format!("{message}\n").as_bytes())
+            .await
+            .unwrap();
+        let response: Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(
+            response,
+            json!({ "jsonrpc": "2.0", "id": id, "result": {} })
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_daemon_only_replaces_stale_sockets() {
+    let temp_dir = TempDir::new().unwrap();
+
+    // A regular file is never removed
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "keep me").unwrap();
+    let error = daemon::bind(&file).await.unwrap_err();
+    assert!(error.to_string().contains("is not a socket"), "{error}");
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "keep me");
+
+    // Nor is a symlink, even one pointing at a socket
+    let socket = temp_dir.path().join("daemon.sock");
+    drop(daemon::bind(&socket).await.unwrap());
+    let link = temp_dir.path().join("link.sock");
+    std::os::unix::fs::symlink(&socket, &link).unwrap();
+    assert!(daemon::bind(&link).await.is_err());
+    assert!(link.symlink_metadata().is_ok());
+
+    // A socket nobody listens on is replaced
+    assert!(daemon::bind(&socket).await.is_ok());
+}
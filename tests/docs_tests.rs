@@ -41,6 +41,7 @@ fn create_test_spec() -> CachedSpec {
                         default_value: None,
                         enum_values: vec![],
                         example: None,
+                        encoding: None,
                     },
                 ],
                 request_body: None,
@@ -76,6 +77,7 @@ fn create_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "users".to_string(),
@@ -91,6 +93,7 @@ fn create_test_spec() -> CachedSpec {
                     required: true,
                     description: Some("User data".to_string()),
                     example: None,
+                    encoding: HashMap::new(),
                 }),
                 responses: vec![
                     CachedResponse {
@@ -111,6 +114,7 @@ fn create_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
     }
@@ -200,6 +204,7 @@ fn test_request_body_example_includes_all_required_flags() {
         default_value: None,
         enum_values: vec![],
         example: None,
+        encoding: None,
     });
 
     let mut specs = BTreeMap::new();
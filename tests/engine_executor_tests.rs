@@ -23,6 +23,7 @@ macro_rules! cached_parameter {
             default_value: None,
             enum_values: vec![],
             example: None,
+            encoding: None,
         }
     };
 }
@@ -49,6 +50,7 @@ macro_rules! cached_command {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }
     };
 }
@@ -352,6 +354,7 @@ async fn test_execute_request_with_global_config_base_url() {
             strict_mode: false,
             secrets: HashMap::new(),
             command_mapping: None,
+            response_validation: None,
         },
     );
 
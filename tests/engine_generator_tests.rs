@@ -21,6 +21,7 @@ macro_rules! cached_parameter {
             default_value: None,
             enum_values: vec![],
             example: None,
+            encoding: None,
         }
     };
     ($name:expr, $location:expr, $required:expr, $schema:expr) => {
@@ -35,6 +36,7 @@ macro_rules! cached_parameter {
             default_value: None,
             enum_values: vec![],
             example: None,
+            encoding: None,
         }
     };
 }
@@ -59,6 +61,7 @@ macro_rules! cached_request_body {
             required: true,
             description: None,
             example: None,
+            encoding: HashMap::new(),
         }
     };
     ($required:expr) => {
@@ -68,6 +71,7 @@ macro_rules! cached_request_body {
             required: $required,
             description: None,
             example: None,
+            encoding: HashMap::new(),
         }
     };
 }
@@ -94,6 +98,7 @@ macro_rules! cached_command {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }
     };
 }
@@ -31,6 +31,7 @@ fn create_test_cached_spec() -> CachedSpec {
                 default_value: None,
                 enum_values: vec![],
                 example: None,
+                encoding: None,
             }],
             request_body: None,
             responses: vec![CachedResponse {
@@ -50,6 +51,7 @@ fn create_test_cached_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
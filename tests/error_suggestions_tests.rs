@@ -30,6 +30,7 @@ fn create_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "create-user".to_string(),
@@ -51,6 +52,7 @@ fn create_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "list-users".to_string(),
@@ -72,6 +74,7 @@ fn create_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         base_url: Some("https://api.example.com".to_string()),
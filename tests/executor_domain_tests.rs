@@ -33,6 +33,7 @@ fn test_spec() -> CachedSpec {
                 default_value: None,
                 enum_values: vec![],
                 example: None,
+                encoding: None,
             }],
             request_body: None,
             responses: vec![],
@@ -46,6 +47,7 @@ fn test_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
@@ -64,7 +66,11 @@ fn user_by_id_call(id: &str) -> OperationCall {
         path_params,
         query_params: HashMap::new(),
         header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
         body: None,
+        files: vec![],
+        raw_body: None,
         custom_headers: vec![],
     }
 }
@@ -27,6 +27,7 @@ fn create_test_spec() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "include_profile".to_string(),
@@ -39,6 +40,7 @@ fn create_test_spec() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -53,6 +55,7 @@ fn create_test_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
@@ -0,0 +1,138 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::CachedEncoding;
+use aperture_cli::cli::translate::matches_to_operation_call;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::engine::generator::generate_command_tree_with_flags;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult};
+use test_helpers::cache_spec;
+use wiremock::matchers::{body_string, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const FORM_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Token API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /token:
+    post:
+      operationId: issueToken
+      tags:
+        - auth
+      requestBody:
+        required: true
+        content:
+          application/x-www-form-urlencoded:
+            schema:
+              $ref: '#/components/schemas/TokenRequest'
+            encoding:
+              scope:
+                style: spaceDelimited
+              audience:
+                style: form
+                explode: false
+      responses:
+        '200':
+          description: Token issued
+components:
+  schemas:
+    TokenRequest:
+      type: object
+      required:
+        - grant_type
+      properties:
+        grant_type:
+          type: string
+        scope:
+          type: array
+          items:
+            type: string
+        audience:
+          type: array
+          items:
+            type: string
+";
+
+#[test]
+fn test_form_body_is_cached_with_encoding() {
+    let (spec, _temp_dir) = cache_spec("token-api", FORM_SPEC);
+
+    let body = spec.commands[0].request_body.as_ref().unwrap();
+    assert_eq!(body.content_type, "application/x-www-form-urlencoded");
+    assert!(body.schema.contains("grant_type"), "{}", body.schema);
+    assert_eq!(
+        body.encoding.get("scope"),
+        Some(&CachedEncoding {
+            style: "spaceDelimited".to_string(),
+            explode: false,
+        })
+    );
+    assert_eq!(
+        body.encoding.get("audience"),
+        Some(&CachedEncoding {
+            style: "form".to_string(),
+            explode: false,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_form_fields_are_encoded_with_spec_encoding() {
+    let server = MockServer::start().await;
+    let (spec, _temp_dir) = cache_spec("token-api", FORM_SPEC);
+
+    let command = generate_command_tree_with_flags(&spec, false);
+    let matches = command
+        .try_get_matches_from([
+            "api",
+            "auth",
+            "issue-token",
+            "--body",
+            r#"{"scope":["read","write"],"audience":["a","b"]}"#,
+            "--grant-type",
+            "client_credentials",
+        ])
+        .unwrap();
+    let call = matches_to_operation_call(&spec, &matches).unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/token"))
+        .and(header("content-type", "application/x-www-form-urlencoded"))
+        .and(body_string(
+            "audience=a%2Cb&grant_type=client_credentials&scope=read%20write",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"access_token":"t"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(&spec, call, ctx)
+        .await
+        .expect("form request should succeed");
+    assert!(matches!(
+        result,
+        ExecutionResult::Success { status: 200, .. }
+    ));
+}
+
+#[test]
+fn test_form_body_requires_a_field() {
+    let (spec, _temp_dir) = cache_spec("token-api", FORM_SPEC);
+
+    let command = generate_command_tree_with_flags(&spec, false);
+    assert!(command
+        .clone()
+        .try_get_matches_from(["api", "auth", "issue-token"])
+        .is_err());
+    // Form bodies never take file attachments
+    assert!(command
+        .try_get_matches_from(["api", "auth", "issue-token", "--file", "scope=@x.txt"])
+        .is_err());
+}
@@ -0,0 +1,313 @@
+mod test_helpers;
+
+use aperture_cli::gateway::{self, Gateway};
+use aperture_cli::invocation::ExecutionContext;
+use aperture_cli::mock::MockRequest;
+use serde_json::{json, Value};
+use test_helpers::cache_spec;
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY_ENV: &str = "APERTURE_TEST_GATEWAY_API_KEY";
+const API_KEY: &str = "gateway-secret";
+
+fn notes_spec(base_url: &str) -> String {
+    format!(
+        r"
+openapi: 3.0.3
+info:
+  title: Notes API
+  version: 1.0.0
+servers:
+  - url: {base_url}/v1
+components:
+  securitySchemes:
+    apiKey:
+      type: apiKey
+      in: header
+      name: X-Api-Key
+      x-aperture-secret:
+        source: env
+        name: {API_KEY_ENV}
+security:
+  - apiKey: []
+paths:
+  /notes:
+    get:
+      operationId: listNotes
+      tags: [notes]
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+            maximum: 50
+      responses:
+        '200':
+          description: The notes
+    post:
+      operationId: createNote
+      tags: [notes]
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [text]
+              properties:
+                text:
+                  type: string
+      responses:
+        '201':
+          description: Created
+  /notes/{{noteId}}:
+    get:
+      operationId: getNote
+      tags: [notes]
+      parameters:
+        - name: noteId
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: The note
+"
+    )
+}
+
+fn request(method: &str, target: &str, headers: &[(&str, &str)], body: &str) -> MockRequest {
+    MockRequest {
+        method: method.to_string(),
+        target: target.to_string(),
+        headers: headers
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect(),
+        body: body.as_bytes().to_vec(),
+    }
+}
+
+fn json_body(body: &[u8]) -> Value {
+    serde_json::from_slice(body).unwrap()
+}
+
+#[tokio::test]
+async fn test_gateway_forwards_with_configured_credentials() {
+    std::env::set_var(API_KEY_ENV, API_KEY);
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/notes"))
+        .and(query_param("limit", "5"))
+        .and(header("X-Api-Key", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{ "id": 1 }])))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/notes"))
+        .and(header("X-Api-Key", API_KEY))
+        .and(body_json(json!({ "text": "hello" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": 2 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (spec, _temp_dir) = cache_spec("notes", &notes_spec(&server.uri()));
+    let context = ExecutionContext::default();
+
+    // Paths are accepted with or without the base URL's path prefix, and the
+    // client's own credentials are not forwarded
+    for target in ["/notes?limit=5", "/v1/notes?limit=5"] {
+        let response = gateway::handle_request(
+            &spec,
+            &context,
+            &request("GET", target, &[("Authorization", "Bearer client")], ""),
+        )
+        .await;
+        assert_eq!(response.status, 200, "{target}");
+        assert_eq!(json_body(&response.body), json!([{ "id": 1 }]));
+    }
+    let upstream = server.received_requests().await.unwrap();
+    assert!(upstream
+        .iter()
+        .all(|request| !request.headers.contains_key("authorization")));
+
+    let response = gateway::handle_request(
+        &spec,
+        &context,
+        &request(
+            "POST",
+            "/notes",
+            &[("Content-Type", "application/json")],
+            r#"{"text":"hello"}"#,
+        ),
+    )
+    .await;
+    assert_eq!(response.status, 201);
+    assert_eq!(json_body(&response.body), json!({ "id": 2 }));
+}
+
+#[tokio::test]
+async fn test_gateway_rejects_requests_outside_the_spec() {
+    let server = MockServer::start().await;
+    let (spec, _temp_dir) = cache_spec("notes", &notes_spec(&server.uri()));
+    let context = ExecutionContext::default();
+
+    let response =
+        gateway::handle_request(&spec, &context, &request("GET", "/admin/users", &[], "")).await;
+    assert_eq!(response.status, 404);
+
+    let response =
+        gateway::handle_request(&spec, &context, &request("DELETE", "/notes/3", &[], "")).await;
+    assert_eq!(response.status, 405);
+
+    let response = gateway::handle_request(
+        &spec,
+        &context,
+        &request("GET", "/notes?limit=500", &[], ""),
+    )
+    .await;
+    assert_eq!(response.status, 400);
+    assert_eq!(
+        json_body(&response.body)["violations"][0]["path"],
+        "/query/limit"
+    );
+
+    let response =
+        gateway::handle_request(&spec, &context, &request("POST", "/notes", &[], "")).await;
+    assert_eq!(response.status, 400);
+
+    // Nothing outside the spec reaches the upstream API
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_gateway_relays_upstream_errors() {
+    std::env::set_var(API_KEY_ENV, API_KEY);
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/notes/9"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "error": "no such note" })))
+        .mount(&server)
+        .await;
+
+    let (spec, _temp_dir) = cache_spec("notes", &notes_spec(&server.uri()));
+    let response = gateway::handle_request(
+        &spec,
+        &ExecutionContext::default(),
+        &request("GET", "/notes/9", &[], ""),
+    )
+    .await;
+    assert_eq!(response.status, 404);
+    assert_eq!(response.content_type.as_deref(), Some("application/json"));
+    assert_eq!(
+        json_body(&response.body),
+        json!({ "error": "no such note" })
+    );
+}
+
+#[tokio::test]
+async fn test_gateway_serves_http() {
+    std::env::set_var(API_KEY_ENV, API_KEY);
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/notes/4"))
+        .and(header("X-Api-Key", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": 4 })))
+        .mount(&server)
+        .await;
+
+    let (spec, _temp_dir) = cache_spec("notes", &notes_spec(&server.uri()));
+    let gateway = Gateway::bind(spec, ExecutionContext::default(), 0)
+        .await
+        .unwrap();
+    let address = gateway.local_addr().unwrap();
+    tokio::spawn(gateway.run());
+
+    let client = reqwest::Client::builder().no_proxy().build().unwrap();
+    let response = client
+        .get(format!("http://{address}/notes/4"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json::<Value>().await.unwrap(), json!({ "id": 4 }));
+
+    let response = client
+        .get(format!("http://{address}/elsewhere"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_gateway_only_answers_to_its_own_host() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (spec, _temp_dir) = cache_spec("notes", &notes_spec("https://api.example.com/v1"));
+    let gateway = Gateway::bind(spec, ExecutionContext::default(), 0)
+        .await
+        .unwrap();
+    let address = gateway.local_addr().unwrap();
+    tokio::spawn(gateway.run());
+
+    let status_for = |host: String| async move {
+        let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        let request = format!("GET /elsewhere HTTP/1.1\r\nHost: {host}\r\n\r\n");
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response[9..12].to_string()
+    };
+    let port = address.port();
+
+    // A name rebound to 127.0.0.1 is refused before routing
+    assert_eq!(status_for(format!("attacker.example:{port}")).await, "403");
+    assert_eq!(status_for("127.0.0.1".to_string()).await, "403");
+    assert_eq!(status_for(format!("127.0.0.1:{port}")).await, "404");
+    assert_eq!(status_for(format!("localhost:{port}")).await, "404");
+}
+
+#[tokio::test]
+async fn test_gateway_refuses_requests_from_web_pages() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    std::env::set_var(API_KEY_ENV, API_KEY);
+    let server = MockServer::start().await;
+    let (spec, _temp_dir) = cache_spec("notes", &notes_spec(&server.uri()));
+    let gateway = Gateway::bind(spec, ExecutionContext::default(), 0)
+        .await
+        .unwrap();
+    let address = gateway.local_addr().unwrap();
+    tokio::spawn(gateway.run());
+
+    let status_for = |headers: &'static str| async move {
+        let body = r#"{"text":"hello"}"#;
+        let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        let request = format!(
+            "POST /notes HTTP/1.1\r\nHost: {address}\r\n{headers}Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response[9..12].to_string()
+    };
+
+    // A no-cors fetch or a form post from any site carries the right Host
+    assert_eq!(
+        status_for("Content-Type: text/plain\r\nOrigin: https://attacker.example\r\n").await,
+        "403"
+    );
+    assert_eq!(
+        status_for("Content-Type: application/json\r\nSec-Fetch-Site: cross-site\r\n").await,
+        "403"
+    );
+    // Without them, a JSON body is still only read under its own type
+    assert_eq!(status_for("Content-Type: text/plain\r\n").await, "415");
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -126,7 +126,7 @@ fn test_validation_requires_either_direct_or_interactive_mode() {
         .assert()
         .failure()
         .stderr(predicate::str::contains(
-            "Either provide --scheme and --env, or use --interactive",
+            "Either provide --scheme and --env (or --source and --name), or use --interactive",
         ));
 }
 
@@ -17,7 +17,11 @@ fn operation_call_preserves_pre_extracted_parameters() {
         path_params,
         query_params,
         header_params,
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
         body: Some(r#"{"name":"Alice"}"#.to_string()),
+        files: vec![],
+        raw_body: None,
         custom_headers: vec!["X-Custom: value".to_string()],
     };
 
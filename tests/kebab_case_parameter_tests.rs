@@ -34,6 +34,7 @@ fn create_snake_case_spec() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "include_members".to_string(),
@@ -46,6 +47,7 @@ fn create_snake_case_spec() -> CachedSpec {
                     default_value: Some("false".to_string()),
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "X_Custom_Header".to_string(),
@@ -58,6 +60,7 @@ fn create_snake_case_spec() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             responses: vec![],
@@ -72,6 +75,7 @@ fn create_snake_case_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: None,
         servers: vec!["https://api.example.com".to_string()],
@@ -218,6 +222,7 @@ fn test_mixed_case_parameters_normalization() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "IncludeMetaData".to_string(),
@@ -230,6 +235,7 @@ fn test_mixed_case_parameters_normalization() {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             responses: vec![],
@@ -244,6 +250,7 @@ fn test_mixed_case_parameters_normalization() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: None,
         servers: vec!["https://api.example.com".to_string()],
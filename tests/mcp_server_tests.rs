@@ -0,0 +1,345 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::CachedSpec;
+use aperture_cli::invocation::ExecutionContext;
+use aperture_cli::mcp::McpServer;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use test_helpers::cache_spec;
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TASKS_SPEC: &str = r"
+openapi: 3.0.3
+info:
+  title: Tasks API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /projects/{projectId}/tasks:
+    get:
+      operationId: listTasks
+      summary: List the tasks of a project
+      tags: [tasks]
+      parameters:
+        - name: projectId
+          in: path
+          required: true
+          schema:
+            type: integer
+        - name: status
+          in: query
+          description: Only tasks in these states
+          schema:
+            type: array
+            items:
+              type: string
+              enum: [open, done]
+      responses:
+        '200':
+          description: The tasks
+    post:
+      operationId: createTask
+      tags: [tasks]
+      parameters:
+        - name: projectId
+          in: path
+          required: true
+          schema:
+            type: integer
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [title]
+              properties:
+                title:
+                  type: string
+      responses:
+        '201':
+          description: Created
+  /internal/reindex:
+    post:
+      operationId: reindex
+      tags: [internal]
+      responses:
+        '204':
+          description: Done
+";
+
+fn cached_tasks_spec() -> (CachedSpec, TempDir) {
+    let (mut spec, temp_dir) = cache_spec("tasks", TASKS_SPEC);
+    // As if hidden with a command mapping
+    for command in &mut spec.commands {
+        command.hidden = command.operation_id == "reindex";
+    }
+    (spec, temp_dir)
+}
+
+fn mcp_server(base_url: &str) -> (McpServer, TempDir) {
+    let (spec, temp_dir) = cached_tasks_spec();
+    let context = ExecutionContext {
+        base_url: Some(base_url.to_string()),
+        ..ExecutionContext::default()
+    };
+    (McpServer::new(vec![spec], context).unwrap(), temp_dir)
+}
+
+async fn request(server: &McpServer, id: u64, method: &str, params: Value) -> Value {
+    let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+    let response = server.handle_message(&message.to_string()).await.unwrap();
+    serde_json::from_str(&response).unwrap()
+}
+
+#[tokio::test]
+async fn test_mcp_initialize_and_list_tools() {
+    let (server, _temp_dir) = mcp_server("https://api.example.com");
+
+    let response = request(
+        &server,
+        1,
+        "initialize",
+        json!({ "protocolVersion": "2025-03-26", "capabilities": {}, "clientInfo": { "name": "test", "version": "1" } }),
+    )
+    .await;
+    assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+    assert_eq!(response["result"]["serverInfo"]["name"], "aperture");
+    assert!(response["result"]["capabilities"]["tools"].is_object());
+
+    let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    assert!(server
+        .handle_message(&notification.to_string())
+        .await
+        .is_none());
+
+    let response = request(&server, 2, "tools/list", json!({})).await;
+    let tools = response["result"]["tools"].as_array().unwrap();
+    let names: Vec<&str> = tools
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["tasks__listTasks", "tasks__createTask"]);
+
+    let list_tasks = &tools[0];
+    assert_eq!(list_tasks["description"], "List the tasks of a project");
+    assert_eq!(list_tasks["annotations"]["readOnlyHint"], true);
+    assert_eq!(
+        list_tasks["inputSchema"],
+        json!({
+            "type": "object",
+            "properties": {
+                "projectId": { "type": "integer", "description": "path parameter" },
+                "status": {
+                    "type": "array",
+                    "items": { "type": "string", "enum": ["open", "done"] },
+                    "description": "Only tasks in these states (query parameter)"
+                }
+            },
+            "required": ["projectId"]
+        })
+    );
+    assert_eq!(
+        tools[1]["inputSchema"]["properties"]["body"]["required"],
+        json!(["title"])
+    );
+    assert_eq!(
+        tools[1]["inputSchema"]["required"],
+        json!(["projectId", "body"])
+    );
+}
+
+#[tokio::test]
+async fn test_mcp_tool_call_executes_operation() {
+    let api = MockServer::start().await;
+    let (server, _temp_dir) = mcp_server(&api.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/projects/7/tasks"))
+        .and(query_param("status", "open"))
+        .and(query_param("status", "done"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{ "id": 1 }])))
+        .expect(1)
+        .mount(&api)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/projects/7/tasks"))
+        .and(body_json(json!({ "title": "Write docs" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": 2 })))
+        .expect(1)
+        .mount(&api)
+        .await;
+
+    let response = request(
+        &server,
+        1,
+        "tools/call",
+        json!({ "name": "tasks__listTasks", "arguments": { "projectId": 7, "status": ["open", "done"] } }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], false, "{response}");
+    let text = response["result"]["content"][0]["text"].as_str().unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(text).unwrap(),
+        json!([{ "id": 1 }])
+    );
+
+    let response = request(
+        &server,
+        2,
+        "tools/call",
+        json!({ "name": "tasks__createTask", "arguments": { "projectId": 7, "body": { "title": "Write docs" } } }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], false);
+}
+
+#[tokio::test]
+async fn test_mcp_tool_call_errors() {
+    let api = MockServer::start().await;
+    let (server, _temp_dir) = mcp_server(&api.uri());
+
+    // Request validation failures are reported as tool errors
+    let response = request(
+        &server,
+        1,
+        "tools/call",
+        json!({ "name": "tasks__createTask", "arguments": { "projectId": 7, "body": {} } }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], true);
+    let text = response["result"]["content"][0]["text"].as_str().unwrap();
+    assert!(text.contains("title"), "{text}");
+
+    // Hidden operations are not published
+    let response = request(
+        &server,
+        2,
+        "tools/call",
+        json!({ "name": "tasks__reindex", "arguments": {} }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+
+    let response = request(&server, 3, "resources/list", json!({})).await;
+    assert_eq!(response["error"]["code"], -32601);
+
+    let response = server.handle_message("{not json").await.unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["error"]["code"], -32700);
+}
+
+#[tokio::test]
+async fn test_mcp_serve_reads_and_writes_lines() {
+    let (server, _temp_dir) = mcp_server("https://api.example.com");
+    let input = [
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }).to_string(),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }).to_string(),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }).to_string(),
+    ]
+    .join("\n");
+
+    let mut output = Vec::new();
+    server.serve(input.as_bytes(), &mut output).await.unwrap();
+
+    let responses: Vec<Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(
+        responses[0],
+        json!({ "jsonrpc": "2.0", "id": 1, "result": {} })
+    );
+    assert_eq!(responses[1]["id"], 2);
+}
+
+const PETS_SPEC: &str = r"
+openapi: 3.0.3
+info:
+  title: Pets API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /pets/{petId}:
+    get:
+      operationId: get_pet
+      tags: [pets]
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: The pet
+    delete:
+      operationId: get.pet
+      tags: [pets]
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '204':
+          description: Deleted
+  /pets/{petId}/vaccinations:
+    get:
+      operationId: listVaccinationRecordsForPetIncludingBoostersAndExpiredOnes
+      tags: [pets]
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: The vaccinations
+";
+
+#[tokio::test]
+async fn test_mcp_tool_names_are_short_and_unique() {
+    let api = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/pets/3"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&api)
+        .await;
+    let (spec, _temp_dir) = cache_spec("pets", PETS_SPEC);
+    let context = ExecutionContext {
+        base_url: Some(api.uri()),
+        ..ExecutionContext::default()
+    };
+    let server = McpServer::new(vec![spec], context).unwrap();
+
+    let names = server.tool_names();
+    assert_eq!(names[0], "pets__get_pet");
+    // `get.pet` sanitizes to the name `get_pet` already has
+    assert!(names[1].starts_with("pets__get_pet_"), "{names:?}");
+    assert_eq!(names[1].len(), "pets__get_pet_".len() + 8);
+    assert!(
+        names[2].starts_with("pets__listVaccinationRecords"),
+        "{names:?}"
+    );
+    assert_eq!(names[2].len(), 64);
+    assert!(names.iter().all(|name| name.len() <= 64));
+
+    // The second tool runs its own operation
+    let response = request(
+        &server,
+        1,
+        "tools/call",
+        json!({ "name": names[1], "arguments": { "petId": 3 } }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], false, "{response}");
+}
@@ -186,9 +186,8 @@ components:
     oauth2Auth:
       type: oauth2
       flows:
-        authorizationCode:
+        implicit:
           authorizationUrl: https://example.com/auth
-          tokenUrl: https://example.com/token
           scopes:
             read: Read access
     apiKey:
@@ -307,9 +306,8 @@ components:
     oauth2Auth:
       type: oauth2
       flows:
-        authorizationCode:
+        implicit:
           authorizationUrl: https://example.com/auth
-          tokenUrl: https://example.com/token
           scopes:
             read: Read access
 paths:
@@ -363,9 +361,8 @@ components:
     oauth2Auth:
       type: oauth2
       flows:
-        authorizationCode:
+        implicit:
           authorizationUrl: https://example.com/auth
-          tokenUrl: https://example.com/token
           scopes:
             read: Read access
 security:
@@ -508,7 +505,7 @@ components:
     oauth2Auth:
       type: oauth2
       flows:
-        clientCredentials:
+        password:
           tokenUrl: https://example.com/token
           scopes:
             read: Read access
@@ -676,10 +673,10 @@ paths:
 }
 
 #[test]
-fn test_openid_connect_scheme() {
+fn test_openid_connect_scheme_with_failed_discovery() {
     let (manager, fs) = setup_manager();
 
-    // Create a spec with OpenID Connect
+    // Create a spec with OpenID Connect whose discovery URL is unreachable
     let spec_content = r"
 openapi: 3.0.0
 info:
@@ -691,7 +688,7 @@ components:
   securitySchemes:
     oidc:
       type: openIdConnect
-      openIdConnectUrl: https://example.com/.well-known/openid-configuration
+      openIdConnectUrl: http://127.0.0.1:1/.well-known/openid-configuration
 paths:
   /profile:
     get:
@@ -736,4 +733,5 @@ paths:
     let skipped = &cached_spec.skipped_endpoints[0];
     assert!(skipped.reason.contains("unsupported authentication"));
     assert!(skipped.reason.contains("oidc"));
+    assert!(skipped.reason.contains("OpenID Connect"));
 }
@@ -0,0 +1,275 @@
+mod test_helpers;
+
+use aperture_cli::mock::MockServer;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use test_helpers::cache_spec;
+
+const PETS_SPEC: &str = r"
+openapi: 3.0.3
+info:
+  title: Pets API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com/v1
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags: [pets]
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+            maximum: 100
+        - name: status
+          in: query
+          schema:
+            type: array
+            items:
+              type: string
+              enum: [available, sold]
+      responses:
+        '200':
+          description: The pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/Pet'
+    post:
+      operationId: createPet
+      tags: [pets]
+      parameters:
+        - name: X-Request-Id
+          in: header
+          required: true
+          schema:
+            type: string
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+      responses:
+        '201':
+          description: Created
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+              example:
+                id: 7
+                name: Rex
+        '400':
+          description: Invalid pet
+  /pets/{petId}:
+    get:
+      operationId: getPet
+      tags: [pets]
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: The pet
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+    delete:
+      operationId: deletePet
+      tags: [pets]
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '204':
+          description: Deleted
+  /pets/mine:
+    get:
+      operationId: listMyPets
+      tags: [pets]
+      responses:
+        '200':
+          description: My pets
+          content:
+            application/json:
+              example: [{id: 1, name: Mine}]
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        id:
+          type: integer
+          minimum: 1
+        name:
+          type: string
+        born:
+          type: string
+          format: date
+";
+
+/// Starts a mock server on a free port and returns its base URL
+async fn start_mock_server() -> (String, TempDir) {
+    let (spec, temp_dir) = cache_spec("pets", PETS_SPEC);
+    let server = MockServer::bind(spec, 0).await.unwrap();
+    let base_url = format!("http://{}", server.local_addr().unwrap());
+    tokio::spawn(server.run());
+    (base_url, temp_dir)
+}
+
+async fn json_body(response: reqwest::Response) -> Value {
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_mock_server_serves_examples_and_synthesized_bodies() {
+    let (base_url, _temp_dir) = start_mock_server().await;
+    let client = reqwest::Client::new();
+
+    let created = client
+        .post(format!("{base_url}/pets"))
+        .header("X-Request-Id", "abc")
+        .json(&json!({"name": "Rex"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(created.status(), 201);
+    assert_eq!(json_body(created).await, json!({"id": 7, "name": "Rex"}));
+
+    let pet = client
+        .get(format!("{base_url}/pets/42"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(pet.status(), 200);
+    assert_eq!(
+        json_body(pet).await,
+        json!({"id": 1, "name": "string", "born": "2024-01-01"})
+    );
+
+    // A literal segment wins over a template, and the server base path is optional
+    let mine = client
+        .get(format!("{base_url}/v1/pets/mine"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(mine.status(), 200);
+    assert_eq!(json_body(mine).await, json!([{"id": 1, "name": "Mine"}]));
+
+    let deleted = client
+        .delete(format!("{base_url}/pets/42"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(deleted.status(), 204);
+    assert!(deleted.bytes().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_server_rejects_invalid_requests() {
+    let (base_url, _temp_dir) = start_mock_server().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{base_url}/pets?limit=500&status=available,lost"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let body = json_body(response).await;
+    let paths: Vec<&str> = body["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|violation| violation["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["/query/limit", "/query/status/1"]);
+
+    let response = client
+        .post(format!("{base_url}/pets"))
+        .json(&json!({"id": 0}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let body = json_body(response).await;
+    assert_eq!(
+        body["violations"],
+        json!([
+            {"path": "/header/X-Request-Id", "message": "is required"},
+            {"path": "/body", "message": "missing required property \"name\""},
+            {"path": "/body/id", "message": "must be at least 1"}
+        ])
+    );
+
+    let response = client
+        .get(format!("{base_url}/pets/abc"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    assert_eq!(
+        json_body(response).await["violations"][0]["path"],
+        "/path/petId"
+    );
+}
+
+#[tokio::test]
+async fn test_mock_server_reports_unknown_routes() {
+    let (base_url, _temp_dir) = start_mock_server().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{base_url}/owners"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    assert_eq!(
+        json_body(response).await["message"],
+        "No operation matches GET /owners"
+    );
+
+    let response = client
+        .put(format!("{base_url}/pets/1"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 405);
+}
+
+#[tokio::test]
+async fn test_mock_server_rejects_chunked_bodies() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (base_url, _temp_dir) = start_mock_server().await;
+    let mut stream = tokio::net::TcpStream::connect(base_url.trim_start_matches("http://"))
+        .await
+        .unwrap();
+    stream
+        .write_all(
+            b"POST /pets HTTP/1.1\r\nHost: 127.0.0.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+              e\r\n{\"name\":\"Rex\"}\r\n0\r\n\r\n",
+        )
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(
+        response.starts_with("HTTP/1.1 411 Length Required"),
+        "{response}"
+    );
+}
@@ -0,0 +1,234 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::CachedSpec;
+use aperture_cli::config::context_name::ApiContextName;
+use aperture_cli::config::manager::ConfigManager;
+use aperture_cli::engine::loader::load_cached_spec;
+use aperture_cli::fs::OsFileSystem;
+use serde_json::{json, Value};
+use std::path::Path;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ROOT_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Directory API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /users:
+    post:
+      operationId: createUser
+      tags:
+        - users
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: './schemas/user.yaml#/User'
+      responses:
+        '201':
+          description: Created
+  /teams:
+    $ref: './paths/teams.yaml'
+";
+
+const USER_SCHEMAS: &str = r"
+User:
+  type: object
+  required: [name]
+  properties:
+    name:
+      type: string
+    role:
+      $ref: '#/Role'
+    address:
+      $ref: '../common.yaml#/Address'
+Role:
+  type: string
+  enum: [admin, member]
+";
+
+const COMMON_SCHEMAS: &str = r"
+Address:
+  type: object
+  properties:
+    city:
+      type: string
+";
+
+const TEAMS_PATH: &str = r"
+get:
+  operationId: listTeams
+  tags:
+    - teams
+  responses:
+    '200':
+      description: The team tree
+      content:
+        application/json:
+          schema:
+            $ref: '../schemas/team.yaml'
+";
+
+const TEAM_SCHEMA: &str = r"
+type: object
+properties:
+  name:
+    type: string
+  subteams:
+    type: array
+    items:
+      $ref: '#'
+";
+
+fn write_spec_files(dir: &Path) -> std::path::PathBuf {
+    let files = [
+        ("api/openapi.yaml", ROOT_SPEC),
+        ("api/schemas/user.yaml", USER_SCHEMAS),
+        ("api/schemas/team.yaml", TEAM_SCHEMA),
+        ("api/paths/teams.yaml", TEAMS_PATH),
+        ("api/common.yaml", COMMON_SCHEMAS),
+    ];
+    for (file, content) in files {
+        let file = dir.join(file);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, content).unwrap();
+    }
+    dir.join("api/openapi.yaml")
+}
+
+fn load(dir: &Path, name: &str) -> CachedSpec {
+    load_cached_spec(dir.join(".cache"), name).unwrap()
+}
+
+fn schema(json: Option<&str>) -> Value {
+    serde_json::from_str(json.unwrap()).unwrap()
+}
+
+fn assert_bundled(spec: &CachedSpec) {
+    let create_user = spec
+        .commands
+        .iter()
+        .find(|command| command.operation_id == "createUser")
+        .unwrap();
+    let body = create_user.request_body.as_ref().unwrap();
+    assert_eq!(
+        schema(Some(&body.schema)),
+        json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {"type": "string"},
+                "role": {"type": "string", "enum": ["admin", "member"]},
+                "address": {"type": "object", "properties": {"city": {"type": "string"}}}
+            }
+        })
+    );
+
+    // The self-referencing team schema is hoisted into the root's components
+    let list_teams = spec
+        .commands
+        .iter()
+        .find(|command| command.operation_id == "listTeams")
+        .unwrap();
+    let team = schema(list_teams.responses[0].schema.as_deref());
+    assert_eq!(team["properties"]["name"], json!({"type": "string"}));
+    assert_eq!(
+        team["properties"]["subteams"]["items"],
+        json!({"$ref": "#/components/schemas/team"})
+    );
+}
+
+#[test]
+fn test_relative_file_references_are_bundled() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = write_spec_files(temp_dir.path());
+    let manager = ConfigManager::with_fs(OsFileSystem, temp_dir.path().to_path_buf());
+    let name = ApiContextName::new("directory-api").unwrap();
+
+    manager.add_spec(&name, &root, false, true).unwrap();
+    assert_bundled(&load(temp_dir.path(), "directory-api"));
+
+    // The stored spec is self-contained, so reinitializing needs no other files
+    let stored = temp_dir.path().join("specs/directory-api.yaml");
+    let stored_content = std::fs::read_to_string(&stored).unwrap();
+    assert!(!stored_content.contains(".yaml#"), "{stored_content}");
+    std::fs::remove_dir_all(temp_dir.path().join("api")).unwrap();
+
+    manager.add_spec(&name, &stored, true, true).unwrap();
+    assert_bundled(&load(temp_dir.path(), "directory-api"));
+}
+
+#[test]
+fn test_missing_referenced_file_is_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = write_spec_files(temp_dir.path());
+    std::fs::remove_file(temp_dir.path().join("api/common.yaml")).unwrap();
+    let manager = ConfigManager::with_fs(OsFileSystem, temp_dir.path().to_path_buf());
+
+    let err = manager
+        .add_spec(
+            &ApiContextName::new("directory-api").unwrap(),
+            &root,
+            false,
+            true,
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("common.yaml"), "{err}");
+}
+
+#[test]
+fn test_missing_fragment_is_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = write_spec_files(temp_dir.path());
+    std::fs::write(temp_dir.path().join("api/common.yaml"), "Location: {}\n").unwrap();
+    let manager = ConfigManager::with_fs(OsFileSystem, temp_dir.path().to_path_buf());
+
+    let err = manager
+        .add_spec(
+            &ApiContextName::new("directory-api").unwrap(),
+            &root,
+            false,
+            true,
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("'#/Address'"), "{err}");
+}
+
+#[tokio::test]
+async fn test_relative_url_references_are_bundled() {
+    let server = MockServer::start().await;
+    let documents = [
+        ("/api/openapi.yaml", ROOT_SPEC),
+        ("/api/schemas/user.yaml", USER_SCHEMAS),
+        ("/api/schemas/team.yaml", TEAM_SCHEMA),
+        ("/api/paths/teams.yaml", TEAMS_PATH),
+        ("/api/common.yaml", COMMON_SCHEMAS),
+    ];
+    for (document, content) in documents {
+        Mock::given(method("GET"))
+            .and(path(document))
+            .respond_with(ResponseTemplate::new(200).set_body_string(content))
+            .mount(&server)
+            .await;
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let manager = ConfigManager::with_fs(OsFileSystem, temp_dir.path().to_path_buf());
+    manager
+        .add_spec_from_url(
+            &ApiContextName::new("directory-api").unwrap(),
+            &format!("{}/api/openapi.yaml", server.uri()),
+            false,
+            true,
+        )
+        .await
+        .unwrap();
+
+    assert_bundled(&load(temp_dir.path(), "directory-api"));
+}
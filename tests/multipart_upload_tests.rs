@@ -0,0 +1,225 @@
+mod test_helpers;
+
+use aperture_cli::cli::translate::matches_to_operation_call;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::engine::generator::generate_command_tree_with_flags;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, FilePart, OperationCall};
+use std::collections::HashMap;
+use std::path::Path;
+use test_helpers::cache_spec;
+use wiremock::matchers::{body_string_contains, header, header_regex, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const UPLOAD_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Upload API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /documents:
+    post:
+      operationId: uploadDocument
+      tags:
+        - documents
+      requestBody:
+        required: true
+        content:
+          multipart/form-data:
+            schema:
+              $ref: '#/components/schemas/DocumentUpload'
+      responses:
+        '201':
+          description: Document uploaded
+components:
+  schemas:
+    DocumentUpload:
+      type: object
+      required:
+        - file
+      properties:
+        file:
+          type: string
+          format: binary
+        title:
+          type: string
+          description: Document title
+        tags:
+          type: array
+          items:
+            type: string
+        quiet:
+          type: boolean
+          description: Skip the upload notification
+";
+
+fn upload_call(dir: &Path) -> OperationCall {
+    let file_path = dir.join("report.txt");
+    std::fs::write(&file_path, "quarterly numbers").unwrap();
+
+    OperationCall {
+        operation_id: "uploadDocument".to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: Some(r#"{"title":"Q3 report","tags":["finance","q3"]}"#.to_string()),
+        files: vec![FilePart {
+            field: "file".to_string(),
+            path: file_path,
+        }],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+#[test]
+fn test_multipart_body_is_cached_with_resolved_schema() {
+    let (spec, _temp_dir) = cache_spec("upload-api", UPLOAD_SPEC);
+
+    let body = spec.commands[0].request_body.as_ref().unwrap();
+    assert_eq!(body.content_type, "multipart/form-data");
+    assert!(body.required);
+    assert!(body.schema.contains("\"binary\""), "{}", body.schema);
+}
+
+#[test]
+fn test_form_field_flags_and_file_args_are_translated() {
+    let (spec, temp_dir) = cache_spec("upload-api", UPLOAD_SPEC);
+    let file_path = temp_dir.path().join("report.txt");
+
+    let command = generate_command_tree_with_flags(&spec, false);
+    let matches = command
+        .clone()
+        .try_get_matches_from([
+            "api",
+            "documents",
+            "upload-document",
+            "--body",
+            r#"{"title":"draft","tags":["q3"]}"#,
+            "--title",
+            "Q3 report",
+            "--file",
+            &format!("file=@{}", file_path.display()),
+        ])
+        .unwrap();
+
+    let call = matches_to_operation_call(&spec, &matches).unwrap();
+    let body: serde_json::Value = serde_json::from_str(call.body.as_deref().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({"title": "Q3 report", "tags": ["q3"]})
+    );
+    assert_eq!(
+        call.files,
+        vec![FilePart {
+            field: "file".to_string(),
+            path: file_path,
+        }]
+    );
+
+    // A field named after a global flag gets no flag of its own
+    let upload = command
+        .find_subcommand("documents")
+        .and_then(|documents| documents.find_subcommand("upload-document"))
+        .unwrap();
+    assert!(upload
+        .get_arguments()
+        .all(|arg| arg.get_long() != Some("quiet")));
+
+    // A required body needs at least one field or file
+    assert!(command
+        .try_get_matches_from(["api", "documents", "upload-document"])
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_multipart_request_streams_fields_and_files() {
+    let server = MockServer::start().await;
+    let (spec, temp_dir) = cache_spec("upload-api", UPLOAD_SPEC);
+
+    Mock::given(method("POST"))
+        .and(path("/documents"))
+        .and(header_regex(
+            "content-type",
+            "^multipart/form-data; boundary=aperture-[0-9a-f]{32}$",
+        ))
+        .and(header("content-length", "508"))
+        .and(body_string_contains(
+            "Content-Disposition: form-data; name=\"title\"\r\n\r\nQ3 report\r\n",
+        ))
+        .and(body_string_contains(
+            "Content-Disposition: form-data; name=\"tags\"\r\n\r\nfinance\r\n",
+        ))
+        .and(body_string_contains(
+            "Content-Disposition: form-data; name=\"file\"; filename=\"report.txt\"\r\nContent-Type: text/plain\r\n\r\nquarterly numbers\r\n",
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_string(r#"{"id":"doc-1"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(&spec, upload_call(temp_dir.path()), ctx)
+        .await
+        .expect("upload should succeed");
+    assert!(matches!(
+        result,
+        ExecutionResult::Success { status: 201, .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_dry_run_describes_parts_without_contents() {
+    let (spec, temp_dir) = cache_spec("upload-api", UPLOAD_SPEC);
+
+    let ctx = ExecutionContext {
+        dry_run: true,
+        base_url: Some("https://example.test".to_string()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(&spec, upload_call(temp_dir.path()), ctx)
+        .await
+        .expect("dry run should succeed");
+
+    let ExecutionResult::DryRun { request_info } = result else {
+        panic!("Expected DryRun result");
+    };
+    assert!(request_info["headers"]["content-type"]
+        .as_str()
+        .unwrap()
+        .starts_with("multipart/form-data; boundary="));
+    assert_eq!(
+        request_info["body"],
+        serde_json::json!({
+            "content_type": "multipart/form-data",
+            "parts": [
+                {"name": "tags", "content_type": "text/plain", "size": 7},
+                {"name": "tags", "content_type": "text/plain", "size": 2},
+                {"name": "title", "content_type": "text/plain", "size": 9},
+                {"name": "file", "filename": "report.txt", "content_type": "text/plain", "size": 17}
+            ]
+        })
+    );
+    let rendered = request_info.to_string();
+    assert!(!rendered.contains("quarterly numbers"));
+    assert!(!rendered.contains("Q3 report"));
+}
+
+#[tokio::test]
+async fn test_missing_upload_file_is_reported() {
+    let (spec, temp_dir) = cache_spec("upload-api", UPLOAD_SPEC);
+    let mut call = upload_call(temp_dir.path());
+    call.files[0].path = temp_dir.path().join("missing.pdf");
+
+    let err = execute(&spec, call, ExecutionContext::default())
+        .await
+        .expect_err("a missing file should fail before sending");
+    let message = err.to_string();
+    assert!(message.contains("missing.pdf"), "{message}");
+}
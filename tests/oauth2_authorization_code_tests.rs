@@ -0,0 +1,445 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::{
+    CachedApertureSecret, CachedCommand, CachedOAuth2Config, CachedSecurityScheme, CachedSpec,
+    PaginationInfo, CACHE_FORMAT_VERSION,
+};
+use aperture_cli::engine::executor::execute;
+use aperture_cli::error::{Error, ErrorKind};
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall};
+use aperture_cli::oauth::{ClientCredentials, PendingAuthorization, StoredToken, TokenStore};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Duration;
+use tempfile::tempdir;
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Builds a security scheme that only supports the authorization-code flow
+fn authorization_code_scheme(server_uri: &str, env_var: &str) -> CachedSecurityScheme {
+    CachedSecurityScheme {
+        name: "userAuth".to_string(),
+        scheme_type: "oauth2".to_string(),
+        scheme: Some("bearer".to_string()),
+        location: Some("header".to_string()),
+        parameter_name: Some("Authorization".to_string()),
+        description: None,
+        bearer_format: None,
+        aperture_secret: Some(CachedApertureSecret {
+            source: "env".to_string(),
+            name: env_var.to_string(),
+        }),
+        oauth2: Some(CachedOAuth2Config {
+            token_url: format!("{server_uri}/oauth/token"),
+            scopes: vec!["profile".to_string()],
+            grant_types: vec!["authorization_code".to_string()],
+            authorization_url: Some(format!("{server_uri}/oauth/authorize")),
+        }),
+    }
+}
+
+fn spec_with_scheme(scheme: CachedSecurityScheme) -> CachedSpec {
+    let mut security_schemes = HashMap::new();
+    security_schemes.insert(scheme.name.clone(), scheme);
+
+    CachedSpec {
+        cache_format_version: CACHE_FORMAT_VERSION,
+        name: "user-api".to_string(),
+        version: "1.0.0".to_string(),
+        commands: vec![CachedCommand {
+            name: "me".to_string(),
+            description: None,
+            summary: None,
+            operation_id: "getMe".to_string(),
+            method: "GET".to_string(),
+            path: "/me".to_string(),
+            parameters: vec![],
+            request_body: None,
+            responses: vec![],
+            security_requirements: vec!["userAuth".to_string()],
+            tags: vec!["me".to_string()],
+            deprecated: false,
+            external_docs_url: None,
+            examples: vec![],
+            display_group: None,
+            display_name: None,
+            aliases: vec![],
+            hidden: false,
+            pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
+        }],
+        base_url: None,
+        servers: vec![],
+        security_schemes,
+        skipped_endpoints: vec![],
+        server_variables: HashMap::new(),
+    }
+}
+
+fn get_me_call() -> OperationCall {
+    OperationCall {
+        operation_id: "getMe".to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+fn query_param(url: &str, name: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .unwrap()
+        .query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+fn public_client(scheme: &CachedSecurityScheme) -> ClientCredentials {
+    ClientCredentials::for_scheme(scheme, "UNUSED", "cli-client").unwrap()
+}
+
+/// Plays the browser: follows the redirect back to the loopback listener
+async fn deliver_redirect(redirect_uri: &str, query: &str) -> reqwest::StatusCode {
+    reqwest::get(format!("{redirect_uri}?{query}"))
+        .await
+        .expect("loopback listener should answer")
+        .status()
+}
+
+#[tokio::test]
+async fn test_login_exchanges_code_with_pkce_verifier() {
+    let server = MockServer::start().await;
+    let scheme = authorization_code_scheme(&server.uri(), "UNUSED");
+    let credentials = public_client(&scheme);
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("grant_type=authorization_code"))
+        .and(body_string_contains("code=auth-code-123"))
+        .and(body_string_contains("client_id=cli-client"))
+        .and(body_string_contains("code_verifier="))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "user-access",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+            "refresh_token": "user-refresh"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let pending = PendingAuthorization::start(&scheme, &credentials, 0)
+        .await
+        .unwrap();
+    let authorization_url = pending.authorization_url().to_string();
+    let redirect_uri = pending.redirect_uri().to_string();
+
+    assert!(authorization_url.starts_with(&format!("{}/oauth/authorize?", server.uri())));
+    assert!(redirect_uri.starts_with("http://127.0.0.1:"));
+    assert_eq!(
+        query_param(&authorization_url, "response_type").as_deref(),
+        Some("code")
+    );
+    assert_eq!(
+        query_param(&authorization_url, "client_id").as_deref(),
+        Some("cli-client")
+    );
+    assert_eq!(
+        query_param(&authorization_url, "redirect_uri").as_deref(),
+        Some(redirect_uri.as_str())
+    );
+    assert_eq!(
+        query_param(&authorization_url, "code_challenge_method").as_deref(),
+        Some("S256")
+    );
+    assert_eq!(
+        query_param(&authorization_url, "scope").as_deref(),
+        Some("profile")
+    );
+
+    let state = query_param(&authorization_url, "state").unwrap();
+    let browser = tokio::spawn(async move {
+        deliver_redirect(&redirect_uri, &format!("code=auth-code-123&state={state}")).await
+    });
+
+    let client = reqwest::Client::new();
+    let token = pending
+        .complete(&client, &credentials, Duration::from_secs(10))
+        .await
+        .expect("login should succeed");
+    assert_eq!(browser.await.unwrap(), reqwest::StatusCode::OK);
+
+    assert_eq!(token.access_token, "user-access");
+    assert_eq!(token.refresh_token.as_deref(), Some("user-refresh"));
+    assert_eq!(token.client_id, "cli-client");
+
+    // The verifier sent to the token endpoint must match the advertised challenge
+    let requests = server.received_requests().await.unwrap();
+    let body = String::from_utf8(requests[0].body.clone()).unwrap();
+    let verifier = body
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("code_verifier="))
+        .unwrap();
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    assert_eq!(
+        query_param(&authorization_url, "code_challenge").as_deref(),
+        Some(challenge.as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_login_rejects_state_mismatch() {
+    let server = MockServer::start().await;
+    let scheme = authorization_code_scheme(&server.uri(), "UNUSED");
+    let credentials = public_client(&scheme);
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let pending = PendingAuthorization::start(&scheme, &credentials, 0)
+        .await
+        .unwrap();
+    let redirect_uri = pending.redirect_uri().to_string();
+    let browser =
+        tokio::spawn(
+            async move { deliver_redirect(&redirect_uri, "code=stolen&state=forged").await },
+        );
+
+    let err = pending
+        .complete(
+            &reqwest::Client::new(),
+            &credentials,
+            Duration::from_secs(10),
+        )
+        .await
+        .expect_err("forged state must be rejected");
+    browser.await.unwrap();
+
+    assert!(err.to_string().contains("state"), "{err}");
+}
+
+#[tokio::test]
+async fn test_login_reports_authorization_server_error() {
+    let server = MockServer::start().await;
+    let scheme = authorization_code_scheme(&server.uri(), "UNUSED");
+    let credentials = public_client(&scheme);
+
+    let pending = PendingAuthorization::start(&scheme, &credentials, 0)
+        .await
+        .unwrap();
+    let state = query_param(pending.authorization_url(), "state").unwrap();
+    let redirect_uri = pending.redirect_uri().to_string();
+    let browser = tokio::spawn(async move {
+        // Browsers commonly request a favicon first; it must not end the login
+        let _ = reqwest::get(redirect_uri.replace("/callback", "/favicon.ico")).await;
+        deliver_redirect(
+            &redirect_uri,
+            &format!("error=access_denied&error_description=User%20declined&state={state}"),
+        )
+        .await
+    });
+
+    let err = pending
+        .complete(
+            &reqwest::Client::new(),
+            &credentials,
+            Duration::from_secs(10),
+        )
+        .await
+        .expect_err("denied authorization should fail");
+    browser.await.unwrap();
+
+    match err {
+        Error::Internal {
+            kind: ErrorKind::Authentication,
+            message,
+            ..
+        } => {
+            assert!(message.contains("access_denied"), "{message}");
+            assert!(message.contains("User declined"), "{message}");
+        }
+        other => panic!("Expected authentication error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_login_requires_authorization_code_flow() {
+    let server = MockServer::start().await;
+    let mut scheme = authorization_code_scheme(&server.uri(), "UNUSED");
+    let credentials = public_client(&scheme);
+    if let Some(config) = scheme.oauth2.as_mut() {
+        config.grant_types = vec!["client_credentials".to_string()];
+        config.authorization_url = None;
+    }
+
+    let err = PendingAuthorization::start(&scheme, &credentials, 0)
+        .await
+        .expect_err("client-credentials-only scheme cannot log in");
+    assert!(err.to_string().contains("authorizationCode"), "{err}");
+}
+
+#[tokio::test]
+async fn test_executor_refreshes_expired_access_token() {
+    let server = MockServer::start().await;
+    let temp = tempdir().unwrap();
+    let store = TokenStore::new(temp.path().join("tokens"));
+    std::env::set_var("OAUTH2_AC_REFRESH_CLIENT", "cli-client:cli-secret");
+
+    store
+        .save(
+            "user-api",
+            "userAuth",
+            &StoredToken {
+                access_token: "expired-access".to_string(),
+                token_type: Some("Bearer".to_string()),
+                expires_at: Some(1),
+                refresh_token: Some("long-lived-refresh".to_string()),
+                client_id: "cli-client".to_string(),
+            },
+        )
+        .unwrap();
+
+    // "cli-client:cli-secret" in base64
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(header(
+            "authorization",
+            "Basic Y2xpLWNsaWVudDpjbGktc2VjcmV0",
+        ))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .and(body_string_contains("refresh_token=long-lived-refresh"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "refreshed-access",
+            "token_type": "Bearer",
+            "expires_in": 3600
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me"))
+        .and(header("authorization", "Bearer refreshed-access"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spec = spec_with_scheme(authorization_code_scheme(
+        &server.uri(),
+        "OAUTH2_AC_REFRESH_CLIENT",
+    ));
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        token_store: Some(store.clone()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(&spec, get_me_call(), ctx)
+        .await
+        .expect("request should succeed");
+    assert!(matches!(
+        result,
+        ExecutionResult::Success { status: 200, .. }
+    ));
+
+    // The refresh token is kept because the server did not rotate it
+    let stored = store.load("user-api", "userAuth").unwrap();
+    assert_eq!(stored.access_token, "refreshed-access");
+    assert_eq!(stored.refresh_token.as_deref(), Some("long-lived-refresh"));
+}
+
+#[tokio::test]
+async fn test_executor_requires_login_without_stored_token() {
+    let server = MockServer::start().await;
+    let temp = tempdir().unwrap();
+    let store = TokenStore::new(temp.path().join("tokens"));
+    std::env::set_var("OAUTH2_AC_NO_TOKEN_CLIENT", "cli-client");
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let spec = spec_with_scheme(authorization_code_scheme(
+        &server.uri(),
+        "OAUTH2_AC_NO_TOKEN_CLIENT",
+    ));
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        token_store: Some(store),
+        ..ExecutionContext::default()
+    };
+    let err = execute(&spec, get_me_call(), ctx)
+        .await
+        .expect_err("missing login should fail");
+
+    let json = err.to_json();
+    assert_eq!(json.error_type, "Authentication");
+    assert!(
+        json.context
+            .as_deref()
+            .is_some_and(|s| s.contains("aperture auth login user-api --scheme userAuth")),
+        "{json:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_rejected_refresh_token_requires_new_login() {
+    let server = MockServer::start().await;
+    let temp = tempdir().unwrap();
+    let store = TokenStore::new(temp.path().join("tokens"));
+    std::env::set_var("OAUTH2_AC_REVOKED_CLIENT", "cli-client");
+
+    store
+        .save(
+            "user-api",
+            "userAuth",
+            &StoredToken {
+                access_token: "expired-access".to_string(),
+                token_type: None,
+                expires_at: Some(1),
+                refresh_token: Some("revoked-refresh".to_string()),
+                client_id: "cli-client".to_string(),
+            },
+        )
+        .unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(body_string_contains("client_id=cli-client"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": "invalid_grant"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spec = spec_with_scheme(authorization_code_scheme(
+        &server.uri(),
+        "OAUTH2_AC_REVOKED_CLIENT",
+    ));
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        token_store: Some(store),
+        ..ExecutionContext::default()
+    };
+    let err = execute(&spec, get_me_call(), ctx)
+        .await
+        .expect_err("revoked refresh token should fail");
+
+    assert!(
+        err.to_string().contains("authorization is required"),
+        "{err}"
+    );
+}
@@ -0,0 +1,319 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::{
+    CachedApertureSecret, CachedCommand, CachedOAuth2Config, CachedSecurityScheme, CachedSpec,
+    PaginationInfo, CACHE_FORMAT_VERSION,
+};
+use aperture_cli::engine::executor::execute;
+use aperture_cli::error::{Error, ErrorKind};
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall};
+use aperture_cli::oauth::{StoredToken, TokenStore};
+use std::collections::HashMap;
+use tempfile::tempdir;
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Builds a spec with one operation guarded by a client-credentials `OAuth2` scheme
+fn oauth2_spec(token_url: &str, env_var: &str) -> CachedSpec {
+    let mut security_schemes = HashMap::new();
+    security_schemes.insert(
+        "clientAuth".to_string(),
+        CachedSecurityScheme {
+            name: "clientAuth".to_string(),
+            scheme_type: "oauth2".to_string(),
+            scheme: Some("bearer".to_string()),
+            location: Some("header".to_string()),
+            parameter_name: Some("Authorization".to_string()),
+            description: None,
+            bearer_format: None,
+            aperture_secret: Some(CachedApertureSecret {
+                source: "env".to_string(),
+                name: env_var.to_string(),
+            }),
+            oauth2: Some(CachedOAuth2Config {
+                token_url: token_url.to_string(),
+                scopes: vec!["read".to_string(), "write".to_string()],
+                grant_types: vec!["client_credentials".to_string()],
+                authorization_url: None,
+            }),
+        },
+    );
+
+    CachedSpec {
+        cache_format_version: CACHE_FORMAT_VERSION,
+        name: "oauth-api".to_string(),
+        version: "1.0.0".to_string(),
+        commands: vec![CachedCommand {
+            name: "items".to_string(),
+            description: None,
+            summary: None,
+            operation_id: "listItems".to_string(),
+            method: "GET".to_string(),
+            path: "/items".to_string(),
+            parameters: vec![],
+            request_body: None,
+            responses: vec![],
+            security_requirements: vec!["clientAuth".to_string()],
+            tags: vec!["items".to_string()],
+            deprecated: false,
+            external_docs_url: None,
+            examples: vec![],
+            display_group: None,
+            display_name: None,
+            aliases: vec![],
+            hidden: false,
+            pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
+        }],
+        base_url: None,
+        servers: vec![],
+        security_schemes,
+        skipped_endpoints: vec![],
+        server_variables: HashMap::new(),
+    }
+}
+
+fn list_items_call() -> OperationCall {
+    OperationCall {
+        operation_id: "listItems".to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+fn context(base_url: &str, store: &TokenStore) -> ExecutionContext {
+    ExecutionContext {
+        base_url: Some(base_url.to_string()),
+        token_store: Some(store.clone()),
+        ..ExecutionContext::default()
+    }
+}
+
+async fn mount_token_endpoint(server: &MockServer, access_token: &str, expires_in: u64) {
+    // "my-client:my-secret" in base64
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .and(header(
+            "authorization",
+            "Basic bXktY2xpZW50Om15LXNlY3JldA==",
+        ))
+        .and(body_string_contains("grant_type=client_credentials"))
+        .and(body_string_contains("scope=read%20write"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": access_token,
+            "token_type": "Bearer",
+            "expires_in": expires_in
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_client_credentials_token_is_fetched_and_sent() {
+    let server = MockServer::start().await;
+    let temp = tempdir().unwrap();
+    let store = TokenStore::new(temp.path().join("tokens"));
+    std::env::set_var("OAUTH2_CC_FETCH_CREDS", "my-client:my-secret");
+
+    mount_token_endpoint(&server, "fresh-token", 3600).await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(header("authorization", "Bearer fresh-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spec = oauth2_spec(
+        &format!("{}/oauth/token", server.uri()),
+        "OAUTH2_CC_FETCH_CREDS",
+    );
+    let result = execute(&spec, list_items_call(), context(&server.uri(), &store))
+        .await
+        .expect("request should succeed");
+    assert!(matches!(
+        result,
+        ExecutionResult::Success { status: 200, .. }
+    ));
+
+    let stored = store
+        .load("oauth-api", "clientAuth")
+        .expect("token should be persisted");
+    assert_eq!(stored.access_token, "fresh-token");
+    assert_eq!(stored.client_id, "my-client");
+    assert!(stored.expires_at.is_some());
+}
+
+#[tokio::test]
+async fn test_stored_token_is_reused_across_invocations() {
+    let server = MockServer::start().await;
+    let temp = tempdir().unwrap();
+    let store = TokenStore::new(temp.path().join("tokens"));
+    std::env::set_var("OAUTH2_CC_REUSE_CREDS", "my-client:my-secret");
+
+    // expect(1) on the token endpoint proves the second call used the store
+    mount_token_endpoint(&server, "reused-token", 3600).await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(header("authorization", "Bearer reused-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let spec = oauth2_spec(
+        &format!("{}/oauth/token", server.uri()),
+        "OAUTH2_CC_REUSE_CREDS",
+    );
+    for _ in 0..2 {
+        execute(&spec, list_items_call(), context(&server.uri(), &store))
+            .await
+            .expect("request should succeed");
+    }
+}
+
+#[tokio::test]
+async fn test_token_near_expiry_is_refreshed() {
+    let server = MockServer::start().await;
+    let temp = tempdir().unwrap();
+    let store = TokenStore::new(temp.path().join("tokens"));
+    std::env::set_var("OAUTH2_CC_EXPIRY_CREDS", "my-client:my-secret");
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    store
+        .save(
+            "oauth-api",
+            "clientAuth",
+            &StoredToken {
+                access_token: "stale-token".to_string(),
+                token_type: Some("Bearer".to_string()),
+                expires_at: Some(now + 10),
+                refresh_token: None,
+                client_id: "my-client".to_string(),
+            },
+        )
+        .unwrap();
+
+    mount_token_endpoint(&server, "renewed-token", 3600).await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(header("authorization", "Bearer renewed-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spec = oauth2_spec(
+        &format!("{}/oauth/token", server.uri()),
+        "OAUTH2_CC_EXPIRY_CREDS",
+    );
+    execute(&spec, list_items_call(), context(&server.uri(), &store))
+        .await
+        .expect("request should succeed");
+
+    assert_eq!(
+        store.load("oauth-api", "clientAuth").unwrap().access_token,
+        "renewed-token"
+    );
+}
+
+#[tokio::test]
+async fn test_token_endpoint_error_is_reported() {
+    let server = MockServer::start().await;
+    let temp = tempdir().unwrap();
+    let store = TokenStore::new(temp.path().join("tokens"));
+    std::env::set_var("OAUTH2_CC_ERROR_CREDS", "my-client:wrong");
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+            "error": "invalid_client",
+            "error_description": "Client authentication failed"
+        })))
+        .mount(&server)
+        .await;
+
+    let spec = oauth2_spec(
+        &format!("{}/oauth/token", server.uri()),
+        "OAUTH2_CC_ERROR_CREDS",
+    );
+    let err = execute(&spec, list_items_call(), context(&server.uri(), &store))
+        .await
+        .expect_err("token failure should abort the request");
+
+    match err {
+        Error::Internal {
+            kind: ErrorKind::Authentication,
+            message,
+            ..
+        } => {
+            assert!(message.contains("clientAuth"), "{message}");
+            assert!(message.contains("invalid_client"), "{message}");
+        }
+        other => panic!("Expected authentication error, got {other:?}"),
+    }
+    assert!(store.load("oauth-api", "clientAuth").is_none());
+}
+
+#[tokio::test]
+async fn test_dry_run_does_not_request_token() {
+    let server = MockServer::start().await;
+    let temp = tempdir().unwrap();
+    let store = TokenStore::new(temp.path().join("tokens"));
+    std::env::set_var("OAUTH2_CC_DRY_RUN_CREDS", "my-client:my-secret");
+
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let spec = oauth2_spec(
+        &format!("{}/oauth/token", server.uri()),
+        "OAUTH2_CC_DRY_RUN_CREDS",
+    );
+    let ctx = ExecutionContext {
+        dry_run: true,
+        ..context(&server.uri(), &store)
+    };
+    let result = execute(&spec, list_items_call(), ctx)
+        .await
+        .expect("dry run should succeed");
+
+    let ExecutionResult::DryRun { request_info } = result else {
+        panic!("Expected DryRun result");
+    };
+    assert_eq!(request_info["headers"]["authorization"], "[REDACTED]");
+}
+
+#[tokio::test]
+async fn test_malformed_client_credentials_are_rejected() {
+    let server = MockServer::start().await;
+    std::env::set_var("OAUTH2_CC_MALFORMED_CREDS", "no-separator");
+
+    let spec = oauth2_spec(
+        &format!("{}/oauth/token", server.uri()),
+        "OAUTH2_CC_MALFORMED_CREDS",
+    );
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        ..ExecutionContext::default()
+    };
+    let err = execute(&spec, list_items_call(), ctx)
+        .await
+        .expect_err("malformed credentials should fail");
+
+    assert!(err.to_string().contains("client_id:client_secret"), "{err}");
+}
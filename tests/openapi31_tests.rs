@@ -0,0 +1,210 @@
+mod test_helpers;
+
+use aperture_cli::agent::generate_capability_manifest_from_document;
+use aperture_cli::cache::models::{CachedCommand, CachedSpec};
+use aperture_cli::engine::validation::validate_request;
+use aperture_cli::invocation::OperationCall;
+use aperture_cli::spec::{SpecDocument, SpecVersion};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use test_helpers::cache_spec;
+
+const OPENAPI_31_SPEC: &str = r"
+openapi: 3.1.0
+info:
+  title: Pets API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /pets:
+    $ref: '#/components/pathItems/Pets'
+  /pets/{petId}:
+    get:
+      operationId: getPet
+      tags: [pets]
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: integer
+            exclusiveMinimum: 0
+        - name: age
+          in: query
+          schema:
+            type: [integer, 'null']
+      responses:
+        '200':
+          description: The pet
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+                description: The requested pet
+webhooks:
+  petAdopted:
+    post:
+      operationId: petAdopted
+      summary: A pet was adopted
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+      responses:
+        '200':
+          description: Acknowledged
+components:
+  pathItems:
+    Pets:
+      post:
+        operationId: createPet
+        tags: [pets]
+        requestBody:
+          required: true
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+        responses:
+          '201':
+            description: Created
+  schemas:
+    Pet:
+      type: object
+      required: [name, kind]
+      properties:
+        name:
+          type: string
+          examples: [Rex]
+        kind:
+          const: dog
+        nickname:
+          type: [string, 'null']
+";
+
+fn command<'a>(spec: &'a CachedSpec, operation_id: &str) -> &'a CachedCommand {
+    spec.commands
+        .iter()
+        .find(|command| command.operation_id == operation_id)
+        .unwrap()
+}
+
+fn schema(json: &str) -> Value {
+    serde_json::from_str(json).unwrap()
+}
+
+fn pet_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["name", "kind"],
+        "properties": {
+            "name": {"type": "string", "examples": ["Rex"]},
+            "kind": {"const": "dog"},
+            "nickname": {"type": ["string", "null"]}
+        }
+    })
+}
+
+fn get_pet_call(pet_id: &str, age: Option<&str>) -> OperationCall {
+    OperationCall {
+        operation_id: "getPet".to_string(),
+        path_params: HashMap::from([("petId".to_string(), pet_id.to_string())]),
+        query_params: age
+            .map(|age| HashMap::from([("age".to_string(), age.to_string())]))
+            .unwrap_or_default(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+#[test]
+fn test_openapi31_schemas_are_cached_as_written() {
+    let (spec, _temp_dir) = cache_spec("pets-api", OPENAPI_31_SPEC);
+
+    // The path item comes from `components/pathItems`
+    let create_pet = command(&spec, "createPet");
+    let body = create_pet.request_body.as_ref().unwrap();
+    assert!(body.required);
+    assert_eq!(schema(&body.schema), pet_schema());
+
+    let get_pet = command(&spec, "getPet");
+    let pet_id = &get_pet.parameters[0];
+    assert_eq!(pet_id.schema_type.as_deref(), Some("integer"));
+    assert_eq!(
+        schema(pet_id.schema.as_deref().unwrap()),
+        json!({"type": "integer", "exclusiveMinimum": 0})
+    );
+    let age = &get_pet.parameters[1];
+    assert_eq!(age.schema_type.as_deref(), Some("integer"));
+    assert_eq!(
+        schema(age.schema.as_deref().unwrap()),
+        json!({"type": ["integer", "null"]})
+    );
+
+    // A description next to a `$ref` overrides the target's
+    let mut expected = pet_schema();
+    expected["description"] = json!("The requested pet");
+    assert_eq!(
+        schema(get_pet.responses[0].schema.as_deref().unwrap()),
+        expected
+    );
+}
+
+#[test]
+fn test_openapi31_keywords_are_validated() {
+    let (spec, _temp_dir) = cache_spec("pets-api", OPENAPI_31_SPEC);
+    let get_pet = command(&spec, "getPet");
+
+    assert!(validate_request(get_pet, &get_pet_call("7", Some("3"))).is_ok());
+
+    let err = validate_request(get_pet, &get_pet_call("0", Some("old"))).unwrap_err();
+    let details = err.to_json().details.unwrap();
+    let paths: Vec<&str> = details["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|violation| violation["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["/path/petId", "/query/age"]);
+}
+
+#[test]
+fn test_describe_json_lists_webhooks() {
+    let (cached_spec, _temp_dir) = cache_spec("pets-api", OPENAPI_31_SPEC);
+    let document = SpecDocument::parse(OPENAPI_31_SPEC).unwrap();
+    assert_eq!(document.version(), SpecVersion::OpenApi31);
+
+    let manifest =
+        generate_capability_manifest_from_document("pets-api", &document, &cached_spec, None)
+            .unwrap();
+    let manifest: Value = serde_json::from_str(&manifest).unwrap();
+
+    assert_eq!(
+        manifest["webhooks"],
+        json!([{
+            "name": "petAdopted",
+            "method": "POST",
+            "operation_id": "petAdopted",
+            "summary": "A pet was adopted",
+            "content_type": "application/json",
+            "payload_schema": pet_schema()
+        }])
+    );
+    let get_pet = manifest["commands"]["pets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|command| command["operation_id"] == "getPet")
+        .unwrap();
+    assert_eq!(
+        get_pet["response_schema"]["schema"]["properties"]["nickname"],
+        json!({"type": ["string", "null"]})
+    );
+}
@@ -36,6 +36,7 @@ fn make_spec_with_pagination(base_url: &str, pagination: PaginationInfo) -> Cach
 const fn base_ctx() -> ExecutionContext {
     ExecutionContext {
         dry_run: false,
+        secret_references: false,
         idempotency_key: None,
         cache_config: None,
         retry_context: None,
@@ -44,6 +45,12 @@ const fn base_ctx() -> ExecutionContext {
         global_config: None,
         server_var_args: vec![],
         auto_paginate: true,
+        token_store: None,
+        auth_scheme: None,
+        skip_validation: false,
+        response_validation: None,
+        cassette: None,
+        http_client: None,
     }
 }
 
@@ -53,7 +60,11 @@ fn base_call(query_params: HashMap<String, String>) -> OperationCall {
         path_params: HashMap::new(),
         query_params,
         header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
         body: None,
+        files: vec![],
+        raw_body: None,
         custom_headers: vec![],
     }
 }
@@ -0,0 +1,220 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::{CachedEncoding, CachedSpec};
+use aperture_cli::cli::translate::matches_to_operation_call;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::engine::generator::generate_command_tree_with_flags;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, ParamValues};
+use test_helpers::cache_spec;
+
+const STYLE_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Search API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /items:
+    get:
+      operationId: searchItems
+      tags:
+        - items
+      parameters:
+        - name: tag
+          in: query
+          schema:
+            type: array
+            items:
+              type: string
+        - name: ids
+          in: query
+          style: pipeDelimited
+          explode: false
+          schema:
+            type: array
+            items:
+              type: integer
+        - name: filter
+          in: query
+          style: deepObject
+          schema:
+            type: object
+        - name: X-Trace
+          in: header
+          schema:
+            type: array
+            items:
+              type: string
+      responses:
+        '200':
+          description: Matching items
+  /shapes/{colors}/{point}:
+    get:
+      operationId: getShape
+      tags:
+        - items
+      parameters:
+        - name: colors
+          in: path
+          required: true
+          style: label
+          schema:
+            type: array
+            items:
+              type: string
+        - name: point
+          in: path
+          required: true
+          style: matrix
+          explode: true
+          schema:
+            type: object
+      responses:
+        '200':
+          description: The shape
+";
+
+/// Parses the arguments and returns the dry-run request info
+async fn dry_run(spec: &CachedSpec, args: &[&str]) -> serde_json::Value {
+    let matches = generate_command_tree_with_flags(spec, false)
+        .try_get_matches_from(args)
+        .unwrap();
+    let call = matches_to_operation_call(spec, &matches).unwrap();
+    let ctx = ExecutionContext {
+        dry_run: true,
+        ..ExecutionContext::default()
+    };
+    let ExecutionResult::DryRun { request_info } = execute(spec, call, ctx).await.unwrap() else {
+        panic!("Expected DryRun result");
+    };
+    request_info
+}
+
+#[test]
+fn test_parameter_styles_are_cached() {
+    let (spec, _temp_dir) = cache_spec("search-api", STYLE_SPEC);
+
+    let encodings: Vec<(&str, Option<&CachedEncoding>)> = spec.commands[0]
+        .parameters
+        .iter()
+        .map(|param| (param.name.as_str(), param.encoding.as_ref()))
+        .collect();
+    let encoding = |style: &str, explode: bool| CachedEncoding {
+        style: style.to_string(),
+        explode,
+    };
+    assert_eq!(
+        encodings,
+        vec![
+            ("tag", Some(&encoding("form", true))),
+            ("ids", Some(&encoding("pipeDelimited", false))),
+            ("filter", Some(&encoding("deepObject", false))),
+            ("X-Trace", Some(&encoding("simple", false))),
+        ]
+    );
+}
+
+#[test]
+fn test_array_flags_are_repeatable_or_comma_separated() {
+    let (spec, _temp_dir) = cache_spec("search-api", STYLE_SPEC);
+
+    let matches = generate_command_tree_with_flags(&spec, false)
+        .try_get_matches_from([
+            "api",
+            "items",
+            "search-items",
+            "--tag",
+            "a",
+            "--tag",
+            "b,c",
+            "--filter",
+            "status=open",
+        ])
+        .unwrap();
+    let call = matches_to_operation_call(&spec, &matches).unwrap();
+    assert_eq!(
+        call.multi_params.get("tag"),
+        Some(&ParamValues::Array(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string()
+        ]))
+    );
+    assert_eq!(
+        call.multi_params.get("filter"),
+        Some(&ParamValues::Object(vec![(
+            "status".to_string(),
+            "open".to_string()
+        )]))
+    );
+    assert!(call.query_params.is_empty());
+}
+
+#[tokio::test]
+async fn test_query_and_header_styles_are_serialized() {
+    let (spec, _temp_dir) = cache_spec("search-api", STYLE_SPEC);
+
+    let request_info = dry_run(
+        &spec,
+        &[
+            "api",
+            "items",
+            "search-items",
+            "--tag",
+            "a b",
+            "--tag",
+            "c",
+            "--ids",
+            "1,2",
+            "--filter",
+            "status=open",
+            "--filter",
+            "owner=me",
+            "--x-trace",
+            "t1,t2",
+        ],
+    )
+    .await;
+
+    assert_eq!(
+        request_info["url"],
+        "https://api.example.com/items?filter[owner]=me&filter[status]=open&ids=1%7C2&tag=a%20b&tag=c"
+    );
+    assert_eq!(request_info["headers"]["x-trace"], "t1,t2");
+}
+
+#[tokio::test]
+async fn test_path_styles_are_serialized() {
+    let (spec, _temp_dir) = cache_spec("search-api", STYLE_SPEC);
+
+    let request_info = dry_run(
+        &spec,
+        &[
+            "api",
+            "items",
+            "get-shape",
+            "--colors",
+            "red,green",
+            "--point",
+            "x=1,y=2",
+        ],
+    )
+    .await;
+
+    assert_eq!(
+        request_info["url"],
+        "https://api.example.com/shapes/.red,green/;x=1;y=2"
+    );
+}
+
+#[test]
+fn test_object_values_must_be_key_value_pairs() {
+    let (spec, _temp_dir) = cache_spec("search-api", STYLE_SPEC);
+
+    let matches = generate_command_tree_with_flags(&spec, false)
+        .try_get_matches_from(["api", "items", "search-items", "--filter", "open"])
+        .unwrap();
+    let err = matches_to_operation_call(&spec, &matches).unwrap_err();
+    assert!(err.to_string().contains("KEY=VALUE"), "{err}");
+}
@@ -138,7 +138,11 @@ fn test_call() -> OperationCall {
         path_params: HashMap::new(),
         query_params: HashMap::new(),
         header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
         body: None,
+        files: vec![],
+        raw_body: None,
         custom_headers: vec![],
     }
 }
@@ -0,0 +1,176 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::CachedSpec;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall};
+use aperture_cli::response_cache::CacheConfig;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+use test_helpers::cache_spec;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const APP_ID_ENV: &str = "APERTURE_TEST_QUERY_APP_ID";
+const APP_ID: &str = "query-secret-123";
+
+const QUERY_KEY_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Weather API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+components:
+  securitySchemes:
+    appId:
+      type: apiKey
+      in: query
+      name: appid
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_QUERY_APP_ID
+security:
+  - appId: []
+paths:
+  /weather:
+    get:
+      operationId: getWeather
+      tags:
+        - weather
+      parameters:
+        - name: q
+          in: query
+          required: true
+          schema:
+            type: string
+      responses:
+        '200':
+          description: Current weather
+";
+
+fn cached_query_key_spec() -> (CachedSpec, TempDir) {
+    let (spec, temp_dir) = cache_spec("weather-api", QUERY_KEY_SPEC);
+    std::env::set_var(APP_ID_ENV, APP_ID);
+    (spec, temp_dir)
+}
+
+fn weather_call() -> OperationCall {
+    OperationCall {
+        operation_id: "getWeather".to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::from([("q".to_string(), "London".to_string())]),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+/// Lists the response files written to the cache directory
+fn cached_responses(cache_dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(cache_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_api_key_is_sent_as_query_parameter() {
+    let server = MockServer::start().await;
+    let (spec, _temp_dir) = cached_query_key_spec();
+
+    Mock::given(method("GET"))
+        .and(path("/weather"))
+        .and(query_param("appid", APP_ID))
+        .and(query_param("q", "London"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"temp":12}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(&spec, weather_call(), ctx)
+        .await
+        .expect("request should succeed");
+    assert!(matches!(
+        result,
+        ExecutionResult::Success { status: 200, .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_dry_run_redacts_query_api_key() {
+    let (spec, _temp_dir) = cached_query_key_spec();
+
+    let ctx = ExecutionContext {
+        dry_run: true,
+        base_url: Some("https://example.test".to_string()),
+        ..ExecutionContext::default()
+    };
+    let result = execute(&spec, weather_call(), ctx).await.unwrap();
+
+    let ExecutionResult::DryRun { request_info } = result else {
+        panic!("Expected DryRun result");
+    };
+    assert_eq!(
+        request_info["url"],
+        "https://example.test/weather?appid=[REDACTED]&q=London"
+    );
+}
+
+#[tokio::test]
+async fn test_cached_request_info_redacts_query_api_key() {
+    let server = MockServer::start().await;
+    let (spec, temp_dir) = cached_query_key_spec();
+    let cache_dir = temp_dir.path().join("responses");
+
+    Mock::given(method("GET"))
+        .and(path("/weather"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"temp":12}"#))
+        .mount(&server)
+        .await;
+
+    let cache_config = |allow_authenticated| CacheConfig {
+        cache_dir: cache_dir.clone(),
+        default_ttl: Duration::from_mins(1),
+        max_entries: 10,
+        enabled: true,
+        allow_authenticated,
+    };
+
+    // A query API key makes the request authenticated, so it is not cached
+    // unless explicitly allowed
+    for allow_authenticated in [false, true] {
+        let ctx = ExecutionContext {
+            base_url: Some(server.uri()),
+            cache_config: Some(cache_config(allow_authenticated)),
+            ..ExecutionContext::default()
+        };
+        execute(&spec, weather_call(), ctx).await.unwrap();
+        assert_eq!(
+            cached_responses(&cache_dir).len(),
+            usize::from(allow_authenticated)
+        );
+    }
+
+    let content = std::fs::read_to_string(&cached_responses(&cache_dir)[0]).unwrap();
+    assert!(!content.contains(APP_ID), "{content}");
+    let cached: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(cached["request_info"]["url"]
+        .as_str()
+        .unwrap()
+        .contains("appid=[REDACTED]"));
+}
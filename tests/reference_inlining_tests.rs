@@ -0,0 +1,288 @@
+mod test_helpers;
+
+use aperture_cli::agent::generate_capability_manifest_from_openapi;
+use aperture_cli::cache::models::{CachedCommand, CachedSpec};
+use aperture_cli::spec::resolver::{inline_document_references, MAX_EXPANDED_REFERENCES};
+use aperture_cli::spec::{inline_references, parse_openapi};
+use serde_json::{json, Value};
+use test_helpers::cache_spec;
+
+const REFERENCE_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Orders API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /orders/{orderId}:
+    put:
+      operationId: updateOrder
+      tags:
+        - orders
+      parameters:
+        - $ref: '#/components/parameters/OrderId'
+      requestBody:
+        $ref: '#/components/requestBodies/OrderBody'
+      responses:
+        '200':
+          $ref: '#/components/responses/OrderResponse'
+  /categories:
+    get:
+      operationId: listCategories
+      tags:
+        - categories
+      responses:
+        '200':
+          description: The category tree
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/Category'
+components:
+  parameters:
+    OrderId:
+      name: orderId
+      in: path
+      required: true
+      schema:
+        $ref: '#/components/schemas/Identifier'
+  requestBodies:
+    OrderBody:
+      required: true
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Order'
+  responses:
+    OrderResponse:
+      description: The updated order
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Order'
+  schemas:
+    Identifier:
+      type: string
+      format: uuid
+    Order:
+      type: object
+      required: [customer]
+      properties:
+        customer:
+          $ref: '#/components/schemas/Customer'
+        lines:
+          type: array
+          items:
+            allOf:
+              - $ref: '#/components/schemas/Line'
+    Customer:
+      type: object
+      properties:
+        id:
+          $ref: '#/components/schemas/Identifier'
+        name:
+          type: string
+      example:
+        $ref: not-a-reference
+    Line:
+      type: object
+      properties:
+        sku:
+          type: string
+    Category:
+      type: object
+      properties:
+        name:
+          type: string
+        children:
+          type: array
+          items:
+            $ref: '#/components/schemas/Category'
+";
+
+fn command<'a>(spec: &'a CachedSpec, operation_id: &str) -> &'a CachedCommand {
+    spec.commands
+        .iter()
+        .find(|command| command.operation_id == operation_id)
+        .unwrap()
+}
+
+fn expected_order_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["customer"],
+        "properties": {
+            "customer": {
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "format": "uuid"},
+                    "name": {"type": "string"}
+                },
+                "example": {"$ref": "not-a-reference"}
+            },
+            "lines": {
+                "type": "array",
+                "items": {
+                    "allOf": [
+                        {"type": "object", "properties": {"sku": {"type": "string"}}}
+                    ]
+                }
+            }
+        }
+    })
+}
+
+#[test]
+fn test_nested_references_are_inlined_into_the_cache() {
+    let (spec, _temp_dir) = cache_spec("orders-api", REFERENCE_SPEC);
+    let update_order = command(&spec, "updateOrder");
+
+    let body = update_order.request_body.as_ref().unwrap();
+    assert!(body.required);
+    let body_schema: Value = serde_json::from_str(&body.schema).unwrap();
+    assert_eq!(body_schema, expected_order_schema());
+
+    let response = &update_order.responses[0];
+    assert_eq!(response.status_code, "200");
+    assert_eq!(response.description.as_deref(), Some("The updated order"));
+    let response_schema: Value = serde_json::from_str(response.schema.as_deref().unwrap()).unwrap();
+    assert_eq!(response_schema, expected_order_schema());
+
+    let order_id = &update_order.parameters[0];
+    assert_eq!(order_id.name, "orderId");
+    let order_id_schema: Value = serde_json::from_str(order_id.schema.as_deref().unwrap()).unwrap();
+    assert_eq!(order_id_schema, json!({"type": "string", "format": "uuid"}));
+}
+
+#[test]
+fn test_self_referencing_schema_keeps_its_cycle_as_a_reference() {
+    let (spec, _temp_dir) = cache_spec("orders-api", REFERENCE_SPEC);
+    let list_categories = command(&spec, "listCategories");
+
+    let schema: Value =
+        serde_json::from_str(list_categories.responses[0].schema.as_deref().unwrap()).unwrap();
+    assert_eq!(
+        schema["items"]["properties"]["name"],
+        json!({"type": "string"})
+    );
+    assert_eq!(
+        schema["items"]["properties"]["children"]["items"],
+        json!({"$ref": "#/components/schemas/Category"})
+    );
+}
+
+#[test]
+fn test_inlining_keeps_components_for_remaining_references() {
+    let spec = parse_openapi(REFERENCE_SPEC).unwrap();
+    let inlined = inline_references(&spec).unwrap();
+
+    assert_eq!(
+        inlined.components.as_ref().unwrap().schemas.len(),
+        spec.components.as_ref().unwrap().schemas.len()
+    );
+    let document = serde_json::to_value(&inlined).unwrap();
+    let request_body = &document["paths"]["/orders/{orderId}"]["put"]["requestBody"];
+    assert!(request_body.get("$ref").is_none());
+}
+
+#[test]
+fn test_describe_json_response_schema_is_self_contained() {
+    let (cached_spec, _temp_dir) = cache_spec("orders-api", REFERENCE_SPEC);
+    let spec = parse_openapi(REFERENCE_SPEC).unwrap();
+
+    let manifest =
+        generate_capability_manifest_from_openapi("orders-api", &spec, &cached_spec, None).unwrap();
+    let manifest: Value = serde_json::from_str(&manifest).unwrap();
+
+    let update_order = &manifest["commands"]["orders"][0];
+    assert_eq!(update_order["operation_id"], "updateOrder");
+    assert_eq!(
+        update_order["response_schema"]["schema"],
+        expected_order_schema()
+    );
+}
+
+#[test]
+fn test_literal_values_are_not_inlined() {
+    // `const` and `enum` hold data that merely looks like a reference
+    let mut document = json!({
+        "openapi": "3.1.0",
+        "paths": {
+            "/links": {
+                "get": {
+                    "parameters": [{
+                        "name": "kind",
+                        "in": "query",
+                        "schema": {
+                            "oneOf": [
+                                {"const": {"$ref": "#/components/schemas/Kind"}},
+                                {"enum": [{"$ref": "#/components/schemas/Kind"}]},
+                                {"$ref": "#/components/schemas/Kind"}
+                            ]
+                        }
+                    }]
+                }
+            }
+        },
+        "components": {"schemas": {"Kind": {"type": "string"}}}
+    });
+    inline_document_references(&mut document);
+
+    let alternatives = &document["paths"]["/links"]["get"]["parameters"][0]["schema"]["oneOf"];
+    assert_eq!(
+        alternatives,
+        &json!([
+            {"const": {"$ref": "#/components/schemas/Kind"}},
+            {"enum": [{"$ref": "#/components/schemas/Kind"}]},
+            {"type": "string"}
+        ])
+    );
+}
+
+#[test]
+fn test_reused_components_expand_a_bounded_number_of_references() {
+    // Each level uses the next one twice: fully inlined, the response would
+    // hold 2^30 copies of the last schema
+    let mut schemas = serde_json::Map::new();
+    for level in 0..30 {
+        let next = json!({"$ref": format!("#/components/schemas/Level{}", level + 1)});
+        schemas.insert(
+            format!("Level{level}"),
+            json!({"type": "object", "properties": {"left": next, "right": next}}),
+        );
+    }
+    schemas.insert("Level30".to_string(), json!({"type": "string"}));
+    let mut document = json!({
+        "openapi": "3.1.0",
+        "paths": {
+            "/tree": {
+                "get": {
+                    "responses": {
+                        "200": {
+                            "description": "A tree",
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": "#/components/schemas/Level0"}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {"schemas": schemas}
+    });
+    inline_document_references(&mut document);
+
+    let schema = document["paths"]["/tree"]["get"]["responses"]["200"]["content"]
+        ["application/json"]["schema"]
+        .to_string();
+    // Each expanded reference became an object level or a string leaf
+    let expanded =
+        schema.matches("\"properties\"").count() + schema.matches("\"type\":\"string\"").count();
+    assert_eq!(expanded, MAX_EXPANDED_REFERENCES);
+    assert!(schema.contains("\"$ref\""));
+}
@@ -0,0 +1,247 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::CachedSpec;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::error::{Error, ErrorKind};
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall, ParamValues};
+use serde_json::json;
+use std::collections::HashMap;
+use test_helpers::cache_spec;
+
+const VALIDATION_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Accounts API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /accounts/{accountId}/users:
+    post:
+      operationId: createUser
+      tags:
+        - users
+      parameters:
+        - name: accountId
+          in: path
+          required: true
+          schema:
+            type: string
+            format: uuid
+        - name: limit
+          in: query
+          schema:
+            type: integer
+            minimum: 1
+            maximum: 100
+        - name: status
+          in: query
+          schema:
+            type: string
+            enum: [active, inactive]
+        - name: tags
+          in: query
+          schema:
+            type: array
+            maxItems: 2
+            items:
+              type: string
+              minLength: 2
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [id, name, email]
+              additionalProperties: false
+              properties:
+                id:
+                  type: integer
+                  readOnly: true
+                name:
+                  type: string
+                  minLength: 1
+                email:
+                  type: string
+                  format: email
+                code:
+                  type: string
+                  pattern: '^[A-Z]{3}$'
+                joined:
+                  type: string
+                  format: date-time
+                roles:
+                  type: array
+                  items:
+                    type: string
+                    enum: [admin, member]
+      responses:
+        '201':
+          description: Created
+";
+
+const ACCOUNT_ID: &str = "3f2b8c1e-6a4d-4c5e-9b7a-1d2e3f4a5b6c";
+
+fn create_user_call(account_id: &str, query: &[(&str, &str)], body: &str) -> OperationCall {
+    OperationCall {
+        operation_id: "createUser".to_string(),
+        path_params: HashMap::from([("accountId".to_string(), account_id.to_string())]),
+        query_params: query
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: Some(body.to_string()),
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+async fn dry_run(
+    spec: &CachedSpec,
+    call: OperationCall,
+    skip_validation: bool,
+) -> Result<ExecutionResult, Error> {
+    let ctx = ExecutionContext {
+        dry_run: true,
+        skip_validation,
+        ..ExecutionContext::default()
+    };
+    execute(spec, call, ctx).await
+}
+
+/// Returns the `(path, message)` pairs reported by a request validation error
+fn reported_violations(err: &Error) -> Vec<(String, String)> {
+    let Error::Internal { kind, .. } = err else {
+        panic!("Expected an internal error, got {err}");
+    };
+    assert_eq!(*kind, ErrorKind::Validation);
+    let details = err.to_json().details.unwrap();
+    details["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|violation| {
+            (
+                violation["path"].as_str().unwrap().to_string(),
+                violation["message"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+fn paths(violations: &[(String, String)]) -> Vec<&str> {
+    violations.iter().map(|(path, _)| path.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_valid_request_passes_validation() {
+    let (spec, _temp_dir) = cache_spec("accounts-api", VALIDATION_SPEC);
+    let body = json!({
+        "name": "Ada",
+        "email": "ada@example.com",
+        "code": "ADA",
+        "joined": "2024-05-01T12:00:00Z",
+        "roles": ["admin"]
+    });
+    let mut call = create_user_call(
+        ACCOUNT_ID,
+        &[("limit", "10"), ("status", "active")],
+        &body.to_string(),
+    );
+    call.multi_params.insert(
+        "tags".to_string(),
+        ParamValues::Array(vec!["ops".to_string(), "dev".to_string()]),
+    );
+
+    let result = dry_run(&spec, call, false).await.unwrap();
+    assert!(matches!(result, ExecutionResult::DryRun { .. }));
+}
+
+#[tokio::test]
+async fn test_invalid_parameters_are_reported_with_pointers() {
+    let (spec, _temp_dir) = cache_spec("accounts-api", VALIDATION_SPEC);
+    let mut call = create_user_call(
+        "not-a-uuid",
+        &[("limit", "0"), ("status", "deleted")],
+        r#"{"name": "Ada", "email": "ada@example.com"}"#,
+    );
+    call.multi_params.insert(
+        "tags".to_string(),
+        ParamValues::Array(vec!["a".to_string(), "ops".to_string(), "dev".to_string()]),
+    );
+
+    let err = dry_run(&spec, call, false).await.unwrap_err();
+    let violations = reported_violations(&err);
+    assert_eq!(
+        paths(&violations),
+        vec![
+            "/path/accountId",
+            "/query/limit",
+            "/query/status",
+            "/query/tags",
+            "/query/tags/0"
+        ]
+    );
+    assert_eq!(violations[1].1, "must be at least 1");
+    assert!(violations[2].1.contains("\"active\", \"inactive\""));
+    assert!(err.to_string().contains("/path/accountId"), "{err}");
+
+    // A value that does not parse as the declared type is a type error
+    let call = create_user_call(
+        ACCOUNT_ID,
+        &[("limit", "ten")],
+        r#"{"name": "Ada", "email": "ada@example.com"}"#,
+    );
+    let err = dry_run(&spec, call, false).await.unwrap_err();
+    assert_eq!(
+        reported_violations(&err),
+        vec![(
+            "/query/limit".to_string(),
+            "expected integer, found string".to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_body_is_reported_with_pointers() {
+    let (spec, _temp_dir) = cache_spec("accounts-api", VALIDATION_SPEC);
+    let body = json!({
+        "email": "ada",
+        "code": "ada",
+        "joined": "yesterday",
+        "roles": ["admin", "owner"],
+        "nickname": "A"
+    });
+    let call = create_user_call(ACCOUNT_ID, &[], &body.to_string());
+
+    let err = dry_run(&spec, call, false).await.unwrap_err();
+    let violations = reported_violations(&err);
+    // The read-only `id` is never required in a request
+    assert_eq!(
+        paths(&violations),
+        vec![
+            "/body",
+            "/body/code",
+            "/body/email",
+            "/body/joined",
+            "/body/nickname",
+            "/body/roles/1"
+        ]
+    );
+    assert_eq!(violations[0].1, "missing required property \"name\"");
+    assert_eq!(violations[4].1, "unknown property \"nickname\"");
+}
+
+#[tokio::test]
+async fn test_skip_validation_sends_invalid_requests() {
+    let (spec, _temp_dir) = cache_spec("accounts-api", VALIDATION_SPEC);
+    let call = create_user_call("not-a-uuid", &[("limit", "0")], r#"{"email": 42}"#);
+
+    let result = dry_run(&spec, call, true).await.unwrap();
+    assert!(matches!(result, ExecutionResult::DryRun { .. }));
+}
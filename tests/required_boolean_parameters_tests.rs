@@ -49,6 +49,7 @@ fn create_spec_with_required_boolean_path_param() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "active".to_string(),
@@ -61,6 +62,7 @@ fn create_spec_with_required_boolean_path_param() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -75,6 +77,7 @@ fn create_spec_with_required_boolean_path_param() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     }
@@ -107,6 +110,7 @@ fn create_spec_with_required_boolean_query_param() -> CachedSpec {
                 default_value: None,
                 enum_values: vec![],
                 example: None,
+                encoding: None,
             }],
             request_body: None,
             security_requirements: vec![],
@@ -120,6 +124,7 @@ fn create_spec_with_required_boolean_query_param() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     }
@@ -153,6 +158,7 @@ fn create_spec_with_mixed_boolean_params() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
                 CachedParameter {
                     name: "optional-flag".to_string(),
@@ -165,6 +171,7 @@ fn create_spec_with_mixed_boolean_params() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 },
             ],
             request_body: None,
@@ -179,6 +186,7 @@ fn create_spec_with_mixed_boolean_params() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         security_schemes: std::collections::HashMap::new(),
     }
@@ -38,6 +38,7 @@ fn create_test_spec() -> CachedSpec {
                 default_value: None,
                 enum_values: vec![],
                 example: None,
+                encoding: None,
             }],
             request_body: None,
             responses: vec![],
@@ -51,6 +52,7 @@ fn create_test_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
@@ -421,6 +423,7 @@ fn create_authenticated_test_spec() -> CachedSpec {
                 source: constants::SOURCE_ENV.to_string(),
                 name: "TEST_AUTH_TOKEN".to_string(),
             }),
+            oauth2: None,
         },
     );
 
@@ -448,6 +451,7 @@ fn create_authenticated_test_spec() -> CachedSpec {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec!["https://api.example.com".to_string()],
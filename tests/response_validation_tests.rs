@@ -0,0 +1,266 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::CachedSpec;
+use aperture_cli::config::context_name::ApiContextName;
+use aperture_cli::config::manager::ConfigManager;
+use aperture_cli::config::models::{GlobalConfig, ResponseValidation};
+use aperture_cli::engine::executor::execute;
+use aperture_cli::error::{Error, ErrorKind};
+use aperture_cli::fs::OsFileSystem;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall};
+use serde_json::json;
+use std::collections::HashMap;
+use tempfile::TempDir;
+use test_helpers::cache_spec;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const CONTRACT_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Inventory API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /items:
+    get:
+      operationId: listItems
+      tags:
+        - items
+      responses:
+        '200':
+          description: The items
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  type: object
+                  required: [id, name, password]
+                  properties:
+                    id:
+                      type: integer
+                      readOnly: true
+                    name:
+                      type: string
+                    password:
+                      type: string
+                      writeOnly: true
+        '404':
+          description: Not found
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [message]
+                properties:
+                  message:
+                    type: string
+";
+
+fn cached_contract_spec() -> (CachedSpec, ConfigManager<OsFileSystem>, TempDir) {
+    let (spec, temp_dir) = cache_spec("inventory-api", CONTRACT_SPEC);
+    let manager = ConfigManager::with_fs(OsFileSystem, temp_dir.path().to_path_buf());
+    (spec, manager, temp_dir)
+}
+
+fn list_items_call() -> OperationCall {
+    OperationCall {
+        operation_id: "listItems".to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+async fn mock_items(server: &MockServer, status: u16, body: &serde_json::Value) {
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(status).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+async fn list_items(
+    server: &MockServer,
+    spec: &CachedSpec,
+    response_validation: Option<ResponseValidation>,
+    global_config: Option<GlobalConfig>,
+) -> Result<ExecutionResult, Error> {
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        response_validation,
+        global_config,
+        ..ExecutionContext::default()
+    };
+    execute(spec, list_items_call(), ctx).await
+}
+
+/// Returns the JSON pointers reported by a contract violation error
+fn violation_paths(err: &Error) -> Vec<String> {
+    let Error::Internal { kind, .. } = err else {
+        panic!("Expected an internal error, got {err}");
+    };
+    assert_eq!(*kind, ErrorKind::Validation);
+    let details = err.to_json().details.unwrap();
+    details["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|violation| violation["path"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_body_violations_fail_in_error_mode() {
+    let server = MockServer::start().await;
+    let (spec, _manager, _temp_dir) = cached_contract_spec();
+    mock_items(
+        &server,
+        200,
+        &json!([{"id": 1, "name": "Bolt"}, {"id": "2"}, {"id": 3, "name": 4}]),
+    )
+    .await;
+
+    let err = list_items(&server, &spec, Some(ResponseValidation::Error), None)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        violation_paths(&err),
+        vec!["/body/1", "/body/1/id", "/body/2/name"]
+    );
+    assert!(err.to_string().contains("status 200"), "{err}");
+}
+
+#[tokio::test]
+async fn test_read_only_properties_are_required_in_responses() {
+    let server = MockServer::start().await;
+    let (spec, _manager, _temp_dir) = cached_contract_spec();
+    // Write-only properties are never returned; read-only ones must be
+    mock_items(
+        &server,
+        200,
+        &json!([{"name": "Bolt"}, {"id": 2, "name": "Nut"}]),
+    )
+    .await;
+
+    let err = list_items(&server, &spec, Some(ResponseValidation::Error), None)
+        .await
+        .unwrap_err();
+    assert_eq!(violation_paths(&err), vec!["/body/0"]);
+    assert!(err.to_string().contains("\"id\""), "{err}");
+}
+
+#[tokio::test]
+async fn test_undocumented_status_is_a_violation() {
+    let server = MockServer::start().await;
+    let (spec, _manager, _temp_dir) = cached_contract_spec();
+    mock_items(&server, 202, &json!([])).await;
+
+    let err = list_items(&server, &spec, Some(ResponseValidation::Error), None)
+        .await
+        .unwrap_err();
+    assert_eq!(violation_paths(&err), vec!["/status"]);
+    assert!(err.to_string().contains("documented: 200, 404"), "{err}");
+}
+
+/// Returns the JSON pointers of the contract violations added to an HTTP error
+fn http_error_violation_paths(err: &Error) -> Vec<String> {
+    let Error::Internal { kind, .. } = err else {
+        panic!("Expected an internal error, got {err}");
+    };
+    assert_eq!(*kind, ErrorKind::HttpRequest);
+    let details = err.to_json().details.unwrap();
+    details["contract_violations"]
+        .as_array()
+        .map(|violations| {
+            violations
+                .iter()
+                .map(|violation| violation["path"].as_str().unwrap().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_undocumented_error_status_is_a_violation() {
+    let server = MockServer::start().await;
+    let (spec, _manager, _temp_dir) = cached_contract_spec();
+    mock_items(&server, 500, &json!({"message": "boom"})).await;
+
+    let err = list_items(&server, &spec, Some(ResponseValidation::Error), None)
+        .await
+        .unwrap_err();
+    assert_eq!(http_error_violation_paths(&err), vec!["/status"]);
+    assert!(
+        err.to_string().contains("status 500 is not documented"),
+        "{err}"
+    );
+
+    // Warn mode logs the violation and reports the HTTP error alone
+    let err = list_items(&server, &spec, Some(ResponseValidation::Warn), None)
+        .await
+        .unwrap_err();
+    assert!(http_error_violation_paths(&err).is_empty());
+}
+
+#[tokio::test]
+async fn test_error_bodies_are_checked_against_their_schema() {
+    let server = MockServer::start().await;
+    let (spec, _manager, _temp_dir) = cached_contract_spec();
+    mock_items(&server, 404, &json!({"error": "no items"})).await;
+
+    let err = list_items(&server, &spec, Some(ResponseValidation::Error), None)
+        .await
+        .unwrap_err();
+    assert_eq!(http_error_violation_paths(&err), vec!["/body"]);
+    let details = err.to_json().details.unwrap();
+    assert_eq!(details["status"], 404);
+}
+
+#[tokio::test]
+async fn test_warn_and_off_modes_return_the_response() {
+    let server = MockServer::start().await;
+    let (spec, _manager, _temp_dir) = cached_contract_spec();
+    mock_items(&server, 200, &json!([{"id": "1"}])).await;
+
+    for mode in [Some(ResponseValidation::Warn), None] {
+        let result = list_items(&server, &spec, mode, None).await.unwrap();
+        assert!(
+            matches!(result, ExecutionResult::Success { status: 200, .. }),
+            "{mode:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_api_setting_applies_unless_overridden() {
+    let server = MockServer::start().await;
+    let (spec, manager, _temp_dir) = cached_contract_spec();
+    mock_items(&server, 200, &json!([{"id": "1"}])).await;
+    manager
+        .set_response_validation(
+            &ApiContextName::new("inventory-api").unwrap(),
+            ResponseValidation::Error,
+        )
+        .unwrap();
+    let config = manager.load_global_config().unwrap();
+
+    let err = list_items(&server, &spec, None, Some(config.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(violation_paths(&err), vec!["/body/0", "/body/0/id"]);
+
+    // The command-line mode takes precedence over the API setting
+    let result = list_items(&server, &spec, Some(ResponseValidation::Off), Some(config))
+        .await
+        .unwrap();
+    assert!(matches!(result, ExecutionResult::Success { .. }));
+}
@@ -24,6 +24,7 @@ macro_rules! cached_parameter {
             default_value: None,
             enum_values: vec![],
             example: None,
+            encoding: None,
         }
     };
 }
@@ -50,6 +51,7 @@ macro_rules! cached_command {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }
     };
 }
@@ -34,6 +34,7 @@ fn create_test_spec(name: &str) -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 }],
                 request_body: None,
                 responses: vec![],
@@ -46,6 +47,7 @@ fn create_test_spec(name: &str) -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 operation_id: "listUsers".to_string(),
@@ -67,6 +69,7 @@ fn create_test_spec(name: &str) -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 operation_id: "createUser".to_string(),
@@ -88,6 +91,7 @@ fn create_test_spec(name: &str) -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 operation_id: "getIssue".to_string(),
@@ -109,6 +113,7 @@ fn create_test_spec(name: &str) -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         servers: vec![],
@@ -343,6 +348,7 @@ fn test_search_finds_by_display_name() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec![],
@@ -394,6 +400,7 @@ fn test_search_finds_by_alias() {
             aliases: vec!["lookup".to_string()],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec![],
@@ -438,6 +445,7 @@ fn test_search_suggestions_include_display_names() {
             aliases: vec!["show".to_string()],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec![],
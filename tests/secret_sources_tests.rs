@@ -0,0 +1,332 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::{
+    CachedApertureSecret, CachedCommand, CachedSecurityScheme, CachedSpec, PaginationInfo,
+    CACHE_FORMAT_VERSION,
+};
+use aperture_cli::config::models::{ApertureSecret, ApiConfig, GlobalConfig, SecretSource};
+use aperture_cli::config::vault::Vault;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::error::{Error, ErrorKind};
+use aperture_cli::invocation::{ExecutionContext, OperationCall};
+use aperture_cli::logging::SecretContext;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Builds a spec with one bearer-protected operation and an optional `x-aperture-secret`
+fn bearer_spec(aperture_secret: Option<(&str, &str)>) -> CachedSpec {
+    let mut security_schemes = HashMap::new();
+    security_schemes.insert(
+        "bearerAuth".to_string(),
+        CachedSecurityScheme {
+            name: "bearerAuth".to_string(),
+            scheme_type: "http".to_string(),
+            scheme: Some("bearer".to_string()),
+            location: Some("header".to_string()),
+            parameter_name: Some("Authorization".to_string()),
+            description: None,
+            bearer_format: None,
+            aperture_secret: aperture_secret.map(|(source, name)| CachedApertureSecret {
+                source: source.to_string(),
+                name: name.to_string(),
+            }),
+            oauth2: None,
+        },
+    );
+
+    CachedSpec {
+        cache_format_version: CACHE_FORMAT_VERSION,
+        name: "secrets-api".to_string(),
+        version: "1.0.0".to_string(),
+        commands: vec![CachedCommand {
+            name: "items".to_string(),
+            description: None,
+            summary: None,
+            operation_id: "listItems".to_string(),
+            method: "GET".to_string(),
+            path: "/items".to_string(),
+            parameters: vec![],
+            request_body: None,
+            responses: vec![],
+            security_requirements: vec!["bearerAuth".to_string()],
+            tags: vec!["items".to_string()],
+            deprecated: false,
+            external_docs_url: None,
+            examples: vec![],
+            display_group: None,
+            display_name: None,
+            aliases: vec![],
+            hidden: false,
+            pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
+        }],
+        base_url: None,
+        servers: vec![],
+        security_schemes,
+        skipped_endpoints: vec![],
+        server_variables: HashMap::new(),
+    }
+}
+
+fn config_with_secret(source: SecretSource, name: &str) -> GlobalConfig {
+    let mut secrets = HashMap::new();
+    secrets.insert(
+        "bearerAuth".to_string(),
+        ApertureSecret {
+            source,
+            name: name.to_string(),
+        },
+    );
+
+    let mut api_configs = HashMap::new();
+    api_configs.insert(
+        "secrets-api".to_string(),
+        ApiConfig {
+            base_url_override: None,
+            environment_urls: HashMap::new(),
+            strict_mode: false,
+            secrets,
+            command_mapping: None,
+            response_validation: None,
+        },
+    );
+
+    GlobalConfig {
+        api_configs,
+        ..Default::default()
+    }
+}
+
+fn list_items_call() -> OperationCall {
+    OperationCall {
+        operation_id: "listItems".to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+fn write_private_file(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).unwrap();
+    }
+}
+
+async fn expect_bearer(server: &MockServer, token: &str) {
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(header("authorization", format!("Bearer {token}").as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn context(server: &MockServer, global_config: Option<GlobalConfig>) -> ExecutionContext {
+    ExecutionContext {
+        base_url: Some(server.uri()),
+        global_config,
+        ..ExecutionContext::default()
+    }
+}
+
+#[tokio::test]
+async fn test_config_secret_from_file_is_trimmed() {
+    let server = MockServer::start().await;
+    let temp_dir = TempDir::new().unwrap();
+    let token_path = temp_dir.path().join("token");
+    write_private_file(&token_path, "  file-token-value\n");
+
+    expect_bearer(&server, "file-token-value").await;
+    let config = config_with_secret(SecretSource::File, token_path.to_str().unwrap());
+    execute(
+        &bearer_spec(None),
+        list_items_call(),
+        context(&server, Some(config)),
+    )
+    .await
+    .expect("request should succeed");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_secret_file_readable_by_others_is_rejected() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let server = MockServer::start().await;
+    let temp_dir = TempDir::new().unwrap();
+    let token_path = temp_dir.path().join("token");
+    std::fs::write(&token_path, "shared-token").unwrap();
+    std::fs::set_permissions(&token_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    let config = config_with_secret(SecretSource::File, token_path.to_str().unwrap());
+    let err = execute(
+        &bearer_spec(None),
+        list_items_call(),
+        context(&server, Some(config)),
+    )
+    .await
+    .expect_err("world-readable secret files must be rejected");
+
+    match err {
+        Error::Internal {
+            kind: ErrorKind::Authentication,
+            message,
+            ..
+        } => {
+            assert!(message.contains("bearerAuth"), "{message}");
+            assert!(message.contains("chmod 600"), "{message}");
+        }
+        other => panic!("Expected authentication error, got {other:?}"),
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_config_secret_from_command() {
+    let server = MockServer::start().await;
+
+    expect_bearer(&server, "helper-token").await;
+    let config = config_with_secret(SecretSource::Command, "echo helper-token");
+    execute(
+        &bearer_spec(None),
+        list_items_call(),
+        context(&server, Some(config)),
+    )
+    .await
+    .expect("request should succeed");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_aperture_secret_command_is_never_run() {
+    let server = MockServer::start().await;
+    let temp_dir = TempDir::new().unwrap();
+    let marker = temp_dir.path().join("pwned");
+
+    // A cached spec may predate the validator rejecting these sources
+    let command = format!("touch {}; echo spec-token", marker.display());
+    let spec = bearer_spec(Some(("command", &command)));
+    let err = execute(&spec, list_items_call(), context(&server, None))
+        .await
+        .expect_err("a spec must not choose the command source");
+
+    assert!(!marker.exists());
+    let message = err.to_string();
+    assert!(message.contains("aperture config secret set"), "{message}");
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_failing_secret_command_is_reported() {
+    let server = MockServer::start().await;
+
+    let config = config_with_secret(SecretSource::Command, "echo 'vault sealed' >&2; exit 2");
+    let err = execute(
+        &bearer_spec(None),
+        list_items_call(),
+        context(&server, Some(config)),
+    )
+    .await
+    .expect_err("a failing helper should abort the request");
+
+    let message = err.to_string();
+    assert!(message.contains("vault sealed"), "{message}");
+}
+
+/// Config directory shared by the tests that resolve `dotenv` and `vault` secrets
+///
+/// Both read `APERTURE_CONFIG_DIR`, so it is set once for the whole test binary.
+fn shared_config_dir() -> &'static Path {
+    static CONFIG_DIR: OnceLock<TempDir> = OnceLock::new();
+    CONFIG_DIR
+        .get_or_init(|| {
+            let dir = TempDir::new().unwrap();
+            std::env::set_var("APERTURE_CONFIG_DIR", dir.path());
+            dir
+        })
+        .path()
+}
+
+#[tokio::test]
+async fn test_aperture_secret_from_dotenv_file() {
+    let server = MockServer::start().await;
+    write_private_file(
+        &shared_config_dir().join("env").join("secrets-api.env"),
+        "# secrets-api credentials\nexport DOTENV_TOKEN=\"dotenv-token-value\"\n",
+    );
+
+    expect_bearer(&server, "dotenv-token-value").await;
+    let spec = bearer_spec(Some(("dotenv", "DOTENV_TOKEN")));
+    execute(&spec, list_items_call(), context(&server, None))
+        .await
+        .expect("request should succeed");
+}
+
+#[tokio::test]
+async fn test_aperture_secret_from_vault() {
+    let server = MockServer::start().await;
+    std::env::set_var("APERTURE_VAULT_PASSPHRASE", "secret-sources-passphrase");
+    let mut vault = Vault::create(
+        Vault::path_in(shared_config_dir()),
+        "secret-sources-passphrase",
+    )
+    .unwrap();
+    vault.insert("api-token", "vault-token-value");
+    vault.save().unwrap();
+
+    expect_bearer(&server, "vault-token-value").await;
+    let spec = bearer_spec(Some(("vault", "api-token")));
+    execute(&spec, list_items_call(), context(&server, None))
+        .await
+        .expect("request should succeed");
+
+    let ctx = SecretContext::from_spec_and_config(&spec, "secrets-api", None);
+    assert!(ctx.is_secret("vault-token-value"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_secret_context_collects_non_env_sources() {
+    let temp_dir = TempDir::new().unwrap();
+    let token_path = temp_dir.path().join("token");
+    write_private_file(&token_path, "config-file-secret\n");
+
+    let spec = bearer_spec(None);
+    let mut config = config_with_secret(SecretSource::File, token_path.to_str().unwrap());
+    config
+        .api_configs
+        .get_mut("secrets-api")
+        .unwrap()
+        .secrets
+        .insert(
+            "otherAuth".to_string(),
+            ApertureSecret {
+                source: SecretSource::Command,
+                name: "echo other-command-secret".to_string(),
+            },
+        );
+
+    let ctx = SecretContext::from_spec_and_config(&spec, "secrets-api", Some(&config));
+    assert!(ctx.is_secret("config-file-secret"));
+    assert!(ctx.is_secret("other-command-secret"));
+    assert_eq!(
+        ctx.redact_secrets_in_text("token=config-file-secret"),
+        "token=[REDACTED]"
+    );
+}
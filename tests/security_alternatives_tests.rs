@@ -0,0 +1,368 @@
+mod test_helpers;
+
+use aperture_cli::agent::generate_capability_manifest;
+use aperture_cli::cache::models::CachedSpec;
+use aperture_cli::config::models::{ApertureSecret, ApiConfig, GlobalConfig, SecretSource};
+use aperture_cli::docs::DocumentationGenerator;
+use aperture_cli::engine::executor::execute;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall};
+use aperture_cli::response_cache::CacheConfig;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use tempfile::TempDir;
+use test_helpers::cache_spec;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// The bearer token and query token variables are never set, so requirement
+// sets using them are never satisfiable in these tests
+const KEY_ENV: &str = "APERTURE_TEST_ALT_KEY";
+const APP_ENV: &str = "APERTURE_TEST_ALT_APP";
+
+const ALTERNATIVES_SPEC: &str = r"
+openapi: 3.0.0
+info:
+  title: Reports API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+components:
+  securitySchemes:
+    bearerAuth:
+      type: http
+      scheme: bearer
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_ALT_TOKEN
+    apiKey:
+      type: apiKey
+      in: header
+      name: X-API-Key
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_ALT_KEY
+    appId:
+      type: apiKey
+      in: query
+      name: app_id
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_ALT_APP
+    queryToken:
+      type: apiKey
+      in: query
+      name: token
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_ALT_QUERY_TOKEN
+security:
+  - bearerAuth: []
+  - apiKey: []
+    appId: []
+paths:
+  /reports:
+    get:
+      operationId: getReports
+      tags:
+        - reports
+      responses:
+        '200':
+          description: Reports
+  /status:
+    get:
+      operationId: getStatus
+      tags:
+        - reports
+      security: []
+      responses:
+        '200':
+          description: Service status
+  /public:
+    get:
+      operationId: getPublic
+      tags:
+        - reports
+      security:
+        - {}
+        - bearerAuth: []
+      responses:
+        '200':
+          description: Public reports
+  /exports:
+    get:
+      operationId: getExports
+      tags:
+        - reports
+      security:
+        - apiKey: []
+          bearerAuth: []
+        - apiKey: []
+      responses:
+        '200':
+          description: Exports
+  /feed:
+    get:
+      operationId: getFeed
+      tags:
+        - reports
+      security:
+        - queryToken: []
+        - {}
+      responses:
+        '200':
+          description: Public feed
+";
+
+fn cached_alternatives_spec() -> (CachedSpec, TempDir) {
+    let (spec, temp_dir) = cache_spec("reports-api", ALTERNATIVES_SPEC);
+    std::env::set_var(KEY_ENV, "key-123");
+    std::env::set_var(APP_ENV, "app-456");
+    (spec, temp_dir)
+}
+
+fn call(operation_id: &str) -> OperationCall {
+    OperationCall {
+        operation_id: operation_id.to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+/// Executes the operation in dry-run mode and returns the request info
+async fn dry_run(
+    spec: &CachedSpec,
+    operation_id: &str,
+    auth_scheme: Option<&str>,
+) -> Result<serde_json::Value, aperture_cli::error::Error> {
+    let ctx = ExecutionContext {
+        dry_run: true,
+        auth_scheme: auth_scheme.map(String::from),
+        ..ExecutionContext::default()
+    };
+    match execute(spec, call(operation_id), ctx).await? {
+        ExecutionResult::DryRun { request_info } => Ok(request_info),
+        _ => panic!("Expected DryRun result"),
+    }
+}
+
+/// Maps the `apiKey` scheme to a shell command, as `aperture config secret set` does
+fn api_key_command_config(command: &str) -> GlobalConfig {
+    let secrets = HashMap::from([(
+        "apiKey".to_string(),
+        ApertureSecret {
+            source: SecretSource::Command,
+            name: command.to_string(),
+        },
+    )]);
+    let api_config = ApiConfig {
+        base_url_override: None,
+        environment_urls: HashMap::new(),
+        strict_mode: false,
+        secrets,
+        command_mapping: None,
+        response_validation: None,
+    };
+    GlobalConfig {
+        api_configs: HashMap::from([("reports-api".to_string(), api_config)]),
+        ..Default::default()
+    }
+}
+
+fn strings(sets: &[&[&str]]) -> Vec<Vec<String>> {
+    sets.iter()
+        .map(|set| set.iter().map(|name| (*name).to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn test_requirement_sets_are_cached() {
+    let (spec, _temp_dir) = cached_alternatives_spec();
+    let command = |operation_id: &str| {
+        spec.commands
+            .iter()
+            .find(|command| command.operation_id == operation_id)
+            .unwrap()
+    };
+
+    let reports = command("getReports");
+    assert_eq!(
+        reports.security_requirement_sets,
+        strings(&[&["bearerAuth"], &["apiKey", "appId"]])
+    );
+    assert_eq!(
+        reports.security_requirements,
+        vec!["bearerAuth", "apiKey", "appId"]
+    );
+
+    // `security: []` opts out of the global requirements
+    assert!(command("getStatus").security_requirement_sets.is_empty());
+    assert_eq!(
+        command("getPublic").security_requirement_sets,
+        strings(&[&[], &["bearerAuth"]])
+    );
+}
+
+#[tokio::test]
+async fn test_first_satisfiable_set_is_sent_together() {
+    let (spec, _temp_dir) = cached_alternatives_spec();
+
+    let request_info = dry_run(&spec, "getReports", None).await.unwrap();
+    assert_eq!(
+        request_info["url"],
+        "https://api.example.com/reports?app_id=[REDACTED]"
+    );
+    assert_eq!(request_info["headers"]["x-api-key"], "[REDACTED]");
+    assert!(request_info["headers"].get("authorization").is_none());
+}
+
+#[tokio::test]
+async fn test_anonymous_alternatives_send_no_credentials() {
+    let (spec, _temp_dir) = cached_alternatives_spec();
+
+    for operation_id in ["getStatus", "getPublic"] {
+        let request_info = dry_run(&spec, operation_id, None).await.unwrap();
+        let headers = &request_info["headers"];
+        assert!(headers.get("authorization").is_none(), "{operation_id}");
+        assert!(headers.get("x-api-key").is_none(), "{operation_id}");
+    }
+}
+
+#[tokio::test]
+async fn test_auth_scheme_forces_a_requirement_set() {
+    let (spec, _temp_dir) = cached_alternatives_spec();
+
+    // The forced set must be satisfiable; there is no silent fallback
+    let err = dry_run(&spec, "getReports", Some("bearerAuth"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("APERTURE_TEST_ALT_TOKEN"), "{err}");
+
+    let request_info = dry_run(&spec, "getReports", Some("appId")).await.unwrap();
+    assert_eq!(request_info["headers"]["x-api-key"], "[REDACTED]");
+
+    let err = dry_run(&spec, "getStatus", Some("apiKey"))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("does not accept security scheme 'apiKey'"),
+        "{err}"
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_shared_scheme_secret_is_resolved_once() {
+    let (spec, temp_dir) = cached_alternatives_spec();
+    let runs = temp_dir.path().join("runs");
+
+    // `apiKey` appears in both sets; the first fails on the bearer token
+    let ctx = ExecutionContext {
+        dry_run: true,
+        global_config: Some(api_key_command_config(&format!(
+            "echo run >> {}; echo command-key",
+            runs.display()
+        ))),
+        ..ExecutionContext::default()
+    };
+    let ExecutionResult::DryRun { request_info } =
+        execute(&spec, call("getExports"), ctx).await.unwrap()
+    else {
+        panic!("Expected DryRun result")
+    };
+
+    assert_eq!(request_info["headers"]["x-api-key"], "[REDACTED]");
+    assert!(request_info["headers"].get("authorization").is_none());
+    assert_eq!(std::fs::read_to_string(&runs).unwrap(), "run\n");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_failing_secret_source_is_reported_when_no_set_resolves() {
+    let (spec, _temp_dir) = cached_alternatives_spec();
+
+    let ctx = ExecutionContext {
+        dry_run: true,
+        global_config: Some(api_key_command_config(
+            "echo 'keyring is locked' >&2; exit 3",
+        )),
+        ..ExecutionContext::default()
+    };
+    let err = execute(&spec, call("getReports"), ctx).await.unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("apiKey"), "{message}");
+    assert!(message.contains("keyring is locked"), "{message}");
+}
+
+#[tokio::test]
+async fn test_cacheability_follows_the_selected_set() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/feed"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .mount(&server)
+        .await;
+    let (spec, temp_dir) = cached_alternatives_spec();
+    let cache_dir = temp_dir.path().join("responses");
+
+    // The query token is unavailable, so the anonymous set is sent and the
+    // response may be cached
+    let ctx = ExecutionContext {
+        base_url: Some(server.uri()),
+        cache_config: Some(CacheConfig {
+            cache_dir: cache_dir.clone(),
+            default_ttl: Duration::from_mins(1),
+            max_entries: 10,
+            enabled: true,
+            allow_authenticated: false,
+        }),
+        ..ExecutionContext::default()
+    };
+    execute(&spec, call("getFeed"), ctx).await.unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    assert_eq!(received[0].url.query(), None);
+    let cached = std::fs::read_dir(&cache_dir)
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .count();
+    assert_eq!(cached, 1);
+}
+
+#[test]
+fn test_manifest_and_docs_report_alternatives() {
+    let (spec, _temp_dir) = cached_alternatives_spec();
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&generate_capability_manifest(&spec, None).unwrap()).unwrap();
+    let commands = manifest["commands"]["reports"].as_array().unwrap();
+    let reports = commands
+        .iter()
+        .find(|command| command["operation_id"] == "getReports")
+        .unwrap();
+    assert_eq!(
+        reports["security_alternatives"],
+        serde_json::json!([["bearerAuth"], ["apiKey", "appId"]])
+    );
+
+    let docs = DocumentationGenerator::new(BTreeMap::from([("reports-api".to_string(), spec)]));
+    let help = docs
+        .generate_command_help("reports-api", "reports", "get-public")
+        .unwrap();
+    assert!(
+        help.contains("- none (anonymous access)\n- bearerAuth\n"),
+        "{help}"
+    );
+    let help = docs
+        .generate_command_help("reports-api", "reports", "get-reports")
+        .unwrap();
+    assert!(help.contains("- bearerAuth\n- apiKey + appId\n"), "{help}");
+}
@@ -26,6 +26,7 @@ macro_rules! cached_parameter {
             default_value: None,
             enum_values: vec![],
             example: None,
+            encoding: None,
         }
     };
 }
@@ -52,6 +53,7 @@ macro_rules! cached_command {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }
     };
 }
@@ -74,6 +76,7 @@ fn create_secure_test_spec(bearer_env_var: &str, api_key_env_var: &str) -> Cache
                 source: "env".to_string(),
                 name: bearer_env_var.to_string(),
             }),
+            oauth2: None,
         },
     );
 
@@ -92,6 +95,7 @@ fn create_secure_test_spec(bearer_env_var: &str, api_key_env_var: &str) -> Cache
                 source: "env".to_string(),
                 name: api_key_env_var.to_string(),
             }),
+            oauth2: None,
         },
     );
 
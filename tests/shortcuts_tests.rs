@@ -33,6 +33,7 @@ fn create_test_spec_with_multiple_operations() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "pets".to_string(),
@@ -54,6 +55,7 @@ fn create_test_spec_with_multiple_operations() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "users".to_string(),
@@ -75,6 +77,7 @@ fn create_test_spec_with_multiple_operations() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         base_url: Some("https://api.petstore.com".to_string()),
@@ -338,6 +341,7 @@ fn test_duplicate_candidates_are_deduplicated_by_effective_command_path() {
             aliases: vec![],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec![],
@@ -390,6 +394,7 @@ fn test_shortcut_uses_display_names_in_full_command() {
             aliases: vec!["get".to_string()],
             hidden: false,
             pagination: PaginationInfo::default(),
+            security_requirement_sets: vec![],
         }],
         base_url: Some("https://api.example.com".to_string()),
         servers: vec![],
@@ -42,6 +42,7 @@ fn create_nested_test_spec() -> CachedSpec {
                     default_value: None,
                     enum_values: vec![],
                     example: None,
+                    encoding: None,
                 }],
                 request_body: None,
                 security_requirements: vec![],
@@ -55,6 +56,7 @@ fn create_nested_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
             CachedCommand {
                 name: "getUser".to_string(),
@@ -75,6 +77,7 @@ fn create_nested_test_spec() -> CachedSpec {
                         default_value: None,
                         enum_values: vec![],
                         example: None,
+                        encoding: None,
                     },
                     CachedParameter {
                         name: "includeDetails".to_string(),
@@ -87,6 +90,7 @@ fn create_nested_test_spec() -> CachedSpec {
                         default_value: None,
                         enum_values: vec![],
                         example: None,
+                        encoding: None,
                     },
                 ],
                 request_body: None,
@@ -101,6 +105,7 @@ fn create_nested_test_spec() -> CachedSpec {
                 aliases: vec![],
                 hidden: false,
                 pagination: PaginationInfo::default(),
+                security_requirement_sets: vec![],
             },
         ],
         security_schemes: std::collections::HashMap::new(),
@@ -284,6 +289,7 @@ async fn test_show_examples_does_not_require_valid_json_body() {
             required: false,
             description: None,
             example: None,
+            encoding: std::collections::HashMap::new(),
         }),
         security_requirements: vec![],
         examples: vec![],
@@ -296,6 +302,7 @@ async fn test_show_examples_does_not_require_valid_json_body() {
         aliases: vec![],
         hidden: false,
         pagination: PaginationInfo::default(),
+        security_requirement_sets: vec![],
     });
 
     let cmd = generate_command_tree_with_flags(&spec, false);
@@ -0,0 +1,318 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::CachedSpec;
+use aperture_cli::cli::EmitFormat;
+use aperture_cli::engine::executor;
+use aperture_cli::invocation::{ExecutionContext, ExecutionResult, OperationCall};
+use aperture_cli::snippet;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use test_helpers::cache_spec;
+
+const NOTES_SPEC: &str = r"
+openapi: 3.0.3
+info:
+  title: Notes API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+components:
+  securitySchemes:
+    bearerAuth:
+      type: http
+      scheme: bearer
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_SNIPPET_TOKEN
+    basicAuth:
+      type: http
+      scheme: basic
+      x-aperture-secret:
+        source: vault
+        name: notes-credentials
+    queryKey:
+      type: apiKey
+      in: query
+      name: api_key
+      x-aperture-secret:
+        source: env
+        name: APERTURE_TEST_SNIPPET_KEY
+paths:
+  /notes:
+    post:
+      operationId: createNote
+      tags: [notes]
+      security:
+        - bearerAuth: []
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                text:
+                  type: string
+      responses:
+        '201':
+          description: Created
+  /notes/{noteId}:
+    get:
+      operationId: getNote
+      tags: [notes]
+      security:
+        - basicAuth: []
+          queryKey: []
+      parameters:
+        - name: noteId
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: The note
+  /notes/{noteId}/attachments:
+    post:
+      operationId: attachToNote
+      tags: [notes]
+      parameters:
+        - name: noteId
+          in: path
+          required: true
+          schema:
+            type: integer
+      requestBody:
+        required: true
+        content:
+          multipart/form-data:
+            schema:
+              type: object
+              properties:
+                caption:
+                  type: string
+      responses:
+        '201':
+          description: Attached
+";
+
+fn call(operation_id: &str) -> OperationCall {
+    OperationCall {
+        operation_id: operation_id.to_string(),
+        path_params: HashMap::new(),
+        query_params: HashMap::new(),
+        header_params: HashMap::new(),
+        cookie_params: HashMap::new(),
+        multi_params: HashMap::new(),
+        body: None,
+        files: vec![],
+        raw_body: None,
+        custom_headers: vec![],
+    }
+}
+
+/// Describes a request with secret references, as `--emit` does
+async fn describe(spec: &CachedSpec, call: OperationCall) -> Value {
+    let context = ExecutionContext {
+        dry_run: true,
+        secret_references: true,
+        ..ExecutionContext::default()
+    };
+    match executor::execute(spec, call, context).await.unwrap() {
+        ExecutionResult::DryRun { request_info } => request_info,
+        other => panic!("expected a request description, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_secrets_become_environment_references_without_being_read() {
+    // None of the secrets are set; references never read them
+    let (spec, _temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let mut create_note = call("createNote");
+    create_note.body = Some(r#"{"text":"it's done"}"#.to_string());
+    let request_info = describe(&spec, create_note).await;
+
+    assert_eq!(
+        request_info["headers"]["authorization"],
+        json!([{"text": "Bearer "}, {"env": "APERTURE_TEST_SNIPPET_TOKEN"}])
+    );
+
+    let curl = snippet::render(&EmitFormat::Curl, &request_info);
+    assert!(
+        curl.starts_with("curl \\\n  -X POST 'https://api.example.com/notes'"),
+        "{curl}"
+    );
+    assert!(
+        curl.contains(r#"-H "authorization: Bearer ${APERTURE_TEST_SNIPPET_TOKEN}""#),
+        "{curl}"
+    );
+    assert!(
+        curl.contains("-H 'content-type: application/json'"),
+        "{curl}"
+    );
+    assert!(
+        curl.contains(r#"--data-raw '{"text":"it'\''s done"}'"#),
+        "{curl}"
+    );
+
+    let python = snippet::render(&EmitFormat::PythonRequests, &request_info);
+    assert!(python.starts_with("import os\n"), "{python}");
+    assert!(
+        python
+            .contains(r#""authorization": f"Bearer {os.environ['APERTURE_TEST_SNIPPET_TOKEN']}","#),
+        "{python}"
+    );
+
+    let js = snippet::render(&EmitFormat::JsFetch, &request_info);
+    assert!(
+        js.contains(r#""authorization": `Bearer ${process.env.APERTURE_TEST_SNIPPET_TOKEN}`,"#),
+        "{js}"
+    );
+    assert!(js.contains(r#"body: "{\"text\":\"it's done\"}","#), "{js}");
+
+    let rust = snippet::render(&EmitFormat::RustReqwest, &request_info);
+    assert!(
+        rust.contains(
+            r#".header("authorization", format!("Bearer {}", std::env::var("APERTURE_TEST_SNIPPET_TOKEN")?))"#
+        ),
+        "{rust}"
+    );
+    assert!(rust.contains(".request(reqwest::Method::POST, "), "{rust}");
+}
+
+#[tokio::test]
+async fn test_basic_auth_and_query_keys_use_references() {
+    let (spec, _temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let mut get_note = call("getNote");
+    get_note
+        .path_params
+        .insert("noteId".to_string(), "7".to_string());
+    let request_info = describe(&spec, get_note).await;
+
+    // Secrets that are not read from the environment get a variable named
+    // after the API and scheme
+    assert_eq!(
+        request_info["url"],
+        json!([
+            {"text": "https://api.example.com/notes/7?api_key="},
+            {"env": "APERTURE_TEST_SNIPPET_KEY"}
+        ])
+    );
+    assert_eq!(
+        request_info["headers"]["authorization"],
+        json!([{"text": "Basic "}, {"env": "NOTES_BASICAUTH"}])
+    );
+
+    let curl = snippet::render(&EmitFormat::Curl, &request_info);
+    assert!(
+        curl.starts_with(
+            r#"curl "https://api.example.com/notes/7?api_key=${APERTURE_TEST_SNIPPET_KEY}""#
+        ),
+        "{curl}"
+    );
+    assert!(curl.contains(r#"--user "${NOTES_BASICAUTH}""#), "{curl}");
+    assert!(!curl.contains("authorization"), "{curl}");
+
+    let httpie = snippet::render(&EmitFormat::Httpie, &request_info);
+    assert!(
+        httpie.starts_with(
+            r#"http \
+  --auth "${NOTES_BASICAUTH}" GET"#
+        ),
+        "{httpie}"
+    );
+
+    let python = snippet::render(&EmitFormat::PythonRequests, &request_info);
+    assert!(
+        python.contains(r#"auth=tuple(os.environ["NOTES_BASICAUTH"].split(":", 1)),"#),
+        "{python}"
+    );
+    assert!(
+        python.contains(
+            r#"f"https://api.example.com/notes/7?api_key={os.environ['APERTURE_TEST_SNIPPET_KEY']}","#
+        ),
+        "{python}"
+    );
+
+    let rust = snippet::render(&EmitFormat::RustReqwest, &request_info);
+    assert!(
+        rust.contains(".basic_auth(username, Some(password))"),
+        "{rust}"
+    );
+}
+
+#[tokio::test]
+async fn test_user_values_are_never_taken_for_references() {
+    let (spec, _temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let mut create_note = call("createNote");
+    create_note.body = Some(r#"{"text":"${HOME}"}"#.to_string());
+    create_note
+        .custom_headers
+        .push("X-Note: keep ${HOME} as is".to_string());
+    let request_info = describe(&spec, create_note).await;
+
+    let curl = snippet::render(&EmitFormat::Curl, &request_info);
+    assert!(curl.contains("-H 'x-note: keep ${HOME} as is'"), "{curl}");
+    assert!(
+        curl.contains(r#"--data-raw '{"text":"${HOME}"}'"#),
+        "{curl}"
+    );
+
+    let python = snippet::render(&EmitFormat::PythonRequests, &request_info);
+    assert!(
+        python.contains(r#""x-note": "keep ${HOME} as is","#),
+        "{python}"
+    );
+
+    let rust = snippet::render(&EmitFormat::RustReqwest, &request_info);
+    assert!(
+        rust.contains(r#".header("x-note", "keep ${HOME} as is")"#),
+        "{rust}"
+    );
+}
+
+#[tokio::test]
+async fn test_header_variables_stay_references() {
+    // Set, so a reference that was expanded would show the value
+    std::env::set_var("APERTURE_TEST_SNIPPET_TRACE", "trace-value");
+    let (spec, _temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let mut create_note = call("createNote");
+    create_note.body = Some("{}".to_string());
+    create_note
+        .custom_headers
+        .push("X-Trace: ${APERTURE_TEST_SNIPPET_TRACE}".to_string());
+    let request_info = describe(&spec, create_note).await;
+    std::env::remove_var("APERTURE_TEST_SNIPPET_TRACE");
+
+    assert_eq!(
+        request_info["headers"]["x-trace"],
+        json!([{"env": "APERTURE_TEST_SNIPPET_TRACE"}])
+    );
+    let curl = snippet::render(&EmitFormat::Curl, &request_info);
+    assert!(
+        curl.contains(r#"-H "x-trace: ${APERTURE_TEST_SNIPPET_TRACE}""#),
+        "{curl}"
+    );
+    assert!(!curl.contains("trace-value"), "{curl}");
+}
+
+#[tokio::test]
+async fn test_multipart_text_is_sent_as_written() {
+    let (spec, _temp_dir) = cache_spec("notes", NOTES_SPEC);
+    let mut attach = call("attachToNote");
+    attach
+        .path_params
+        .insert("noteId".to_string(), "7".to_string());
+    attach.body = Some(r#"{"caption":"@/etc/passwd"}"#.to_string());
+    let request_info = describe(&spec, attach).await;
+
+    let curl = snippet::render(&EmitFormat::Curl, &request_info);
+    assert!(
+        curl.contains("--form-string 'caption=@/etc/passwd'"),
+        "{curl}"
+    );
+    assert!(!curl.contains("-F"), "{curl}");
+
+    let httpie = snippet::render(&EmitFormat::Httpie, &request_info);
+    assert!(httpie.contains(r"'caption=\@/etc/passwd'"), "{httpie}");
+}
@@ -30,27 +30,27 @@ fn create_temp_config_manager() -> (ConfigManager<OsFileSystem>, TempDir) {
 }
 
 #[test]
-fn test_non_strict_mode_accepts_spec_with_multipart() {
+fn test_non_strict_mode_accepts_spec_with_xml() {
     let (config_manager, _temp_dir) = create_temp_config_manager();
 
-    // Add spec with multipart endpoints (non-strict mode - default)
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml");
-    let result = config_manager.add_spec(&name("test-multipart"), spec_path, false, false);
+    // Add spec with XML endpoints (non-strict mode - default)
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml");
+    let result = config_manager.add_spec(&name("test-xml"), spec_path, false, false);
 
     assert!(
         result.is_ok(),
-        "Non-strict mode should accept spec with multipart endpoints"
+        "Non-strict mode should accept spec with XML endpoints"
     );
 
     // Verify the spec was cached
     let cache_dir = _temp_dir.path().join(".cache");
-    let cached_spec = load_cached_spec(&cache_dir, "test-multipart").unwrap();
+    let cached_spec = load_cached_spec(&cache_dir, "test-xml").unwrap();
 
     // Verify that only JSON endpoints were included
     assert_eq!(
         cached_spec.commands.len(),
         3,
-        "Should have 3 commands (excluding multipart endpoints)"
+        "Should have 3 commands (excluding XML endpoints)"
     );
 
     let operation_ids: Vec<&str> = cached_spec
@@ -63,22 +63,22 @@ fn test_non_strict_mode_accepts_spec_with_multipart() {
     assert!(operation_ids.contains(&"getUserById"));
     assert!(operation_ids.contains(&"generateReport"));
 
-    // Multipart endpoints should be excluded
-    assert!(!operation_ids.contains(&"uploadUserAvatar"));
-    assert!(!operation_ids.contains(&"uploadDocument"));
+    // XML endpoints should be excluded
+    assert!(!operation_ids.contains(&"importUserProfile"));
+    assert!(!operation_ids.contains(&"importDocument"));
 }
 
 #[test]
-fn test_strict_mode_rejects_spec_with_multipart() {
+fn test_strict_mode_rejects_spec_with_xml() {
     let (config_manager, _temp_dir) = create_temp_config_manager();
 
-    // Add spec with multipart endpoints (strict mode)
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml");
-    let result = config_manager.add_spec(&name("test-multipart"), spec_path, false, true);
+    // Add spec with XML endpoints (strict mode)
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml");
+    let result = config_manager.add_spec(&name("test-xml"), spec_path, false, true);
 
     assert!(
         result.is_err(),
-        "Strict mode should reject spec with multipart endpoints"
+        "Strict mode should reject spec with XML endpoints"
     );
 
     match result.unwrap_err() {
@@ -88,12 +88,12 @@ fn test_strict_mode_rejects_spec_with_multipart() {
             ..
         } => {
             assert!(
-                msg.contains("multipart/form-data"),
-                "Error should mention multipart/form-data"
+                msg.contains("application/xml"),
+                "Error should mention application/xml"
             );
             assert!(
-                msg.contains("v1.0"),
-                "Error should mention version limitation"
+                msg.contains("multipart/form-data"),
+                "Error should list the supported content types"
             );
         }
         _ => panic!("Expected Validation error"),
@@ -104,7 +104,7 @@ fn test_strict_mode_rejects_spec_with_multipart() {
 fn test_cli_non_strict_mode_with_warnings() {
     let temp_dir = TempDir::new().unwrap();
     let config_dir = temp_dir.path().join(".aperture");
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml")
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml")
         .canonicalize()
         .unwrap();
 
@@ -133,11 +133,11 @@ fn test_cli_non_strict_mode_with_warnings() {
         "Should mention unsupported content types"
     );
     assert!(
-        stderr.contains("multipart/form-data"),
+        stderr.contains("application/xml"),
         "Should mention specific content type"
     );
     assert!(
-        stderr.contains("POST /users/{userId}/avatar"),
+        stderr.contains("POST /users/{userId}/profile"),
         "Should list specific endpoints"
     );
     assert!(
@@ -162,7 +162,7 @@ fn test_cli_non_strict_mode_with_warnings() {
 fn test_cli_strict_mode_rejection() {
     let temp_dir = TempDir::new().unwrap();
     let config_dir = temp_dir.path().join(".aperture");
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml")
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml")
         .canonicalize()
         .unwrap();
 
@@ -186,12 +186,12 @@ fn test_cli_strict_mode_rejection() {
         "Command should fail in strict mode"
     );
     assert!(
-        stderr.contains("Unsupported request body content type 'multipart/form-data'"),
+        stderr.contains("Unsupported request body content type 'application/xml'"),
         "Should show error about unsupported content type"
     );
     assert!(
-        stderr.contains("Only 'application/json' is supported in v1.0"),
-        "Should mention version limitation"
+        stderr.contains("Only 'application/json', 'multipart/form-data', 'application/x-www-form-urlencoded' and binary"),
+        "Should list the supported content types"
     );
 
     // Verify spec was NOT added
@@ -207,7 +207,7 @@ fn test_cli_strict_mode_rejection() {
 fn test_cli_force_flag_with_non_strict_mode() {
     let temp_dir = TempDir::new().unwrap();
     let config_dir = temp_dir.path().join(".aperture");
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml")
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml")
         .canonicalize()
         .unwrap();
 
@@ -249,10 +249,10 @@ fn test_cli_force_flag_with_non_strict_mode() {
 }
 
 #[test]
-fn test_generated_commands_exclude_multipart_endpoints() {
+fn test_generated_commands_exclude_xml_endpoints() {
     let temp_dir = TempDir::new().unwrap();
     let config_dir = temp_dir.path().join(".aperture");
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml")
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml")
         .canonicalize()
         .unwrap();
 
@@ -263,7 +263,7 @@ fn test_generated_commands_exclude_multipart_endpoints() {
         .assert()
         .success();
 
-    // Check available commands - multipart endpoints should not be available
+    // Check available commands - XML endpoints should not be available
     let output = aperture_cmd()
         .env("APERTURE_CONFIG_DIR", config_dir.to_str().unwrap())
         .args(["api", "test-api", "users", "--help"])
@@ -286,10 +286,10 @@ fn test_generated_commands_exclude_multipart_endpoints() {
         "Should have get-user-by-id command"
     );
 
-    // Should NOT have upload-user-avatar
+    // Should NOT have import-user-profile
     assert!(
-        !help_text.contains("upload-user-avatar") && !help_text.contains("Upload user avatar"),
-        "Should NOT have upload-user-avatar command"
+        !help_text.contains("import-user-profile") && !help_text.contains("Import user profile"),
+        "Should NOT have import-user-profile command"
     );
 
     // Check documents namespace should not exist at all
@@ -332,7 +332,7 @@ paths:
       operationId: createUser
       requestBody:
         content:
-          multipart/form-data:
+          application/xml:
             schema:
               type: object
         required: true
@@ -359,7 +359,7 @@ paths:
     let cache_dir = _temp_dir.path().join(".cache");
     let cached_spec = load_cached_spec(&cache_dir, "case-test").unwrap();
 
-    // Should have 3 endpoints total (excluding only the multipart one)
+    // Should have 3 endpoints total (excluding only the XML one)
     assert_eq!(cached_spec.commands.len(), 3);
 
     let operation_ids: Vec<&str> = cached_spec
@@ -373,7 +373,7 @@ paths:
     assert!(operation_ids.contains(&"getLowercaseUsers"));
     assert!(operation_ids.contains(&"getAllCapsUsers"));
 
-    // The multipart endpoint should be excluded
+    // The XML endpoint should be excluded
     assert!(!operation_ids.contains(&"createUser"));
 }
 
@@ -381,7 +381,7 @@ paths:
 fn test_cli_list_verbose_shows_skipped_endpoints() {
     let temp_dir = TempDir::new().unwrap();
     let config_dir = temp_dir.path().join(".aperture");
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml")
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml")
         .canonicalize()
         .unwrap();
 
@@ -416,8 +416,8 @@ fn test_cli_list_verbose_shows_skipped_endpoints() {
         "Should show skipped endpoints section. Got: {combined}"
     );
     assert!(
-        combined.contains("POST /users/{userId}/avatar"),
-        "Should list skipped avatar endpoint. Got: {combined}"
+        combined.contains("POST /users/{userId}/profile"),
+        "Should list skipped profile endpoint. Got: {combined}"
     );
     assert!(
         combined.contains("POST /documents"),
@@ -450,7 +450,7 @@ paths:
       operationId: uploadFile
       requestBody:
         content:
-          multipart/form-data:
+          application/xml:
             schema:
               type: object
         required: true
@@ -510,11 +510,11 @@ fn test_strict_mode_with_url_spec() {
     let runtime = tokio::runtime::Runtime::new().unwrap();
     let mock_server = runtime.block_on(async { MockServer::start().await });
 
-    // Create a spec with multipart content
+    // Create a spec with XML content
     let spec_content = r"
 openapi: 3.0.0
 info:
-  title: Remote API with Multipart
+  title: Remote API with XML
   version: 1.0.0
 servers:
   - url: https://api.example.com
@@ -530,7 +530,7 @@ paths:
       operationId: uploadFile
       requestBody:
         content:
-          multipart/form-data:
+          application/xml:
             schema:
               type: object
         required: true
@@ -575,7 +575,7 @@ paths:
 fn test_describe_json_includes_endpoint_statistics() {
     let temp_dir = TempDir::new().unwrap();
     let config_dir = temp_dir.path().join(".aperture");
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml")
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml")
         .canonicalize()
         .unwrap();
 
@@ -605,9 +605,9 @@ fn test_describe_json_includes_endpoint_statistics() {
         "endpoints field should be present in --describe-json output"
     );
 
-    // The spec-with-multipart.yaml has 5 total endpoints: 3 JSON + 2 multipart
+    // The spec-with-xml.yaml has 5 total endpoints: 3 JSON + 2 XML
     // 3 are available (getUsers, getUserById, generateReport)
-    // 2 are skipped (uploadUserAvatar, uploadDocument)
+    // 2 are skipped (importUserProfile, importDocument)
     let endpoints = &manifest["endpoints"];
     assert_eq!(
         endpoints["total"].as_u64().unwrap(),
@@ -622,7 +622,7 @@ fn test_describe_json_includes_endpoint_statistics() {
     assert_eq!(
         endpoints["skipped"].as_u64().unwrap(),
         2,
-        "skipped should be 2 (multipart endpoints)"
+        "skipped should be 2 (XML endpoints)"
     );
 
     // Verify actual command count matches the available count
@@ -643,7 +643,7 @@ fn test_describe_json_includes_endpoint_statistics() {
 fn test_config_list_verbose_shows_endpoint_statistics() {
     let temp_dir = TempDir::new().unwrap();
     let config_dir = temp_dir.path().join(".aperture");
-    let spec_path = Path::new("tests/fixtures/openapi/spec-with-multipart.yaml")
+    let spec_path = Path::new("tests/fixtures/openapi/spec-with-xml.yaml")
         .canonicalize()
         .unwrap();
 
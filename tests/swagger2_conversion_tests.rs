@@ -0,0 +1,305 @@
+mod test_helpers;
+
+use aperture_cli::cache::models::{CachedCommand, CachedEncoding, CachedSpec};
+use aperture_cli::config::manager::ConfigManager;
+use aperture_cli::fs::OsFileSystem;
+use aperture_cli::spec::convert_swagger2;
+use serde_json::{json, Value};
+use test_helpers::cache_spec;
+
+const SWAGGER_SPEC: &str = r"
+swagger: '2.0'
+info:
+  title: Pet Store
+  version: 1.0.0
+host: api.example.com
+basePath: /v1
+schemes:
+  - https
+  - http
+consumes:
+  - application/json
+produces:
+  - application/json
+securityDefinitions:
+  apiKey:
+    type: apiKey
+    name: X-API-Key
+    in: header
+    x-aperture-secret:
+      source: env
+      name: PETSTORE_KEY
+  basicAuth:
+    type: basic
+  oauth:
+    type: oauth2
+    flow: application
+    tokenUrl: https://auth.example.com/token
+    scopes:
+      pets:read: Read pets
+security:
+  - apiKey: []
+parameters:
+  PetId:
+    name: petId
+    in: path
+    required: true
+    type: integer
+    format: int64
+responses:
+  NotFound:
+    description: Pet not found
+    schema:
+      $ref: '#/definitions/Error'
+definitions:
+  Pet:
+    type: object
+    required: [name]
+    properties:
+      name:
+        type: string
+      tag:
+        type: string
+        x-nullable: true
+      owner:
+        $ref: '#/definitions/Owner'
+  Owner:
+    type: object
+    properties:
+      email:
+        type: string
+  Error:
+    type: object
+    properties:
+      message:
+        type: string
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags: [pets]
+      parameters:
+        - name: tags
+          in: query
+          type: array
+          items:
+            type: string
+        - name: status
+          in: query
+          type: array
+          collectionFormat: multi
+          items:
+            type: string
+            enum: [available, sold]
+        - name: sort
+          in: query
+          type: array
+          collectionFormat: tsv
+          items:
+            type: string
+      responses:
+        200:
+          description: The pets
+          schema:
+            type: array
+            items:
+              $ref: '#/definitions/Pet'
+    post:
+      operationId: createPet
+      tags: [pets]
+      parameters:
+        - name: pet
+          in: body
+          required: true
+          schema:
+            $ref: '#/definitions/Pet'
+      responses:
+        201:
+          description: Created
+  /pets/{petId}:
+    parameters:
+      - $ref: '#/parameters/PetId'
+    get:
+      operationId: getPet
+      tags: [pets]
+      responses:
+        200:
+          description: The pet
+          schema:
+            $ref: '#/definitions/Pet'
+        404:
+          $ref: '#/responses/NotFound'
+  /pets/{petId}/photo:
+    post:
+      operationId: uploadPhoto
+      tags: [pets]
+      consumes:
+        - multipart/form-data
+      parameters:
+        - $ref: '#/parameters/PetId'
+        - name: file
+          in: formData
+          required: true
+          type: file
+        - name: caption
+          in: formData
+          type: string
+      responses:
+        204:
+          description: Uploaded
+";
+
+fn command<'a>(spec: &'a CachedSpec, operation_id: &str) -> &'a CachedCommand {
+    spec.commands
+        .iter()
+        .find(|command| command.operation_id == operation_id)
+        .unwrap()
+}
+
+fn schema(json: &str) -> Value {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn test_swagger2_spec_is_converted_on_add() {
+    let (spec, temp_dir) = cache_spec("petstore", SWAGGER_SPEC);
+
+    assert_eq!(spec.base_url.as_deref(), Some("https://api.example.com/v1"));
+    assert_eq!(
+        spec.servers,
+        vec!["https://api.example.com/v1", "http://api.example.com/v1"]
+    );
+
+    // The stored spec is the converted document, so reinit and --describe-json can parse it
+    let stored = std::fs::read_to_string(temp_dir.path().join("specs/petstore.yaml")).unwrap();
+    assert!(stored.contains("openapi: 3.0.3"), "{stored}");
+
+    let create_pet = command(&spec, "createPet");
+    let body = create_pet.request_body.as_ref().unwrap();
+    assert_eq!(body.content_type, "application/json");
+    assert!(body.required);
+    assert_eq!(
+        schema(&body.schema),
+        json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {"type": "string"},
+                "tag": {"type": "string", "nullable": true},
+                "owner": {"type": "object", "properties": {"email": {"type": "string"}}}
+            }
+        })
+    );
+}
+
+#[test]
+fn test_swagger2_parameters_and_responses_are_converted() {
+    let (spec, _temp_dir) = cache_spec("petstore", SWAGGER_SPEC);
+
+    let list_pets = command(&spec, "listPets");
+    let encodings: Vec<_> = list_pets
+        .parameters
+        .iter()
+        .map(|parameter| (parameter.name.as_str(), parameter.encoding.clone()))
+        .collect();
+    let form = |explode| {
+        Some(CachedEncoding {
+            style: "form".to_string(),
+            explode,
+        })
+    };
+    assert_eq!(
+        encodings,
+        vec![
+            ("tags", form(false)),
+            ("status", form(true)),
+            ("sort", form(false))
+        ]
+    );
+
+    // Path-level parameter references are inherited by each operation
+    let get_pet = command(&spec, "getPet");
+    assert_eq!(get_pet.parameters[0].name, "petId");
+    assert_eq!(get_pet.parameters[0].location, "path");
+    assert_eq!(
+        get_pet.parameters[0].schema_type.as_deref(),
+        Some("integer")
+    );
+    let not_found = get_pet
+        .responses
+        .iter()
+        .find(|response| response.status_code == "404")
+        .unwrap();
+    assert_eq!(not_found.description.as_deref(), Some("Pet not found"));
+    assert_eq!(
+        schema(not_found.schema.as_deref().unwrap())["properties"]["message"],
+        json!({"type": "string"})
+    );
+
+    let upload_photo = command(&spec, "uploadPhoto");
+    let body = upload_photo.request_body.as_ref().unwrap();
+    assert_eq!(body.content_type, "multipart/form-data");
+    assert_eq!(
+        schema(&body.schema),
+        json!({
+            "type": "object",
+            "required": ["file"],
+            "properties": {
+                "file": {"type": "string", "format": "binary"},
+                "caption": {"type": "string"}
+            }
+        })
+    );
+}
+
+#[test]
+fn test_swagger2_security_definitions_are_converted() {
+    let (spec, _temp_dir) = cache_spec("petstore", SWAGGER_SPEC);
+
+    let api_key = &spec.security_schemes["apiKey"];
+    assert_eq!(api_key.scheme_type, "apiKey");
+    assert_eq!(api_key.location.as_deref(), Some("header"));
+    assert_eq!(api_key.parameter_name.as_deref(), Some("X-API-Key"));
+    assert_eq!(
+        api_key.aperture_secret.as_ref().unwrap().name,
+        "PETSTORE_KEY"
+    );
+
+    let basic = &spec.security_schemes["basicAuth"];
+    assert_eq!(basic.scheme_type, "http");
+    assert_eq!(basic.scheme.as_deref(), Some("basic"));
+
+    let oauth = spec.security_schemes["oauth"].oauth2.as_ref().unwrap();
+    assert_eq!(oauth.token_url, "https://auth.example.com/token");
+    assert_eq!(oauth.scopes, vec!["pets:read"]);
+
+    assert_eq!(
+        command(&spec, "listPets").security_requirements,
+        vec!["apiKey"]
+    );
+}
+
+#[test]
+fn test_conversion_warnings_are_reported() {
+    let content = SWAGGER_SPEC.replace("host: api.example.com\n", "");
+    let (converted, warnings) = convert_swagger2(content).unwrap();
+    assert!(converted.contains("url: /v1"), "{converted}");
+
+    let lines = ConfigManager::<OsFileSystem>::format_validation_warnings(&warnings, None, "");
+    assert_eq!(
+        lines,
+        vec![
+            "Swagger 2.0 conversion notes:",
+            "  - no host is declared, so the server URL is relative; set one with 'aperture config set-url'",
+            "  - GET /pets: query parameter 'sort' uses collectionFormat 'tsv', which has no OpenAPI 3.0 equivalent; values are comma-separated instead",
+        ]
+    );
+}
+
+#[test]
+fn test_openapi3_content_is_not_converted() {
+    let content = "openapi: 3.0.0\ninfo:\n  title: swagger\n  version: '1'\npaths: {}\n";
+    let (converted, warnings) = convert_swagger2(content.to_string()).unwrap();
+    assert_eq!(converted, content);
+    assert!(warnings.is_empty());
+}
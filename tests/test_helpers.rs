@@ -1,9 +1,14 @@
 #![allow(dead_code)]
 
 use aperture_cli::cache::models::{
-    CachedCommand, CachedParameter, CachedRequestBody, CachedResponse, PaginationInfo,
+    CachedCommand, CachedParameter, CachedRequestBody, CachedResponse, CachedSpec, PaginationInfo,
 };
+use aperture_cli::config::context_name::ApiContextName;
+use aperture_cli::config::manager::ConfigManager;
 use aperture_cli::constants;
+use aperture_cli::engine::loader::load_cached_spec;
+use aperture_cli::fs::OsFileSystem;
+use tempfile::TempDir;
 
 /// Initialize the rustls crypto provider before any tests run.
 /// This runs once per test binary when `test_helpers` is included.
@@ -28,6 +33,7 @@ pub fn test_parameter(name: &str, location: &str, required: bool) -> CachedParam
         default_value: None,
         enum_values: vec![],
         example: None,
+        encoding: None,
     }
 }
 
@@ -50,6 +56,7 @@ pub fn test_request_body() -> CachedRequestBody {
         required: true,
         description: None,
         example: None,
+        encoding: std::collections::HashMap::new(),
     }
 }
 
@@ -75,5 +82,25 @@ pub fn test_command(name: &str, operation_id: &str, method: &str, path: &str) ->
         aliases: vec![],
         hidden: false,
         pagination: PaginationInfo::default(),
+        security_requirement_sets: vec![],
     }
 }
+
+/// Adds `yaml` as the API `name` in strict mode to a fresh config directory
+/// and returns its cached form along with the directory
+///
+/// # Panics
+///
+/// Panics if the spec is rejected or cannot be cached.
+#[must_use]
+pub fn cache_spec(name: &str, yaml: &str) -> (CachedSpec, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let spec_path = temp_dir.path().join(format!("{name}.yaml"));
+    std::fs::write(&spec_path, yaml).unwrap();
+
+    ConfigManager::with_fs(OsFileSystem, temp_dir.path().to_path_buf())
+        .add_spec(&ApiContextName::new(name).unwrap(), &spec_path, false, true)
+        .unwrap_or_else(|e| panic!("'{name}' should be accepted in strict mode: {e}"));
+    let spec = load_cached_spec(temp_dir.path().join(".cache"), name).unwrap();
+    (spec, temp_dir)
+}
@@ -0,0 +1,256 @@
+mod test_helpers;
+
+use aperture_cli::cli::ToolFormat;
+use aperture_cli::tool_export::generate_tools;
+use serde_json::{json, Value};
+use test_helpers::cache_spec;
+
+const TASKS_SPEC: &str = r"
+openapi: 3.0.3
+info:
+  title: Tasks API
+  version: 1.0.0
+servers:
+  - url: https://api.example.com
+paths:
+  /projects/{projectId}/tasks:
+    get:
+      operationId: listTasks
+      summary: List the tasks of a project
+      description: >-
+        Returns every task of the project, newest first, including tasks that
+        were archived during the last thirty days.
+      tags: [tasks]
+      parameters:
+        - name: projectId
+          in: path
+          required: true
+          schema:
+            type: integer
+        - name: status
+          in: query
+          schema:
+            type: string
+            enum: [open, done]
+      responses:
+        '200':
+          description: The tasks
+    post:
+      operationId: createTask
+      tags: [tasks]
+      parameters:
+        - name: projectId
+          in: path
+          required: true
+          schema:
+            type: integer
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [title]
+              properties:
+                title:
+                  type: string
+                due:
+                  type: string
+                  format: date
+      responses:
+        '201':
+          description: Created
+  /projects/{projectId}/attachments:
+    post:
+      operationId: uploadAttachment
+      tags: [attachments]
+      parameters:
+        - name: projectId
+          in: path
+          required: true
+          schema:
+            type: integer
+        - name: labels
+          in: query
+          style: deepObject
+          schema:
+            type: object
+            additionalProperties:
+              type: string
+      requestBody:
+        content:
+          application/octet-stream:
+            schema:
+              type: string
+              format: binary
+      responses:
+        '201':
+          description: Uploaded
+";
+
+fn tool<'a>(tools: &'a Value, name: &str) -> &'a Value {
+    tools
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|tool| {
+            tool["name"] == name || tool["function"]["name"] == name || tool["title"] == name
+        })
+        .unwrap_or_else(|| panic!("no tool named {name} in {tools}"))
+}
+
+#[test]
+fn test_anthropic_tools_use_flag_names_and_body() {
+    let (spec, _temp_dir) = cache_spec("tasks", TASKS_SPEC);
+    let tools = generate_tools(&spec, &ToolFormat::AnthropicTools, 0).unwrap();
+
+    assert_eq!(tools.as_array().unwrap().len(), 3);
+    let list_tasks = tool(&tools, "tasks__list-tasks");
+    assert_eq!(
+        list_tasks["input_schema"],
+        json!({
+            "type": "object",
+            "properties": {
+                "project-id": { "type": "integer", "description": "path parameter" },
+                "status": {
+                    "type": "string",
+                    "enum": ["open", "done"],
+                    "description": "query parameter"
+                }
+            },
+            "required": ["project-id"],
+            "additionalProperties": false
+        })
+    );
+
+    let create_task = tool(&tools, "tasks__create-task");
+    assert_eq!(
+        create_task["input_schema"]["properties"]["body"]["required"],
+        json!(["title"])
+    );
+    assert_eq!(
+        create_task["input_schema"]["required"],
+        json!(["project-id", "body"])
+    );
+
+    let upload = tool(&tools, "attachments__upload-attachment");
+    assert_eq!(
+        upload["input_schema"]["properties"]["body-file"]["type"],
+        "string"
+    );
+}
+
+#[test]
+fn test_openai_tools_are_strict_when_possible() {
+    let (spec, _temp_dir) = cache_spec("tasks", TASKS_SPEC);
+    let tools = generate_tools(&spec, &ToolFormat::OpenaiTools, 0).unwrap();
+
+    let create_task = tool(&tools, "tasks__create-task");
+    assert_eq!(create_task["type"], "function");
+    assert_eq!(create_task["function"]["strict"], true);
+    let body = &create_task["function"]["parameters"]["properties"]["body"];
+    assert_eq!(
+        body,
+        &json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string" },
+                "due": { "type": ["string", "null"] }
+            },
+            "required": ["due", "title"],
+            "additionalProperties": false,
+            "description": "Request body (application/json)"
+        })
+    );
+
+    let list_tasks = tool(&tools, "tasks__list-tasks");
+    assert_eq!(
+        list_tasks["function"]["parameters"]["properties"]["status"],
+        json!({
+            "type": ["string", "null"],
+            "enum": ["open", "done", null],
+            "description": "query parameter"
+        })
+    );
+    assert_eq!(
+        list_tasks["function"]["parameters"]["required"],
+        json!(["project-id", "status"])
+    );
+
+    // A free-form object parameter cannot be expressed in strict mode
+    let upload = tool(&tools, "attachments__upload-attachment");
+    assert_eq!(upload["function"]["strict"], false);
+    assert!(upload["function"]["parameters"]["properties"]["labels"].is_object());
+}
+
+#[test]
+fn test_json_schema_documents_and_description_budget() {
+    let (spec, _temp_dir) = cache_spec("tasks", TASKS_SPEC);
+    let tools = generate_tools(&spec, &ToolFormat::JsonSchema, 10).unwrap();
+
+    let list_tasks = tool(&tools, "tasks__list-tasks");
+    assert_eq!(
+        list_tasks["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert_eq!(list_tasks["type"], "object");
+    assert_eq!(list_tasks["additionalProperties"], false);
+
+    let description = list_tasks["description"].as_str().unwrap();
+    assert!(description.starts_with("List the tasks of a project"));
+    assert!(description.ends_with('…'), "{description}");
+    assert!(description.chars().count() <= 41, "{description}");
+}
+
+#[test]
+fn test_parameters_sharing_a_flag_are_an_error() {
+    let (mut spec, _dir) = cache_spec("tasks", TASKS_SPEC);
+    let list_tasks = spec
+        .commands
+        .iter_mut()
+        .find(|command| command.operation_id == "listTasks")
+        .unwrap();
+    let mut project_header = list_tasks.parameters[0].clone();
+    project_header.name = "project-id".to_string();
+    project_header.location = "header".to_string();
+    list_tasks.parameters.push(project_header);
+
+    let error = generate_tools(&spec, &ToolFormat::AnthropicTools, 0).unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("listTasks"), "{message}");
+    assert!(message.contains("project-id"), "{message}");
+}
+
+#[test]
+fn test_tool_names_are_shortened_and_never_shared() {
+    let (mut spec, _dir) = cache_spec("tasks", TASKS_SPEC);
+    let list_tasks = spec
+        .commands
+        .iter()
+        .find(|command| command.operation_id == "listTasks")
+        .unwrap()
+        .clone();
+    let mut long = list_tasks.clone();
+    long.operation_id = "listTasksArchivedDuringTheLastThirtyDaysIncludingSubtasks".to_string();
+    let twin = list_tasks;
+    spec.commands.extend([long, twin.clone()]);
+
+    let tools = generate_tools(&spec, &ToolFormat::AnthropicTools, 0).unwrap();
+    let names: Vec<&str> = tools
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert!(names.iter().all(|name| name.len() <= 64), "{names:?}");
+    let mut unique = names.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    assert_eq!(unique.len(), names.len(), "{names:?}");
+    assert!(names.contains(&"tasks__list-tasks"), "{names:?}");
+
+    // A third command with the same name has none left to take
+    spec.commands.push(twin);
+    let error = generate_tools(&spec, &ToolFormat::AnthropicTools, 0).unwrap_err();
+    assert!(error.to_string().contains("tasks__list-tasks"), "{error}");
+}